@@ -0,0 +1,115 @@
+// ============================================================================
+// Participant Access Control
+//
+// Screens the addresses that move funds in and out of escrows:
+//
+//   Blacklisted(address)        persistent  address may not deposit or receive
+//   ParticipantWhitelist(addr)  persistent  address is on the allow list
+//   WhitelistMode               instance    when true, only allow-listed
+//                                           addresses may participate
+//
+// The blacklist always wins: an address that is both blacklisted and
+// allow-listed is rejected. This list is independent of the anti-abuse
+// whitelist, which only exempts addresses from rate limiting.
+// ============================================================================
+
+use crate::events::{
+    emit_blacklist_updated, emit_whitelist_mode_changed, emit_whitelist_updated, BlacklistUpdated,
+    WhitelistModeChanged, WhitelistUpdated,
+};
+use crate::{ttl, DataKey, Error};
+use soroban_sdk::{Address, Env, String};
+
+pub(crate) fn is_blacklisted(env: &Env, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Blacklisted(address.clone()))
+}
+
+pub(crate) fn is_whitelisted(env: &Env, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::ParticipantWhitelist(address.clone()))
+}
+
+pub(crate) fn whitelist_mode(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::WhitelistMode)
+        .unwrap_or(false)
+}
+
+pub(crate) fn is_allowed(env: &Env, address: &Address) -> bool {
+    if is_blacklisted(env, address) {
+        return false;
+    }
+    !whitelist_mode(env) || is_whitelisted(env, address)
+}
+
+/// Reject `address` as a depositor or recipient if it is screened out.
+pub(crate) fn require_allowed(env: &Env, address: &Address) -> Result<(), Error> {
+    if is_allowed(env, address) {
+        Ok(())
+    } else {
+        Err(Error::ParticipantNotAllowed)
+    }
+}
+
+pub(crate) fn set_blacklisted(
+    env: &Env,
+    admin: &Address,
+    address: &Address,
+    blacklisted: bool,
+    reason: Option<String>,
+) {
+    let key = DataKey::Blacklisted(address.clone());
+    if blacklisted {
+        env.storage().persistent().set(&key, &true);
+        ttl::extend(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    emit_blacklist_updated(
+        env,
+        BlacklistUpdated {
+            address: address.clone(),
+            blacklisted,
+            reason,
+            updated_by: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+pub(crate) fn set_whitelisted(env: &Env, admin: &Address, address: &Address, whitelisted: bool) {
+    let key = DataKey::ParticipantWhitelist(address.clone());
+    if whitelisted {
+        env.storage().persistent().set(&key, &true);
+        ttl::extend(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    emit_whitelist_updated(
+        env,
+        WhitelistUpdated {
+            address: address.clone(),
+            whitelisted,
+            updated_by: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+pub(crate) fn set_whitelist_mode(env: &Env, admin: &Address, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&DataKey::WhitelistMode, &enabled);
+    emit_whitelist_mode_changed(
+        env,
+        WhitelistModeChanged {
+            enabled,
+            updated_by: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
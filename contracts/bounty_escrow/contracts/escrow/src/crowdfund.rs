@@ -0,0 +1,246 @@
+// ============================================================================
+// Crowdfunded Escrows
+//
+// Anyone may top up a locked escrow with `contribute`. Contributions from
+// addresses other than the original depositor are recorded per funder:
+//
+//   FunderKey::Ledger(bounty_id)   Vec<Contribution>  stake per funder, in
+//                                                     first-contribution order
+//   FunderKey::Settled(bounty_id)  i128               part of `Escrow::amount`
+//                                                     no stake holds any more
+//
+// The depositor's own stake is not stored; it is whatever part of
+// `Escrow::amount` is neither settled nor put in by the other funders, so
+// top-ups through `lock_funds` on a template or `renew_escrow` land on the
+// depositor without touching the ledger. Refunds are split pro rata over the
+// stakes. Releases shrink every stake alike, so before a new contribution is
+// credited the stakes are rebased onto `remaining_amount`.
+// ============================================================================
+
+use crate::{token_math, ttl, Error, Escrow};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Upper bound on distinct funders besides the depositor; every refund
+/// pays each of them.
+pub(crate) const MAX_FUNDERS: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FunderKey {
+    Ledger(u64),
+    Settled(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    pub funder: Address,
+    /// Net amount credited to the escrow after the lock fee, less this
+    /// stake's part of what was released before the latest contribution.
+    pub amount: i128,
+}
+
+/// A funder's stake in an escrow and what a refund would return right now.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunderShare {
+    pub funder: Address,
+    pub contributed: i128,
+    /// Stake as a fraction of the escrow, in basis points (floored).
+    pub share_bps: u32,
+    /// Pro-rata portion of `remaining_amount`.
+    pub refundable: i128,
+}
+
+fn ledger(env: &Env, bounty_id: u64) -> Vec<Contribution> {
+    env.storage()
+        .persistent()
+        .get(&FunderKey::Ledger(bounty_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub(crate) fn is_crowdfunded(env: &Env, bounty_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&FunderKey::Ledger(bounty_id))
+}
+
+/// Record `amount` from `funder`. Contributions by the depositor are
+/// already covered by `Escrow::amount` and are not recorded.
+pub(crate) fn credit(
+    env: &Env,
+    bounty_id: u64,
+    escrow: &Escrow,
+    funder: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    if *funder == escrow.depositor {
+        return Ok(());
+    }
+    let mut entries = ledger(env, bounty_id);
+    match entries.iter().position(|c| c.funder == *funder) {
+        Some(pos) => {
+            let mut entry = entries.get_unchecked(pos as u32);
+            entry.amount += amount;
+            entries.set(pos as u32, entry);
+        }
+        None => {
+            if entries.len() >= MAX_FUNDERS {
+                return Err(Error::InvalidBatchSize);
+            }
+            entries.push_back(Contribution {
+                funder: funder.clone(),
+                amount,
+            });
+        }
+    }
+    let key = FunderKey::Ledger(bounty_id);
+    env.storage().persistent().set(&key, &entries);
+    ttl::extend(env, &key);
+    Ok(())
+}
+
+fn settled(env: &Env, bounty_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&FunderKey::Settled(bounty_id))
+        .unwrap_or(0)
+}
+
+/// Sum of all stakes: `Escrow::amount` less what has been settled.
+fn staked(env: &Env, bounty_id: u64, escrow: &Escrow) -> i128 {
+    escrow.amount - settled(env, bounty_id)
+}
+
+/// Every stake in the escrow, depositor first.
+pub(crate) fn contributions(env: &Env, bounty_id: u64, escrow: &Escrow) -> Vec<Contribution> {
+    let others = ledger(env, bounty_id);
+    let mut funded: i128 = 0;
+    for c in others.iter() {
+        funded += c.amount;
+    }
+    let mut all = Vec::new(env);
+    all.push_back(Contribution {
+        funder: escrow.depositor.clone(),
+        amount: staked(env, bounty_id, escrow) - funded,
+    });
+    all.append(&others);
+    all
+}
+
+/// Shrink every stake to its pro-rata part of `remaining_amount`, so that
+/// funds released so far come out of the existing stakes and not out of
+/// whatever is contributed next. Funders left with nothing are dropped.
+pub(crate) fn rebase(env: &Env, bounty_id: u64, escrow: &Escrow) -> Result<(), Error> {
+    let total = staked(env, bounty_id, escrow);
+    if escrow.remaining_amount >= total {
+        return Ok(());
+    }
+    let stakes = contributions(env, bounty_id, escrow);
+    let parts = pro_rata(env, &stakes, escrow.remaining_amount)?;
+
+    if is_crowdfunded(env, bounty_id) {
+        let mut entries = Vec::new(env);
+        for (i, c) in stakes.iter().enumerate().skip(1) {
+            let amount = parts.get_unchecked(i as u32);
+            if amount > 0 {
+                entries.push_back(Contribution {
+                    funder: c.funder,
+                    amount,
+                });
+            }
+        }
+        let key = FunderKey::Ledger(bounty_id);
+        env.storage().persistent().set(&key, &entries);
+        ttl::extend(env, &key);
+    }
+
+    let key = FunderKey::Settled(bounty_id);
+    env.storage().persistent().set(
+        &key,
+        &(settled(env, bounty_id) + total - escrow.remaining_amount),
+    );
+    ttl::extend(env, &key);
+    Ok(())
+}
+
+/// Split `amount` over `stakes` in proportion to each stake, floored. The
+/// rounding remainder goes to the first stake (the depositor). Fails with
+/// `Error::InvalidAmount` if a stake times `amount` overflows.
+pub(crate) fn pro_rata(
+    env: &Env,
+    stakes: &Vec<Contribution>,
+    amount: i128,
+) -> Result<Vec<i128>, Error> {
+    let mut total: i128 = 0;
+    for c in stakes.iter() {
+        total += c.amount;
+    }
+    let mut parts = Vec::new(env);
+    let mut allocated: i128 = 0;
+    for c in stakes.iter() {
+        let part = if total > 0 {
+            c.amount.checked_mul(amount).ok_or(Error::InvalidAmount)? / total
+        } else {
+            0
+        };
+        allocated += part;
+        parts.push_back(part);
+    }
+    if !parts.is_empty() {
+        parts.set(0, parts.get_unchecked(0) + amount - allocated);
+    }
+    Ok(parts)
+}
+
+pub(crate) fn shares(
+    env: &Env,
+    bounty_id: u64,
+    escrow: &Escrow,
+) -> Result<Vec<FunderShare>, Error> {
+    let stakes = contributions(env, bounty_id, escrow);
+    let total = staked(env, bounty_id, escrow);
+    let refundable = pro_rata(env, &stakes, escrow.remaining_amount)?;
+    let mut out = Vec::new(env);
+    for (i, c) in stakes.iter().enumerate() {
+        let share_bps = if total > 0 {
+            (c.amount
+                .checked_mul(token_math::BASIS_POINTS)
+                .ok_or(Error::InvalidAmount)?
+                / total) as u32
+        } else {
+            0
+        };
+        out.push_back(FunderShare {
+            funder: c.funder,
+            contributed: c.amount,
+            share_bps,
+            refundable: refundable.get_unchecked(i as u32),
+        });
+    }
+    Ok(out)
+}
+
+/// Who a deadline refund of `amount` pays: every funder pro rata on a
+/// crowdfunded escrow, otherwise the depositor alone.
+pub(crate) fn refund_payouts(
+    env: &Env,
+    bounty_id: u64,
+    escrow: &Escrow,
+    amount: i128,
+) -> Result<Vec<(Address, i128)>, Error> {
+    let mut payouts = Vec::new(env);
+    if !is_crowdfunded(env, bounty_id) {
+        payouts.push_back((escrow.depositor.clone(), amount));
+        return Ok(payouts);
+    }
+    let stakes = contributions(env, bounty_id, escrow);
+    let parts = pro_rata(env, &stakes, amount)?;
+    for (i, stake) in stakes.iter().enumerate() {
+        let part = parts.get_unchecked(i as u32);
+        if part > 0 {
+            payouts.push_back((stake.funder, part));
+        }
+    }
+    Ok(payouts)
+}
@@ -1,5 +1,5 @@
-use crate::{CapabilityAction, DisputeOutcome, DisputeReason};
-use soroban_sdk::{contracttype, symbol_short, Address, Env};
+use crate::{CapabilityAction, CapabilityScope, DisputeOutcome, DisputeReason};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env};
 
 pub const EVENT_VERSION_V2: u32 = 2;
 
@@ -22,9 +22,12 @@ pub fn emit_bounty_initialized(env: &Env, event: BountyEscrowInitialized) {
 pub struct FundsLocked {
     pub version: u32,
     pub bounty_id: u64,
+    /// Net amount credited to the escrow (`gross_amount - fee_amount`).
     pub amount: i128,
     pub depositor: Address,
     pub deadline: u64,
+    pub gross_amount: i128,
+    pub fee_amount: i128,
 }
 
 pub fn emit_funds_locked(env: &Env, event: FundsLocked) {
@@ -37,9 +40,12 @@ pub fn emit_funds_locked(env: &Env, event: FundsLocked) {
 pub struct FundsReleased {
     pub version: u32,
     pub bounty_id: u64,
+    /// Net amount paid to the recipient (`gross_amount - fee_amount`).
     pub amount: i128,
     pub recipient: Address,
     pub timestamp: u64,
+    pub gross_amount: i128,
+    pub fee_amount: i128,
 }
 
 pub fn emit_funds_released(env: &Env, event: FundsReleased) {
@@ -62,6 +68,37 @@ pub fn emit_funds_refunded(env: &Env, event: FundsRefunded) {
     env.events().publish(topics, event.clone());
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MilestoneSettled {
+    pub bounty_id: u64,
+    pub index: u32,
+    pub status: crate::milestones::MilestoneStatus,
+    pub amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_milestone_settled(env: &Env, event: MilestoneSettled) {
+    let topics = (symbol_short!("ms_set"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct MilestoneExtended {
+    pub bounty_id: u64,
+    pub index: u32,
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+    pub timestamp: u64,
+}
+
+pub fn emit_milestone_extended(env: &Env, event: MilestoneExtended) {
+    let topics = (symbol_short!("ms_ext"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum FeeOperationType {
@@ -73,6 +110,7 @@ pub enum FeeOperationType {
 #[derive(Clone, Debug)]
 pub struct FeeCollected {
     pub version: u32,
+    pub bounty_id: u64,
     pub operation_type: FeeOperationType,
     pub amount: i128,
     pub fee_rate: i128,
@@ -128,6 +166,24 @@ pub fn emit_batch_funds_released(env: &Env, event: BatchFundsReleased) {
     env.events().publish(topics, event.clone());
 }
 
+/// Summary of a `batch_refund_expired` sweep; each refund also emits
+/// its own `FundsRefunded`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BatchFundsRefunded {
+    pub version: u32,
+    pub keeper: Address,
+    pub count: u32,
+    pub total_amount: i128,
+    pub keeper_reward: i128,
+    pub timestamp: u64,
+}
+
+pub fn emit_batch_funds_refunded(env: &Env, event: BatchFundsRefunded) {
+    let topics = (symbol_short!("b_ref"),);
+    env.events().publish(topics, event.clone());
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ApprovalAdded {
@@ -173,6 +229,54 @@ pub struct ClaimCancelled {
     pub outcome: DisputeOutcome,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeOpened {
+    pub dispute_id: u64,
+    pub bounty_id: u64,
+    pub opened_by: Address,
+    pub contributor: Address,
+    pub reason: DisputeReason,
+    pub opened_at: u64,
+}
+
+pub fn emit_dispute_opened(env: &Env, event: DisputeOpened) {
+    let topics = (symbol_short!("dsp_open"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeEvidenceSubmitted {
+    pub dispute_id: u64,
+    pub bounty_id: u64,
+    pub submitter: Address,
+    pub evidence_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
+pub fn emit_dispute_evidence(env: &Env, event: DisputeEvidenceSubmitted) {
+    let topics = (symbol_short!("dsp_evid"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolved {
+    pub dispute_id: u64,
+    pub bounty_id: u64,
+    pub arbiter: Address,
+    pub outcome: DisputeOutcome,
+    pub contributor_amount: i128,
+    pub refund_amount: i128,
+    pub resolved_at: u64,
+}
+
+pub fn emit_dispute_resolved(env: &Env, event: DisputeResolved) {
+    let topics = (symbol_short!("dsp_res"), event.bounty_id);
+    env.events().publish(topics, event.clone());
+}
+
 /// Event emitted when a claim ticket is issued to a bounty winner
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -206,6 +310,22 @@ pub fn emit_ticket_claimed(env: &Env, event: TicketClaimed) {
     env.events().publish(topics, event.clone());
 }
 
+/// Event emitted when a beneficiary redeems an off-chain signed claim voucher
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoucherRedeemed {
+    pub bounty_id: u64,
+    pub beneficiary: Address,
+    pub amount: i128,
+    pub nonce: u64,
+    pub redeemed_at: u64,
+}
+
+pub fn emit_voucher_redeemed(env: &Env, event: VoucherRedeemed) {
+    let topics = (symbol_short!("vch_red"), event.bounty_id);
+    env.events().publish(topics, event);
+}
+
 pub fn emit_pause_state_changed(env: &Env, event: crate::PauseStateChanged) {
     let topics = (symbol_short!("pause"), event.operation.clone());
     env.events().publish(topics, event);
@@ -216,6 +336,8 @@ pub fn emit_pause_state_changed(env: &Env, event: crate::PauseStateChanged) {
 pub struct EmergencyWithdrawEvent {
     pub admin: Address,
     pub recipient: Address,
+    /// Asset drained; one event is emitted per asset.
+    pub token: Address,
     pub amount: i128,
     pub timestamp: u64,
 }
@@ -234,7 +356,6 @@ pub struct PromotionalPeriodCreated {
     pub end_time: u64,
     pub lock_fee_rate: i128,
     pub release_fee_rate: i128,
-    pub is_global: bool,
     pub timestamp: u64,
 }
 
@@ -303,6 +424,45 @@ pub fn emit_capability_issued(env: &Env, event: CapabilityIssued) {
     env.events().publish(topics, event);
 }
 
+/// Issued in place of `CapabilityIssued` for scopes wider than one bounty,
+/// so `cap_new` keeps the schema indexers already decode.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapabilityScopeIssued {
+    pub capability_id: u64,
+    pub owner: Address,
+    pub holder: Address,
+    pub action: CapabilityAction,
+    pub scope: CapabilityScope,
+    pub amount_limit: i128,
+    pub expires_at: u64,
+    pub max_uses: u32,
+    pub timestamp: u64,
+}
+
+pub fn emit_capability_scope_issued(env: &Env, event: CapabilityScopeIssued) {
+    let topics = (symbol_short!("cap_scope"), event.capability_id);
+    env.events().publish(topics, event);
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapabilityDelegated {
+    pub capability_id: u64,
+    pub parent_id: u64,
+    pub delegator: Address,
+    pub holder: Address,
+    pub amount_limit: i128,
+    pub expires_at: u64,
+    pub max_uses: u32,
+    pub timestamp: u64,
+}
+
+pub fn emit_capability_delegated(env: &Env, event: CapabilityDelegated) {
+    let topics = (symbol_short!("cap_del"), event.capability_id);
+    env.events().publish(topics, event);
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CapabilityUsed {
@@ -455,3 +615,47 @@ pub fn emit_new_cycle_created(env: &Env, event: NewCycleCreatedEvent) {
     let topics = (symbol_short!("new_cyc"), event.new_bounty_id);
     env.events().publish(topics, event.clone());
 }
+
+// ==================== Participant Access Control ====================
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlacklistUpdated {
+    pub address: Address,
+    pub blacklisted: bool,
+    pub reason: Option<soroban_sdk::String>,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_blacklist_updated(env: &Env, event: BlacklistUpdated) {
+    let topics = (symbol_short!("blk_upd"), event.address.clone());
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WhitelistUpdated {
+    pub address: Address,
+    pub whitelisted: bool,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_whitelist_updated(env: &Env, event: WhitelistUpdated) {
+    let topics = (symbol_short!("wl_upd"), event.address.clone());
+    env.events().publish(topics, event.clone());
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WhitelistModeChanged {
+    pub enabled: bool,
+    pub updated_by: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_whitelist_mode_changed(env: &Env, event: WhitelistModeChanged) {
+    let topics = (symbol_short!("wl_mode"),);
+    env.events().publish(topics, event.clone());
+}
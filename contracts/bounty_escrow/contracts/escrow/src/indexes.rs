@@ -0,0 +1,581 @@
+// ============================================================================
+// Secondary Escrow Indexes
+//
+// Every escrow write goes through `store_escrow`, which diffs the new record
+// against the stored one and keeps the following in step:
+//
+//   IndexKind::Status(status)  bounty ids in that status, by bounty id
+//   IndexKind::Deadline        (deadline, bounty_id), sorted
+//   IndexKind::Expiry          (deadline, bounty_id) of escrows still
+//                              holding funds, sorted
+//   IndexKind::Amount          (amount, bounty_id), sorted
+//   IndexKind::Created         (sequence, bounty_id): every escrow in
+//                              creation order
+//   DataKey::AggregateTotals   running totals per status
+//   IndexKey::Held(token)      remaining amount of the escrows in `token`
+//                              still holding funds (instance storage)
+//
+// An escrow's creation sequence is kept under `IndexKey::Sequence` so its
+// `Created` entry can be found again when the escrow is compacted. Compaction
+// also takes the escrow out of `DataKey::DepositorIndex`. INV-2
+// checks the contract balance against `IndexKey::Held` rather than summing
+// every escrow.
+//
+// Each index is split into shards of at most `MAX_SHARD_LEN` sorted entries,
+// stored under `IndexKey::Shard`, plus a directory under
+// `IndexKey::Directory` holding the lowest entry each shard may contain. An
+// escrow write reads the directory and rewrites one shard; the directory is
+// only rewritten when a shard is split, emptied or gains a new lowest entry
+// at the front of the index. Queries locate the first shard through the
+// directory and read forward only as far as the requested page.
+//
+// Entries within a shard are kept as two parallel vectors rather than a
+// vector of tuples: every tuple would be its own host object, which makes
+// each read-modify-write noticeably more expensive.
+//
+// Escrows written before the indexes existed are not in them until they are
+// next written or the admin runs `backfill_indexes` over their ids.
+// ============================================================================
+
+use crate::{crowdfund, legacy, metadata, ttl, AggregateStats, DataKey, Escrow, EscrowStatus};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Most entries a shard holds before it is split in two.
+pub(crate) const MAX_SHARD_LEN: u32 = 64;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexKind {
+    /// Bounty ids in one status. Every entry has key 0.
+    Status(EscrowStatus),
+    Deadline,
+    Amount,
+    /// Deadlines of escrows still holding funds (`Locked` or
+    /// `PartiallyRefunded`); settled escrows leave this index.
+    Expiry,
+    /// Every escrow, keyed by its creation sequence.
+    Created,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexKey {
+    /// IndexKind -> ShardDirectory
+    Directory(IndexKind),
+    /// (IndexKind, shard number) -> OrderedIndex of that shard's entries
+    Shard(IndexKind, u32),
+    /// bounty_id -> u64 key of the escrow's `IndexKind::Created` entry
+    Sequence(u64),
+    /// u64 sequence the next created escrow gets (instance storage)
+    NextSequence,
+    /// u32 number of entries in `IndexKind::Created` (instance storage)
+    CreatedCount,
+    /// token -> i128 remaining amount of escrows in that token still
+    /// holding funds (instance storage)
+    Held(Address),
+}
+
+/// Entries sorted by `(keys[i], ids[i])` ascending. Deadlines are widened to
+/// `i128` so every index shares one layout.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderedIndex {
+    pub keys: Vec<i128>,
+    pub ids: Vec<u64>,
+}
+
+/// Shards of one index in order. Every entry of `shards[i]` is at least
+/// `(fence_keys[i], fence_ids[i])` and below the next fence.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShardDirectory {
+    pub fence_keys: Vec<i128>,
+    pub fence_ids: Vec<u64>,
+    pub shards: Vec<u32>,
+    pub next_shard: u32,
+}
+
+/// Persist `escrow` under `bounty_id` and update every secondary index.
+pub(crate) fn store_escrow(env: &Env, bounty_id: u64, escrow: &Escrow) {
+    let key = DataKey::Escrow(bounty_id);
+    // A record the indexes have never seen is indexed from scratch.
+    let previous =
+        legacy::load_escrow(env, bounty_id).filter(|prev| is_indexed(env, bounty_id, prev));
+    env.storage().persistent().set(&key, escrow);
+
+    let mut totals = get_totals(env);
+    match &previous {
+        Some(prev) => {
+            if prev.status != escrow.status {
+                remove(env, &status_kind(&prev.status), 0, bounty_id);
+                insert(env, &status_kind(&escrow.status), 0, bounty_id);
+                metadata::sync_status(env, bounty_id, &prev.status, &escrow.status);
+            }
+            if prev.deadline != escrow.deadline {
+                remove(env, &IndexKind::Deadline, prev.deadline as i128, bounty_id);
+                insert(
+                    env,
+                    &IndexKind::Deadline,
+                    escrow.deadline as i128,
+                    bounty_id,
+                );
+            }
+            if prev.amount != escrow.amount {
+                remove(env, &IndexKind::Amount, prev.amount, bounty_id);
+                insert(env, &IndexKind::Amount, escrow.amount, bounty_id);
+            }
+            let (was_funded, is_funded) = (holds_funds(prev), holds_funds(escrow));
+            if was_funded != is_funded || (is_funded && prev.deadline != escrow.deadline) {
+                if was_funded {
+                    remove(env, &IndexKind::Expiry, prev.deadline as i128, bounty_id);
+                }
+                if is_funded {
+                    insert(env, &IndexKind::Expiry, escrow.deadline as i128, bounty_id);
+                }
+            }
+            if prev.token == escrow.token {
+                add_held(env, &escrow.token, held(escrow) - held(prev));
+            } else {
+                add_held(env, &prev.token, -held(prev));
+                add_held(env, &escrow.token, held(escrow));
+            }
+            apply_to_totals(&mut totals, prev, -1);
+        }
+        None => {
+            add_entries(env, bounty_id, escrow);
+            add_held(env, &escrow.token, held(escrow));
+        }
+    }
+    apply_to_totals(&mut totals, escrow, 1);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AggregateTotals, &totals);
+    ttl::extend(env, &DataKey::AggregateTotals);
+    ttl::touch_escrow(env, bounty_id);
+    ttl::extend_instance(env);
+}
+
+/// Index an escrow stored before the indexes existed. Returns `false` if
+/// there is no escrow under `bounty_id` or it is already indexed.
+pub(crate) fn backfill(env: &Env, bounty_id: u64) -> bool {
+    let escrow = match legacy::load_escrow(env, bounty_id) {
+        Some(escrow) if !is_indexed(env, bounty_id, &escrow) => escrow,
+        _ => return false,
+    };
+    add_entries(env, bounty_id, &escrow);
+    add_held(env, &escrow.token, held(&escrow));
+    let mut totals = get_totals(env);
+    apply_to_totals(&mut totals, &escrow, 1);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AggregateTotals, &totals);
+    ttl::extend(env, &DataKey::AggregateTotals);
+    true
+}
+
+/// Remove a compacted escrow's record and its index entries, including its
+/// listing under the depositor and every crowdfunding funder. Aggregate
+/// totals keep its contribution, so stats still cover archived history.
+pub(crate) fn drop_escrow(env: &Env, bounty_id: u64, escrow: &Escrow) {
+    for stake in crowdfund::contributions(env, bounty_id, escrow).iter() {
+        unlist_depositor(env, &stake.funder, bounty_id);
+    }
+    let sequence_key = IndexKey::Sequence(bounty_id);
+    if let Some(sequence) = env.storage().persistent().get::<_, u64>(&sequence_key) {
+        if remove(env, &IndexKind::Created, sequence as i128, bounty_id) {
+            set_created_count(env, created_count(env) - 1);
+        }
+        env.storage().persistent().remove(&sequence_key);
+    }
+    add_held(env, &escrow.token, -held(escrow));
+    remove(env, &status_kind(&escrow.status), 0, bounty_id);
+    remove(
+        env,
+        &IndexKind::Deadline,
+        escrow.deadline as i128,
+        bounty_id,
+    );
+    remove(env, &IndexKind::Amount, escrow.amount, bounty_id);
+    remove(env, &IndexKind::Expiry, escrow.deadline as i128, bounty_id);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Escrow(bounty_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EscrowExpiry(bounty_id));
+}
+
+/// Number of escrows in the creation index.
+pub(crate) fn created_count(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&IndexKey::CreatedCount)
+        .unwrap_or(0)
+}
+
+/// Key of `bounty_id`'s entry in the creation index, if it has one.
+pub(crate) fn sequence(env: &Env, bounty_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&IndexKey::Sequence(bounty_id))
+}
+
+/// Every bounty id in `kind`, in index order. For views and emergency
+/// paths only: reads the whole index.
+pub(crate) fn all_ids(env: &Env, kind: &IndexKind) -> Vec<u64> {
+    scan(env, kind, 0, 0, 0, u32::MAX).ids
+}
+
+/// Remaining amount held in `token` by escrows still holding funds.
+pub(crate) fn held_total(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&IndexKey::Held(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Up to `limit` bounty ids in `status` after skipping `offset`, in bounty
+/// id order.
+pub(crate) fn status_page(env: &Env, status: &EscrowStatus, offset: u32, limit: u32) -> Vec<u64> {
+    scan(env, &status_kind(status), 0, 0, offset, limit).ids
+}
+
+/// Up to `max` entries of `kind` not less than `(key, bounty_id)`, after
+/// skipping the first `skip` of them. Reads only the shards skipped over
+/// and those the result spans.
+pub(crate) fn scan(
+    env: &Env,
+    kind: &IndexKind,
+    key: i128,
+    bounty_id: u64,
+    skip: u32,
+    max: u32,
+) -> OrderedIndex {
+    let mut page = OrderedIndex {
+        keys: Vec::new(env),
+        ids: Vec::new(env),
+    };
+    let dir = load_directory(env, kind);
+    if dir.shards.is_empty() {
+        return page;
+    }
+    let mut skip = skip;
+    let mut slot = locate(&dir, key, bounty_id);
+    let mut first = true;
+    while slot < dir.shards.len() && page.keys.len() < max {
+        let entries = load_shard(env, kind, dir.shards.get_unchecked(slot));
+        slot += 1;
+        let mut pos = if first {
+            lower_bound(&entries, key, bounty_id)
+        } else {
+            0
+        };
+        first = false;
+        let available = entries.keys.len() - pos;
+        if skip >= available {
+            skip -= available;
+            continue;
+        }
+        pos += skip;
+        skip = 0;
+        while pos < entries.keys.len() && page.keys.len() < max {
+            page.keys.push_back(entries.keys.get_unchecked(pos));
+            page.ids.push_back(entries.ids.get_unchecked(pos));
+            pos += 1;
+        }
+    }
+    page
+}
+
+/// Position of the first entry not less than `(key, bounty_id)`.
+pub(crate) fn lower_bound(index: &OrderedIndex, key: i128, bounty_id: u64) -> u32 {
+    let (mut lo, mut hi) = (0u32, index.keys.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let entry = (index.keys.get_unchecked(mid), index.ids.get_unchecked(mid));
+        if entry < (key, bounty_id) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+pub(crate) fn get_totals(env: &Env) -> AggregateStats {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AggregateTotals)
+        .unwrap_or(AggregateStats {
+            total_locked: 0,
+            total_released: 0,
+            total_refunded: 0,
+            count_locked: 0,
+            count_released: 0,
+            count_refunded: 0,
+        })
+}
+
+/// Add (`sign = 1`) or remove (`sign = -1`) an escrow's contribution.
+fn apply_to_totals(totals: &mut AggregateStats, escrow: &Escrow, sign: i32) {
+    let amount = escrow.amount * sign as i128;
+    match escrow.status {
+        EscrowStatus::Locked => {
+            totals.total_locked += amount;
+            totals.count_locked = totals.count_locked.wrapping_add_signed(sign);
+        }
+        EscrowStatus::Released => {
+            totals.total_released += amount;
+            totals.count_released = totals.count_released.wrapping_add_signed(sign);
+        }
+        EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
+            totals.total_refunded += amount;
+            totals.count_refunded = totals.count_refunded.wrapping_add_signed(sign);
+        }
+        // Template escrows have 0 amount; no aggregate contribution
+        EscrowStatus::Template => {}
+    }
+}
+
+fn status_kind(status: &EscrowStatus) -> IndexKind {
+    IndexKind::Status(status.clone())
+}
+
+fn add_entries(env: &Env, bounty_id: u64, escrow: &Escrow) {
+    insert(env, &status_kind(&escrow.status), 0, bounty_id);
+    insert(
+        env,
+        &IndexKind::Deadline,
+        escrow.deadline as i128,
+        bounty_id,
+    );
+    insert(env, &IndexKind::Amount, escrow.amount, bounty_id);
+    if holds_funds(escrow) {
+        insert(env, &IndexKind::Expiry, escrow.deadline as i128, bounty_id);
+    }
+    let sequence_key = IndexKey::Sequence(bounty_id);
+    if !env.storage().persistent().has(&sequence_key) {
+        let sequence: u64 = env
+            .storage()
+            .instance()
+            .get(&IndexKey::NextSequence)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&IndexKey::NextSequence, &(sequence + 1));
+        env.storage().persistent().set(&sequence_key, &sequence);
+        ttl::extend(env, &sequence_key);
+        insert(env, &IndexKind::Created, sequence as i128, bounty_id);
+        set_created_count(env, created_count(env) + 1);
+    }
+}
+
+/// Amount an escrow contributes to its token's `Held` total.
+fn held(escrow: &Escrow) -> i128 {
+    if holds_funds(escrow) {
+        escrow.remaining_amount
+    } else {
+        0
+    }
+}
+
+fn add_held(env: &Env, token: &Address, delta: i128) {
+    if delta != 0 {
+        env.storage().instance().set(
+            &IndexKey::Held(token.clone()),
+            &(held_total(env, token) + delta),
+        );
+    }
+}
+
+fn unlist_depositor(env: &Env, depositor: &Address, bounty_id: u64) {
+    let key = DataKey::DepositorIndex(depositor.clone());
+    let Some(mut ids) = env.storage().persistent().get::<_, Vec<u64>>(&key) else {
+        return;
+    };
+    let Some(pos) = ids.first_index_of(bounty_id) else {
+        return;
+    };
+    ids.remove(pos);
+    if ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &ids);
+        ttl::extend(env, &key);
+    }
+}
+
+fn set_created_count(env: &Env, count: u32) {
+    env.storage()
+        .instance()
+        .set(&IndexKey::CreatedCount, &count);
+}
+
+fn holds_funds(escrow: &Escrow) -> bool {
+    escrow.status == EscrowStatus::Locked || escrow.status == EscrowStatus::PartiallyRefunded
+}
+
+/// Every indexed escrow has a deadline entry, so that one stands for all.
+fn is_indexed(env: &Env, bounty_id: u64, escrow: &Escrow) -> bool {
+    let dir = load_directory(env, &IndexKind::Deadline);
+    if dir.shards.is_empty() {
+        return false;
+    }
+    let key = escrow.deadline as i128;
+    let shard = dir.shards.get_unchecked(locate(&dir, key, bounty_id));
+    let entries = load_shard(env, &IndexKind::Deadline, shard);
+    is_at(
+        &entries,
+        lower_bound(&entries, key, bounty_id),
+        key,
+        bounty_id,
+    )
+}
+
+fn load_directory(env: &Env, kind: &IndexKind) -> ShardDirectory {
+    env.storage()
+        .persistent()
+        .get(&IndexKey::Directory(kind.clone()))
+        .unwrap_or(ShardDirectory {
+            fence_keys: Vec::new(env),
+            fence_ids: Vec::new(env),
+            shards: Vec::new(env),
+            next_shard: 0,
+        })
+}
+
+fn save_directory(env: &Env, kind: &IndexKind, dir: &ShardDirectory) {
+    let key = IndexKey::Directory(kind.clone());
+    env.storage().persistent().set(&key, dir);
+    ttl::extend(env, &key);
+}
+
+fn load_shard(env: &Env, kind: &IndexKind, shard: u32) -> OrderedIndex {
+    env.storage()
+        .persistent()
+        .get(&IndexKey::Shard(kind.clone(), shard))
+        .unwrap_or(OrderedIndex {
+            keys: Vec::new(env),
+            ids: Vec::new(env),
+        })
+}
+
+fn save_shard(env: &Env, kind: &IndexKind, shard: u32, entries: &OrderedIndex) {
+    let key = IndexKey::Shard(kind.clone(), shard);
+    env.storage().persistent().set(&key, entries);
+    ttl::extend(env, &key);
+}
+
+/// Directory slot of the shard that holds `(key, bounty_id)` if present:
+/// the last fence not above it, or the first shard.
+fn locate(dir: &ShardDirectory, key: i128, bounty_id: u64) -> u32 {
+    let (mut lo, mut hi) = (0u32, dir.shards.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let fence = (
+            dir.fence_keys.get_unchecked(mid),
+            dir.fence_ids.get_unchecked(mid),
+        );
+        if fence <= (key, bounty_id) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo.saturating_sub(1)
+}
+
+fn is_at(index: &OrderedIndex, pos: u32, key: i128, bounty_id: u64) -> bool {
+    pos < index.keys.len()
+        && index.keys.get_unchecked(pos) == key
+        && index.ids.get_unchecked(pos) == bounty_id
+}
+
+fn insert(env: &Env, kind: &IndexKind, key: i128, bounty_id: u64) {
+    let mut dir = load_directory(env, kind);
+    if dir.shards.is_empty() {
+        let shard = dir.next_shard;
+        dir.next_shard += 1;
+        dir.fence_keys.push_back(key);
+        dir.fence_ids.push_back(bounty_id);
+        dir.shards.push_back(shard);
+        let entries = OrderedIndex {
+            keys: Vec::from_array(env, [key]),
+            ids: Vec::from_array(env, [bounty_id]),
+        };
+        save_shard(env, kind, shard, &entries);
+        save_directory(env, kind, &dir);
+        return;
+    }
+
+    let slot = locate(&dir, key, bounty_id);
+    let shard = dir.shards.get_unchecked(slot);
+    let mut entries = load_shard(env, kind, shard);
+    let pos = lower_bound(&entries, key, bounty_id);
+    if is_at(&entries, pos, key, bounty_id) {
+        return;
+    }
+    entries.keys.insert(pos, key);
+    entries.ids.insert(pos, bounty_id);
+
+    let mut dir_changed = false;
+    // Only the first shard can receive an entry below its fence.
+    let fence = (
+        dir.fence_keys.get_unchecked(0),
+        dir.fence_ids.get_unchecked(0),
+    );
+    if slot == 0 && (key, bounty_id) < fence {
+        dir.fence_keys.set(0, key);
+        dir.fence_ids.set(0, bounty_id);
+        dir_changed = true;
+    }
+    if entries.keys.len() > MAX_SHARD_LEN {
+        let half = entries.keys.len() / 2;
+        let upper = OrderedIndex {
+            keys: entries.keys.slice(half..),
+            ids: entries.ids.slice(half..),
+        };
+        entries.keys = entries.keys.slice(..half);
+        entries.ids = entries.ids.slice(..half);
+        let new_shard = dir.next_shard;
+        dir.next_shard += 1;
+        dir.fence_keys.insert(slot + 1, upper.keys.get_unchecked(0));
+        dir.fence_ids.insert(slot + 1, upper.ids.get_unchecked(0));
+        dir.shards.insert(slot + 1, new_shard);
+        save_shard(env, kind, new_shard, &upper);
+        dir_changed = true;
+    }
+    save_shard(env, kind, shard, &entries);
+    if dir_changed {
+        save_directory(env, kind, &dir);
+    }
+}
+
+/// Returns `false` if the entry was not in the index.
+fn remove(env: &Env, kind: &IndexKind, key: i128, bounty_id: u64) -> bool {
+    let mut dir = load_directory(env, kind);
+    if dir.shards.is_empty() {
+        return false;
+    }
+    let slot = locate(&dir, key, bounty_id);
+    let shard = dir.shards.get_unchecked(slot);
+    let mut entries = load_shard(env, kind, shard);
+    let pos = lower_bound(&entries, key, bounty_id);
+    if !is_at(&entries, pos, key, bounty_id) {
+        return false;
+    }
+    entries.keys.remove(pos);
+    entries.ids.remove(pos);
+    if entries.keys.is_empty() {
+        env.storage()
+            .persistent()
+            .remove(&IndexKey::Shard(kind.clone(), shard));
+        dir.fence_keys.remove(slot);
+        dir.fence_ids.remove(slot);
+        dir.shards.remove(slot);
+        save_directory(env, kind, &dir);
+    } else {
+        save_shard(env, kind, shard, &entries);
+    }
+    true
+}
@@ -0,0 +1,132 @@
+// ============================================================================
+// Keeper-Driven Expiry Sweeps
+//
+// `batch_refund_expired` lets anyone refund escrows whose deadline has
+// passed, walking the expiry index (deadlines of escrows still holding
+// funds) from the oldest entry. The admin may
+// set a keeper reward, stored in instance storage:
+//
+//   KeeperKey::RewardBps     u32   share of each lock fee set aside for keepers
+//   KeeperKey::Pool(token)   i128  set-aside fees not yet paid to a keeper
+//
+// The reward comes out of fees, never out of the escrow: when a lock fee is
+// collected, `RewardBps` of it stays in the contract in the pool for that
+// token instead of going to the fee recipient. Sweeping an escrow pays the
+// keeper the same share of that escrow's lock fee, as far as the pool
+// covers it. Shares of escrows that settle without a sweep stay in the pool
+// until the admin sends it to the fee recipient with `withdraw_keeper_pool`.
+// Depositors and funders always get back exactly what `refund` would return.
+//
+// A sweep scans at most `MAX_SWEEP_SCAN` index entries and reports at most
+// `limit` items. When it stops early it
+// returns the last entry scanned as a cursor to resume from.
+// ============================================================================
+
+use crate::token_math;
+use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
+
+/// Upper bound on expiry index entries one sweep looks at.
+pub(crate) const MAX_SWEEP_SCAN: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeeperKey {
+    RewardBps,
+    Pool(Address),
+}
+
+/// Outcome of one expired escrow in a sweep.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepItem {
+    pub bounty_id: u64,
+    /// `true` when the escrow was refunded.
+    pub success: bool,
+    /// Why the escrow was skipped; zero when it was refunded.
+    pub error_code: u32,
+    /// Amount returned to the depositor or funders.
+    pub refunded: i128,
+    pub keeper_reward: i128,
+}
+
+/// Result of one `batch_refund_expired` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepReport {
+    pub items: Vec<SweepItem>,
+    /// Opaque resume point (the deadline and bounty id of the last entry
+    /// scanned); `None` once every expired entry has been scanned.
+    pub next_cursor: Option<Bytes>,
+}
+
+pub(crate) fn reward_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&KeeperKey::RewardBps)
+        .unwrap_or(0)
+}
+
+pub(crate) fn set_reward_bps(env: &Env, bps: u32) {
+    env.storage().instance().set(&KeeperKey::RewardBps, &bps);
+}
+
+/// Set-aside fees in `token` held for keeper rewards.
+pub(crate) fn pool(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&KeeperKey::Pool(token.clone()))
+        .unwrap_or(0)
+}
+
+fn set_pool(env: &Env, token: &Address, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&KeeperKey::Pool(token.clone()), &amount);
+}
+
+/// Forget the set-aside fees in `token`; the caller has moved them out.
+pub(crate) fn clear_pool(env: &Env, token: &Address) {
+    env.storage()
+        .instance()
+        .remove(&KeeperKey::Pool(token.clone()));
+}
+
+/// Keep the keeper share of a collected lock fee. Returns what is left for
+/// the fee recipient.
+pub(crate) fn set_aside(env: &Env, token: &Address, fee: i128) -> i128 {
+    let share = token_math::calculate_share(fee, reward_bps(env) as i128);
+    if share > 0 {
+        set_pool(env, token, pool(env, token) + share);
+    }
+    fee - share
+}
+
+/// Take the reward for sweeping an escrow that paid `lock_fee` out of the
+/// pool. Returns the amount to pay the keeper.
+pub(crate) fn take_reward(env: &Env, token: &Address, lock_fee: i128) -> i128 {
+    let share = token_math::calculate_share(lock_fee, reward_bps(env) as i128);
+    let available = pool(env, token);
+    let reward = share.min(available);
+    if reward > 0 {
+        set_pool(env, token, available - reward);
+    }
+    reward
+}
+
+/// Encode the `(deadline, bounty_id)` index entry a sweep stopped at.
+pub(crate) fn encode_cursor(env: &Env, deadline: u64, bounty_id: u64) -> Bytes {
+    let mut cursor = Bytes::from_array(env, &deadline.to_be_bytes());
+    cursor.append(&Bytes::from_array(env, &bounty_id.to_be_bytes()));
+    cursor
+}
+
+/// Inverse of `encode_cursor`; `None` for anything it did not produce.
+pub(crate) fn decode_cursor(cursor: &Bytes) -> Option<(u64, u64)> {
+    if cursor.len() != 16 {
+        return None;
+    }
+    let (mut deadline, mut bounty_id) = ([0u8; 8], [0u8; 8]);
+    cursor.slice(..8).copy_into_slice(&mut deadline);
+    cursor.slice(8..).copy_into_slice(&mut bounty_id);
+    Some((u64::from_be_bytes(deadline), u64::from_be_bytes(bounty_id)))
+}
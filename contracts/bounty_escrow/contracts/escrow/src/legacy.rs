@@ -0,0 +1,206 @@
+// ============================================================================
+// Legacy Storage Layouts
+//
+// A `contracttype` struct is stored as a map keyed by field name and only
+// decodes from a map holding exactly its fields, so adding a field to a
+// persisted struct makes records written by earlier versions unreadable.
+// Every read of `DataKey::Escrow` goes through `load_escrow`, and every read
+// of `DataKey::Capability` through `load_capability`; both check which
+// fields the stored map has and convert older layouts:
+//
+//   EscrowV0       before fees: no `lock_fee`, `release_fee` or `token`
+//   EscrowV1       before per-escrow assets: fees but no `token`
+//   CapabilityV0   before delegation: `bounty_id`, no `parent_id`
+//   CapabilityV1   before scopes: delegation fields, still `bounty_id`
+//
+// Missing fields take the values the contract used when the record was
+// written: no fees, the token the contract was initialized with, and a
+// single-bounty scope issued directly by the owner.
+// `migrate_escrows` and `migrate_capabilities` rewrite old records in the
+// current layout so later reads skip the conversion.
+// ============================================================================
+
+use crate::{
+    Capability, CapabilityAction, CapabilityScope, DataKey, Escrow, EscrowStatus, RefundRecord,
+};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+
+/// `Escrow` as stored before lock and release fees were charged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowV0 {
+    pub depositor: Address,
+    pub amount: i128,
+    pub remaining_amount: i128,
+    pub status: EscrowStatus,
+    pub deadline: u64,
+    pub refund_history: Vec<RefundRecord>,
+}
+
+/// `Escrow` as stored before each escrow recorded its own token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowV1 {
+    pub depositor: Address,
+    pub amount: i128,
+    pub remaining_amount: i128,
+    pub status: EscrowStatus,
+    pub deadline: u64,
+    pub refund_history: Vec<RefundRecord>,
+    pub lock_fee: i128,
+    pub release_fee: i128,
+}
+
+/// `Capability` as stored before capabilities could be delegated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapabilityV0 {
+    pub owner: Address,
+    pub holder: Address,
+    pub action: CapabilityAction,
+    pub bounty_id: u64,
+    pub amount_limit: i128,
+    pub remaining_amount: i128,
+    pub expiry: u64,
+    pub remaining_uses: u32,
+    pub revoked: bool,
+}
+
+/// `Capability` as stored before scopes wider than one bounty.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapabilityV1 {
+    pub owner: Address,
+    pub holder: Address,
+    pub action: CapabilityAction,
+    pub bounty_id: u64,
+    pub amount_limit: i128,
+    pub remaining_amount: i128,
+    pub expiry: u64,
+    pub remaining_uses: u32,
+    pub revoked: bool,
+    pub parent_id: Option<u64>,
+    pub delegation_chain: Vec<Address>,
+}
+
+fn raw(env: &Env, key: &DataKey) -> Option<Map<Symbol, Val>> {
+    env.storage().persistent().get(key)
+}
+
+fn default_token(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Token).unwrap()
+}
+
+fn is_current_escrow(raw: &Map<Symbol, Val>) -> bool {
+    raw.contains_key(symbol_short!("token"))
+}
+
+fn decode_escrow(env: &Env, raw: Map<Symbol, Val>) -> Escrow {
+    if is_current_escrow(&raw) {
+        return raw.to_val().into_val(env);
+    }
+    if raw.contains_key(symbol_short!("lock_fee")) {
+        let v1: EscrowV1 = raw.to_val().into_val(env);
+        return Escrow {
+            depositor: v1.depositor,
+            amount: v1.amount,
+            remaining_amount: v1.remaining_amount,
+            status: v1.status,
+            deadline: v1.deadline,
+            refund_history: v1.refund_history,
+            lock_fee: v1.lock_fee,
+            release_fee: v1.release_fee,
+            token: default_token(env),
+        };
+    }
+    let v0: EscrowV0 = raw.to_val().into_val(env);
+    Escrow {
+        depositor: v0.depositor,
+        amount: v0.amount,
+        remaining_amount: v0.remaining_amount,
+        status: v0.status,
+        deadline: v0.deadline,
+        refund_history: v0.refund_history,
+        lock_fee: 0,
+        release_fee: 0,
+        token: default_token(env),
+    }
+}
+
+fn is_current_capability(raw: &Map<Symbol, Val>) -> bool {
+    raw.contains_key(symbol_short!("scope"))
+}
+
+fn decode_capability(env: &Env, raw: Map<Symbol, Val>) -> Capability {
+    if is_current_capability(&raw) {
+        return raw.to_val().into_val(env);
+    }
+    if raw.contains_key(symbol_short!("parent_id")) {
+        let v1: CapabilityV1 = raw.to_val().into_val(env);
+        return Capability {
+            owner: v1.owner,
+            holder: v1.holder,
+            action: v1.action,
+            scope: CapabilityScope::Bounty(v1.bounty_id),
+            amount_limit: v1.amount_limit,
+            remaining_amount: v1.remaining_amount,
+            expiry: v1.expiry,
+            remaining_uses: v1.remaining_uses,
+            revoked: v1.revoked,
+            parent_id: v1.parent_id,
+            delegation_chain: v1.delegation_chain,
+        };
+    }
+    let v0: CapabilityV0 = raw.to_val().into_val(env);
+    Capability {
+        delegation_chain: Vec::from_array(env, [v0.owner.clone()]),
+        owner: v0.owner,
+        holder: v0.holder,
+        action: v0.action,
+        scope: CapabilityScope::Bounty(v0.bounty_id),
+        amount_limit: v0.amount_limit,
+        remaining_amount: v0.remaining_amount,
+        expiry: v0.expiry,
+        remaining_uses: v0.remaining_uses,
+        revoked: v0.revoked,
+        parent_id: None,
+    }
+}
+
+/// Read the escrow stored under `bounty_id` in whichever layout it was
+/// written.
+pub(crate) fn load_escrow(env: &Env, bounty_id: u64) -> Option<Escrow> {
+    raw(env, &DataKey::Escrow(bounty_id)).map(|raw| decode_escrow(env, raw))
+}
+
+/// Rewrite the escrow under `bounty_id` in the current layout. Returns
+/// `false` if there is none or it is already current.
+pub(crate) fn migrate_escrow(env: &Env, bounty_id: u64) -> bool {
+    let key = DataKey::Escrow(bounty_id);
+    let raw = match raw(env, &key) {
+        Some(raw) if !is_current_escrow(&raw) => raw,
+        _ => return false,
+    };
+    let escrow = decode_escrow(env, raw);
+    env.storage().persistent().set(&key, &escrow);
+    true
+}
+
+/// Read the capability stored under `capability_id` in whichever layout it
+/// was written.
+pub(crate) fn load_capability(env: &Env, capability_id: u64) -> Option<Capability> {
+    raw(env, &DataKey::Capability(capability_id)).map(|raw| decode_capability(env, raw))
+}
+
+/// Rewrite the capability under `capability_id` in the current layout.
+/// Returns `false` if there is none or it is already current.
+pub(crate) fn migrate_capability(env: &Env, capability_id: u64) -> bool {
+    let key = DataKey::Capability(capability_id);
+    let raw = match raw(env, &key) {
+        Some(raw) if !is_current_capability(&raw) => raw,
+        _ => return false,
+    };
+    let capability = decode_capability(env, raw);
+    env.storage().persistent().set(&key, &capability);
+    true
+}
@@ -1,7 +1,14 @@
 #![no_std]
+mod access_control;
+mod crowdfund;
 #[allow(dead_code)]
 mod events;
+mod indexes;
 mod invariants;
+mod keeper;
+mod legacy;
+mod metadata;
+mod milestones;
 mod multitoken_invariants;
 #[cfg(test)]
 mod test_metadata;
@@ -19,6 +26,8 @@ mod test_multi_token_fees;
 #[cfg(test)]
 mod test_rbac;
 mod traits;
+mod ttl;
+mod vouchers;
 
 use events::{
     emit_batch_funds_locked, emit_batch_funds_released, emit_bounty_initialized,
@@ -31,8 +40,8 @@ use events::{
     NewCycleCreatedEvent, TicketClaimed, TicketIssued, EVENT_VERSION_V2,
 };
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, BytesN,
+    Env, String, Symbol, Vec,
 };
 
 pub(crate) mod monitoring {
@@ -375,6 +384,16 @@ mod anti_abuse {
 
 const MAX_FEE_RATE: i128 = token_math::MAX_FEE_RATE;
 const MAX_BATCH_SIZE: u32 = 20;
+/// Largest page a query entrypoint returns, whatever `limit` asks for.
+const MAX_PAGE_SIZE: u32 = 50;
+/// Most index entries one `query_escrows` call examines.
+const MAX_QUERY_SCAN: u32 = 100;
+/// How many times a capability may be re-delegated below the one its owner
+/// issued.
+const MAX_DELEGATION_DEPTH: u32 = 3;
+/// Upper bound on scheduled promotions scanned on every fee resolution.
+const MAX_ACTIVE_PROMOTIONS: u32 = 10;
+const MAX_DISPUTE_EVIDENCE: u32 = 20;
 
 extern crate grainlify_core;
 use grainlify_core::asset;
@@ -384,7 +403,11 @@ use grainlify_core::asset;
 #[repr(u32)]
 pub enum Error {
     AlreadyInitialized = 1,
+    /// Also returned when redeeming a claim voucher before a voucher signer
+    /// and network id are configured
     NotInitialized = 2,
+    /// Also returned when another active escrow already covers the same
+    /// (repo_id, issue_id)
     BountyExists = 3,
     BountyNotFound = 4,
     FundsNotLocked = 5,
@@ -392,10 +415,14 @@ pub enum Error {
     Unauthorized = 7,
     InvalidFeeRate = 8,
     FeeRecipientNotSet = 9,
+    /// Also returned when metadata carries more than metadata::MAX_TAGS tags
     InvalidBatchSize = 10,
     BatchSizeMismatch = 11,
     DuplicateBountyId = 12,
-    /// Returned when amount is invalid (zero, negative, or exceeds available)
+    /// Returned when amount is invalid (zero, negative, or exceeds available).
+    /// Also returned when `release_split` shares are zero, repeat a
+    /// recipient or do not sum to 10_000 bps, and when splitting an amount
+    /// over crowdfunding stakes overflows
     InvalidAmount = 13,
     /// Returned when deadline is invalid (in the past or too far in the future)
     InvalidDeadline = 14,
@@ -415,9 +442,11 @@ pub enum Error {
     ClaimPending = 22,
     /// Returned when claim ticket is not found
     TicketNotFound = 23,
-    /// Returned when claim ticket has already been used (replay prevention)
+    /// Returned when claim ticket has already been used (replay prevention).
+    /// Also returned when a claim voucher's nonce is not the beneficiary's
+    /// next voucher nonce
     TicketAlreadyUsed = 24,
-    /// Returned when claim ticket has expired
+    /// Returned when claim ticket or claim voucher has expired
     TicketExpired = 25,
     CapabilityNotFound = 26,
     CapabilityExpired = 27,
@@ -427,7 +456,9 @@ pub enum Error {
     CapabilityUsesExhausted = 31,
     CapabilityExceedsAuthority = 32,
     InvalidAssetId = 33,
-    /// Returned when escrow is locked by owner/admin (Issue #675)
+    /// Returned when escrow is locked by owner/admin (Issue #675).
+    /// Also returned when a whole-escrow release, refund, claim, renewal or
+    /// dispute targets a milestone escrow; settle it per milestone instead
     EscrowLocked = 34,
     /// Returned when clone source not found or invalid (Issue #678)
     CloneSourceNotFound = 35,
@@ -437,6 +468,35 @@ pub enum Error {
     RenewalNotAllowed = 37,
     /// Returned when renewal parameters are invalid (Issue #679)
     InvalidRenewal = 38,
+    /// Returned when a promotional period id does not exist
+    PromotionNotFound = 39,
+    /// Returned when a promotional period window or rate is invalid
+    InvalidPromotionPeriod = 40,
+    /// Returned when MAX_ACTIVE_PROMOTIONS promotions are already scheduled
+    TooManyPromotions = 41,
+    /// Returned when a release at or above the multisig threshold lacks enough
+    /// distinct signer approvals for the target contributor
+    InsufficientApprovals = 42,
+    /// Returned when locking in an asset that is not on the allow-list
+    AssetNotAllowed = 43,
+    /// Returned when a dispute id does not exist
+    DisputeNotFound = 44,
+    /// Returned when opening a dispute on an escrow that already has one open
+    DisputeAlreadyOpen = 45,
+    /// Returned when release/refund is attempted while a dispute is open
+    DisputeOpen = 46,
+    /// Returned when acting on a dispute that has already been resolved
+    DisputeClosed = 47,
+    /// Returned when a dispute already holds MAX_DISPUTE_EVIDENCE entries
+    DisputeEvidenceLimit = 48,
+    /// Returned when a pagination cursor was issued for a different filter
+    /// or points at an escrow the index does not contain
+    InvalidCursor = 49,
+    /// Returned when a depositor or recipient is blacklisted, or is not
+    /// allow-listed while whitelist-only mode is enabled
+    ParticipantNotAllowed = 50,
+    // Contract specs cap error enums at 50 variants. New failure modes must
+    // reuse the closest existing code and document it on that variant.
 }
 
 #[contracttype]
@@ -445,6 +505,8 @@ pub struct EscrowMetadata {
     pub repo_id: u64,
     pub issue_id: u64,
     pub bounty_type: soroban_sdk::String,
+    /// Free-form labels, indexed for `query_escrows_by_tag`
+    pub tags: Vec<soroban_sdk::String>,
 }
 
 #[contracttype]
@@ -458,11 +520,13 @@ pub enum EscrowStatus {
     Template,
 }
 
+/// Mirrored field-for-field by `contracts/view-facade`; keep the two in sync.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Escrow {
     pub depositor: Address,
-    /// Total amount originally locked into this escrow.
+    /// Net amount locked into this escrow after the lock fee.
+    /// The gross deposit is `amount + lock_fee`.
     pub amount: i128,
     /// Amount still available for release; decremented on each partial_release.
     /// Reaches 0 when fully paid out, at which point status becomes Released.
@@ -470,6 +534,13 @@ pub struct Escrow {
     pub status: EscrowStatus,
     pub deadline: u64,
     pub refund_history: Vec<RefundRecord>,
+    /// Fee deducted from the gross deposit at lock time.
+    pub lock_fee: i128,
+    /// Cumulative fees deducted from released amounts.
+    pub release_fee: i128,
+    /// Token this escrow is denominated in. All transfers, refunds and fees
+    /// for the escrow move this asset.
+    pub token: asset::AssetId,
 }
 
 #[contracttype]
@@ -478,7 +549,6 @@ pub enum DataKey {
     Token,
     Escrow(u64), // bounty_id
     Metadata(u64),
-    EscrowIndex,             // Vec<u64> of all bounty_ids
     DepositorIndex(Address), // Vec<u64> of bounty_ids by depositor
     FeeConfig,               // Fee configuration
     RefundApproval(u64),     // bounty_id -> RefundApproval
@@ -520,6 +590,40 @@ pub enum DataKey {
     CycleLink(u64),
     /// How many times an escrow has been renewed (Issue #679): bounty_id -> u32
     CycleCount(u64),
+
+    /// Vec<AssetId> of assets currently accepted by `lock_funds_with_asset`
+    AllowedAssets,
+    /// Vec<AssetId> of every asset ever allowed; escrows may still hold removed ones
+    KnownAssets,
+
+    /// Vec<Address> allowed to resolve disputes
+    Arbiters,
+    /// u64 counter for generating dispute ids
+    DisputeCounter,
+    /// dispute_id -> Dispute
+    Dispute(u64),
+    /// bounty_id -> dispute_id of the currently open dispute
+    ActiveDispute(u64),
+    /// bounty_id -> Vec<u64> of every dispute id raised on the escrow
+    DisputeHistory(u64),
+
+    /// AggregateStats maintained incrementally on every escrow write
+    AggregateTotals,
+
+    /// bounty_id -> u32 ledger the escrow entry is known to live until
+    EscrowExpiry(u64),
+    /// bounty_id -> EscrowSummary left behind by `ArchiveMode::Summary`
+    EscrowSummary(u64),
+
+    /// address -> true while the participant is blacklisted
+    Blacklisted(Address),
+    /// address -> true while the participant is on the allow list
+    ParticipantWhitelist(Address),
+    /// bool: only allow-listed participants may deposit or receive funds
+    WhitelistMode,
+    // Contract specs cap enums at 50 variants. New modules key their
+    // storage with their own enum (see anti_abuse::AntiAbuseKey and
+    // metadata::MetadataKey).
 }
 
 #[contracttype]
@@ -529,6 +633,30 @@ pub struct EscrowWithId {
     pub escrow: Escrow,
 }
 
+/// Combined filter for `query_escrows`. An empty `statuses` list and a
+/// `None` depositor match everything; pass 0 and the type's MAX to open a
+/// range. `depositor` also matches escrows the address has contributed to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowFilter {
+    pub statuses: Vec<EscrowStatus>,
+    pub depositor: Option<Address>,
+    pub min_deadline: u64,
+    pub max_deadline: u64,
+    pub min_amount: i128,
+    pub max_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowPage {
+    pub items: Vec<EscrowWithId>,
+    /// Opaque resume point (last bounty_id followed by a fingerprint of the
+    /// filter); set whenever `has_more` is true.
+    pub next_cursor: Option<soroban_sdk::Bytes>,
+    pub has_more: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PauseFlags {
@@ -578,6 +706,49 @@ pub struct AutoArchiveConfig {
     pub cooldown_seconds: u64,
 }
 
+/// Where `archive_escrow_with_mode` leaves a terminal escrow's record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArchiveMode {
+    /// Mark archived only; the full record stays in persistent storage.
+    FlagOnly,
+    /// Move the full record to temporary storage, where it lapses instead
+    /// of accruing persistent rent.
+    Temporary,
+    /// Replace the record with an `EscrowSummary`, dropping refund history.
+    Summary,
+}
+
+/// Compact record of a settled escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowSummary {
+    pub depositor: Address,
+    pub token: asset::AssetId,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub completed_at: u64,
+}
+
+/// Tracked expiry of an escrow entry, from `get_escrows_nearing_expiry`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowExpiry {
+    pub bounty_id: u64,
+    /// 0 when the escrow predates expiry tracking.
+    pub live_until_ledger: u32,
+}
+
+/// One page of `get_escrows_nearing_expiry`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryPage {
+    pub items: Vec<EscrowExpiry>,
+    /// Creation sequence to pass as `start` for the next page; `None` once
+    /// every escrow has been scanned.
+    pub next_start: Option<u64>,
+}
+
 /// Public view of anti-abuse config (rate limit and cooldown).
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -606,8 +777,8 @@ pub struct PromotionalPeriod {
     pub end_time: u64,
     pub lock_fee_rate: i128, // Promotional lock fee rate (can be 0 for free)
     pub release_fee_rate: i128, // Promotional release fee rate (can be 0 for free)
-    pub is_global: bool,     // If true, applies to all operations
     pub enabled: bool,       // Can be disabled without deleting
+    pub activated: bool,     // Set once the activation event has been emitted
 }
 
 #[contracttype]
@@ -626,6 +797,26 @@ pub struct ReleaseApproval {
     pub approvals: Vec<Address>,
 }
 
+/// Storage keys for approvals that do not fit `ReleaseApproval`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ApprovalKey {
+    /// bounty_id -> SplitApproval
+    Split(u64),
+    /// bounty_id -> i128 released amount covered by consumed approvals
+    Approved(u64),
+}
+
+/// Multisig approval for one exact `release_split` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitApproval {
+    pub bounty_id: u64,
+    pub shares: Vec<(Address, u32)>,
+    pub remainder_to: Address,
+    pub approvals: Vec<Address>,
+}
+
 #[contracttype]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -645,6 +836,7 @@ pub enum DisputeOutcome {
     ResolvedByRefund = 2,
     CancelledByAdmin = 3,
     NoActionTaken = 4,
+    ResolvedBySplit = 5,
 }
 
 #[contracttype]
@@ -658,6 +850,38 @@ pub struct ClaimRecord {
     pub reason: DisputeReason,
 }
 
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
+}
+
+/// A dispute raised by the depositor or contributor on a Locked escrow.
+/// Release and refund are blocked until an arbiter resolves it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub dispute_id: u64,
+    pub bounty_id: u64,
+    pub opened_by: Address,
+    pub depositor: Address,
+    pub contributor: Address,
+    pub reason: DisputeReason,
+    /// Hashes of off-chain evidence submitted by either party.
+    pub evidence: Vec<soroban_sdk::BytesN<32>>,
+    pub status: DisputeStatus,
+    pub opened_at: u64,
+    pub resolved_at: u64,
+    pub resolved_by: Option<Address>,
+    /// `NoActionTaken` until the dispute is resolved.
+    pub outcome: DisputeOutcome,
+    /// Amount paid to the contributor on resolution.
+    pub contributor_amount: i128,
+    /// Amount returned to the depositor on resolution.
+    pub refund_amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CapabilityAction {
@@ -666,18 +890,38 @@ pub enum CapabilityAction {
     Refund,
 }
 
+/// Which escrows a capability may act on.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CapabilityScope {
+    /// A single escrow.
+    Bounty(u64),
+    /// Any escrow in the set.
+    Bounties(Vec<u64>),
+    /// Any escrow whose metadata names this repository.
+    Repo(u64),
+    /// Any escrow whose metadata has this bounty type.
+    BountyType(soroban_sdk::String),
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Capability {
     pub owner: Address,
     pub holder: Address,
     pub action: CapabilityAction,
-    pub bounty_id: u64,
+    /// Escrows covered; `remaining_amount` is one budget shared by all of them.
+    pub scope: CapabilityScope,
     pub amount_limit: i128,
     pub remaining_amount: i128,
     pub expiry: u64,
     pub remaining_uses: u32,
     pub revoked: bool,
+    /// Capability this one was delegated from; `None` when issued by the owner.
+    pub parent_id: Option<u64>,
+    /// Everyone who granted this authority: the owner, then each holder
+    /// that delegated it further, in order.
+    pub delegation_chain: Vec<Address>,
 }
 
 #[contracttype]
@@ -759,6 +1003,18 @@ pub struct ReleaseFundsItem {
     pub bounty_id: u64,
     pub contributor: Address,
 }
+
+/// Per-item outcome of a best-effort batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchItemResult {
+    pub bounty_id: u64,
+    /// `true` when the item was applied.
+    pub success: bool,
+    /// The `Error` the item failed with; zero when it succeeded.
+    pub error_code: u32,
+}
+
 /// A record of a single renewal event (Issue #679)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -803,6 +1059,7 @@ impl BountyEscrowContract {
         env.storage()
             .instance()
             .set(&DataKey::Token, &normalized_token);
+        Self::allow_asset(&env, &normalized_token);
 
         emit_bounty_initialized(
             &env,
@@ -849,6 +1106,7 @@ impl BountyEscrowContract {
         env.storage()
             .instance()
             .set(&DataKey::Token, &normalized_token);
+        Self::allow_asset(&env, &normalized_token);
 
         // Store chain and network identifiers
         env.storage().instance().set(&DataKey::ChainId, &chain_id);
@@ -869,12 +1127,112 @@ impl BountyEscrowContract {
         Ok(())
     }
 
+    /// Add or remove an asset from the lock allow-list (admin only).
+    ///
+    /// Removing an asset only blocks new locks; existing escrows in that asset
+    /// can still be released or refunded.
+    pub fn set_asset_allowed(env: Env, asset: asset::AssetId, allowed: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let asset = asset::normalize_asset_id(&env, &asset).map_err(|_| Error::InvalidAssetId)?;
+        if allowed {
+            Self::allow_asset(&env, &asset);
+        } else {
+            let mut allowed_assets = Self::get_allowed_assets(env.clone());
+            if let Some(idx) = allowed_assets.first_index_of(&asset) {
+                allowed_assets.remove(idx);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::AllowedAssets, &allowed_assets);
+            }
+        }
+        Ok(())
+    }
+
+    /// Assets currently accepted by `lock_funds_with_asset`.
+    pub fn get_allowed_assets(env: Env) -> Vec<asset::AssetId> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedAssets)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Contract balance of a specific asset.
+    pub fn get_asset_balance(env: Env, asset: asset::AssetId) -> Result<i128, Error> {
+        if !env.storage().instance().has(&DataKey::Token) {
+            return Err(Error::NotInitialized);
+        }
+        let client = token::Client::new(&env, &asset);
+        Ok(client.balance(&env.current_contract_address()))
+    }
+
+    fn allow_asset(env: &Env, asset: &asset::AssetId) {
+        let mut allowed_assets = Self::get_allowed_assets(env.clone());
+        if !allowed_assets.contains(asset) {
+            allowed_assets.push_back(asset.clone());
+            env.storage()
+                .instance()
+                .set(&DataKey::AllowedAssets, &allowed_assets);
+        }
+        let mut known: Vec<asset::AssetId> = env
+            .storage()
+            .instance()
+            .get(&DataKey::KnownAssets)
+            .unwrap_or(Vec::new(env));
+        if !known.contains(asset) {
+            known.push_back(asset.clone());
+            env.storage().instance().set(&DataKey::KnownAssets, &known);
+        }
+    }
+
     /// Calculate fee using floor rounding. Delegates to `token_math::calculate_fee`.
     #[allow(dead_code)]
     fn calculate_fee(amount: i128, fee_rate: i128) -> i128 {
         token_math::calculate_fee(amount, fee_rate)
     }
 
+    /// Pay `fee` out of the contract balance to the configured fee recipient and
+    /// emit `FeeCollected`. No-op when the fee rounds down to zero.
+    fn transfer_fee(
+        env: &Env,
+        client: &token::Client,
+        bounty_id: u64,
+        fee: i128,
+        fee_rate: i128,
+        operation_type: events::FeeOperationType,
+    ) {
+        if fee <= 0 {
+            return;
+        }
+        // Lock fees fund keeper rewards; see `keeper`.
+        let fee = match operation_type {
+            events::FeeOperationType::Lock => keeper::set_aside(env, &client.address, fee),
+            events::FeeOperationType::Release => fee,
+        };
+        if fee <= 0 {
+            return;
+        }
+        let recipient = Self::get_fee_config_internal(env).fee_recipient;
+        client.transfer(&env.current_contract_address(), &recipient, &fee);
+        events::emit_fee_collected(
+            env,
+            events::FeeCollected {
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                operation_type,
+                amount: fee,
+                fee_rate,
+                recipient,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
     /// Get fee configuration (internal helper)
     fn get_fee_config_internal(env: &Env) -> FeeConfig {
         env.storage()
@@ -1045,47 +1403,45 @@ impl BountyEscrowContract {
             return Err(Error::NotPaused);
         }
 
-        let token_address: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let token_client = token::TokenClient::new(&env, &token_address);
-
         let contract_address = env.current_contract_address();
-        let balance = token_client.balance(&contract_address);
-
-        if balance > 0 {
-            // INTERACTION: external token transfer is last
-            token_client.transfer(&contract_address, &target, &balance);
-            events::emit_emergency_withdraw(
-                &env,
-                events::EmergencyWithdrawEvent {
-                    admin,
-                    recipient: target,
-                    amount: balance,
-                    timestamp: env.ledger().timestamp(),
-                },
-            );
+        let assets: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::KnownAssets)
+            .unwrap_or(Vec::new(&env));
+        for token_address in assets.iter() {
+            let token_client = token::TokenClient::new(&env, &token_address);
+            let balance = token_client.balance(&contract_address);
+            // The withdrawal takes the keeper pool along with the escrows.
+            keeper::clear_pool(&env, &token_address);
+
+            if balance > 0 {
+                // INTERACTION: external token transfer is last
+                token_client.transfer(&contract_address, &target, &balance);
+                events::emit_emergency_withdraw(
+                    &env,
+                    events::EmergencyWithdrawEvent {
+                        admin: admin.clone(),
+                        recipient: target.clone(),
+                        token: token_address.clone(),
+                        amount: balance,
+                        timestamp: env.ledger().timestamp(),
+                    },
+                );
+            }
         }
 
         // Zero out all active escrows to maintain INV-2 invariant.
         // The funds have been withdrawn, so escrow records must reflect this.
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        for bounty_id in index.iter() {
-            if let Some(mut escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
+        let funded = indexes::all_ids(&env, &indexes::IndexKind::Expiry);
+        for bounty_id in funded.iter() {
+            if let Some(mut escrow) = legacy::load_escrow(&env, bounty_id) {
                 if escrow.status == EscrowStatus::Locked
                     || escrow.status == EscrowStatus::PartiallyRefunded
                 {
                     escrow.remaining_amount = 0;
                     escrow.status = EscrowStatus::Refunded;
-                    env.storage()
-                        .persistent()
-                        .set(&DataKey::Escrow(bounty_id), &escrow);
+                    indexes::store_escrow(&env, bounty_id, &escrow);
                 }
             }
         }
@@ -1121,11 +1477,7 @@ impl BountyEscrowContract {
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         let is_depositor = caller == escrow.depositor;
         let is_admin = caller == admin;
@@ -1162,11 +1514,7 @@ impl BountyEscrowContract {
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         let is_depositor = caller == escrow.depositor;
         let is_admin = caller == admin;
@@ -1222,20 +1570,35 @@ impl BountyEscrowContract {
 
     /// Archive a single escrow after completion cooldown (Issue #684). Admin only.
     pub fn archive_escrow(env: Env, bounty_id: u64) -> Result<(), Error> {
+        Self::archive_escrow_with_mode(env, bounty_id, ArchiveMode::FlagOnly)
+    }
+
+    /// Archive a terminal escrow and optionally compact its record to cut
+    /// rent. Admin only. Compaction also applies to an escrow that was
+    /// previously archived flag-only; compacted ids can never be reused.
+    pub fn archive_escrow_with_mode(
+        env: Env,
+        bounty_id: u64,
+        mode: ArchiveMode,
+    ) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
-        }
         let already: bool = env
             .storage()
             .persistent()
             .get(&DataKey::Archived(bounty_id))
             .unwrap_or(false);
-        if already {
+        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            // Already compacted
+            if already {
+                return Ok(());
+            }
+            return Err(Error::BountyNotFound);
+        }
+        if already && mode == ArchiveMode::FlagOnly {
             return Ok(());
         }
         let completed_at: u64 = env
@@ -1246,16 +1609,45 @@ impl BountyEscrowContract {
         if completed_at == 0 {
             return Err(Error::ArchiveCooldownNotElapsed); // not in terminal state
         }
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
         let config = Self::get_auto_archive_config(env.clone());
         let now = env.ledger().timestamp();
         if config.enabled && now < completed_at.saturating_add(config.cooldown_seconds) {
             return Err(Error::ArchiveCooldownNotElapsed);
         }
+
+        match mode {
+            ArchiveMode::FlagOnly => {}
+            ArchiveMode::Temporary => {
+                let key = DataKey::Escrow(bounty_id);
+                env.storage().temporary().set(&key, &escrow);
+                env.storage().temporary().extend_ttl(
+                    &key,
+                    ttl::ARCHIVE_TEMPORARY_TTL,
+                    ttl::ARCHIVE_TEMPORARY_TTL,
+                );
+                indexes::drop_escrow(&env, bounty_id, &escrow);
+            }
+            ArchiveMode::Summary => {
+                let key = DataKey::EscrowSummary(bounty_id);
+                env.storage().persistent().set(
+                    &key,
+                    &EscrowSummary {
+                        depositor: escrow.depositor.clone(),
+                        token: escrow.token.clone(),
+                        amount: escrow.amount,
+                        status: escrow.status.clone(),
+                        completed_at,
+                    },
+                );
+                ttl::extend(&env, &key);
+                indexes::drop_escrow(&env, bounty_id, &escrow);
+            }
+        }
+
+        if already {
+            return Ok(());
+        }
         env.storage()
             .persistent()
             .set(&DataKey::Archived(bounty_id), &true);
@@ -1275,6 +1667,179 @@ impl BountyEscrowContract {
         Ok(())
     }
 
+    /// Compact view of an escrow, available before and after compaction.
+    /// `completed_at` is 0 while the escrow is still active.
+    pub fn get_escrow_summary(env: Env, bounty_id: u64) -> Result<EscrowSummary, Error> {
+        if let Some(summary) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::EscrowSummary(bounty_id))
+        {
+            return Ok(summary);
+        }
+        let escrow = Self::get_escrow_info(env.clone(), bounty_id)?;
+        Ok(EscrowSummary {
+            depositor: escrow.depositor,
+            token: escrow.token,
+            amount: escrow.amount,
+            status: escrow.status,
+            completed_at: env
+                .storage()
+                .persistent()
+                .get(&DataKey::CompletedAt(bounty_id))
+                .unwrap_or(0),
+        })
+    }
+
+    /// Extend the storage TTL of the given escrows to the full policy
+    /// target. Open to anyone (keepers, depositors): paying rent cannot
+    /// change escrow state. Unknown or compacted ids are skipped; returns
+    /// the number of escrows bumped.
+    pub fn bump_escrows(env: Env, bounty_ids: Vec<u64>) -> Result<u32, Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        if bounty_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let mut bumped = 0u32;
+        for bounty_id in bounty_ids.iter() {
+            if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+                continue;
+            }
+            ttl::bump_escrow(&env, bounty_id);
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Metadata(bounty_id))
+            {
+                ttl::extend(&env, &DataKey::Metadata(bounty_id));
+            }
+            bumped += 1;
+        }
+        ttl::extend_instance(&env);
+        Ok(bumped)
+    }
+
+    /// Rewrite escrows stored in an older layout (see `legacy`) in the
+    /// current one. Admin only. Reads already convert old records, so this
+    /// only saves the conversion on later reads; ids that are unknown or
+    /// already current are skipped. Returns the number rewritten.
+    pub fn migrate_escrows(env: Env, bounty_ids: Vec<u64>) -> Result<u32, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        if bounty_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let mut migrated = 0u32;
+        for bounty_id in bounty_ids.iter() {
+            if legacy::migrate_escrow(&env, bounty_id) {
+                migrated += 1;
+            }
+        }
+        Ok(migrated)
+    }
+
+    /// Rewrite capabilities stored before delegation or scopes (see
+    /// `legacy`) in the current layout. Same rules as `migrate_escrows`.
+    pub fn migrate_capabilities(env: Env, capability_ids: Vec<u64>) -> Result<u32, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        if capability_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let mut migrated = 0u32;
+        for capability_id in capability_ids.iter() {
+            if legacy::migrate_capability(&env, capability_id) {
+                migrated += 1;
+            }
+        }
+        Ok(migrated)
+    }
+
+    /// Add escrows stored before the secondary indexes existed (see
+    /// `indexes`) to the status, deadline and amount indexes and the
+    /// aggregate totals. Admin only; ids that are unknown or already indexed
+    /// are skipped. Returns the number indexed.
+    pub fn backfill_indexes(env: Env, bounty_ids: Vec<u64>) -> Result<u32, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        if bounty_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let mut indexed = 0u32;
+        for bounty_id in bounty_ids.iter() {
+            if indexes::backfill(&env, bounty_id) {
+                indexed += 1;
+            }
+        }
+        Ok(indexed)
+    }
+
+    /// Escrows whose tracked expiry falls within `within_ledgers` of the
+    /// current ledger, in creation order. Candidates for `bump_escrows`.
+    ///
+    /// Scans the creation index from sequence `start` (0 for the oldest
+    /// escrow), examining at most `MAX_QUERY_SCAN` escrows and returning at
+    /// most `limit` (capped at `MAX_PAGE_SIZE`). Pass `next_start` back in to
+    /// continue; a page may be empty while `next_start` is still set.
+    pub fn get_escrows_nearing_expiry(
+        env: Env,
+        within_ledgers: u32,
+        start: u64,
+        limit: u32,
+    ) -> ExpiryPage {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let horizon = env.ledger().sequence().saturating_add(within_ledgers);
+        // One entry past the scan bound tells whether anything is left.
+        let window = indexes::scan(
+            &env,
+            &indexes::IndexKind::Created,
+            start as i128,
+            0,
+            0,
+            MAX_QUERY_SCAN + 1,
+        );
+        let scanned = window.ids.len().min(MAX_QUERY_SCAN);
+        let mut items = Vec::new(&env);
+        let mut pos = 0;
+
+        while pos < scanned && items.len() < limit {
+            let bounty_id = window.ids.get_unchecked(pos);
+            pos += 1;
+            let live_until_ledger: u32 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowExpiry(bounty_id))
+                .unwrap_or(0);
+            if live_until_ledger > horizon {
+                continue;
+            }
+            items.push_back(EscrowExpiry {
+                bounty_id,
+                live_until_ledger,
+            });
+        }
+        let next_start = if pos < window.ids.len() {
+            Some(window.keys.get_unchecked(pos) as u64)
+        } else {
+            None
+        };
+        ExpiryPage { items, next_start }
+    }
+
     /// Clone an escrow to create a new instance with same config, new owner (Issue #678).
     /// New escrow is created in Template status with 0 amount; new_owner must call lock_funds to add funds.
     pub fn clone_escrow(
@@ -1295,14 +1860,14 @@ impl BountyEscrowContract {
             .storage()
             .persistent()
             .has(&DataKey::Escrow(new_bounty_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Archived(new_bounty_id))
         {
             return Err(Error::BountyExists);
         }
-        let source: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(source_bounty_id))
-            .unwrap();
+        let source: Escrow = legacy::load_escrow(&env, source_bounty_id).unwrap();
         let template = Escrow {
             depositor: new_depositor.clone(),
             amount: 0,
@@ -1310,30 +1875,23 @@ impl BountyEscrowContract {
             status: EscrowStatus::Template,
             deadline: source.deadline,
             refund_history: vec![&env],
+            lock_fee: 0,
+            release_fee: 0,
+            token: source.token.clone(),
         };
         invariants::assert_escrow(&env, &template);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(new_bounty_id), &template);
-        let mut index: Vec<u64> = env
+        indexes::store_escrow(&env, new_bounty_id, &template);
+        let mut depositor_index: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.push_back(new_bounty_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::EscrowIndex, &index);
-        let mut depositor_index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::DepositorIndex(new_depositor.clone()))
+            .get(&DataKey::DepositorIndex(new_depositor.clone()))
             .unwrap_or(Vec::new(&env));
         depositor_index.push_back(new_bounty_id);
         env.storage().persistent().set(
             &DataKey::DepositorIndex(new_depositor.clone()),
             &depositor_index,
         );
+        ttl::extend(&env, &DataKey::DepositorIndex(new_depositor.clone()));
         emit_escrow_cloned(
             &env,
             EscrowClonedEvent {
@@ -1359,6 +1917,13 @@ impl BountyEscrowContract {
         false
     }
 
+    /// Check if an unresolved dispute is blocking this escrow.
+    fn has_open_dispute(env: &Env, bounty_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::ActiveDispute(bounty_id))
+    }
+
     /// Check if escrow is owner-locked (Issue #675). Distinct from global pause.
     fn is_escrow_locked(env: &Env, bounty_id: u64) -> bool {
         let key = DataKey::EscrowLock(bounty_id);
@@ -1395,23 +1960,66 @@ impl BountyEscrowContract {
     }
 
     fn load_capability(env: &Env, capability_id: u64) -> Result<Capability, Error> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Capability(capability_id))
-            .ok_or(Error::CapabilityNotFound)
+        legacy::load_capability(env, capability_id).ok_or(Error::CapabilityNotFound)
+    }
+
+    /// Every capability `capability` was delegated from, nearest first.
+    fn load_ancestors(env: &Env, capability: &Capability) -> Result<Vec<(u64, Capability)>, Error> {
+        let mut ancestors = Vec::new(env);
+        let mut parent_id = capability.parent_id;
+        while let Some(id) = parent_id {
+            let parent = Self::load_capability(env, id)?;
+            parent_id = parent.parent_id;
+            ancestors.push_back((id, parent));
+        }
+        Ok(ancestors)
+    }
+
+    /// Revocation, expiry and use checks shared by a capability and its
+    /// ancestors.
+    fn check_capability_usable(env: &Env, capability: &Capability) -> Result<(), Error> {
+        if capability.revoked {
+            return Err(Error::CapabilityRevoked);
+        }
+        if env.ledger().timestamp() > capability.expiry {
+            return Err(Error::CapabilityExpired);
+        }
+        if capability.remaining_uses == 0 {
+            return Err(Error::CapabilityUsesExhausted);
+        }
+        Ok(())
+    }
+
+    /// Whether `scope` covers `bounty_id`. Repo and bounty-type scopes
+    /// match on the escrow's current metadata.
+    fn scope_covers(env: &Env, scope: &CapabilityScope, bounty_id: u64) -> bool {
+        match scope {
+            CapabilityScope::Bounty(id) => *id == bounty_id,
+            CapabilityScope::Bounties(ids) => ids.contains(bounty_id),
+            CapabilityScope::Repo(repo_id) => {
+                metadata::load_metadata(env, bounty_id).is_some_and(|m| m.repo_id == *repo_id)
+            }
+            CapabilityScope::BountyType(bounty_type) => metadata::load_metadata(env, bounty_id)
+                .is_some_and(|m| m.bounty_type == *bounty_type),
+        }
     }
 
     fn validate_capability_scope_at_issue(
         env: &Env,
         owner: &Address,
         action: &CapabilityAction,
-        bounty_id: u64,
+        scope: &CapabilityScope,
         amount_limit: i128,
     ) -> Result<(), Error> {
         if amount_limit <= 0 {
             return Err(Error::InvalidAmount);
         }
 
+        let bounty_id = match scope {
+            CapabilityScope::Bounty(id) => *id,
+            _ => return Self::validate_multi_bounty_scope(env, owner, action, scope, amount_limit),
+        };
+
         match action {
             CapabilityAction::Claim => {
                 let claim: ClaimRecord = env
@@ -1441,11 +2049,8 @@ impl BountyEscrowContract {
                 if admin != owner.clone() {
                     return Err(Error::Unauthorized);
                 }
-                let escrow: Escrow = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::Escrow(bounty_id))
-                    .ok_or(Error::BountyNotFound)?;
+                let escrow: Escrow =
+                    legacy::load_escrow(env, bounty_id).ok_or(Error::BountyNotFound)?;
                 if escrow.status != EscrowStatus::Locked {
                     return Err(Error::FundsNotLocked);
                 }
@@ -1462,11 +2067,8 @@ impl BountyEscrowContract {
                 if admin != owner.clone() {
                     return Err(Error::Unauthorized);
                 }
-                let escrow: Escrow = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::Escrow(bounty_id))
-                    .ok_or(Error::BountyNotFound)?;
+                let escrow: Escrow =
+                    legacy::load_escrow(env, bounty_id).ok_or(Error::BountyNotFound)?;
                 if escrow.status != EscrowStatus::Locked
                     && escrow.status != EscrowStatus::PartiallyRefunded
                 {
@@ -1481,9 +2083,59 @@ impl BountyEscrowContract {
         Ok(())
     }
 
+    /// Scopes over more than one escrow carry admin authority only: claims
+    /// belong to a single recipient on a single bounty. The amount limit is
+    /// a budget and is not tied to what the escrows hold today; each payout
+    /// is checked against its escrow on use.
+    fn validate_multi_bounty_scope(
+        env: &Env,
+        owner: &Address,
+        action: &CapabilityAction,
+        scope: &CapabilityScope,
+        amount_limit: i128,
+    ) -> Result<(), Error> {
+        if *action == CapabilityAction::Claim {
+            return Err(Error::CapabilityActionMismatch);
+        }
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if admin != owner.clone() {
+            return Err(Error::Unauthorized);
+        }
+        if let CapabilityScope::Bounties(ids) = scope {
+            if ids.is_empty() || ids.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidBatchSize);
+            }
+            for id in ids.iter() {
+                if !env.storage().persistent().has(&DataKey::Escrow(id)) {
+                    return Err(Error::BountyNotFound);
+                }
+            }
+        }
+        if *action == CapabilityAction::Release {
+            Self::check_scoped_release_budget(env, amount_limit)?;
+        }
+        Ok(())
+    }
+
+    /// Multisig approvals are counted per escrow, so a release budget shared
+    /// across escrows could pay out more than the threshold without any.
+    /// Such budgets must stay below the threshold while multisig is on.
+    fn check_scoped_release_budget(env: &Env, budget: i128) -> Result<(), Error> {
+        let config = Self::get_multisig_config(env.clone());
+        if config.required_signatures > 0 && budget >= config.threshold_amount {
+            return Err(Error::CapabilityExceedsAuthority);
+        }
+        Ok(())
+    }
+
     fn ensure_owner_still_authorized(
         env: &Env,
         capability: &Capability,
+        bounty_id: u64,
         requested_amount: i128,
     ) -> Result<(), Error> {
         if requested_amount <= 0 {
@@ -1495,7 +2147,7 @@ impl BountyEscrowContract {
                 let claim: ClaimRecord = env
                     .storage()
                     .persistent()
-                    .get(&DataKey::PendingClaim(capability.bounty_id))
+                    .get(&DataKey::PendingClaim(bounty_id))
                     .ok_or(Error::BountyNotFound)?;
                 if claim.claimed {
                     return Err(Error::FundsNotLocked);
@@ -1519,11 +2171,8 @@ impl BountyEscrowContract {
                 if admin != capability.owner {
                     return Err(Error::Unauthorized);
                 }
-                let escrow: Escrow = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::Escrow(capability.bounty_id))
-                    .ok_or(Error::BountyNotFound)?;
+                let escrow: Escrow =
+                    legacy::load_escrow(env, bounty_id).ok_or(Error::BountyNotFound)?;
                 if escrow.status != EscrowStatus::Locked {
                     return Err(Error::FundsNotLocked);
                 }
@@ -1540,11 +2189,8 @@ impl BountyEscrowContract {
                 if admin != capability.owner {
                     return Err(Error::Unauthorized);
                 }
-                let escrow: Escrow = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::Escrow(capability.bounty_id))
-                    .ok_or(Error::BountyNotFound)?;
+                let escrow: Escrow =
+                    legacy::load_escrow(env, bounty_id).ok_or(Error::BountyNotFound)?;
                 if escrow.status != EscrowStatus::Locked
                     && escrow.status != EscrowStatus::PartiallyRefunded
                 {
@@ -1574,7 +2220,7 @@ impl BountyEscrowContract {
         if capability.action != expected_action {
             return Err(Error::CapabilityActionMismatch);
         }
-        if capability.bounty_id != bounty_id {
+        if !Self::scope_covers(env, &capability.scope, bounty_id) {
             return Err(Error::CapabilityActionMismatch);
         }
         if capability.holder != holder.clone() {
@@ -1590,14 +2236,44 @@ impl BountyEscrowContract {
             return Err(Error::CapabilityAmountExceeded);
         }
 
+        // A delegated capability spends its ancestors' budgets too, so no
+        // branch of the delegation tree can outspend the grant it came from.
+        let ancestors = Self::load_ancestors(env, &capability)?;
+        for (_, ancestor) in ancestors.iter() {
+            Self::check_capability_usable(env, &ancestor)?;
+            if amount > ancestor.remaining_amount {
+                return Err(Error::CapabilityAmountExceeded);
+            }
+        }
+        // Re-checked at use: the multisig threshold may have dropped since
+        // issue. The root's scope and limit bound every branch of the tree.
+        if capability.action == CapabilityAction::Release {
+            let (scope, limit) = match ancestors.last() {
+                Some((_, root)) => (root.scope, root.amount_limit),
+                None => (capability.scope.clone(), capability.amount_limit),
+            };
+            if !matches!(scope, CapabilityScope::Bounty(_)) {
+                Self::check_scoped_release_budget(env, limit)?;
+            }
+        }
+
         holder.require_auth();
-        Self::ensure_owner_still_authorized(env, &capability, amount)?;
+        Self::ensure_owner_still_authorized(env, &capability, bounty_id, amount)?;
 
         capability.remaining_amount -= amount;
         capability.remaining_uses -= 1;
         env.storage()
             .persistent()
             .set(&DataKey::Capability(capability_id), &capability);
+        ttl::extend(env, &DataKey::Capability(capability_id));
+        for (ancestor_id, mut ancestor) in ancestors.iter() {
+            ancestor.remaining_amount -= amount;
+            ancestor.remaining_uses -= 1;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Capability(ancestor_id), &ancestor);
+            ttl::extend(env, &DataKey::Capability(ancestor_id));
+        }
 
         events::emit_capability_used(
             env,
@@ -1626,6 +2302,34 @@ impl BountyEscrowContract {
         amount_limit: i128,
         expiry: u64,
         max_uses: u32,
+    ) -> Result<u64, Error> {
+        Self::issue_scoped_capability(
+            env,
+            owner,
+            holder,
+            action,
+            CapabilityScope::Bounty(bounty_id),
+            amount_limit,
+            expiry,
+            max_uses,
+        )
+    }
+
+    /// Issue a capability over a set of escrows, a repository or a bounty
+    /// type. `amount_limit` is a budget shared by every escrow in scope.
+    /// Scopes wider than one bounty are only available for release and
+    /// refund capabilities issued by the admin, and while multisig is on a
+    /// release budget must stay below its threshold.
+    #[allow(clippy::too_many_arguments)]
+    pub fn issue_scoped_capability(
+        env: Env,
+        owner: Address,
+        holder: Address,
+        action: CapabilityAction,
+        scope: CapabilityScope,
+        amount_limit: i128,
+        expiry: u64,
+        max_uses: u32,
     ) -> Result<u64, Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
@@ -1640,33 +2344,130 @@ impl BountyEscrowContract {
         }
 
         owner.require_auth();
-        Self::validate_capability_scope_at_issue(&env, &owner, &action, bounty_id, amount_limit)?;
+        Self::validate_capability_scope_at_issue(&env, &owner, &action, &scope, amount_limit)?;
 
         let capability_id = Self::next_capability_id(&env);
         let capability = Capability {
             owner: owner.clone(),
             holder: holder.clone(),
             action: action.clone(),
-            bounty_id,
+            scope: scope.clone(),
             amount_limit,
             remaining_amount: amount_limit,
             expiry,
             remaining_uses: max_uses,
             revoked: false,
+            parent_id: None,
+            delegation_chain: Vec::from_array(&env, [owner.clone()]),
         };
 
         env.storage()
             .persistent()
             .set(&DataKey::Capability(capability_id), &capability);
+        ttl::extend(&env, &DataKey::Capability(capability_id));
+
+        match scope {
+            CapabilityScope::Bounty(bounty_id) => events::emit_capability_issued(
+                &env,
+                events::CapabilityIssued {
+                    capability_id,
+                    owner,
+                    holder,
+                    action,
+                    bounty_id,
+                    amount_limit,
+                    expires_at: expiry,
+                    max_uses,
+                    timestamp: now,
+                },
+            ),
+            scope => events::emit_capability_scope_issued(
+                &env,
+                events::CapabilityScopeIssued {
+                    capability_id,
+                    owner,
+                    holder,
+                    action,
+                    scope,
+                    amount_limit,
+                    expires_at: expiry,
+                    max_uses,
+                    timestamp: now,
+                },
+            ),
+        }
+
+        Ok(capability_id)
+    }
+
+    /// Delegate part of a capability to `new_holder`. Only the current
+    /// holder may delegate, and the child may not exceed what is left of
+    /// the parent: its amount, uses and expiry must be equal or narrower.
+    /// Spending the child also spends the parent, and revoking the parent
+    /// disables the child.
+    #[allow(clippy::too_many_arguments)]
+    pub fn delegate_capability(
+        env: Env,
+        holder: Address,
+        parent_id: u64,
+        new_holder: Address,
+        amount_limit: i128,
+        expiry: u64,
+        max_uses: u32,
+    ) -> Result<u64, Error> {
+        let parent = Self::load_capability(&env, parent_id)?;
+        if parent.holder != holder {
+            return Err(Error::Unauthorized);
+        }
+        holder.require_auth();
+
+        Self::check_capability_usable(&env, &parent)?;
+        for (_, ancestor) in Self::load_ancestors(&env, &parent)?.iter() {
+            Self::check_capability_usable(&env, &ancestor)?;
+        }
+        if amount_limit <= 0 || max_uses == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let now = env.ledger().timestamp();
+        if expiry <= now {
+            return Err(Error::InvalidDeadline);
+        }
+        if parent.delegation_chain.len() > MAX_DELEGATION_DEPTH
+            || amount_limit > parent.remaining_amount
+            || max_uses > parent.remaining_uses
+            || expiry > parent.expiry
+        {
+            return Err(Error::CapabilityExceedsAuthority);
+        }
+
+        let mut delegation_chain = parent.delegation_chain.clone();
+        delegation_chain.push_back(holder.clone());
+        let capability_id = Self::next_capability_id(&env);
+        let capability = Capability {
+            owner: parent.owner.clone(),
+            holder: new_holder.clone(),
+            action: parent.action.clone(),
+            scope: parent.scope.clone(),
+            amount_limit,
+            remaining_amount: amount_limit,
+            expiry,
+            remaining_uses: max_uses,
+            revoked: false,
+            parent_id: Some(parent_id),
+            delegation_chain,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Capability(capability_id), &capability);
+        ttl::extend(&env, &DataKey::Capability(capability_id));
 
-        events::emit_capability_issued(
+        events::emit_capability_delegated(
             &env,
-            events::CapabilityIssued {
+            events::CapabilityDelegated {
                 capability_id,
-                owner,
-                holder,
-                action,
-                bounty_id,
+                parent_id,
+                delegator: holder,
+                holder: new_holder,
                 amount_limit,
                 expires_at: expiry,
                 max_uses,
@@ -1677,9 +2478,11 @@ impl BountyEscrowContract {
         Ok(capability_id)
     }
 
+    /// Revoke a capability and, through it, everything delegated from it.
+    /// The owner or anyone in the delegation chain may revoke.
     pub fn revoke_capability(env: Env, owner: Address, capability_id: u64) -> Result<(), Error> {
         let mut capability = Self::load_capability(&env, capability_id)?;
-        if capability.owner != owner {
+        if capability.owner != owner && !capability.delegation_chain.contains(&owner) {
             return Err(Error::Unauthorized);
         }
         owner.require_auth();
@@ -1692,6 +2495,7 @@ impl BountyEscrowContract {
         env.storage()
             .persistent()
             .set(&DataKey::Capability(capability_id), &capability);
+        ttl::extend(&env, &DataKey::Capability(capability_id));
 
         events::emit_capability_revoked(
             &env,
@@ -1705,8 +2509,16 @@ impl BountyEscrowContract {
         Ok(())
     }
 
+    /// A capability with its delegation chain. `revoked` is also set when
+    /// any capability it was delegated from has been revoked.
     pub fn get_capability(env: Env, capability_id: u64) -> Result<Capability, Error> {
-        Self::load_capability(&env, capability_id)
+        let mut capability = Self::load_capability(&env, capability_id)?;
+        if !capability.revoked {
+            capability.revoked = Self::load_ancestors(&env, &capability)?
+                .iter()
+                .any(|(_, ancestor)| ancestor.revoked);
+        }
+        Ok(capability)
     }
 
     /// Get current fee configuration (view function)
@@ -1714,107 +2526,433 @@ impl BountyEscrowContract {
         Self::get_fee_config_internal(&env)
     }
 
-    /// Retrieves the chain identifier.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    ///
-    /// # Returns
-    /// * `Option<String>` - Chain identifier if set, None if not initialized with network config
-    ///
-    /// # Usage
-    /// Use this to verify the chain environment for:
-    /// - Cross-network protection
-    /// - Replay attack prevention
-    /// - Environment-specific behavior
-    pub fn get_chain_id(env: Env) -> Option<String> {
-        env.storage().instance().get(&DataKey::ChainId)
-    }
-
-    /// Retrieves the network identifier.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    ///
-    /// # Returns
-    /// * `Option<String>` - Network identifier if set, None if not initialized with network config
-    ///
-    /// # Usage
-    /// Use this to verify the network environment for:
-    /// - Environment-specific behavior
-    /// - Testnet vs mainnet differentiation
-    /// - Safe replay protection
-    pub fn get_network_id(env: Env) -> Option<String> {
-        env.storage().instance().get(&DataKey::NetworkId)
-    }
+    // ==================== Promotional Periods (Fee Holidays) ====================
 
-    /// Gets both chain and network identifiers as a tuple.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
+    /// Create a promotional fee period (admin only).
     ///
-    /// # Returns
-    /// * `(Option<String>, Option<String>)` - Tuple of (chain_id, network_id)
-    ///
-    /// # Usage
-    /// Convenience function to get both identifiers in one call.
-    pub fn get_network_info(env: Env) -> (Option<String>, Option<String>) {
-        (
-            env.storage().instance().get(&DataKey::ChainId),
-            env.storage().instance().get(&DataKey::NetworkId),
-        )
-    }
-
-    /// Update multisig configuration (admin only)
-    pub fn update_multisig_config(
+    /// While `start_time <= now < end_time` and the promotion is enabled, lock and
+    /// release operations are charged the lower of the `FeeConfig` rate and the
+    /// promotional rate. A rate of 0 makes the window fee-free.
+    pub fn create_promotional_period(
         env: Env,
-        threshold_amount: i128,
-        signers: Vec<Address>,
-        required_signatures: u32,
-    ) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            return Err(Error::NotInitialized);
-        }
-
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        name: String,
+        start_time: u64,
+        end_time: u64,
+        lock_fee_rate: i128,
+        release_fee_rate: i128,
+    ) -> Result<u64, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
         admin.require_auth();
 
-        if required_signatures > signers.len() {
-            return Err(Error::InvalidAmount);
+        let now = env.ledger().timestamp();
+        Self::validate_promotion(now, start_time, end_time, lock_fee_rate, release_fee_rate)?;
+
+        let mut active: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActivePromotions)
+            .unwrap_or(Vec::new(&env));
+        if active.len() >= MAX_ACTIVE_PROMOTIONS {
+            return Err(Error::TooManyPromotions);
         }
 
-        let config = MultisigConfig {
-            threshold_amount,
-            signers,
-            required_signatures,
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u64>(&DataKey::PromotionCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::PromotionCounter, &id);
+
+        let promotion = PromotionalPeriod {
+            id,
+            name: name.clone(),
+            start_time,
+            end_time,
+            lock_fee_rate,
+            release_fee_rate,
+            enabled: true,
+            activated: false,
         };
-
+        env.storage()
+            .persistent()
+            .set(&DataKey::PromotionalPeriod(id), &promotion);
+        active.push_back(id);
         env.storage()
             .instance()
-            .set(&DataKey::MultisigConfig, &config);
+            .set(&DataKey::ActivePromotions, &active);
 
-        Ok(())
-    }
+        events::emit_promotional_period_created(
+            &env,
+            events::PromotionalPeriodCreated {
+                id,
+                name,
+                start_time,
+                end_time,
+                lock_fee_rate,
+                release_fee_rate,
+                timestamp: now,
+            },
+        );
 
-    /// Get multisig configuration
-    pub fn get_multisig_config(env: Env) -> MultisigConfig {
-        env.storage()
-            .instance()
-            .get(&DataKey::MultisigConfig)
-            .unwrap_or(MultisigConfig {
-                threshold_amount: i128::MAX,
-                signers: vec![&env],
-                required_signatures: 0,
-            })
+        Ok(id)
     }
 
-    /// Approve release for large amount (requires multisig)
-    pub fn approve_large_release(
+    /// Update the window or rates of a promotion that has not yet expired (admin only).
+    pub fn update_promotional_period(
         env: Env,
-        bounty_id: u64,
-        contributor: Address,
-        approver: Address,
-    ) -> Result<(), Error> {
+        id: u64,
+        start_time: Option<u64>,
+        end_time: Option<u64>,
+        lock_fee_rate: Option<i128>,
+        release_fee_rate: Option<i128>,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let mut promotion = Self::load_promotion(&env, id)?;
+        let now = env.ledger().timestamp();
+        if now >= promotion.end_time {
+            return Err(Error::InvalidPromotionPeriod);
+        }
+
+        if let Some(start) = start_time {
+            promotion.start_time = start;
+        }
+        if let Some(end) = end_time {
+            promotion.end_time = end;
+        }
+        if let Some(rate) = lock_fee_rate {
+            promotion.lock_fee_rate = rate;
+        }
+        if let Some(rate) = release_fee_rate {
+            promotion.release_fee_rate = rate;
+        }
+        Self::validate_promotion(
+            now,
+            promotion.start_time,
+            promotion.end_time,
+            promotion.lock_fee_rate,
+            promotion.release_fee_rate,
+        )?;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PromotionalPeriod(id), &promotion);
+
+        events::emit_promotional_period_updated(
+            &env,
+            events::PromotionalPeriodUpdated {
+                id,
+                enabled: promotion.enabled,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Enable or disable a promotion without deleting it (admin only).
+    pub fn set_promotional_period_enabled(env: Env, id: u64, enabled: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let mut promotion = Self::load_promotion(&env, id)?;
+        promotion.enabled = enabled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::PromotionalPeriod(id), &promotion);
+
+        events::emit_promotional_period_updated(
+            &env,
+            events::PromotionalPeriodUpdated {
+                id,
+                enabled,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// View: get a promotion by id.
+    pub fn get_promotional_period(env: Env, id: u64) -> Result<PromotionalPeriod, Error> {
+        Self::load_promotion(&env, id)
+    }
+
+    /// View: list all promotions ever created, ordered by id.
+    pub fn get_promotional_periods(env: Env, offset: u32, limit: u32) -> Vec<PromotionalPeriod> {
+        let last_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PromotionCounter)
+            .unwrap_or(0);
+        let mut results = Vec::new(&env);
+        let mut id = (offset as u64).saturating_add(1);
+        while id <= last_id && results.len() < limit {
+            if let Some(promotion) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, PromotionalPeriod>(&DataKey::PromotionalPeriod(id))
+            {
+                results.push_back(promotion);
+            }
+            id += 1;
+        }
+        results
+    }
+
+    /// View: promotions that are scheduled or running and have not yet expired.
+    pub fn get_active_promotions(env: Env) -> Vec<PromotionalPeriod> {
+        let now = env.ledger().timestamp();
+        let active: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActivePromotions)
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        for id in active.iter() {
+            if let Some(promotion) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, PromotionalPeriod>(&DataKey::PromotionalPeriod(id))
+            {
+                if now < promotion.end_time {
+                    results.push_back(promotion);
+                }
+            }
+        }
+        results
+    }
+
+    /// View: the `(lock_fee_rate, release_fee_rate)` that would be charged right now.
+    pub fn get_effective_fee_rates(env: Env) -> (i128, i128) {
+        Self::effective_fee_rates(&env)
+    }
+
+    fn load_promotion(env: &Env, id: u64) -> Result<PromotionalPeriod, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PromotionalPeriod(id))
+            .ok_or(Error::PromotionNotFound)
+    }
+
+    fn validate_promotion(
+        now: u64,
+        start_time: u64,
+        end_time: u64,
+        lock_fee_rate: i128,
+        release_fee_rate: i128,
+    ) -> Result<(), Error> {
+        if end_time <= start_time || end_time <= now {
+            return Err(Error::InvalidPromotionPeriod);
+        }
+        if !(0..=MAX_FEE_RATE).contains(&lock_fee_rate)
+            || !(0..=MAX_FEE_RATE).contains(&release_fee_rate)
+        {
+            return Err(Error::InvalidFeeRate);
+        }
+        Ok(())
+    }
+
+    /// Resolve the lowest applicable `(lock, release)` fee rates at the current
+    /// ledger timestamp. Returns `(0, 0)` when fees are disabled.
+    fn effective_fee_rates(env: &Env) -> (i128, i128) {
+        let config = Self::get_fee_config_internal(env);
+        if !config.fee_enabled {
+            return (0, 0);
+        }
+        let mut lock_rate = config.lock_fee_rate;
+        let mut release_rate = config.release_fee_rate;
+
+        let now = env.ledger().timestamp();
+        let active: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActivePromotions)
+            .unwrap_or(Vec::new(env));
+        for id in active.iter() {
+            if let Some(promotion) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, PromotionalPeriod>(&DataKey::PromotionalPeriod(id))
+            {
+                if promotion.enabled && promotion.start_time <= now && now < promotion.end_time {
+                    lock_rate = lock_rate.min(promotion.lock_fee_rate);
+                    release_rate = release_rate.min(promotion.release_fee_rate);
+                }
+            }
+        }
+        (lock_rate, release_rate)
+    }
+
+    /// Emit activation events for promotions whose window has opened and drop
+    /// expired promotions from `ActivePromotions`. Called from fee-bearing flows.
+    fn sync_promotions(env: &Env) {
+        let active: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActivePromotions)
+            .unwrap_or(Vec::new(env));
+        if active.is_empty() {
+            return;
+        }
+
+        let now = env.ledger().timestamp();
+        let mut still_active: Vec<u64> = Vec::new(env);
+        for id in active.iter() {
+            let key = DataKey::PromotionalPeriod(id);
+            let mut promotion: PromotionalPeriod = match env.storage().persistent().get(&key) {
+                Some(p) => p,
+                None => continue,
+            };
+            if now >= promotion.end_time {
+                events::emit_promotional_period_expired(
+                    env,
+                    events::PromotionalPeriodExpired {
+                        id,
+                        name: promotion.name,
+                        timestamp: now,
+                    },
+                );
+                continue;
+            }
+            if promotion.enabled && !promotion.activated && promotion.start_time <= now {
+                promotion.activated = true;
+                env.storage().persistent().set(&key, &promotion);
+                events::emit_promotional_period_activated(
+                    env,
+                    events::PromotionalPeriodActivated {
+                        id,
+                        name: promotion.name,
+                        lock_fee_rate: promotion.lock_fee_rate,
+                        release_fee_rate: promotion.release_fee_rate,
+                        timestamp: now,
+                    },
+                );
+            }
+            still_active.push_back(id);
+        }
+        if still_active.len() != active.len() {
+            env.storage()
+                .instance()
+                .set(&DataKey::ActivePromotions, &still_active);
+        }
+    }
+
+    /// Retrieves the chain identifier.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Option<String>` - Chain identifier if set, None if not initialized with network config
+    ///
+    /// # Usage
+    /// Use this to verify the chain environment for:
+    /// - Cross-network protection
+    /// - Replay attack prevention
+    /// - Environment-specific behavior
+    pub fn get_chain_id(env: Env) -> Option<String> {
+        env.storage().instance().get(&DataKey::ChainId)
+    }
+
+    /// Retrieves the network identifier.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `Option<String>` - Network identifier if set, None if not initialized with network config
+    ///
+    /// # Usage
+    /// Use this to verify the network environment for:
+    /// - Environment-specific behavior
+    /// - Testnet vs mainnet differentiation
+    /// - Safe replay protection
+    pub fn get_network_id(env: Env) -> Option<String> {
+        env.storage().instance().get(&DataKey::NetworkId)
+    }
+
+    /// Gets both chain and network identifiers as a tuple.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    ///
+    /// # Returns
+    /// * `(Option<String>, Option<String>)` - Tuple of (chain_id, network_id)
+    ///
+    /// # Usage
+    /// Convenience function to get both identifiers in one call.
+    pub fn get_network_info(env: Env) -> (Option<String>, Option<String>) {
+        (
+            env.storage().instance().get(&DataKey::ChainId),
+            env.storage().instance().get(&DataKey::NetworkId),
+        )
+    }
+
+    /// Update multisig configuration (admin only)
+    pub fn update_multisig_config(
+        env: Env,
+        threshold_amount: i128,
+        signers: Vec<Address>,
+        required_signatures: u32,
+    ) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        admin.require_auth();
+
+        if required_signatures > signers.len() {
+            return Err(Error::InvalidAmount);
+        }
+
+        let config = MultisigConfig {
+            threshold_amount,
+            signers,
+            required_signatures,
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MultisigConfig, &config);
+
+        Ok(())
+    }
+
+    /// Get multisig configuration
+    pub fn get_multisig_config(env: Env) -> MultisigConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::MultisigConfig)
+            .unwrap_or(MultisigConfig {
+                threshold_amount: i128::MAX,
+                signers: vec![&env],
+                required_signatures: 0,
+            })
+    }
+
+    /// Approve release for large amount (requires multisig)
+    pub fn approve_large_release(
+        env: Env,
+        bounty_id: u64,
+        contributor: Address,
+        approver: Address,
+    ) -> Result<(), Error> {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
@@ -1846,6 +2984,12 @@ impl BountyEscrowContract {
                 approvals: vec![&env],
             });
 
+        // Approvals are bound to a single contributor; retargeting starts over.
+        if approval.contributor != contributor {
+            approval.contributor = contributor.clone();
+            approval.approvals = vec![&env];
+        }
+
         for existing in approval.approvals.iter() {
             if existing == approver {
                 return Ok(());
@@ -1868,34 +3012,394 @@ impl BountyEscrowContract {
         Ok(())
     }
 
-    /// Lock funds for a specific bounty.
+    /// Approve a `release_split` of a large escrow (requires multisig).
     ///
-    /// # Reentrancy
-    /// Protected by the shared reentrancy guard. State (escrow record,
-    /// indexes) is written before the inbound token transfer so that
-    /// a malicious token callback cannot re-enter with stale state.
-    pub fn lock_funds(
-        env: Env,
-        depositor: Address,
-        bounty_id: u64,
-        amount: i128,
-        deadline: u64,
-    ) -> Result<(), Error> {
-        let res =
-            Self::lock_funds_logic(env.clone(), depositor.clone(), bounty_id, amount, deadline);
-        monitoring::track_operation(&env, symbol_short!("lock"), depositor, res.is_ok());
-        res
-    }
-
-    fn lock_funds_logic(
+    /// The approval covers exactly `shares` and `remainder_to`; approving a
+    /// different split starts over.
+    pub fn approve_split_release(
         env: Env,
-        depositor: Address,
         bounty_id: u64,
-        amount: i128,
-        deadline: u64,
+        shares: Vec<(Address, u32)>,
+        remainder_to: Address,
+        approver: Address,
     ) -> Result<(), Error> {
-        // GUARD: acquire reentrancy lock
-        reentrancy_guard::acquire(&env);
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        let multisig_config: MultisigConfig = Self::get_multisig_config(env.clone());
+        if !multisig_config.signers.contains(&approver) {
+            return Err(Error::Unauthorized);
+        }
+        approver.require_auth();
+
+        let approval_key = ApprovalKey::Split(bounty_id);
+        let mut approval = env
+            .storage()
+            .persistent()
+            .get::<ApprovalKey, SplitApproval>(&approval_key)
+            .filter(|a| a.shares == shares && a.remainder_to == remainder_to)
+            .unwrap_or(SplitApproval {
+                bounty_id,
+                shares,
+                remainder_to: remainder_to.clone(),
+                approvals: vec![&env],
+            });
+        if approval.approvals.contains(&approver) {
+            return Ok(());
+        }
+        approval.approvals.push_back(approver.clone());
+        env.storage().persistent().set(&approval_key, &approval);
+        ttl::extend(&env, &approval_key);
+
+        events::emit_approval_added(
+            &env,
+            events::ApprovalAdded {
+                bounty_id,
+                contributor: remainder_to,
+                approver,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// `check_release_approval` for `release_split`: the approval must be
+    /// for exactly this split.
+    fn check_split_approval(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        shares: &Vec<(Address, u32)>,
+        remainder_to: &Address,
+    ) -> Result<bool, Error> {
+        let config = Self::get_multisig_config(env.clone());
+        if !Self::release_needs_approval(env, bounty_id, escrow, escrow.remaining_amount, &config) {
+            return Ok(false);
+        }
+
+        let approval: SplitApproval = env
+            .storage()
+            .persistent()
+            .get(&ApprovalKey::Split(bounty_id))
+            .ok_or(Error::InsufficientApprovals)?;
+        if approval.shares != *shares || approval.remainder_to != *remainder_to {
+            return Err(Error::InsufficientApprovals);
+        }
+        let mut valid = 0u32;
+        for approver in approval.approvals.iter() {
+            if config.signers.contains(&approver) {
+                valid += 1;
+            }
+        }
+        if valid < config.required_signatures {
+            return Err(Error::InsufficientApprovals);
+        }
+        Ok(true)
+    }
+
+    /// Everything paid out of `escrow` so far: what was locked less what is
+    /// left and what was refunded.
+    fn released_total(escrow: &Escrow) -> i128 {
+        let mut refunded: i128 = 0;
+        for record in escrow.refund_history.iter() {
+            refunded += record.amount;
+        }
+        escrow.amount - escrow.remaining_amount - refunded
+    }
+
+    /// Whether paying `amount` more out of `escrow` takes the releases not
+    /// yet covered by an approval to the multisig threshold. Counting the
+    /// cumulative total keeps a large bounty from being paid out in
+    /// below-threshold slices.
+    fn release_needs_approval(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        amount: i128,
+        config: &MultisigConfig,
+    ) -> bool {
+        if config.required_signatures == 0 {
+            return false;
+        }
+        let approved: i128 = env
+            .storage()
+            .persistent()
+            .get(&ApprovalKey::Approved(bounty_id))
+            .unwrap_or(0);
+        Self::released_total(escrow)
+            .saturating_add(amount)
+            .saturating_sub(approved)
+            >= config.threshold_amount
+    }
+
+    /// Check that a release of `amount` to `contributor` has enough multisig
+    /// approvals. Returns `true` when an approval was needed, meaning the
+    /// caller must spend it with `consume_release_approval` once it pays.
+    fn check_release_approval(
+        env: &Env,
+        bounty_id: u64,
+        escrow: &Escrow,
+        contributor: &Address,
+        amount: i128,
+    ) -> Result<bool, Error> {
+        let config = Self::get_multisig_config(env.clone());
+        if !Self::release_needs_approval(env, bounty_id, escrow, amount, &config) {
+            return Ok(false);
+        }
+
+        let approval: ReleaseApproval = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReleaseApproval(bounty_id))
+            .ok_or(Error::InsufficientApprovals)?;
+        if approval.contributor != *contributor {
+            return Err(Error::InsufficientApprovals);
+        }
+
+        // Only count approvers who are still signers under the current config.
+        let mut valid = 0u32;
+        for approver in approval.approvals.iter() {
+            if config.signers.contains(&approver) {
+                valid += 1;
+            }
+        }
+        if valid < config.required_signatures {
+            return Err(Error::InsufficientApprovals);
+        }
+        Ok(true)
+    }
+
+    /// Spend the approval for paying `amount` out of `escrow` (as it was
+    /// before the payout). Everything released up to here counts as approved.
+    fn consume_release_approval(env: &Env, bounty_id: u64, escrow: &Escrow, amount: i128) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReleaseApproval(bounty_id));
+        let key = ApprovalKey::Approved(bounty_id);
+        env.storage()
+            .persistent()
+            .set(&key, &(Self::released_total(escrow) + amount));
+        ttl::extend(env, &key);
+    }
+
+    /// Lock funds for a specific bounty.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. State (escrow record,
+    /// indexes) is written before the inbound token transfer so that
+    /// a malicious token callback cannot re-enter with stale state.
+    pub fn lock_funds(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+    ) -> Result<(), Error> {
+        let token: Option<Address> = env.storage().instance().get(&DataKey::Token);
+        let Some(token) = token else {
+            return Err(Error::NotInitialized);
+        };
+        let res = Self::lock_funds_logic(
+            env.clone(),
+            depositor.clone(),
+            bounty_id,
+            amount,
+            deadline,
+            token,
+        );
+        monitoring::track_operation(&env, symbol_short!("lock"), depositor, res.is_ok());
+        res
+    }
+
+    /// Lock funds for a bounty in a specific allow-listed asset.
+    ///
+    /// Behaves exactly like `lock_funds`, except the escrow is denominated in
+    /// `asset` instead of the contract's default token.
+    pub fn lock_funds_with_asset(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        asset: asset::AssetId,
+    ) -> Result<(), Error> {
+        if !Self::get_allowed_assets(env.clone()).contains(&asset) {
+            return Err(Error::AssetNotAllowed);
+        }
+        let res = Self::lock_funds_logic(
+            env.clone(),
+            depositor.clone(),
+            bounty_id,
+            amount,
+            deadline,
+            asset,
+        );
+        monitoring::track_operation(&env, symbol_short!("lock"), depositor, res.is_ok());
+        res
+    }
+
+    /// Top up a locked escrow on behalf of `funder`.
+    ///
+    /// The lock fee applies as in `lock_funds`. The net amount is credited to
+    /// the funder's stake (see `get_funders`), which decides their pro-rata
+    /// part of a deadline refund. Earlier stakes count only for what is left
+    /// of them after past releases. Milestone escrows and escrows with an
+    /// unclaimed authorized claim cannot be topped up.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. Escrow state and the funder
+    /// ledger are written before the inbound token transfer.
+    pub fn contribute(
+        env: Env,
+        bounty_id: u64,
+        funder: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        anti_abuse::check_rate_limit(&env, funder.clone());
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::FundsPaused);
+        }
+        funder.require_auth();
+
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+        access_control::require_allowed(&env, &funder)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        milestones::reject_whole_escrow(&env, bounty_id)?;
+        Self::sync_promotions(&env);
+
+        let mut escrow: Escrow =
+            legacy::load_escrow(&env, bounty_id).ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        // An authorized claim covers the amount locked when it was made.
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+        {
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
+        }
+
+        // EFFECTS: update state before external call (CEI)
+        let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(amount, lock_fee_rate);
+        crowdfund::rebase(&env, bounty_id, &escrow)?;
+        crowdfund::credit(&env, bounty_id, &escrow, &funder, net)?;
+        escrow.amount = escrow.amount.checked_add(net).ok_or(Error::InvalidAmount)?;
+        escrow.remaining_amount = escrow
+            .remaining_amount
+            .checked_add(net)
+            .ok_or(Error::InvalidAmount)?;
+        escrow.lock_fee = escrow.lock_fee.checked_add(fee).unwrap();
+        invariants::assert_escrow(&env, &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+
+        let index_key = DataKey::DepositorIndex(funder.clone());
+        let mut funder_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        if !funder_index.contains(bounty_id) {
+            funder_index.push_back(bounty_id);
+            env.storage().persistent().set(&index_key, &funder_index);
+        }
+        ttl::extend(&env, &index_key);
+
+        // INTERACTION: external token transfers are last
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(&funder, &env.current_contract_address(), &amount);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            lock_fee_rate,
+            events::FeeOperationType::Lock,
+        );
+
+        emit_funds_locked(
+            &env,
+            FundsLocked {
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: net,
+                depositor: funder,
+                deadline: escrow.deadline,
+                gross_amount: amount,
+                fee_amount: fee,
+            },
+        );
+
+        multitoken_invariants::assert_after_lock(&env, &escrow.token);
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(())
+    }
+
+    /// Every stake in an escrow, depositor first, with each funder's share
+    /// and what a deadline refund would return to them now.
+    pub fn get_funders(env: Env, bounty_id: u64) -> Result<Vec<crowdfund::FunderShare>, Error> {
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).ok_or(Error::BountyNotFound)?;
+        crowdfund::shares(&env, bounty_id, &escrow)
+    }
+
+    /// `funder`'s stake in an escrow; all zero if they have not contributed.
+    pub fn get_funder_share(
+        env: Env,
+        bounty_id: u64,
+        funder: Address,
+    ) -> Result<crowdfund::FunderShare, Error> {
+        let shares = Self::get_funders(env, bounty_id)?;
+        Ok(shares
+            .iter()
+            .find(|share| share.funder == funder)
+            .unwrap_or(crowdfund::FunderShare {
+                funder,
+                contributed: 0,
+                share_bps: 0,
+                refundable: 0,
+            }))
+    }
+
+    /// Lock funds and attach metadata in one call.
+    ///
+    /// Fails with `BountyExists` if another escrow for the same
+    /// (repo_id, issue_id) is still locked, and with `InvalidBatchSize` past
+    /// `metadata::MAX_TAGS` tags. Either failure leaves no escrow behind.
+    pub fn lock_funds_with_metadata(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        metadata: EscrowMetadata,
+    ) -> Result<(), Error> {
+        Self::lock_funds(env.clone(), depositor, bounty_id, amount, deadline)?;
+        metadata::store_metadata(&env, bounty_id, &metadata)
+    }
+
+    fn lock_funds_logic(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        amount: i128,
+        deadline: u64,
+        token_addr: Address,
+    ) -> Result<(), Error> {
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
 
         // Apply rate limiting
         anti_abuse::check_rate_limit(&env, depositor.clone());
@@ -1913,15 +3417,25 @@ impl BountyEscrowContract {
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
+        access_control::require_allowed(&env, &depositor)?;
+        Self::sync_promotions(&env);
+
+        // Compacted archives no longer hold an Escrow entry; keep their ids retired.
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Archived(bounty_id))
+        {
+            return Err(Error::BountyExists);
+        }
 
         // Allow filling a Template escrow (clone) with same depositor (Issue #678).
         if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            let existing: Escrow = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Escrow(bounty_id))
-                .unwrap();
+            let existing: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
             if existing.status == EscrowStatus::Template && existing.depositor == depositor {
+                if existing.token != token_addr {
+                    return Err(Error::AssetNotAllowed);
+                }
                 // Enforce amount policy for template fill
                 if let Some((min_amount, max_amount)) = env
                     .storage()
@@ -1938,32 +3452,44 @@ impl BountyEscrowContract {
                 if amount <= 0 {
                     return Err(Error::InvalidAmount);
                 }
+                let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+                let (fee, net) = token_math::split_amount(amount, lock_fee_rate);
                 let escrow = Escrow {
                     depositor: depositor.clone(),
-                    amount,
+                    amount: net,
                     status: EscrowStatus::Locked,
                     deadline: existing.deadline,
                     refund_history: vec![&env],
-                    remaining_amount: amount,
+                    lock_fee: fee,
+                    release_fee: 0,
+                    remaining_amount: net,
+                    token: token_addr.clone(),
                 };
                 invariants::assert_escrow(&env, &escrow);
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::Escrow(bounty_id), &escrow);
-                let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+                indexes::store_escrow(&env, bounty_id, &escrow);
                 let client = token::Client::new(&env, &token_addr);
                 client.transfer(&depositor, &env.current_contract_address(), &amount);
+                Self::transfer_fee(
+                    &env,
+                    &client,
+                    bounty_id,
+                    fee,
+                    lock_fee_rate,
+                    events::FeeOperationType::Lock,
+                );
                 emit_funds_locked(
                     &env,
                     FundsLocked {
                         version: EVENT_VERSION_V2,
                         bounty_id,
-                        amount,
+                        amount: net,
                         depositor: depositor.clone(),
                         deadline: existing.deadline,
+                        gross_amount: amount,
+                        fee_amount: fee,
                     },
                 );
-                multitoken_invariants::assert_after_lock(&env);
+                multitoken_invariants::assert_after_lock(&env, &token_addr);
                 reentrancy_guard::release(&env);
                 return Ok(());
             }
@@ -1986,31 +3512,25 @@ impl BountyEscrowContract {
             }
         }
 
+        // Lock fee is floored; the remainder stays in the escrow.
+        let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(amount, lock_fee_rate);
+
         // EFFECTS: write escrow state and indexes before the external call
         let escrow = Escrow {
             depositor: depositor.clone(),
-            amount,
+            amount: net,
             status: EscrowStatus::Locked,
             deadline,
             refund_history: vec![&env],
-            remaining_amount: amount,
+            lock_fee: fee,
+            release_fee: 0,
+            remaining_amount: net,
+            token: token_addr.clone(),
         };
         invariants::assert_escrow(&env, &escrow);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
-
-        // Update indexes
-        let mut index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.push_back(bounty_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::EscrowIndex, &index);
+        indexes::store_escrow(&env, bounty_id, &escrow);
 
         let mut depositor_index: Vec<u64> = env
             .storage()
@@ -2022,11 +3542,19 @@ impl BountyEscrowContract {
             &DataKey::DepositorIndex(depositor.clone()),
             &depositor_index,
         );
+        ttl::extend(&env, &DataKey::DepositorIndex(depositor.clone()));
 
-        // INTERACTION: external token transfer is last
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        // INTERACTION: external token transfers are last
         let client = token::Client::new(&env, &token_addr);
         client.transfer(&depositor, &env.current_contract_address(), &amount);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            lock_fee_rate,
+            events::FeeOperationType::Lock,
+        );
 
         // Emit value allows for off-chain indexing
         emit_funds_locked(
@@ -2034,14 +3562,16 @@ impl BountyEscrowContract {
             FundsLocked {
                 version: EVENT_VERSION_V2,
                 bounty_id,
-                amount,
+                amount: net,
                 depositor: depositor.clone(),
                 deadline,
+                gross_amount: amount,
+                fee_amount: fee,
             },
         );
 
         // INV-2: Verify aggregate balance matches token balance after lock
-        multitoken_invariants::assert_after_lock(&env);
+        multitoken_invariants::assert_after_lock(&env, &token_addr);
 
         // GUARD: release reentrancy lock
         reentrancy_guard::release(&env);
@@ -2064,9 +3594,14 @@ impl BountyEscrowContract {
         if Self::check_paused(&env, symbol_short!("release")) {
             return Err(Error::FundsPaused);
         }
+        access_control::require_allowed(&env, &contributor)?;
         if Self::is_escrow_locked(&env, bounty_id) {
             return Err(Error::EscrowLocked);
         }
+        milestones::reject_whole_escrow(&env, bounty_id)?;
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
 
         // Block direct release while an active dispute (pending claim) exists.
         if env
@@ -2095,41 +3630,48 @@ impl BountyEscrowContract {
 
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        Self::sync_promotions(&env);
 
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
 
+        let release_amount = escrow.remaining_amount;
+        Self::check_release_approval(&env, bounty_id, &escrow, &contributor, release_amount)?;
+
         // EFFECTS: update state before external call (CEI)
-        let release_amount = escrow.amount;
+        // A full release closes the escrow, so any approval record is spent.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReleaseApproval(bounty_id));
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(release_amount, release_fee_rate);
         escrow.status = EscrowStatus::Released;
         escrow.remaining_amount = 0;
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
         invariants::assert_escrow(&env, &escrow);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
         let now_ts = env.ledger().timestamp();
         env.storage()
             .persistent()
             .set(&DataKey::CompletedAt(bounty_id), &now_ts);
 
-        // INTERACTION: external token transfer is last
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
-            &contributor,
-            &release_amount,
+        // INTERACTION: external token transfers are last
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(&env.current_contract_address(), &contributor, &net);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
         );
 
         emit_funds_released(
@@ -2137,14 +3679,16 @@ impl BountyEscrowContract {
             FundsReleased {
                 version: EVENT_VERSION_V2,
                 bounty_id,
-                amount: release_amount,
+                amount: net,
                 recipient: contributor.clone(),
                 timestamp: env.ledger().timestamp(),
+                gross_amount: release_amount,
+                fee_amount: fee,
             },
         );
 
         // INV-2: Verify aggregate balance matches token balance after release
-        multitoken_invariants::assert_after_disbursement(&env);
+        multitoken_invariants::assert_after_disbursement(&env, &escrow.token);
 
         // GUARD: release reentrancy lock
         reentrancy_guard::release(&env);
@@ -2164,9 +3708,14 @@ impl BountyEscrowContract {
         if Self::check_paused(&env, symbol_short!("release")) {
             return Err(Error::FundsPaused);
         }
+        access_control::require_allowed(&env, &contributor)?;
         if Self::is_escrow_locked(&env, bounty_id) {
             return Err(Error::EscrowLocked);
         }
+        milestones::reject_whole_escrow(&env, bounty_id)?;
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
         if payout_amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -2174,17 +3723,16 @@ impl BountyEscrowContract {
             return Err(Error::BountyNotFound);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
         if payout_amount > escrow.remaining_amount {
             return Err(Error::InsufficientFunds);
         }
+        if Self::check_release_approval(&env, bounty_id, &escrow, &contributor, payout_amount)? {
+            Self::consume_release_approval(&env, bounty_id, &escrow, payout_amount);
+        }
 
         Self::consume_capability(
             &env,
@@ -2195,15 +3743,13 @@ impl BountyEscrowContract {
             payout_amount,
         )?;
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
-            &contributor,
-            &payout_amount,
-        );
+        // The release fee applies as in `partial_release`.
+        Self::sync_promotions(&env);
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(payout_amount, release_fee_rate);
 
         escrow.remaining_amount -= payout_amount;
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
         if escrow.remaining_amount == 0 {
             escrow.status = EscrowStatus::Released;
             let now_ts = env.ledger().timestamp();
@@ -2211,18 +3757,29 @@ impl BountyEscrowContract {
                 .persistent()
                 .set(&DataKey::CompletedAt(bounty_id), &now_ts);
         }
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(&env.current_contract_address(), &contributor, &net);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
+        );
 
         emit_funds_released(
             &env,
             FundsReleased {
                 version: EVENT_VERSION_V2,
                 bounty_id,
-                amount: payout_amount,
+                amount: net,
                 recipient: contributor,
                 timestamp: env.ledger().timestamp(),
+                gross_amount: payout_amount,
+                fee_amount: fee,
             },
         );
 
@@ -2255,6 +3812,8 @@ impl BountyEscrowContract {
         if Self::check_paused(&env, symbol_short!("release")) {
             return Err(Error::FundsPaused);
         }
+        access_control::require_allowed(&env, &recipient)?;
+        milestones::reject_whole_escrow(&env, bounty_id)?;
         if !env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::NotInitialized);
         }
@@ -2265,15 +3824,16 @@ impl BountyEscrowContract {
             return Err(Error::BountyNotFound);
         }
 
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
+        // The approval is spent when the claim is paid.
+        Self::check_release_approval(&env, bounty_id, &escrow, &recipient, escrow.amount)?;
 
         let now = env.ledger().timestamp();
         let claim_window: u64 = env
@@ -2293,6 +3853,7 @@ impl BountyEscrowContract {
         env.storage()
             .persistent()
             .set(&DataKey::PendingClaim(bounty_id), &claim);
+        ttl::extend(&env, &DataKey::PendingClaim(bounty_id));
 
         env.events().publish(
             (symbol_short!("claim"), symbol_short!("created")),
@@ -2334,6 +3895,7 @@ impl BountyEscrowContract {
             .unwrap();
 
         claim.recipient.require_auth();
+        access_control::require_allowed(&env, &claim.recipient)?;
 
         let now = env.ledger().timestamp();
         if now > claim.expires_at {
@@ -2342,34 +3904,43 @@ impl BountyEscrowContract {
         if claim.claimed {
             return Err(Error::FundsNotLocked);
         }
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
 
         // EFFECTS: update escrow and claim state before external call (CEI)
         let claim_amount = claim.amount;
         let claim_recipient = claim.recipient.clone();
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
+        if Self::check_release_approval(&env, bounty_id, &escrow, &claim_recipient, claim_amount)? {
+            Self::consume_release_approval(&env, bounty_id, &escrow, claim_amount);
+        }
+        Self::sync_promotions(&env);
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(claim_amount, release_fee_rate);
         escrow.status = EscrowStatus::Released;
         escrow.remaining_amount = 0;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
+        indexes::store_escrow(&env, bounty_id, &escrow);
 
         claim.claimed = true;
         env.storage()
             .persistent()
             .set(&DataKey::PendingClaim(bounty_id), &claim);
+        ttl::extend(&env, &DataKey::PendingClaim(bounty_id));
 
-        // INTERACTION: external token transfer is last
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        // INTERACTION: external token transfers are last
+        let token_addr = escrow.token.clone();
         let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
-            &claim_recipient,
-            &claim_amount,
+        client.transfer(&env.current_contract_address(), &claim_recipient, &net);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
         );
 
         env.events().publish(
@@ -2412,6 +3983,7 @@ impl BountyEscrowContract {
             .persistent()
             .get(&DataKey::PendingClaim(bounty_id))
             .unwrap();
+        access_control::require_allowed(&env, &claim.recipient)?;
 
         let now = env.ledger().timestamp();
         if now > claim.expires_at {
@@ -2420,6 +3992,9 @@ impl BountyEscrowContract {
         if claim.claimed {
             return Err(Error::FundsNotLocked);
         }
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
 
         Self::consume_capability(
             &env,
@@ -2430,28 +4005,34 @@ impl BountyEscrowContract {
             claim.amount,
         )?;
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
-            &claim.recipient,
-            &claim.amount,
-        );
-
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
+        if Self::check_release_approval(&env, bounty_id, &escrow, &claim.recipient, claim.amount)? {
+            Self::consume_release_approval(&env, bounty_id, &escrow, claim.amount);
+        }
+        Self::sync_promotions(&env);
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(claim.amount, release_fee_rate);
         escrow.status = EscrowStatus::Released;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        escrow.remaining_amount = 0;
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
+        indexes::store_escrow(&env, bounty_id, &escrow);
 
         claim.claimed = true;
         env.storage()
             .persistent()
             .set(&DataKey::PendingClaim(bounty_id), &claim);
+        ttl::extend(&env, &DataKey::PendingClaim(bounty_id));
+
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(&env.current_contract_address(), &claim.recipient, &net);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
+        );
 
         env.events().publish(
             (symbol_short!("claim"), symbol_short!("done")),
@@ -2521,6 +4102,405 @@ impl BountyEscrowContract {
             .ok_or(Error::BountyNotFound)
     }
 
+    /// Replace the set of arbiters allowed to resolve disputes (admin only).
+    pub fn set_arbiters(env: Env, arbiters: Vec<Address>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Arbiters, &arbiters);
+        Ok(())
+    }
+
+    /// View: current arbiter set.
+    pub fn get_arbiters(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Arbiters)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Open a dispute on a Locked escrow.
+    ///
+    /// The depositor or the admin may open a dispute and name the
+    /// `contributor`. Once a claim has been authorized the contributor is the
+    /// claim's recipient; on a milestone escrow it must be one of the pending
+    /// milestones' assignees. The assigned contributor (claim recipient,
+    /// milestone assignee, or the recipient release approvals are collected
+    /// for) may open the dispute naming themselves. While the dispute is open
+    /// every release, claim and refund path for the escrow returns
+    /// `DisputeOpen`.
+    pub fn open_dispute(
+        env: Env,
+        bounty_id: u64,
+        opened_by: Address,
+        contributor: Address,
+        reason: DisputeReason,
+    ) -> Result<u64, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        opened_by.require_auth();
+
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        let claimant = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+            .filter(|claim| !claim.claimed)
+            .map(|claim| claim.recipient);
+        let mut assignees: Vec<Address> = Vec::new(&env);
+        for milestone in milestones::get(&env, bounty_id)
+            .unwrap_or(Vec::new(&env))
+            .iter()
+        {
+            if milestone.status != milestones::MilestoneStatus::Pending {
+                continue;
+            }
+            if let Some(assignee) = milestone.assignee {
+                if !assignees.contains(&assignee) {
+                    assignees.push_back(assignee);
+                }
+            }
+        }
+        if let Some(recipient) = &claimant {
+            if contributor != *recipient {
+                return Err(Error::Unauthorized);
+            }
+        } else if !assignees.is_empty() && !assignees.contains(&contributor) {
+            return Err(Error::Unauthorized);
+        }
+        let approved = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ReleaseApproval>(&DataKey::ReleaseApproval(bounty_id))
+            .map(|approval| approval.contributor);
+        let assigned = claimant.as_ref() == Some(&contributor)
+            || assignees.contains(&contributor)
+            || approved.as_ref() == Some(&contributor);
+        if opened_by != escrow.depositor
+            && opened_by != admin
+            && !(opened_by == contributor && assigned)
+        {
+            return Err(Error::Unauthorized);
+        }
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeAlreadyOpen);
+        }
+
+        let dispute_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisputeCounter)
+            .unwrap_or(0)
+            + 1;
+        let now = env.ledger().timestamp();
+        let dispute = Dispute {
+            dispute_id,
+            bounty_id,
+            opened_by: opened_by.clone(),
+            depositor: escrow.depositor.clone(),
+            contributor: contributor.clone(),
+            reason,
+            evidence: Vec::new(&env),
+            status: DisputeStatus::Open,
+            opened_at: now,
+            resolved_at: 0,
+            resolved_by: None,
+            outcome: DisputeOutcome::NoActionTaken,
+            contributor_amount: 0,
+            refund_amount: 0,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::DisputeCounter, &dispute_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(dispute_id), &dispute);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ActiveDispute(bounty_id), &dispute_id);
+        let mut history: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisputeHistory(bounty_id))
+            .unwrap_or(Vec::new(&env));
+        history.push_back(dispute_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DisputeHistory(bounty_id), &history);
+
+        events::emit_dispute_opened(
+            &env,
+            events::DisputeOpened {
+                dispute_id,
+                bounty_id,
+                opened_by,
+                contributor,
+                reason,
+                opened_at: now,
+            },
+        );
+        Ok(dispute_id)
+    }
+
+    /// Attach an evidence hash to an open dispute. Either party may submit.
+    pub fn submit_dispute_evidence(
+        env: Env,
+        dispute_id: u64,
+        submitter: Address,
+        evidence_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), Error> {
+        submitter.require_auth();
+
+        let mut dispute = Self::get_dispute(env.clone(), dispute_id)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::DisputeClosed);
+        }
+        if submitter != dispute.depositor && submitter != dispute.contributor {
+            return Err(Error::Unauthorized);
+        }
+        if dispute.evidence.len() >= MAX_DISPUTE_EVIDENCE {
+            return Err(Error::DisputeEvidenceLimit);
+        }
+
+        dispute.evidence.push_back(evidence_hash.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(dispute_id), &dispute);
+
+        events::emit_dispute_evidence(
+            &env,
+            events::DisputeEvidenceSubmitted {
+                dispute_id,
+                bounty_id: dispute.bounty_id,
+                submitter,
+                evidence_hash,
+                submitted_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Resolve an open dispute (arbiter only).
+    ///
+    /// `contributor_amount` of the escrow's remaining balance is paid to the
+    /// contributor and the rest is refunded to the depositor. The outcome is
+    /// recorded as a payout, a refund or a split accordingly. On a milestone
+    /// escrow every pending milestone is settled with it: refunded when the
+    /// ruling is a refund and released otherwise.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. Escrow and dispute state
+    /// are updated *before* the outbound token transfers (CEI pattern).
+    pub fn resolve_dispute(
+        env: Env,
+        dispute_id: u64,
+        arbiter: Address,
+        contributor_amount: i128,
+    ) -> Result<DisputeOutcome, Error> {
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        if !Self::get_arbiters(env.clone()).contains(&arbiter) {
+            return Err(Error::Unauthorized);
+        }
+        arbiter.require_auth();
+
+        let mut dispute = Self::get_dispute(env.clone(), dispute_id)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::DisputeClosed);
+        }
+        let bounty_id = dispute.bounty_id;
+        let mut escrow: Escrow =
+            legacy::load_escrow(&env, bounty_id).ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        if contributor_amount < 0 || contributor_amount > escrow.remaining_amount {
+            return Err(Error::InvalidAmount);
+        }
+        if contributor_amount > 0
+            && Self::check_release_approval(
+                &env,
+                bounty_id,
+                &escrow,
+                &dispute.contributor,
+                contributor_amount,
+            )?
+        {
+            Self::consume_release_approval(&env, bounty_id, &escrow, contributor_amount);
+        }
+
+        let refund_amount = escrow.remaining_amount - contributor_amount;
+        let outcome = if refund_amount == 0 {
+            DisputeOutcome::ResolvedByPayout
+        } else if contributor_amount == 0 {
+            DisputeOutcome::ResolvedByRefund
+        } else {
+            DisputeOutcome::ResolvedBySplit
+        };
+        let now = env.ledger().timestamp();
+        // The contributor's share is a release and pays the release fee.
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(contributor_amount, release_fee_rate);
+
+        // EFFECTS: settle the escrow and close the dispute before transfers
+        let refunds = if refund_amount > 0 {
+            crowdfund::refund_payouts(&env, bounty_id, &escrow, refund_amount)?
+        } else {
+            Vec::new(&env)
+        };
+        escrow.remaining_amount = 0;
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
+        for (recipient, amount) in refunds.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount,
+                recipient,
+                timestamp: now,
+                mode: if contributor_amount == 0 {
+                    RefundMode::Full
+                } else {
+                    RefundMode::Partial
+                },
+            });
+        }
+        escrow.status = if outcome == DisputeOutcome::ResolvedByRefund {
+            EscrowStatus::Refunded
+        } else {
+            EscrowStatus::Released
+        };
+        if let Some(mut stages) = milestones::get(&env, bounty_id) {
+            let settled = if outcome == DisputeOutcome::ResolvedByRefund {
+                milestones::MilestoneStatus::Refunded
+            } else {
+                milestones::MilestoneStatus::Released
+            };
+            for i in 0..stages.len() {
+                let mut milestone = stages.get(i).unwrap();
+                if milestone.status == milestones::MilestoneStatus::Pending {
+                    milestone.status = settled.clone();
+                    stages.set(i, milestone);
+                }
+            }
+            milestones::save(&env, bounty_id, &stages);
+            milestones::sync_escrow(&mut escrow, &stages);
+        }
+        invariants::assert_escrow(&env, &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::CompletedAt(bounty_id), &now);
+
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolved_at = now;
+        dispute.resolved_by = Some(arbiter.clone());
+        dispute.outcome = outcome;
+        dispute.contributor_amount = contributor_amount;
+        dispute.refund_amount = refund_amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(dispute_id), &dispute);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ActiveDispute(bounty_id));
+        // The ruling replaces any claim authorized before the dispute.
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PendingClaim(bounty_id));
+
+        // INTERACTION: external token transfers are last
+        let client = token::Client::new(&env, &escrow.token);
+        if contributor_amount > 0 {
+            client.transfer(&env.current_contract_address(), &dispute.contributor, &net);
+            Self::transfer_fee(
+                &env,
+                &client,
+                bounty_id,
+                fee,
+                release_fee_rate,
+                events::FeeOperationType::Release,
+            );
+            emit_funds_released(
+                &env,
+                FundsReleased {
+                    version: EVENT_VERSION_V2,
+                    bounty_id,
+                    amount: net,
+                    recipient: dispute.contributor.clone(),
+                    timestamp: now,
+                    gross_amount: contributor_amount,
+                    fee_amount: fee,
+                },
+            );
+        }
+        for (recipient, amount) in refunds.iter() {
+            client.transfer(&env.current_contract_address(), &recipient, &amount);
+        }
+
+        events::emit_dispute_resolved(
+            &env,
+            events::DisputeResolved {
+                dispute_id,
+                bounty_id,
+                arbiter,
+                outcome,
+                contributor_amount,
+                refund_amount,
+                resolved_at: now,
+            },
+        );
+
+        multitoken_invariants::assert_after_disbursement(&env, &escrow.token);
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(outcome)
+    }
+
+    /// View: a dispute by id.
+    pub fn get_dispute(env: Env, dispute_id: u64) -> Result<Dispute, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Dispute(dispute_id))
+            .ok_or(Error::DisputeNotFound)
+    }
+
+    /// View: the currently open dispute on an escrow, if any.
+    pub fn get_active_dispute(env: Env, bounty_id: u64) -> Option<Dispute> {
+        let dispute_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ActiveDispute(bounty_id))?;
+        env.storage()
+            .persistent()
+            .get(&DataKey::Dispute(dispute_id))
+    }
+
+    /// View: every dispute raised on an escrow, oldest first.
+    pub fn get_dispute_history(env: Env, bounty_id: u64) -> Vec<Dispute> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisputeHistory(bounty_id))
+            .unwrap_or(Vec::new(&env));
+        let mut disputes = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(dispute) = env.storage().persistent().get(&DataKey::Dispute(id)) {
+                disputes.push_back(dispute);
+            }
+        }
+        disputes
+    }
+
     /// Approve a refund before deadline (admin only).
     /// This allows early refunds with admin approval.
     pub fn approve_refund(
@@ -2541,11 +4521,7 @@ impl BountyEscrowContract {
             return Err(Error::BountyNotFound);
         }
 
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
         {
@@ -2598,20 +4574,38 @@ impl BountyEscrowContract {
 
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        Self::release_partial_amount(&env, bounty_id, &contributor, payout_amount)?;
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(())
+    }
+
+    /// Pay `payout_amount` of `bounty_id` to `contributor`, less the release
+    /// fee, once the caller has been authorized. Shared by `partial_release`
+    /// and `redeem_claim_voucher`.
+    fn release_partial_amount(
+        env: &Env,
+        bounty_id: u64,
+        contributor: &Address,
+        payout_amount: i128,
+    ) -> Result<(), Error> {
+        access_control::require_allowed(env, contributor)?;
+        milestones::reject_whole_escrow(env, bounty_id)?;
+        Self::sync_promotions(env);
 
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
+        if Self::has_open_dispute(env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
 
         // Guard: zero or negative payout makes no sense and would corrupt state
         if payout_amount <= 0 {
@@ -2623,11 +4617,20 @@ impl BountyEscrowContract {
             return Err(Error::InsufficientFunds);
         }
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
+        if Self::check_release_approval(env, bounty_id, &escrow, contributor, payout_amount)? {
+            Self::consume_release_approval(env, bounty_id, &escrow, payout_amount);
+        }
+
+        let token_addr = escrow.token.clone();
+        let client = token::Client::new(env, &token_addr);
+
+        // The release fee comes out of the payout; the contributor receives `net`.
+        let (_, release_fee_rate) = Self::effective_fee_rates(env);
+        let (fee, net) = token_math::split_amount(payout_amount, release_fee_rate);
 
         // Decrement remaining; this is always an exact integer subtraction — no rounding
         escrow.remaining_amount = escrow.remaining_amount.checked_sub(payout_amount).unwrap();
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
 
         // Automatically transition to Released once fully paid out
         if escrow.remaining_amount == 0 {
@@ -2637,38 +4640,491 @@ impl BountyEscrowContract {
                 .persistent()
                 .set(&DataKey::CompletedAt(bounty_id), &now_ts);
         }
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        indexes::store_escrow(env, bounty_id, &escrow);
 
-        // INTERACTION: external token transfer is last (CEI pattern)
-        client.transfer(
-            &env.current_contract_address(),
-            &contributor,
-            &payout_amount,
+        // INTERACTION: external token transfers are last (CEI pattern)
+        client.transfer(&env.current_contract_address(), contributor, &net);
+        Self::transfer_fee(
+            env,
+            &client,
+            bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
         );
 
-        // INTERACTION: external token transfer is last (single transfer; state already updated above)
         events::emit_funds_released(
-            &env,
+            env,
             FundsReleased {
                 version: EVENT_VERSION_V2,
                 bounty_id,
-                amount: payout_amount,
+                amount: net,
                 recipient: contributor.clone(),
                 timestamp: env.ledger().timestamp(),
+                gross_amount: payout_amount,
+                fee_amount: fee,
             },
         );
 
-        // GUARD: release reentrancy lock
-        reentrancy_guard::release(&env);
         Ok(())
     }
 
-    /// Refund funds to the original depositor if the deadline has passed.
-    /// Refunds the full remaining_amount (accounts for any prior partial releases).
+    /// Release the whole `remaining_amount` to several contributors at once.
+    /// Only the admin (backend) can authorize this.
     ///
-    /// # Reentrancy
+    /// - `shares` pairs each recipient with a share in basis points; every
+    ///   share must be > 0, no recipient may appear twice and the total must
+    ///   be exactly 10_000.
+    /// - Each share is floored with `token_math::calculate_share`; the
+    ///   rounding remainder is added to `remainder_to`'s share, or paid to it
+    ///   separately if it holds none.
+    /// - The release fee is taken from each share as in `partial_release`.
+    /// - Under multisig, signers approve this exact `shares` and
+    ///   `remainder_to` with `approve_split_release`.
+    ///
+    /// The escrow moves to `Released` and one `FundsReleased` event is
+    /// emitted per payout.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. Escrow state is updated
+    /// *before* the outbound token transfers (CEI pattern).
+    pub fn release_split(
+        env: Env,
+        bounty_id: u64,
+        shares: Vec<(Address, u32)>,
+        remainder_to: Address,
+    ) -> Result<(), Error> {
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        if shares.is_empty() || shares.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let mut total_bps: u32 = 0;
+        for (i, (recipient, share_bps)) in shares.iter().enumerate() {
+            if share_bps == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            for j in 0..i as u32 {
+                if shares.get_unchecked(j).0 == recipient {
+                    return Err(Error::InvalidAmount);
+                }
+            }
+            total_bps = total_bps.saturating_add(share_bps);
+            access_control::require_allowed(&env, &recipient)?;
+        }
+        if total_bps as i128 != token_math::BASIS_POINTS {
+            return Err(Error::InvalidAmount);
+        }
+        access_control::require_allowed(&env, &remainder_to)?;
+        if Self::is_escrow_locked(&env, bounty_id) {
+            return Err(Error::EscrowLocked);
+        }
+        milestones::reject_whole_escrow(&env, bounty_id)?;
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+        {
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
+        }
+
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        Self::sync_promotions(&env);
+
+        let mut escrow: Escrow =
+            legacy::load_escrow(&env, bounty_id).ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+
+        let release_amount = escrow.remaining_amount;
+        if Self::check_split_approval(&env, bounty_id, &escrow, &shares, &remainder_to)? {
+            env.storage()
+                .persistent()
+                .remove(&ApprovalKey::Split(bounty_id));
+        }
+
+        // Work out every payout up front so state is final before transfers.
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let mut payouts: Vec<(Address, i128)> = Vec::new(&env);
+        let mut distributed: i128 = 0;
+        for (recipient, share_bps) in shares.iter() {
+            let gross = token_math::calculate_share(release_amount, share_bps as i128);
+            distributed += gross;
+            payouts.push_back((recipient, gross));
+        }
+        let remainder = release_amount - distributed;
+        if remainder > 0 {
+            let mut merged = false;
+            for i in 0..payouts.len() {
+                let (recipient, gross) = payouts.get_unchecked(i);
+                if recipient == remainder_to {
+                    payouts.set(i, (recipient, gross + remainder));
+                    merged = true;
+                    break;
+                }
+            }
+            if !merged {
+                payouts.push_back((remainder_to, remainder));
+            }
+        }
+
+        let mut total_fee: i128 = 0;
+        for (_, gross) in payouts.iter() {
+            let (fee, _) = token_math::split_amount(gross, release_fee_rate);
+            total_fee += fee;
+        }
+
+        // EFFECTS: update state before external calls (CEI)
+        escrow.status = EscrowStatus::Released;
+        escrow.remaining_amount = 0;
+        escrow.release_fee = escrow.release_fee.checked_add(total_fee).unwrap();
+        invariants::assert_escrow(&env, &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+        let now_ts = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::CompletedAt(bounty_id), &now_ts);
+
+        // INTERACTION: external token transfers are last
+        let client = token::Client::new(&env, &escrow.token);
+        for (recipient, gross) in payouts.iter() {
+            let (fee, net) = token_math::split_amount(gross, release_fee_rate);
+            if net > 0 {
+                client.transfer(&env.current_contract_address(), &recipient, &net);
+            }
+            emit_funds_released(
+                &env,
+                FundsReleased {
+                    version: EVENT_VERSION_V2,
+                    bounty_id,
+                    amount: net,
+                    recipient,
+                    timestamp: now_ts,
+                    gross_amount: gross,
+                    fee_amount: fee,
+                },
+            );
+        }
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            total_fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
+        );
+
+        multitoken_invariants::assert_after_disbursement(&env, &escrow.token);
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(())
+    }
+
+    /// Lock a bounty that is paid out in ordered stages.
+    ///
+    /// The deposit is the sum of the milestone amounts; the lock fee, if any,
+    /// is spread over the milestones pro rata. The escrow's `deadline` tracks
+    /// the earliest pending milestone and its status is derived from the
+    /// milestones (see `milestones::derive_status`).
+    ///
+    /// Milestone escrows are settled only through `release_milestone`,
+    /// `refund_milestone` and `extend_milestone`.
+    pub fn lock_funds_with_milestones(
+        env: Env,
+        depositor: Address,
+        bounty_id: u64,
+        milestones: Vec<milestones::MilestoneSpec>,
+    ) -> Result<(), Error> {
+        if milestones.is_empty() || milestones.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        if env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
+            return Err(Error::BountyExists);
+        }
+        let mut total: i128 = 0;
+        let mut deadline = u64::MAX;
+        for spec in milestones.iter() {
+            if spec.amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            total = total.checked_add(spec.amount).ok_or(Error::InvalidAmount)?;
+            deadline = deadline.min(spec.deadline);
+            if let Some(assignee) = &spec.assignee {
+                access_control::require_allowed(&env, assignee)?;
+            }
+        }
+
+        Self::lock_funds(env.clone(), depositor, bounty_id, total, deadline)?;
+
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
+        let stages = milestones::from_specs(&env, &milestones, total, escrow.amount)?;
+        milestones::save(&env, bounty_id, &stages);
+        Ok(())
+    }
+
+    /// Release one pending milestone to `contributor` (admin only).
+    ///
+    /// If the milestone has an assignee, `contributor` must be that address.
+    /// The release fee is taken from the milestone amount.
+    pub fn release_milestone(
+        env: Env,
+        bounty_id: u64,
+        index: u32,
+        contributor: Address,
+    ) -> Result<(), Error> {
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        access_control::require_allowed(&env, &contributor)?;
+        if Self::is_escrow_locked(&env, bounty_id) {
+            return Err(Error::EscrowLocked);
+        }
+
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        Self::sync_promotions(&env);
+
+        let (mut escrow, mut stages, mut milestone) =
+            Self::load_pending_milestone(&env, bounty_id, index)?;
+        if let Some(assignee) = &milestone.assignee {
+            if *assignee != contributor {
+                return Err(Error::Unauthorized);
+            }
+        }
+        if Self::check_release_approval(&env, bounty_id, &escrow, &contributor, milestone.amount)? {
+            Self::consume_release_approval(&env, bounty_id, &escrow, milestone.amount);
+        }
+
+        // EFFECTS: update state before external calls (CEI)
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(milestone.amount, release_fee_rate);
+        milestone.status = milestones::MilestoneStatus::Released;
+        stages.set(index, milestone.clone());
+        milestones::save(&env, bounty_id, &stages);
+        milestones::sync_escrow(&mut escrow, &stages);
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
+        invariants::assert_escrow(&env, &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+        let now = env.ledger().timestamp();
+        if escrow.status == EscrowStatus::Released || escrow.status == EscrowStatus::Refunded {
+            env.storage()
+                .persistent()
+                .set(&DataKey::CompletedAt(bounty_id), &now);
+        }
+
+        // INTERACTION: external token transfers are last
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(&env.current_contract_address(), &contributor, &net);
+        Self::transfer_fee(
+            &env,
+            &client,
+            bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
+        );
+
+        emit_funds_released(
+            &env,
+            FundsReleased {
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: net,
+                recipient: contributor.clone(),
+                timestamp: now,
+                gross_amount: milestone.amount,
+                fee_amount: fee,
+            },
+        );
+        events::emit_milestone_settled(
+            &env,
+            events::MilestoneSettled {
+                bounty_id,
+                index,
+                status: milestones::MilestoneStatus::Released,
+                amount: milestone.amount,
+                recipient: contributor,
+                timestamp: now,
+            },
+        );
+
+        multitoken_invariants::assert_after_disbursement(&env, &escrow.token);
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(())
+    }
+
+    /// Refund one pending milestone to the depositor once its deadline has
+    /// passed. Released milestones and milestones still before their
+    /// deadline cannot be refunded.
+    pub fn refund_milestone(env: Env, bounty_id: u64, index: u32) -> Result<(), Error> {
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+        if Self::is_escrow_locked(&env, bounty_id) {
+            return Err(Error::EscrowLocked);
+        }
+
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        let (mut escrow, mut stages, mut milestone) =
+            Self::load_pending_milestone(&env, bounty_id, index)?;
+        let now = env.ledger().timestamp();
+        if now < milestone.deadline {
+            return Err(Error::DeadlineNotPassed);
+        }
+
+        // EFFECTS: update state before external call (CEI)
+        milestone.status = milestones::MilestoneStatus::Refunded;
+        stages.set(index, milestone.clone());
+        milestones::save(&env, bounty_id, &stages);
+        milestones::sync_escrow(&mut escrow, &stages);
+        escrow.refund_history.push_back(RefundRecord {
+            amount: milestone.amount,
+            recipient: escrow.depositor.clone(),
+            timestamp: now,
+            mode: RefundMode::Partial,
+        });
+        invariants::assert_escrow(&env, &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+        if escrow.status == EscrowStatus::Released || escrow.status == EscrowStatus::Refunded {
+            env.storage()
+                .persistent()
+                .set(&DataKey::CompletedAt(bounty_id), &now);
+        }
+
+        // INTERACTION: external token transfer is last
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(
+            &env.current_contract_address(),
+            &escrow.depositor,
+            &milestone.amount,
+        );
+
+        emit_funds_refunded(
+            &env,
+            FundsRefunded {
+                version: EVENT_VERSION_V2,
+                bounty_id,
+                amount: milestone.amount,
+                refund_to: escrow.depositor.clone(),
+                timestamp: now,
+            },
+        );
+        events::emit_milestone_settled(
+            &env,
+            events::MilestoneSettled {
+                bounty_id,
+                index,
+                status: milestones::MilestoneStatus::Refunded,
+                amount: milestone.amount,
+                recipient: escrow.depositor.clone(),
+                timestamp: now,
+            },
+        );
+
+        multitoken_invariants::assert_after_disbursement(&env, &escrow.token);
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(())
+    }
+
+    /// Push a pending milestone's deadline later (admin only).
+    /// `new_deadline` must be in the future and after the current deadline.
+    pub fn extend_milestone(
+        env: Env,
+        bounty_id: u64,
+        index: u32,
+        new_deadline: u64,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let (mut escrow, mut stages, mut milestone) =
+            Self::load_pending_milestone(&env, bounty_id, index)?;
+        let now = env.ledger().timestamp();
+        if new_deadline <= now || new_deadline <= milestone.deadline {
+            return Err(Error::InvalidDeadline);
+        }
+
+        let old_deadline = milestone.deadline;
+        milestone.deadline = new_deadline;
+        stages.set(index, milestone);
+        milestones::save(&env, bounty_id, &stages);
+        milestones::sync_escrow(&mut escrow, &stages);
+        indexes::store_escrow(&env, bounty_id, &escrow);
+
+        events::emit_milestone_extended(
+            &env,
+            events::MilestoneExtended {
+                bounty_id,
+                index,
+                old_deadline,
+                new_deadline,
+                timestamp: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Milestones of a milestone escrow, in order.
+    pub fn get_milestones(env: Env, bounty_id: u64) -> Result<Vec<milestones::Milestone>, Error> {
+        milestones::get(&env, bounty_id).ok_or(Error::BountyNotFound)
+    }
+
+    /// Load a milestone escrow and one of its milestones, which must still be
+    /// pending. Unknown escrows and out-of-range indexes are `BountyNotFound`.
+    fn load_pending_milestone(
+        env: &Env,
+        bounty_id: u64,
+        index: u32,
+    ) -> Result<(Escrow, Vec<milestones::Milestone>, milestones::Milestone), Error> {
+        if Self::has_open_dispute(env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
+        let escrow: Escrow = legacy::load_escrow(env, bounty_id).ok_or(Error::BountyNotFound)?;
+        let stages = milestones::get(env, bounty_id).ok_or(Error::BountyNotFound)?;
+        let milestone = stages.get(index).ok_or(Error::BountyNotFound)?;
+        if milestone.status != milestones::MilestoneStatus::Pending {
+            return Err(Error::FundsNotLocked);
+        }
+        Ok((escrow, stages, milestone))
+    }
+
+    /// Refund funds to the original depositor if the deadline has passed.
+    /// Refunds the full remaining_amount (accounts for any prior partial releases).
+    ///
+    /// # Reentrancy
     /// Protected by the shared reentrancy guard. Escrow state, refund
     /// history, and approval cleanup are performed *before* the outbound
     /// token transfer (CEI pattern).
@@ -2690,6 +5146,10 @@ impl BountyEscrowContract {
         if Self::is_escrow_locked(&env, bounty_id) {
             return Err(Error::EscrowLocked);
         }
+        milestones::reject_whole_escrow(&env, bounty_id)?;
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
 
         // GUARD: acquire reentrancy lock
         reentrancy_guard::acquire(&env);
@@ -2698,11 +5158,7 @@ impl BountyEscrowContract {
             return Err(Error::BountyNotFound);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
         {
@@ -2748,6 +5204,14 @@ impl BountyEscrowContract {
             return Err(Error::InvalidAmount);
         }
 
+        // Deadline refunds of a crowdfunded escrow go back to every funder
+        // pro rata; an approved refund always pays its named recipient.
+        let payouts = if approval.is_none() {
+            crowdfund::refund_payouts(&env, bounty_id, &escrow, refund_amount)?
+        } else {
+            Vec::from_array(&env, [(refund_to, refund_amount)])
+        };
+
         // EFFECTS: update state before external call (CEI)
         invariants::assert_escrow(&env, &escrow);
         // Update escrow state: subtract the amount exactly refunded
@@ -2759,21 +5223,21 @@ impl BountyEscrowContract {
         }
 
         // Add to refund history
-        escrow.refund_history.push_back(RefundRecord {
-            amount: refund_amount,
-            recipient: refund_to.clone(),
-            timestamp: now,
-            mode: if is_full {
-                RefundMode::Full
-            } else {
-                RefundMode::Partial
-            },
-        });
+        for (recipient, amount) in payouts.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount,
+                recipient,
+                timestamp: now,
+                mode: if is_full {
+                    RefundMode::Full
+                } else {
+                    RefundMode::Partial
+                },
+            });
+        }
 
         // Save updated escrow
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
         if escrow.status == EscrowStatus::Refunded {
             env.storage()
                 .persistent()
@@ -2785,24 +5249,25 @@ impl BountyEscrowContract {
             env.storage().persistent().remove(&approval_key);
         }
 
-        // INTERACTION: external token transfer is last
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        // INTERACTION: external token transfers are last
+        let token_addr = escrow.token.clone();
         let client = token::Client::new(&env, &token_addr);
-        client.transfer(&env.current_contract_address(), &refund_to, &refund_amount);
-
-        emit_funds_refunded(
-            &env,
-            FundsRefunded {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount: refund_amount,
-                refund_to: refund_to.clone(),
-                timestamp: now,
-            },
-        );
+        for (recipient, amount) in payouts.iter() {
+            client.transfer(&env.current_contract_address(), &recipient, &amount);
+            emit_funds_refunded(
+                &env,
+                FundsRefunded {
+                    version: EVENT_VERSION_V2,
+                    bounty_id,
+                    amount,
+                    refund_to: recipient,
+                    timestamp: now,
+                },
+            );
+        }
 
         // INV-2: Verify aggregate balance matches token balance after refund
-        multitoken_invariants::assert_after_disbursement(&env);
+        multitoken_invariants::assert_after_disbursement(&env, &token_addr);
 
         // GUARD: release reentrancy lock
         reentrancy_guard::release(&env);
@@ -2824,6 +5289,10 @@ impl BountyEscrowContract {
         if Self::is_escrow_locked(&env, bounty_id) {
             return Err(Error::EscrowLocked);
         }
+        milestones::reject_whole_escrow(&env, bounty_id)?;
+        if Self::has_open_dispute(&env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
@@ -2831,11 +5300,7 @@ impl BountyEscrowContract {
             return Err(Error::BountyNotFound);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
         {
@@ -2869,12 +5334,10 @@ impl BountyEscrowContract {
             amount,
         )?;
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_addr = escrow.token.clone();
         let client = token::Client::new(&env, &token_addr);
         let now = env.ledger().timestamp();
-        let refund_to = escrow.depositor.clone();
-
-        client.transfer(&env.current_contract_address(), &refund_to, &amount);
+        let payouts = crowdfund::refund_payouts(&env, bounty_id, &escrow, amount)?;
 
         escrow.remaining_amount -= amount;
         if escrow.remaining_amount == 0 {
@@ -2882,46 +5345,49 @@ impl BountyEscrowContract {
         } else {
             escrow.status = EscrowStatus::PartiallyRefunded;
         }
+        let mode = if escrow.status == EscrowStatus::Refunded {
+            RefundMode::Full
+        } else {
+            RefundMode::Partial
+        };
+        for (recipient, part) in payouts.iter() {
+            escrow.refund_history.push_back(RefundRecord {
+                amount: part,
+                recipient,
+                timestamp: now,
+                mode: mode.clone(),
+            });
+        }
 
-        escrow.refund_history.push_back(RefundRecord {
-            amount,
-            recipient: refund_to.clone(),
-            timestamp: now,
-            mode: if escrow.status == EscrowStatus::Refunded {
-                RefundMode::Full
-            } else {
-                RefundMode::Partial
-            },
-        });
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
 
-        emit_funds_refunded(
-            &env,
-            FundsRefunded {
-                version: EVENT_VERSION_V2,
-                bounty_id,
-                amount,
-                refund_to,
-                timestamp: now,
-            },
-        );
+        for (refund_to, part) in payouts.iter() {
+            client.transfer(&env.current_contract_address(), &refund_to, &part);
+            emit_funds_refunded(
+                &env,
+                FundsRefunded {
+                    version: EVENT_VERSION_V2,
+                    bounty_id,
+                    amount: part,
+                    refund_to,
+                    timestamp: now,
+                },
+            );
+        }
 
         Ok(())
     }
 
     /// view function to get escrow info
     pub fn get_escrow_info(env: Env, bounty_id: u64) -> Result<Escrow, Error> {
-        if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
-            return Err(Error::BountyNotFound);
+        if let Some(escrow) = legacy::load_escrow(&env, bounty_id) {
+            return Ok(escrow);
         }
-        Ok(env
-            .storage()
-            .persistent()
+        // Archived with `ArchiveMode::Temporary` and not yet lapsed
+        env.storage()
+            .temporary()
             .get(&DataKey::Escrow(bounty_id))
-            .unwrap())
+            .ok_or(Error::BountyNotFound)
     }
 
     /// view function to get contract balance of the token
@@ -3048,12 +5514,14 @@ impl BountyEscrowContract {
         }
 
         // --- Would succeed ---
+        let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+        let (_, net) = token_math::split_amount(amount, lock_fee_rate);
         SimulationResult {
             success: true,
             error_code: 0,
             amount,
             resulting_status: EscrowStatus::Locked,
-            remaining_amount: amount,
+            remaining_amount: net,
         }
     }
 
@@ -3093,11 +5561,7 @@ impl BountyEscrowContract {
             };
         }
 
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked {
             return SimulationResult {
@@ -3110,10 +5574,12 @@ impl BountyEscrowContract {
         }
 
         // --- Would succeed ---
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (_, net) = token_math::split_amount(escrow.remaining_amount, release_fee_rate);
         SimulationResult {
             success: true,
             error_code: 0,
-            amount: escrow.amount,
+            amount: net,
             resulting_status: EscrowStatus::Released,
             remaining_amount: 0,
         }
@@ -3146,11 +5612,7 @@ impl BountyEscrowContract {
             };
         }
 
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
         {
@@ -3235,47 +5697,27 @@ impl BountyEscrowContract {
     }
 
     /// Query escrows with filtering and pagination
-    /// Pass 0 for min values and i128::MAX/u64::MAX for max values to disable those filters
+    /// Pages directly out of the status index; ids are returned in bounty id
+    /// order and `limit` is capped at `MAX_PAGE_SIZE`.
     pub fn query_escrows_by_status(
         env: Env,
         status: EscrowStatus,
         offset: u32,
         limit: u32,
     ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
+        let page = indexes::status_page(&env, &status, offset, limit.min(MAX_PAGE_SIZE));
         let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
-
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
-
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.status == status {
-                    if skipped < offset {
-                        skipped = skipped.checked_add(1).unwrap();
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count = count.checked_add(1).unwrap();
-                }
+        for bounty_id in page.iter() {
+            if let Some(escrow) = legacy::load_escrow(&env, bounty_id) {
+                results.push_back(EscrowWithId { bounty_id, escrow });
             }
         }
         results
     }
 
     /// Query escrows with amount range filtering
+    /// Results are ordered by amount ascending (ties by bounty id); `limit`
+    /// is capped at `MAX_PAGE_SIZE`.
     pub fn query_escrows_by_amount(
         env: Env,
         min_amount: i128,
@@ -3283,40 +5725,34 @@ impl BountyEscrowContract {
         offset: u32,
         limit: u32,
     ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
         let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
+        if min_amount > max_amount {
+            return results;
+        }
+        let page = indexes::scan(
+            &env,
+            &indexes::IndexKind::Amount,
+            min_amount,
+            0,
+            offset,
+            limit.min(MAX_PAGE_SIZE),
+        );
 
-        for i in 0..index.len() {
-            if count >= limit {
+        for i in 0..page.keys.len() {
+            if page.keys.get_unchecked(i) > max_amount {
                 break;
             }
-
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.amount >= min_amount && escrow.amount <= max_amount {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count = count.checked_add(1).unwrap();
-                }
+            let bounty_id = page.ids.get_unchecked(i);
+            if let Some(escrow) = legacy::load_escrow(&env, bounty_id) {
+                results.push_back(EscrowWithId { bounty_id, escrow });
             }
         }
         results
     }
 
     /// Query escrows with deadline range filtering
+    /// Results are ordered by deadline ascending (ties by bounty id);
+    /// `limit` is capped at `MAX_PAGE_SIZE`.
     pub fn query_escrows_by_deadline(
         env: Env,
         min_deadline: u64,
@@ -3324,34 +5760,26 @@ impl BountyEscrowContract {
         offset: u32,
         limit: u32,
     ) -> Vec<EscrowWithId> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
         let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
+        if min_deadline > max_deadline {
+            return results;
+        }
+        let page = indexes::scan(
+            &env,
+            &indexes::IndexKind::Deadline,
+            min_deadline as i128,
+            0,
+            offset,
+            limit.min(MAX_PAGE_SIZE),
+        );
 
-        for i in 0..index.len() {
-            if count >= limit {
+        for i in 0..page.keys.len() {
+            if page.keys.get_unchecked(i) > max_deadline as i128 {
                 break;
             }
-
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.deadline >= min_deadline && escrow.deadline <= max_deadline {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(EscrowWithId { bounty_id, escrow });
-                    count += 1;
-                }
+            let bounty_id = page.ids.get_unchecked(i);
+            if let Some(escrow) = legacy::load_escrow(&env, bounty_id) {
+                results.push_back(EscrowWithId { bounty_id, escrow });
             }
         }
         results
@@ -3375,72 +5803,190 @@ impl BountyEscrowContract {
 
         for i in start..end {
             let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
+            if let Some(escrow) = legacy::load_escrow(&env, bounty_id) {
                 results.push_back(EscrowWithId { bounty_id, escrow });
             }
         }
         results
     }
 
-    /// Get aggregate statistics
-    pub fn get_aggregate_stats(env: Env) -> AggregateStats {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut stats = AggregateStats {
-            total_locked: 0,
-            total_released: 0,
-            total_refunded: 0,
-            count_locked: 0,
-            count_released: 0,
-            count_refunded: 0,
+    /// Query open escrows by the repository named in their metadata, in the
+    /// order the metadata was attached. Released and refunded escrows drop
+    /// out of this view; `limit` is capped at `MAX_PAGE_SIZE`.
+    pub fn query_escrows_by_repo(
+        env: Env,
+        repo_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        metadata::page(&env, &metadata::MetadataKey::Repo(repo_id), offset, limit)
+    }
+
+    /// Query open escrows by metadata bounty type. Same ordering and page
+    /// cap as `query_escrows_by_repo`.
+    pub fn query_escrows_by_bounty_type(
+        env: Env,
+        bounty_type: soroban_sdk::String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        metadata::page(
+            &env,
+            &metadata::MetadataKey::BountyType(bounty_type),
+            offset,
+            limit,
+        )
+    }
+
+    /// Query open escrows carrying `tag`. Same ordering and page cap as
+    /// `query_escrows_by_repo`.
+    pub fn query_escrows_by_tag(
+        env: Env,
+        tag: soroban_sdk::String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EscrowWithId> {
+        metadata::page(&env, &metadata::MetadataKey::Tag(tag), offset, limit)
+    }
+
+    /// Query escrows with combined filters and cursor pagination.
+    ///
+    /// Escrows are walked in creation order (the depositor index when a
+    /// depositor is given, otherwise the creation index). Neither reorders
+    /// entries, so a cursor keeps its place even when escrows change
+    /// status between pages. A cursor minted for one filter is rejected
+    /// with `InvalidCursor` when replayed against another.
+    ///
+    /// `limit` must be non-zero and is capped at `MAX_PAGE_SIZE`. One call
+    /// examines at most `MAX_QUERY_SCAN` index entries, so a selective
+    /// filter may return a short or empty page with `has_more` set; the
+    /// cursor then points at the last entry examined.
+    pub fn query_escrows(
+        env: Env,
+        filter: EscrowFilter,
+        cursor: Option<soroban_sdk::Bytes>,
+        limit: u32,
+    ) -> Result<EscrowPage, Error> {
+        use soroban_sdk::xdr::ToXdr;
+
+        if limit == 0 {
+            return Err(Error::InvalidBatchSize);
+        }
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let fingerprint: soroban_sdk::Bytes = env
+            .crypto()
+            .sha256(&filter.clone().to_xdr(&env))
+            .to_bytes()
+            .into();
+        let after = match cursor {
+            Some(cursor) => {
+                if cursor.len() != 40 || cursor.slice(8..) != fingerprint {
+                    return Err(Error::InvalidCursor);
+                }
+                let mut id_bytes = [0u8; 8];
+                cursor.slice(..8).copy_into_slice(&mut id_bytes);
+                Some(u64::from_be_bytes(id_bytes))
+            }
+            None => None,
         };
 
-        for i in 0..index.len() {
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                match escrow.status {
-                    EscrowStatus::Locked => {
-                        stats.total_locked = stats.total_locked.checked_add(escrow.amount).unwrap();
-                        stats.count_locked = stats.count_locked.checked_add(1).unwrap();
-                    }
-                    EscrowStatus::Released => {
-                        stats.total_released =
-                            stats.total_released.checked_add(escrow.amount).unwrap();
-                        stats.count_released = stats.count_released.checked_add(1).unwrap();
-                    }
-                    EscrowStatus::Refunded | EscrowStatus::PartiallyRefunded => {
-                        stats.total_refunded =
-                            stats.total_refunded.checked_add(escrow.amount).unwrap();
-                        stats.count_refunded = stats.count_refunded.checked_add(1).unwrap();
-                    }
-                    EscrowStatus::Template => {
-                        // Template escrows have 0 amount; no aggregate contribution
+        // The next `MAX_QUERY_SCAN` ids after the cursor, and whether the
+        // index continues past them.
+        let (window, more) = match &filter.depositor {
+            Some(depositor) => {
+                let index: Vec<u64> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::DepositorIndex(depositor.clone()))
+                    .unwrap_or(Vec::new(&env));
+                let start = match after {
+                    Some(bounty_id) => {
+                        index
+                            .first_index_of(bounty_id)
+                            .ok_or(Error::InvalidCursor)?
+                            + 1
                     }
+                    None => 0,
+                };
+                let end = index.len().min(start.saturating_add(MAX_QUERY_SCAN));
+                (index.slice(start..end), end < index.len())
+            }
+            None => {
+                let (key, bounty_id) = match after {
+                    Some(bounty_id) => (
+                        indexes::sequence(&env, bounty_id).ok_or(Error::InvalidCursor)? as i128,
+                        bounty_id.saturating_add(1),
+                    ),
+                    None => (0, 0),
+                };
+                let mut ids = indexes::scan(
+                    &env,
+                    &indexes::IndexKind::Created,
+                    key,
+                    bounty_id,
+                    0,
+                    MAX_QUERY_SCAN + 1,
+                )
+                .ids;
+                let more = ids.len() > MAX_QUERY_SCAN;
+                if more {
+                    ids.pop_back();
                 }
+                (ids, more)
             }
+        };
+
+        let mut items = Vec::new(&env);
+        let mut pos = 0;
+        while pos < window.len() && items.len() < limit {
+            let bounty_id = window.get_unchecked(pos);
+            pos += 1;
+            let escrow: Escrow = match legacy::load_escrow(&env, bounty_id) {
+                Some(escrow) => escrow,
+                None => continue,
+            };
+            if Self::matches_filter(&filter, &escrow) {
+                items.push_back(EscrowWithId { bounty_id, escrow });
+            }
+        }
+
+        let has_more = pos < window.len() || more;
+        let next_cursor = if has_more {
+            let last = window.get_unchecked(pos - 1);
+            let mut cursor = soroban_sdk::Bytes::from_array(&env, &last.to_be_bytes());
+            cursor.append(&fingerprint);
+            Some(cursor)
+        } else {
+            None
+        };
+        Ok(EscrowPage {
+            items,
+            next_cursor,
+            has_more,
+        })
+    }
+
+    fn matches_filter(filter: &EscrowFilter, escrow: &Escrow) -> bool {
+        if !filter.statuses.is_empty() && !filter.statuses.contains(&escrow.status) {
+            return false;
         }
-        stats
+        // A depositor filter is applied by walking that address's
+        // DepositorIndex, which also lists escrows it has contributed to.
+        escrow.deadline >= filter.min_deadline
+            && escrow.deadline <= filter.max_deadline
+            && escrow.amount >= filter.min_amount
+            && escrow.amount <= filter.max_amount
+    }
+
+    /// Get aggregate statistics
+    /// Totals are maintained on every escrow write, so this is a single read.
+    pub fn get_aggregate_stats(env: Env) -> AggregateStats {
+        indexes::get_totals(&env)
     }
 
     /// Get total count of escrows
     pub fn get_escrow_count(env: Env) -> u32 {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.len()
+        indexes::created_count(&env)
     }
 
     /// Set the minimum and maximum allowed lock amount (admin only).
@@ -3480,43 +6026,15 @@ impl BountyEscrowContract {
         Ok(())
     }
 
-    /// Get escrow IDs by status
+    /// Get escrow IDs by status, in bounty id order. `limit` is capped at
+    /// `MAX_PAGE_SIZE`.
     pub fn get_escrow_ids_by_status(
         env: Env,
         status: EscrowStatus,
         offset: u32,
         limit: u32,
     ) -> Vec<u64> {
-        let index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        let mut results = Vec::new(&env);
-        let mut count = 0u32;
-        let mut skipped = 0u32;
-
-        for i in 0..index.len() {
-            if count >= limit {
-                break;
-            }
-            let bounty_id = index.get(i).unwrap();
-            if let Some(escrow) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-            {
-                if escrow.status == status {
-                    if skipped < offset {
-                        skipped += 1;
-                        continue;
-                    }
-                    results.push_back(bounty_id);
-                    count += 1;
-                }
-            }
-        }
-        results
+        indexes::status_page(&env, &status, offset, limit.min(MAX_PAGE_SIZE))
     }
 
     pub fn set_anti_abuse_admin(env: Env, admin: Address) -> Result<(), Error> {
@@ -3550,12 +6068,14 @@ impl BountyEscrowContract {
         Ok(())
     }
 
-    /// Update anti-abuse config (rate limit window, max operations per window, cooldown). Admin only.
-    pub fn update_anti_abuse_config(
+    /// Add or remove a participant from the blacklist (admin only).
+    /// Blacklisted addresses cannot lock funds or receive releases, claims
+    /// or claim tickets.
+    pub fn set_blacklist(
         env: Env,
-        window_size: u64,
-        max_operations: u32,
-        cooldown_period: u64,
+        address: Address,
+        blacklisted: bool,
+        reason: Option<String>,
     ) -> Result<(), Error> {
         let admin: Address = env
             .storage()
@@ -3563,28 +6083,92 @@ impl BountyEscrowContract {
             .get(&DataKey::Admin)
             .ok_or(Error::NotInitialized)?;
         admin.require_auth();
-        let config = anti_abuse::AntiAbuseConfig {
-            window_size,
-            max_operations,
-            cooldown_period,
-        };
-        anti_abuse::set_config(&env, config);
+        access_control::set_blacklisted(&env, &admin, &address, blacklisted, reason);
         Ok(())
     }
 
-    /// Get current anti-abuse config (rate limit and cooldown).
-    pub fn get_anti_abuse_config(env: Env) -> AntiAbuseConfigView {
-        let c = anti_abuse::get_config(&env);
-        AntiAbuseConfigView {
-            window_size: c.window_size,
-            max_operations: c.max_operations,
-            cooldown_period: c.cooldown_period,
-        }
-    }
-
-    /// Retrieves the refund history for a specific bounty.
-    ///
-    /// # Arguments
+    /// Add or remove a participant from the allow list used by
+    /// whitelist-only mode (admin only).
+    pub fn set_participant_whitelist(
+        env: Env,
+        address: Address,
+        whitelisted: bool,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        access_control::set_whitelisted(&env, &admin, &address, whitelisted);
+        Ok(())
+    }
+
+    /// Enable or disable whitelist-only mode (admin only). While enabled,
+    /// only allow-listed participants may deposit or receive funds.
+    pub fn set_whitelist_mode(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        access_control::set_whitelist_mode(&env, &admin, enabled);
+        Ok(())
+    }
+
+    pub fn is_blacklisted(env: Env, address: Address) -> bool {
+        access_control::is_blacklisted(&env, &address)
+    }
+
+    pub fn is_participant_whitelisted(env: Env, address: Address) -> bool {
+        access_control::is_whitelisted(&env, &address)
+    }
+
+    pub fn get_whitelist_mode(env: Env) -> bool {
+        access_control::whitelist_mode(&env)
+    }
+
+    /// Whether `address` currently passes participant screening.
+    pub fn is_participant_allowed(env: Env, address: Address) -> bool {
+        access_control::is_allowed(&env, &address)
+    }
+
+    /// Update anti-abuse config (rate limit window, max operations per window, cooldown). Admin only.
+    pub fn update_anti_abuse_config(
+        env: Env,
+        window_size: u64,
+        max_operations: u32,
+        cooldown_period: u64,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        let config = anti_abuse::AntiAbuseConfig {
+            window_size,
+            max_operations,
+            cooldown_period,
+        };
+        anti_abuse::set_config(&env, config);
+        Ok(())
+    }
+
+    /// Get current anti-abuse config (rate limit and cooldown).
+    pub fn get_anti_abuse_config(env: Env) -> AntiAbuseConfigView {
+        let c = anti_abuse::get_config(&env);
+        AntiAbuseConfigView {
+            window_size: c.window_size,
+            max_operations: c.max_operations,
+            cooldown_period: c.cooldown_period,
+        }
+    }
+
+    /// Retrieves the refund history for a specific bounty.
+    ///
+    /// # Arguments
     /// * `env` - The contract environment
     /// * `bounty_id` - The bounty to query
     ///
@@ -3595,21 +6179,13 @@ impl BountyEscrowContract {
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
         Ok(escrow.refund_history)
     }
 
     /// NEW: Verify escrow invariants for a specific bounty
     pub fn verify_state(env: Env, bounty_id: u64) -> bool {
-        if let Some(escrow) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-        {
+        if let Some(escrow) = legacy::load_escrow(&env, bounty_id) {
             invariants::verify_escrow_invariants(&escrow)
         } else {
             false
@@ -3653,11 +6229,7 @@ impl BountyEscrowContract {
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         let now = env.ledger().timestamp();
         let deadline_passed = now >= escrow.deadline;
@@ -3739,19 +6311,7 @@ impl BountyEscrowContract {
 
         // Validate all items before processing (all-or-nothing approach)
         for item in items.iter() {
-            // Check if bounty already exists
-            if env
-                .storage()
-                .persistent()
-                .has(&DataKey::Escrow(item.bounty_id))
-            {
-                return Err(Error::BountyExists);
-            }
-
-            // Validate amount
-            if item.amount <= 0 {
-                return Err(Error::InvalidAmount);
-            }
+            Self::check_batch_lock_item(&env, &item)?;
 
             // Check for duplicate bounty_ids in the batch
             let mut count = 0u32;
@@ -3777,50 +6337,18 @@ impl BountyEscrowContract {
                 }
             }
             if !found {
+                access_control::require_allowed(&env, &item.depositor)?;
                 seen_depositors.push_back(item.depositor.clone());
                 item.depositor.require_auth();
             }
         }
 
         // EFFECTS: write all escrow records before any external calls (CEI)
+        let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
         let mut locked_count = 0u32;
         for item in items.iter() {
-            let escrow = Escrow {
-                depositor: item.depositor.clone(),
-                amount: item.amount,
-                status: EscrowStatus::Locked,
-                deadline: item.deadline,
-                refund_history: vec![&env],
-                remaining_amount: item.amount,
-            };
-
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(item.bounty_id), &escrow);
-
-            // Update EscrowIndex (same as lock_funds)
-            let mut index: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::EscrowIndex)
-                .unwrap_or(Vec::new(&env));
-            index.push_back(item.bounty_id);
-            env.storage()
-                .persistent()
-                .set(&DataKey::EscrowIndex, &index);
-
-            // Update DepositorIndex
-            let mut depositor_index: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::DepositorIndex(item.depositor.clone()))
-                .unwrap_or(Vec::new(&env));
-            depositor_index.push_back(item.bounty_id);
-            env.storage().persistent().set(
-                &DataKey::DepositorIndex(item.depositor.clone()),
-                &depositor_index,
-            );
-
+            let (fee, net) = token_math::split_amount(item.amount, lock_fee_rate);
+            Self::record_batch_lock(&env, &item, fee, net, &token_addr);
             locked_count += 1;
         }
 
@@ -3828,12 +6356,21 @@ impl BountyEscrowContract {
         let mut action_summaries: Vec<ActionSummary> = Vec::new(&env);
         let mut total_amount: i128 = 0;
         for item in items.iter() {
+            let (fee, net) = token_math::split_amount(item.amount, lock_fee_rate);
             client.transfer(&item.depositor, &contract_address, &item.amount);
-            total_amount = total_amount.checked_add(item.amount).unwrap();
+            Self::transfer_fee(
+                &env,
+                &client,
+                item.bounty_id,
+                fee,
+                lock_fee_rate,
+                events::FeeOperationType::Lock,
+            );
+            total_amount = total_amount.checked_add(net).unwrap();
             action_summaries.push_back(ActionSummary {
                 bounty_id: item.bounty_id,
                 action_type: 1u32, // Lock
-                amount: item.amount,
+                amount: net,
                 timestamp,
             });
             emit_funds_locked(
@@ -3841,9 +6378,11 @@ impl BountyEscrowContract {
                 FundsLocked {
                     version: EVENT_VERSION_V2,
                     bounty_id: item.bounty_id,
-                    amount: item.amount,
+                    amount: net,
                     depositor: item.depositor.clone(),
                     deadline: item.deadline,
+                    gross_amount: item.amount,
+                    fee_amount: fee,
                 },
             );
         }
@@ -3919,122 +6458,742 @@ impl BountyEscrowContract {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
 
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
         let contract_address = env.current_contract_address();
         let timestamp = env.ledger().timestamp();
 
         // Validate all items before processing (all-or-nothing approach)
         let mut total_amount: i128 = 0;
         for item in items.iter() {
-            if Self::is_escrow_locked(&env, item.bounty_id) {
-                return Err(Error::EscrowLocked);
+            let escrow = Self::check_batch_release_item(&env, &item)?;
+
+            let mut count = 0u32;
+            for other_item in items.iter() {
+                if other_item.bounty_id == item.bounty_id {
+                    count += 1;
+                }
             }
-            if !env
-                .storage()
+            if count > 1 {
+                return Err(Error::DuplicateBountyId);
+            }
+
+            total_amount = total_amount
+                .checked_add(escrow.remaining_amount)
+                .ok_or(Error::InvalidAmount)?;
+        }
+
+        // EFFECTS: update all escrow records before any external calls (CEI)
+        // We collect (contributor, token, gross amount) for the transfer pass.
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let mut release_pairs: Vec<(Address, Address, i128)> = Vec::new(&env);
+        let mut released_count = 0u32;
+        for item in items.iter() {
+            let mut escrow: Escrow = legacy::load_escrow(&env, item.bounty_id).unwrap();
+
+            let amount = escrow.remaining_amount;
+            let (fee, _) = token_math::split_amount(amount, release_fee_rate);
+            env.storage()
                 .persistent()
-                .has(&DataKey::Escrow(item.bounty_id))
+                .remove(&DataKey::ReleaseApproval(item.bounty_id));
+            escrow.status = EscrowStatus::Released;
+            escrow.remaining_amount = 0;
+            escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
+            indexes::store_escrow(&env, item.bounty_id, &escrow);
+            env.storage()
+                .persistent()
+                .set(&DataKey::CompletedAt(item.bounty_id), &timestamp);
+
+            release_pairs.push_back((item.contributor.clone(), escrow.token.clone(), amount));
+            released_count += 1;
+        }
+
+        // INTERACTION: all external token transfers happen after state is finalized
+        let mut action_summaries: Vec<ActionSummary> = Vec::new(&env);
+        for (idx, item) in items.iter().enumerate() {
+            let (ref contributor, ref token_addr, amount) = release_pairs.get(idx as u32).unwrap();
+            let client = token::Client::new(&env, token_addr);
+            let (fee, net) = token_math::split_amount(amount, release_fee_rate);
+            client.transfer(&contract_address, contributor, &net);
+            Self::transfer_fee(
+                &env,
+                &client,
+                item.bounty_id,
+                fee,
+                release_fee_rate,
+                events::FeeOperationType::Release,
+            );
+            action_summaries.push_back(ActionSummary {
+                bounty_id: item.bounty_id,
+                action_type: 2u32, // Release
+                amount: net,
+                timestamp,
+            });
+            emit_funds_released(
+                &env,
+                FundsReleased {
+                    version: EVENT_VERSION_V2,
+                    bounty_id: item.bounty_id,
+                    amount: net,
+                    recipient: contributor.clone(),
+                    timestamp,
+                    gross_amount: amount,
+                    fee_amount: fee,
+                },
+            );
+        }
+
+        // Emit batch event (Issue #676)
+        emit_batch_funds_released(
+            &env,
+            BatchFundsReleased {
+                version: EVENT_VERSION_V2,
+                count: released_count,
+                total_amount,
+                timestamp,
+            },
+        );
+        emit_event_batch(
+            &env,
+            EventBatch {
+                version: EVENT_VERSION_V2,
+                batch_type: 2u32, // release
+                actions: action_summaries,
+                total_amount,
+                timestamp,
+            },
+        );
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(released_count)
+    }
+
+    /// Lock funds for several bounties, skipping items that fail instead of
+    /// reverting the batch. Returns one `BatchItemResult` per item, in order.
+    ///
+    /// Items fail with the error `batch_lock_funds` would return for them,
+    /// plus `DuplicateBountyId` for a repeat of an earlier item and
+    /// `InsufficientFunds` when the depositor cannot cover the amount.
+    /// Batch-level problems (pause, batch size, uninitialized contract)
+    /// still fail the whole call.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. Each escrow is written
+    /// before its inbound transfer; INV-2 is checked once after the batch.
+    pub fn batch_lock_funds_best_effort(
+        env: Env,
+        items: Vec<LockFundsItem>,
+    ) -> Result<Vec<BatchItemResult>, Error> {
+        if Self::check_paused(&env, symbol_short!("lock")) {
+            return Err(Error::FundsPaused);
+        }
+
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        if items.is_empty() || items.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        if !env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::NotInitialized);
+        }
+
+        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let client = token::Client::new(&env, &token_addr);
+        let contract_address = env.current_contract_address();
+        let timestamp = env.ledger().timestamp();
+        let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+
+        let mut seen_depositors: Vec<Address> = Vec::new(&env);
+        // Balance each depositor had when the batch started, less what
+        // earlier items in the batch have already committed.
+        let mut uncommitted: soroban_sdk::Map<Address, i128> = soroban_sdk::Map::new(&env);
+        let mut results: Vec<BatchItemResult> = Vec::new(&env);
+        let mut action_summaries: Vec<ActionSummary> = Vec::new(&env);
+        let (mut locked_count, mut total_amount) = (0u32, 0i128);
+        for (i, item) in items.iter().enumerate() {
+            let available = uncommitted
+                .get(item.depositor.clone())
+                .unwrap_or_else(|| client.balance(&item.depositor));
+            let checked = if items
+                .slice(0..i as u32)
+                .iter()
+                .any(|earlier| earlier.bounty_id == item.bounty_id)
+            {
+                Err(Error::DuplicateBountyId)
+            } else {
+                Self::check_batch_lock_item(&env, &item)
+                    .and_then(|_| access_control::require_allowed(&env, &item.depositor))
+                    .and(if available < item.amount {
+                        Err(Error::InsufficientFunds)
+                    } else {
+                        Ok(())
+                    })
+            };
+            if let Err(err) = checked {
+                results.push_back(BatchItemResult {
+                    bounty_id: item.bounty_id,
+                    success: false,
+                    error_code: err as u32,
+                });
+                continue;
+            }
+            if !seen_depositors.contains(&item.depositor) {
+                seen_depositors.push_back(item.depositor.clone());
+                item.depositor.require_auth();
+            }
+            uncommitted.set(item.depositor.clone(), available - item.amount);
+
+            // EFFECTS: update state before external call (CEI)
+            let (fee, net) = token_math::split_amount(item.amount, lock_fee_rate);
+            Self::record_batch_lock(&env, &item, fee, net, &token_addr);
+
+            // INTERACTION: external token transfers are last
+            client.transfer(&item.depositor, &contract_address, &item.amount);
+            Self::transfer_fee(
+                &env,
+                &client,
+                item.bounty_id,
+                fee,
+                lock_fee_rate,
+                events::FeeOperationType::Lock,
+            );
+            emit_funds_locked(
+                &env,
+                FundsLocked {
+                    version: EVENT_VERSION_V2,
+                    bounty_id: item.bounty_id,
+                    amount: net,
+                    depositor: item.depositor.clone(),
+                    deadline: item.deadline,
+                    gross_amount: item.amount,
+                    fee_amount: fee,
+                },
+            );
+            action_summaries.push_back(ActionSummary {
+                bounty_id: item.bounty_id,
+                action_type: 1u32, // Lock
+                amount: net,
+                timestamp,
+            });
+            locked_count += 1;
+            total_amount = total_amount.checked_add(net).unwrap();
+            results.push_back(BatchItemResult {
+                bounty_id: item.bounty_id,
+                success: true,
+                error_code: 0,
+            });
+        }
+
+        if locked_count > 0 {
+            emit_batch_funds_locked(
+                &env,
+                BatchFundsLocked {
+                    version: EVENT_VERSION_V2,
+                    count: locked_count,
+                    total_amount,
+                    timestamp,
+                },
+            );
+            emit_event_batch(
+                &env,
+                EventBatch {
+                    version: EVENT_VERSION_V2,
+                    batch_type: 1u32, // lock
+                    actions: action_summaries,
+                    total_amount,
+                    timestamp,
+                },
+            );
+        }
+
+        // INV-2: Verify aggregate balance matches token balance after the batch
+        multitoken_invariants::assert_after_lock(&env, &token_addr);
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(results)
+    }
+
+    /// Release several escrows, skipping items that fail instead of
+    /// reverting the batch. Returns one `BatchItemResult` per item, in order.
+    ///
+    /// Items fail with the error `batch_release_funds` would return for
+    /// them, e.g. `DisputeOpen` for a disputed bounty or `DuplicateBountyId`
+    /// for a repeat of an earlier item. Batch-level problems (pause, batch
+    /// size, uninitialized contract) still fail the whole call.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. Each escrow is updated to
+    /// `Released` before its outbound transfer; INV-2 is checked once after
+    /// the batch.
+    pub fn batch_release_funds_best_effort(
+        env: Env,
+        items: Vec<ReleaseFundsItem>,
+    ) -> Result<Vec<BatchItemResult>, Error> {
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        if items.is_empty() || items.len() > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let contract_address = env.current_contract_address();
+        let timestamp = env.ledger().timestamp();
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+
+        let mut results: Vec<BatchItemResult> = Vec::new(&env);
+        let mut action_summaries: Vec<ActionSummary> = Vec::new(&env);
+        let mut tokens: Vec<Address> = Vec::new(&env);
+        let (mut released_count, mut total_amount) = (0u32, 0i128);
+        for (i, item) in items.iter().enumerate() {
+            let checked = if items
+                .slice(0..i as u32)
+                .iter()
+                .any(|earlier| earlier.bounty_id == item.bounty_id)
             {
-                return Err(Error::BountyNotFound);
+                Err(Error::DuplicateBountyId)
+            } else {
+                Self::check_batch_release_item(&env, &item)
+            };
+            let mut escrow = match checked {
+                Ok(escrow) => escrow,
+                Err(err) => {
+                    results.push_back(BatchItemResult {
+                        bounty_id: item.bounty_id,
+                        success: false,
+                        error_code: err as u32,
+                    });
+                    continue;
+                }
+            };
+
+            // EFFECTS: update state before external call (CEI)
+            let amount = escrow.remaining_amount;
+            let (fee, net) = token_math::split_amount(amount, release_fee_rate);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::ReleaseApproval(item.bounty_id));
+            escrow.status = EscrowStatus::Released;
+            escrow.remaining_amount = 0;
+            escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
+            indexes::store_escrow(&env, item.bounty_id, &escrow);
+            env.storage()
+                .persistent()
+                .set(&DataKey::CompletedAt(item.bounty_id), &timestamp);
+
+            // INTERACTION: external token transfers are last
+            let client = token::Client::new(&env, &escrow.token);
+            client.transfer(&contract_address, &item.contributor, &net);
+            Self::transfer_fee(
+                &env,
+                &client,
+                item.bounty_id,
+                fee,
+                release_fee_rate,
+                events::FeeOperationType::Release,
+            );
+            emit_funds_released(
+                &env,
+                FundsReleased {
+                    version: EVENT_VERSION_V2,
+                    bounty_id: item.bounty_id,
+                    amount: net,
+                    recipient: item.contributor.clone(),
+                    timestamp,
+                    gross_amount: amount,
+                    fee_amount: fee,
+                },
+            );
+            action_summaries.push_back(ActionSummary {
+                bounty_id: item.bounty_id,
+                action_type: 2u32, // Release
+                amount: net,
+                timestamp,
+            });
+            if !tokens.contains(&escrow.token) {
+                tokens.push_back(escrow.token.clone());
             }
+            released_count += 1;
+            total_amount = total_amount.checked_add(amount).unwrap();
+            results.push_back(BatchItemResult {
+                bounty_id: item.bounty_id,
+                success: true,
+                error_code: 0,
+            });
+        }
+
+        if released_count > 0 {
+            emit_batch_funds_released(
+                &env,
+                BatchFundsReleased {
+                    version: EVENT_VERSION_V2,
+                    count: released_count,
+                    total_amount,
+                    timestamp,
+                },
+            );
+            emit_event_batch(
+                &env,
+                EventBatch {
+                    version: EVENT_VERSION_V2,
+                    batch_type: 2u32, // release
+                    actions: action_summaries,
+                    total_amount,
+                    timestamp,
+                },
+            );
+        }
+
+        // INV-2: Verify aggregate balance matches token balance after the batch
+        for token_addr in tokens.iter() {
+            multitoken_invariants::assert_after_disbursement(&env, &token_addr);
+        }
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(results)
+    }
+
+    /// Checks shared by both batch lock modes for one item.
+    fn check_batch_lock_item(env: &Env, item: &LockFundsItem) -> Result<(), Error> {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Escrow(item.bounty_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Archived(item.bounty_id))
+        {
+            return Err(Error::BountyExists);
+        }
+        if item.amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Ok(())
+    }
+
+    /// Write a batch-locked escrow and add it to the escrow and depositor
+    /// indexes, as `lock_funds` does.
+    fn record_batch_lock(
+        env: &Env,
+        item: &LockFundsItem,
+        fee: i128,
+        net: i128,
+        token_addr: &Address,
+    ) {
+        let escrow = Escrow {
+            depositor: item.depositor.clone(),
+            amount: net,
+            status: EscrowStatus::Locked,
+            deadline: item.deadline,
+            refund_history: vec![env],
+            lock_fee: fee,
+            release_fee: 0,
+            remaining_amount: net,
+            token: token_addr.clone(),
+        };
+        indexes::store_escrow(env, item.bounty_id, &escrow);
+
+        let depositor_key = DataKey::DepositorIndex(item.depositor.clone());
+        let mut depositor_index: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&depositor_key)
+            .unwrap_or(Vec::new(env));
+        depositor_index.push_back(item.bounty_id);
+        env.storage()
+            .persistent()
+            .set(&depositor_key, &depositor_index);
+        ttl::extend(env, &depositor_key);
+    }
+
+    /// Checks shared by both batch release modes for one item. Returns the
+    /// escrow to release.
+    fn check_batch_release_item(env: &Env, item: &ReleaseFundsItem) -> Result<Escrow, Error> {
+        if Self::is_escrow_locked(env, item.bounty_id) {
+            return Err(Error::EscrowLocked);
+        }
+        milestones::reject_whole_escrow(env, item.bounty_id)?;
+        if Self::has_open_dispute(env, item.bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
+        access_control::require_allowed(env, &item.contributor)?;
+        let escrow: Escrow =
+            legacy::load_escrow(env, item.bounty_id).ok_or(Error::BountyNotFound)?;
+        if escrow.status != EscrowStatus::Locked {
+            return Err(Error::FundsNotLocked);
+        }
+        Self::check_release_approval(
+            env,
+            item.bounty_id,
+            &escrow,
+            &item.contributor,
+            escrow.remaining_amount,
+        )?;
+        Ok(escrow)
+    }
+
+    /// Set the keeper reward for `batch_refund_expired`, in basis points of
+    /// the lock fee (admin only). That share of every lock fee collected
+    /// from now on is set aside to pay keepers; zero disables the reward.
+    pub fn set_keeper_reward(env: Env, reward_bps: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        if reward_bps as i128 > MAX_FEE_RATE {
+            return Err(Error::InvalidFeeRate);
+        }
+        keeper::set_reward_bps(&env, reward_bps);
+        Ok(())
+    }
+
+    pub fn get_keeper_reward(env: Env) -> u32 {
+        keeper::reward_bps(&env)
+    }
+
+    /// Lock fees in `token` set aside for keepers and not yet paid out.
+    pub fn get_keeper_pool(env: Env, token: Address) -> i128 {
+        keeper::pool(&env, &token)
+    }
+
+    /// Send the unpaid keeper pool in `token` on to the fee recipient
+    /// (admin only), e.g. after turning the keeper reward off. Returns the
+    /// amount sent.
+    pub fn withdraw_keeper_pool(env: Env, token: Address) -> Result<i128, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
+
+        // EFFECTS: empty the pool before the transfer (CEI)
+        let amount = keeper::pool(&env, &token);
+        keeper::clear_pool(&env, &token);
+        if amount > 0 {
+            let recipient = Self::get_fee_config_internal(&env).fee_recipient;
+            token::Client::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &recipient,
+                &amount,
+            );
+        }
+
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(amount)
+    }
+
+    /// Refund up to `limit` expired escrows, oldest deadline first. Anyone
+    /// may call this; `keeper` receives the configured keeper reward.
+    ///
+    /// Every escrow still holding funds whose deadline has passed is either
+    /// refunded to its depositor (pro rata to its funders when crowdfunded)
+    /// or reported as skipped with the error `refund` would return: owner
+    /// lock or milestone escrow (`EscrowLocked`), open dispute
+    /// (`DisputeOpen`) or pending claim (`ClaimPending`). Skipped escrows
+    /// count towards `limit`. Escrows with a pending refund approval are
+    /// left to `refund`, which pays the approved recipient.
+    ///
+    /// At most `keeper::MAX_SWEEP_SCAN` index entries are scanned per call.
+    /// Pass the returned `next_cursor` to continue where this call stopped,
+    /// or `None` to start from the oldest deadline.
+    ///
+    /// # Reentrancy
+    /// Protected by the shared reentrancy guard. Each escrow is written
+    /// before its outbound transfers.
+    pub fn batch_refund_expired(
+        env: Env,
+        keeper: Address,
+        cursor: Option<soroban_sdk::Bytes>,
+        limit: u32,
+    ) -> Result<keeper::SweepReport, Error> {
+        if Self::check_paused(&env, symbol_short!("refund")) {
+            return Err(Error::FundsPaused);
+        }
+        if limit == 0 || limit > MAX_BATCH_SIZE {
+            return Err(Error::InvalidBatchSize);
+        }
+        let resume = match &cursor {
+            Some(cursor) => Some(keeper::decode_cursor(cursor).ok_or(Error::InvalidCursor)?),
+            None => None,
+        };
+        keeper.require_auth();
 
-            let escrow: Escrow = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Escrow(item.bounty_id))
-                .unwrap();
+        // GUARD: acquire reentrancy lock
+        reentrancy_guard::acquire(&env);
 
-            if escrow.status != EscrowStatus::Locked {
-                return Err(Error::FundsNotLocked);
+        let now = env.ledger().timestamp();
+        let (from_deadline, from_id) = match resume {
+            Some((deadline, bounty_id)) => (deadline as i128, bounty_id.saturating_add(1)),
+            None => (0, 0),
+        };
+        let window = indexes::scan(
+            &env,
+            &indexes::IndexKind::Expiry,
+            from_deadline,
+            from_id,
+            0,
+            keeper::MAX_SWEEP_SCAN,
+        );
+        // Entries from `end` on have not expired yet.
+        let end = indexes::lower_bound(&window, now as i128 + 1, 0);
+
+        let mut items: Vec<keeper::SweepItem> = Vec::new(&env);
+        let (mut count, mut total_amount, mut total_reward) = (0u32, 0i128, 0i128);
+        let mut pos = 0;
+        while pos < end && items.len() < limit {
+            let bounty_id = window.ids.get_unchecked(pos);
+            pos += 1;
+            let Some(escrow) = legacy::load_escrow(&env, bounty_id) else {
+                continue;
+            };
+            if (escrow.status != EscrowStatus::Locked
+                && escrow.status != EscrowStatus::PartiallyRefunded)
+                || escrow.remaining_amount == 0
+                || env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::RefundApproval(bounty_id))
+            {
+                continue;
             }
 
-            let mut count = 0u32;
-            for other_item in items.iter() {
-                if other_item.bounty_id == item.bounty_id {
+            match Self::sweep_expired(&env, bounty_id, escrow, &keeper, now) {
+                Ok((refunded, reward)) => {
                     count += 1;
+                    total_amount += refunded;
+                    total_reward += reward;
+                    items.push_back(keeper::SweepItem {
+                        bounty_id,
+                        success: true,
+                        error_code: 0,
+                        refunded,
+                        keeper_reward: reward,
+                    });
                 }
+                Err(err) => items.push_back(keeper::SweepItem {
+                    bounty_id,
+                    success: false,
+                    error_code: err as u32,
+                    refunded: 0,
+                    keeper_reward: 0,
+                }),
             }
-            if count > 1 {
-                return Err(Error::DuplicateBountyId);
-            }
-
-            total_amount = total_amount
-                .checked_add(escrow.amount)
-                .ok_or(Error::InvalidAmount)?;
         }
+        // A full window of expired entries may continue past what was read.
+        let next_cursor = if pos < end || end == keeper::MAX_SWEEP_SCAN {
+            Some(keeper::encode_cursor(
+                &env,
+                window.keys.get_unchecked(pos - 1) as u64,
+                window.ids.get_unchecked(pos - 1),
+            ))
+        } else {
+            None
+        };
 
-        // EFFECTS: update all escrow records before any external calls (CEI)
-        // We collect (contributor, amount) pairs for the transfer pass.
-        let mut release_pairs: Vec<(Address, i128)> = Vec::new(&env);
-        let mut released_count = 0u32;
-        for item in items.iter() {
-            let mut escrow: Escrow = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Escrow(item.bounty_id))
-                .unwrap();
+        if count > 0 {
+            events::emit_batch_funds_refunded(
+                &env,
+                events::BatchFundsRefunded {
+                    version: EVENT_VERSION_V2,
+                    keeper,
+                    count,
+                    total_amount,
+                    keeper_reward: total_reward,
+                    timestamp: now,
+                },
+            );
+        }
 
-            let amount = escrow.amount;
-            escrow.status = EscrowStatus::Released;
-            escrow.remaining_amount = 0;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(item.bounty_id), &escrow);
-            env.storage()
-                .persistent()
-                .set(&DataKey::CompletedAt(item.bounty_id), &timestamp);
+        // GUARD: release reentrancy lock
+        reentrancy_guard::release(&env);
+        Ok(keeper::SweepReport { items, next_cursor })
+    }
 
-            release_pairs.push_back((item.contributor.clone(), amount));
-            released_count += 1;
+    /// Refund one expired escrow for `batch_refund_expired`. Checks come
+    /// first so a skipped escrow is left untouched. Returns the amount paid
+    /// out and the keeper reward paid from the fee pool.
+    fn sweep_expired(
+        env: &Env,
+        bounty_id: u64,
+        mut escrow: Escrow,
+        keeper: &Address,
+        now: u64,
+    ) -> Result<(i128, i128), Error> {
+        if Self::is_escrow_locked(env, bounty_id) {
+            return Err(Error::EscrowLocked);
+        }
+        milestones::reject_whole_escrow(env, bounty_id)?;
+        if Self::has_open_dispute(env, bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
+        if let Some(claim) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, ClaimRecord>(&DataKey::PendingClaim(bounty_id))
+        {
+            if !claim.claimed {
+                return Err(Error::ClaimPending);
+            }
         }
 
-        // INTERACTION: all external token transfers happen after state is finalized
-        let mut action_summaries: Vec<ActionSummary> = Vec::new(&env);
-        for (idx, item) in items.iter().enumerate() {
-            let (ref contributor, amount) = release_pairs.get(idx as u32).unwrap();
-            client.transfer(&contract_address, contributor, &amount);
-            action_summaries.push_back(ActionSummary {
-                bounty_id: item.bounty_id,
-                action_type: 2u32, // Release
+        // EFFECTS: update state before external call (CEI)
+        let refunded = escrow.remaining_amount;
+        let payouts = crowdfund::refund_payouts(env, bounty_id, &escrow, refunded)?;
+        let reward = keeper::take_reward(env, &escrow.token, escrow.lock_fee);
+        escrow.remaining_amount = 0;
+        escrow.status = EscrowStatus::Refunded;
+        for (recipient, amount) in payouts.iter() {
+            escrow.refund_history.push_back(RefundRecord {
                 amount,
-                timestamp,
+                recipient,
+                timestamp: now,
+                mode: RefundMode::Full,
             });
-            emit_funds_released(
-                &env,
-                FundsReleased {
+        }
+        indexes::store_escrow(env, bounty_id, &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::CompletedAt(bounty_id), &now);
+
+        // INTERACTION: external token transfers are last
+        let client = token::Client::new(env, &escrow.token);
+        for (recipient, amount) in payouts.iter() {
+            client.transfer(&env.current_contract_address(), &recipient, &amount);
+            emit_funds_refunded(
+                env,
+                FundsRefunded {
                     version: EVENT_VERSION_V2,
-                    bounty_id: item.bounty_id,
+                    bounty_id,
                     amount,
-                    recipient: contributor.clone(),
-                    timestamp,
+                    refund_to: recipient,
+                    timestamp: now,
                 },
             );
         }
-
-        // Emit batch event (Issue #676)
-        emit_batch_funds_released(
-            &env,
-            BatchFundsReleased {
-                version: EVENT_VERSION_V2,
-                count: released_count,
-                total_amount,
-                timestamp,
-            },
-        );
-        emit_event_batch(
-            &env,
-            EventBatch {
-                version: EVENT_VERSION_V2,
-                batch_type: 2u32, // release
-                actions: action_summaries,
-                total_amount,
-                timestamp,
-            },
-        );
-
-        // GUARD: release reentrancy lock
-        reentrancy_guard::release(&env);
-        Ok(released_count)
+        if reward > 0 {
+            client.transfer(&env.current_contract_address(), keeper, &reward);
+        }
+        multitoken_invariants::assert_after_disbursement(env, &escrow.token);
+        Ok((refunded, reward))
     }
     pub fn update_metadata(
         env: Env,
@@ -4051,15 +7210,35 @@ impl BountyEscrowContract {
             .ok_or(Error::NotInitialized)?;
         stored_admin.require_auth();
 
+        // Tags are managed by `update_metadata_tags`; keep whatever is set.
+        let tags = metadata::load_metadata(&env, bounty_id)
+            .map(|m| m.tags)
+            .unwrap_or(Vec::new(&env));
         let metadata = EscrowMetadata {
             repo_id,
             issue_id,
             bounty_type,
+            tags,
         };
-        env.storage()
-            .persistent()
-            .set(&DataKey::Metadata(bounty_id), &metadata);
-        Ok(())
+        metadata::store_metadata(&env, bounty_id, &metadata)
+    }
+
+    /// Replace the tags on an escrow's metadata (admin only).
+    pub fn update_metadata_tags(
+        env: Env,
+        bounty_id: u64,
+        tags: Vec<soroban_sdk::String>,
+    ) -> Result<(), Error> {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        stored_admin.require_auth();
+
+        let mut metadata = metadata::load_metadata(&env, bounty_id).ok_or(Error::BountyNotFound)?;
+        metadata.tags = tags;
+        metadata::store_metadata(&env, bounty_id, &metadata)
     }
 
     pub fn get_analytics(env: Env) -> monitoring::Analytics {
@@ -4079,10 +7258,7 @@ impl BountyEscrowContract {
     }
 
     pub fn get_metadata(env: Env, bounty_id: u64) -> Result<EscrowMetadata, Error> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Metadata(bounty_id))
-            .ok_or(Error::BountyNotFound)
+        metadata::load_metadata(&env, bounty_id).ok_or(Error::BountyNotFound)
     }
 
     /// Issue a single-use claim ticket to a bounty winner (admin only)
@@ -4117,16 +7293,14 @@ impl BountyEscrowContract {
         }
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         admin.require_auth();
+        access_control::require_allowed(&env, &beneficiary)?;
+        milestones::reject_whole_escrow(&env, bounty_id)?;
 
         // Verify bounty exists and funds are locked
         if !env.storage().persistent().has(&DataKey::Escrow(bounty_id)) {
             return Err(Error::BountyNotFound);
         }
-        let escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .unwrap();
+        let escrow: Escrow = legacy::load_escrow(&env, bounty_id).unwrap();
 
         // Verify escrow is in locked state
         if escrow.status != EscrowStatus::Locked {
@@ -4137,6 +7311,8 @@ impl BountyEscrowContract {
         if amount <= 0 || amount > escrow.amount {
             return Err(Error::InvalidAmount);
         }
+        // The approval is spent when the ticket is redeemed.
+        Self::check_release_approval(&env, bounty_id, &escrow, &beneficiary, amount)?;
 
         // Validate expiry
         let now = env.ledger().timestamp();
@@ -4170,6 +7346,7 @@ impl BountyEscrowContract {
         env.storage()
             .persistent()
             .set(&DataKey::ClaimTicket(ticket_id), &ticket);
+        ttl::extend(&env, &DataKey::ClaimTicket(ticket_id));
 
         // Add to global ticket index
         let mut ticket_index: Vec<u64> = env
@@ -4181,6 +7358,7 @@ impl BountyEscrowContract {
         env.storage()
             .persistent()
             .set(&DataKey::ClaimTicketIndex, &ticket_index);
+        ttl::extend(&env, &DataKey::ClaimTicketIndex);
 
         // Add to beneficiary's ticket list
         let mut beneficiary_tickets: Vec<u64> = env
@@ -4193,6 +7371,7 @@ impl BountyEscrowContract {
             &DataKey::BeneficiaryTickets(beneficiary.clone()),
             &beneficiary_tickets,
         );
+        ttl::extend(&env, &DataKey::BeneficiaryTickets(beneficiary.clone()));
 
         // Emit event
         emit_ticket_issued(
@@ -4262,6 +7441,7 @@ impl BountyEscrowContract {
 
         // Verify caller is the beneficiary
         ticket.beneficiary.require_auth();
+        access_control::require_allowed(&env, &ticket.beneficiary)?;
 
         // Verify bounty still exists
         if !env
@@ -4273,38 +7453,54 @@ impl BountyEscrowContract {
         }
 
         // Get escrow and verify it's locked
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(ticket.bounty_id))
-            .unwrap();
+        let mut escrow: Escrow = legacy::load_escrow(&env, ticket.bounty_id).unwrap();
 
         if escrow.status != EscrowStatus::Locked {
             return Err(Error::FundsNotLocked);
         }
-
-        // Transfer funds to beneficiary
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-        let client = token::Client::new(&env, &token_addr);
-        client.transfer(
-            &env.current_contract_address(),
+        if Self::has_open_dispute(&env, ticket.bounty_id) {
+            return Err(Error::DisputeOpen);
+        }
+        if Self::check_release_approval(
+            &env,
+            ticket.bounty_id,
+            &escrow,
             &ticket.beneficiary,
-            &ticket.amount,
-        );
+            ticket.amount,
+        )? {
+            Self::consume_release_approval(&env, ticket.bounty_id, &escrow, ticket.amount);
+        }
+
+        // The release fee comes out of the ticket amount
+        Self::sync_promotions(&env);
+        let (_, release_fee_rate) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(ticket.amount, release_fee_rate);
 
         // Mark ticket as used (prevent replay)
         ticket.used = true;
         env.storage()
             .persistent()
             .set(&DataKey::ClaimTicket(ticket_id), &ticket);
+        ttl::extend(&env, &DataKey::ClaimTicket(ticket_id));
 
         // Update escrow status to Released
         escrow.status = EscrowStatus::Released;
         escrow.remaining_amount = 0;
+        escrow.release_fee = escrow.release_fee.checked_add(fee).unwrap();
         invariants::assert_escrow(&env, &escrow);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(ticket.bounty_id), &escrow);
+        indexes::store_escrow(&env, ticket.bounty_id, &escrow);
+
+        // Transfer funds to beneficiary
+        let client = token::Client::new(&env, &escrow.token);
+        client.transfer(&env.current_contract_address(), &ticket.beneficiary, &net);
+        Self::transfer_fee(
+            &env,
+            &client,
+            ticket.bounty_id,
+            fee,
+            release_fee_rate,
+            events::FeeOperationType::Release,
+        );
 
         // Emit event
         emit_ticket_claimed(
@@ -4337,6 +7533,97 @@ impl BountyEscrowContract {
             .ok_or(Error::TicketNotFound)
     }
 
+    /// Register the ed25519 public key that signs claim vouchers (admin only).
+    /// Replacing the key invalidates every voucher signed with the old one.
+    pub fn set_voucher_signer(env: Env, public_key: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        vouchers::set_signer(&env, &public_key);
+        Ok(())
+    }
+
+    pub fn get_voucher_signer(env: Env) -> Option<BytesN<32>> {
+        vouchers::signer(&env)
+    }
+
+    /// Nonce the next voucher for `beneficiary` must carry.
+    pub fn get_voucher_nonce(env: Env, beneficiary: Address) -> u64 {
+        vouchers::next_nonce(&env, &beneficiary)
+    }
+
+    /// Skip `beneficiary`'s current voucher nonce, typically because the
+    /// voucher signed for it expired unredeemed, so the next voucher can be
+    /// redeemed. `caller` must be the beneficiary or the admin. Returns the
+    /// new nonce.
+    pub fn advance_voucher_nonce(
+        env: Env,
+        caller: Address,
+        beneficiary: Address,
+    ) -> Result<u64, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if caller != beneficiary && caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+        Ok(vouchers::skip_nonce(&env, &beneficiary))
+    }
+
+    /// Redeem a claim voucher signed off-chain by the voucher signer.
+    ///
+    /// Pays `voucher.amount` to the beneficiary like `partial_release`: the
+    /// release fee applies and the escrow becomes Released once nothing
+    /// remains. The beneficiary must authorize the call.
+    ///
+    /// # Returns
+    /// * `Err(Error::NotInitialized)` - No voucher signer or network id is configured
+    /// * `Err(Error::Unauthorized)` - Voucher is for another network or contract
+    /// * `Err(Error::TicketExpired)` - Voucher has expired
+    /// * `Err(Error::TicketAlreadyUsed)` - Nonce is not the beneficiary's next voucher nonce
+    /// * `Err(Error::FundsPaused)` - Release operations are paused
+    /// * Any error `partial_release` returns for the escrow itself
+    ///
+    /// An invalid signature aborts the invocation in the host.
+    pub fn redeem_claim_voucher(
+        env: Env,
+        voucher: vouchers::ClaimVoucher,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        reentrancy_guard::acquire(&env);
+
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(Error::FundsPaused);
+        }
+        vouchers::verify_and_consume(&env, &voucher, &signature)?;
+        Self::release_partial_amount(
+            &env,
+            voucher.bounty_id,
+            &voucher.beneficiary,
+            voucher.amount,
+        )?;
+
+        events::emit_voucher_redeemed(
+            &env,
+            events::VoucherRedeemed {
+                bounty_id: voucher.bounty_id,
+                beneficiary: voucher.beneficiary,
+                amount: voucher.amount,
+                nonce: voucher.nonce,
+                redeemed_at: env.ledger().timestamp(),
+            },
+        );
+
+        reentrancy_guard::release(&env);
+        Ok(())
+    }
+
     /// Get all claim tickets for a beneficiary
     ///
     /// Returns a paginated list of ticket IDs for a specific beneficiary address.
@@ -4441,6 +7728,7 @@ impl BountyEscrowContract {
             .get(&DataKey::Admin)
             .ok_or(Error::NotInitialized)?;
         admin.require_auth();
+        milestones::reject_whole_escrow(&env, bounty_id)?;
 
         // Must not be archived
         if env
@@ -4453,11 +7741,8 @@ impl BountyEscrowContract {
         }
 
         // Load escrow
-        let mut escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(bounty_id))
-            .ok_or(Error::BountyNotFound)?;
+        let mut escrow: Escrow =
+            legacy::load_escrow(&env, bounty_id).ok_or(Error::BountyNotFound)?;
 
         // Only Locked or PartiallyRefunded escrows can be renewed
         if escrow.status != EscrowStatus::Locked && escrow.status != EscrowStatus::PartiallyRefunded
@@ -4481,21 +7766,32 @@ impl BountyEscrowContract {
         // Update deadline
         escrow.deadline = new_deadline;
 
-        // If topping up, transfer additional funds
+        // If topping up, transfer additional funds; the top-up pays the lock fee
         if additional_amount > 0 {
-            escrow.amount += additional_amount;
-            escrow.remaining_amount += additional_amount;
+            let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+            let (fee, net) = token_math::split_amount(additional_amount, lock_fee_rate);
+            escrow.amount += net;
+            escrow.remaining_amount += net;
+            escrow.lock_fee = escrow.lock_fee.checked_add(fee).unwrap();
 
             // Depositor must authorize the top-up transfer
             escrow.depositor.require_auth();
 
-            let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+            let token_addr = escrow.token.clone();
             let client = token::Client::new(&env, &token_addr);
             client.transfer(
                 &escrow.depositor,
                 &env.current_contract_address(),
                 &additional_amount,
             );
+            Self::transfer_fee(
+                &env,
+                &client,
+                bounty_id,
+                fee,
+                lock_fee_rate,
+                events::FeeOperationType::Lock,
+            );
         }
 
         // Increment cycle count
@@ -4528,9 +7824,7 @@ impl BountyEscrowContract {
             .set(&DataKey::RenewalHistory(bounty_id), &history);
 
         // Save updated escrow
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(bounty_id), &escrow);
+        indexes::store_escrow(&env, bounty_id, &escrow);
 
         // Emit event
         emit_escrow_renewed(
@@ -4577,11 +7871,8 @@ impl BountyEscrowContract {
         admin.require_auth();
 
         // Load previous escrow
-        let prev_escrow: Escrow = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(previous_bounty_id))
-            .ok_or(Error::BountyNotFound)?;
+        let prev_escrow: Escrow =
+            legacy::load_escrow(&env, previous_bounty_id).ok_or(Error::BountyNotFound)?;
 
         // Previous must be in terminal state
         if prev_escrow.status != EscrowStatus::Released
@@ -4606,6 +7897,10 @@ impl BountyEscrowContract {
             .storage()
             .persistent()
             .has(&DataKey::Escrow(new_bounty_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Archived(new_bounty_id))
         {
             return Err(Error::BountyExists);
         }
@@ -4627,34 +7922,27 @@ impl BountyEscrowContract {
             .unwrap_or(0);
         let new_cycle = prev_cycle + 1;
 
-        // Create the new escrow (inherits depositor from previous)
+        // Create the new escrow (inherits depositor from previous); the lock
+        // fee is floored like on any other lock
+        let (lock_fee_rate, _) = Self::effective_fee_rates(&env);
+        let (fee, net) = token_math::split_amount(amount, lock_fee_rate);
         let escrow = Escrow {
             depositor: prev_escrow.depositor.clone(),
-            amount,
-            remaining_amount: amount,
+            amount: net,
+            remaining_amount: net,
             status: EscrowStatus::Locked,
             deadline,
             refund_history: vec![&env],
+            lock_fee: fee,
+            release_fee: 0,
+            token: prev_escrow.token.clone(),
         };
 
         // GUARD: acquire reentrancy lock
         reentrancy_guard::acquire(&env);
 
         // EFFECTS: write state before external call (CEI)
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(new_bounty_id), &escrow);
-
-        // Update EscrowIndex
-        let mut index: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(Vec::new(&env));
-        index.push_back(new_bounty_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::EscrowIndex, &index);
+        indexes::store_escrow(&env, new_bounty_id, &escrow);
 
         // Update DepositorIndex
         let mut depositor_index: Vec<u64> = env
@@ -4667,6 +7955,10 @@ impl BountyEscrowContract {
             &DataKey::DepositorIndex(prev_escrow.depositor.clone()),
             &depositor_index,
         );
+        ttl::extend(
+            &env,
+            &DataKey::DepositorIndex(prev_escrow.depositor.clone()),
+        );
 
         // Set cycle count on new escrow
         env.storage()
@@ -4708,13 +8000,21 @@ impl BountyEscrowContract {
 
         // INTERACTION: transfer funds (depositor must authorize)
         prev_escrow.depositor.require_auth();
-        let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
+        let token_addr = prev_escrow.token.clone();
         let client = token::Client::new(&env, &token_addr);
         client.transfer(
             &prev_escrow.depositor,
             &env.current_contract_address(),
             &amount,
         );
+        Self::transfer_fee(
+            &env,
+            &client,
+            new_bounty_id,
+            fee,
+            lock_fee_rate,
+            events::FeeOperationType::Lock,
+        );
 
         // Emit events
         emit_funds_locked(
@@ -4722,9 +8022,11 @@ impl BountyEscrowContract {
             FundsLocked {
                 version: EVENT_VERSION_V2,
                 bounty_id: new_bounty_id,
-                amount,
+                amount: net,
                 depositor: prev_escrow.depositor.clone(),
                 deadline,
+                gross_amount: amount,
+                fee_amount: fee,
             },
         );
 
@@ -4829,6 +8131,8 @@ impl traits::UpgradeInterface for BountyEscrowContract {
 
 #[cfg(test)]
 mod test_state_verification;
+#[cfg(test)]
+mod test_storage_ttl;
 
 #[cfg(test)]
 mod test;
@@ -4836,40 +8140,66 @@ mod test;
 mod test_analytics_monitoring;
 #[cfg(test)]
 mod test_auto_refund_permissions;
-// #[cfg(test)]
 #[cfg(test)]
-// Temporarily disabled: this suite targets a different blacklist API surface
-// (`initialize`, `set_blacklist`, `set_whitelist_mode`) than this contract exposes.
-// Re-enable after API/test alignment.
-// mod test_blacklist_and_whitelist;
+mod test_batch_best_effort;
+#[cfg(test)]
+mod test_blacklist_and_whitelist;
 #[cfg(test)]
 mod test_bounty_escrow;
 #[cfg(test)]
+mod test_capability_delegation;
+#[cfg(test)]
+mod test_capability_scopes;
+#[cfg(test)]
+mod test_claim_vouchers;
+#[cfg(test)]
 mod test_compatibility;
 #[cfg(test)]
+mod test_crowdfund;
+#[cfg(test)]
+mod test_cursor_pagination;
+#[cfg(test)]
+mod test_dispute_lifecycle;
+#[cfg(test)]
 mod test_dispute_resolution;
 #[cfg(test)]
 mod test_dry_run_simulation;
 #[cfg(test)]
 mod test_expiration_and_dispute;
 #[cfg(test)]
+mod test_expiry_sweep;
+#[cfg(test)]
+mod test_fee_collection;
+#[cfg(test)]
 mod test_front_running_ordering;
 #[cfg(test)]
 mod test_granular_pause;
 #[cfg(test)]
 mod test_invariants;
+#[cfg(test)]
+mod test_legacy_escrow;
 mod test_lifecycle;
 #[cfg(test)]
 mod test_metadata_tagging;
 #[cfg(test)]
+mod test_milestones;
+#[cfg(test)]
+mod test_multisig_release;
+#[cfg(test)]
 mod test_multitoken_invariants;
 #[cfg(test)]
 mod test_partial_payout_rounding;
 #[cfg(test)]
 mod test_pause;
 #[cfg(test)]
+mod test_promotional_periods;
+#[cfg(test)]
 mod test_reentrancy_guard;
 #[cfg(test)]
+mod test_release_split;
+#[cfg(test)]
+mod test_secondary_indexes;
+#[cfg(test)]
 mod escrow_status_transition_tests {
     use super::*;
     use soroban_sdk::{
@@ -4898,6 +8228,7 @@ mod escrow_status_transition_tests {
         amount: i128,
         status: EscrowStatus,
         deadline: u64,
+        token: Address,
     ) -> Escrow {
         Escrow {
             depositor,
@@ -4906,6 +8237,9 @@ mod escrow_status_transition_tests {
             status,
             deadline,
             refund_history: vec![env],
+            lock_fee: 0,
+            release_fee: 0,
+            token,
         }
     }
 
@@ -4957,6 +8291,7 @@ mod escrow_status_transition_tests {
                 amount,
                 status,
                 deadline,
+                self.token_admin.address.clone(),
             );
 
             // Mint tokens directly to the contract to bypass lock_funds logic but guarantee token transfer succeeds for valid transitions
@@ -0,0 +1,275 @@
+// ============================================================================
+// Escrow Metadata Indexes
+//
+// Every metadata write goes through `store_metadata`, which diffs the new
+// record against the stored one and keeps the following in step:
+//
+//   Tags(bounty_id)            Vec<String>  the escrow's tags
+//   Repo(repo_id)              Vec<u64>     active bounty ids in the repo
+//   BountyType(bounty_type)    Vec<u64>     active bounty ids of that type
+//   Tag(tag)                   Vec<u64>     active bounty ids with the tag
+//   Issue(repo_id, issue_id)   u64          last bounty id opened for the issue
+//
+// `DataKey::Metadata` keeps the layout it had before tags were added, so
+// records written by earlier versions still decode; tags live under their
+// own key and `load_metadata` joins the two.
+//
+// The Repo, BountyType and Tag buckets only list escrows that can still pay
+// out. `indexes::store_escrow` calls `sync_status` when an escrow is
+// released or refunded, which drops it from them, so each bucket is bounded
+// by the open escrows rather than by history. Settled escrows keep their
+// metadata and stay readable through `get_metadata`.
+//
+// `Issue` is not cleared when an escrow settles; the duplicate guard
+// instead checks whether the escrow it points at is still active, so a
+// released or refunded issue can be funded again.
+// ============================================================================
+
+use crate::{
+    legacy, ttl, DataKey, Error, EscrowMetadata, EscrowStatus, EscrowWithId, MAX_PAGE_SIZE,
+};
+use soroban_sdk::{contracttype, Env, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MetadataKey {
+    Repo(u64),
+    BountyType(String),
+    Tag(String),
+    Issue(u64, u64),
+    Tags(u64),
+}
+
+/// `EscrowMetadata` as persisted under `DataKey::Metadata`, without tags.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StoredMetadata {
+    pub repo_id: u64,
+    pub issue_id: u64,
+    pub bounty_type: String,
+}
+
+/// Upper bound on tags per escrow; each tag costs an index write.
+pub(crate) const MAX_TAGS: u32 = 10;
+
+/// Validate `metadata`, persist it under `bounty_id` and update the indexes.
+/// Duplicate tags are collapsed, keeping the first occurrence.
+pub(crate) fn store_metadata(
+    env: &Env,
+    bounty_id: u64,
+    metadata: &EscrowMetadata,
+) -> Result<(), Error> {
+    let mut tags: Vec<String> = Vec::new(env);
+    for tag in metadata.tags.iter() {
+        if !tags.contains(&tag) {
+            tags.push_back(tag);
+        }
+    }
+    if tags.len() > MAX_TAGS {
+        return Err(Error::InvalidBatchSize);
+    }
+
+    let issue_key = MetadataKey::Issue(metadata.repo_id, metadata.issue_id);
+    if let Some(holder) = env
+        .storage()
+        .persistent()
+        .get::<MetadataKey, u64>(&issue_key)
+    {
+        if holder != bounty_id && is_active(env, holder) {
+            return Err(Error::BountyExists);
+        }
+    }
+
+    let metadata = EscrowMetadata {
+        tags,
+        ..metadata.clone()
+    };
+    let previous = load_metadata(env, bounty_id);
+    if !is_settled(env, bounty_id) {
+        if let Some(prev) = &previous {
+            unindex(env, bounty_id, prev, &metadata);
+        }
+        index(env, bounty_id, &metadata, previous.as_ref());
+    }
+    if let Some(prev) = &previous {
+        release_issue(env, bounty_id, prev, &metadata);
+    }
+    env.storage().persistent().set(&issue_key, &bounty_id);
+    ttl::extend(env, &issue_key);
+
+    let key = DataKey::Metadata(bounty_id);
+    let stored = StoredMetadata {
+        repo_id: metadata.repo_id,
+        issue_id: metadata.issue_id,
+        bounty_type: metadata.bounty_type.clone(),
+    };
+    env.storage().persistent().set(&key, &stored);
+    ttl::extend(env, &key);
+
+    let tags_key = MetadataKey::Tags(bounty_id);
+    if metadata.tags.is_empty() {
+        env.storage().persistent().remove(&tags_key);
+    } else {
+        env.storage().persistent().set(&tags_key, &metadata.tags);
+        ttl::extend(env, &tags_key);
+    }
+    Ok(())
+}
+
+/// The metadata attached to `bounty_id`, with its tags.
+pub(crate) fn load_metadata(env: &Env, bounty_id: u64) -> Option<EscrowMetadata> {
+    let stored: StoredMetadata = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Metadata(bounty_id))?;
+    let tags = env
+        .storage()
+        .persistent()
+        .get(&MetadataKey::Tags(bounty_id))
+        .unwrap_or(Vec::new(env));
+    Some(EscrowMetadata {
+        repo_id: stored.repo_id,
+        issue_id: stored.issue_id,
+        bounty_type: stored.bounty_type,
+        tags,
+    })
+}
+
+/// Drop `bounty_id` from, or return it to, the Repo, BountyType and Tag
+/// buckets when its escrow moves between `from` and `to`.
+pub(crate) fn sync_status(env: &Env, bounty_id: u64, from: &EscrowStatus, to: &EscrowStatus) {
+    if settled(from) == settled(to) {
+        return;
+    }
+    let Some(metadata) = load_metadata(env, bounty_id) else {
+        return;
+    };
+    if settled(to) {
+        remove_from(env, &MetadataKey::Repo(metadata.repo_id), bounty_id);
+        remove_from(
+            env,
+            &MetadataKey::BountyType(metadata.bounty_type.clone()),
+            bounty_id,
+        );
+        for tag in metadata.tags.iter() {
+            remove_from(env, &MetadataKey::Tag(tag), bounty_id);
+        }
+    } else {
+        index(env, bounty_id, &metadata, None);
+    }
+}
+
+/// Page through an index bucket, skipping ids whose escrow no longer exists
+/// (metadata set ahead of funding). `limit` is capped at `MAX_PAGE_SIZE`.
+pub(crate) fn page(env: &Env, key: &MetadataKey, offset: u32, limit: u32) -> Vec<EscrowWithId> {
+    let bucket = bucket(env, key);
+    let mut results = Vec::new(env);
+    let start = offset.min(bucket.len());
+    let end = offset
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(bucket.len());
+
+    for i in start..end {
+        let bounty_id = bucket.get_unchecked(i);
+        if let Some(escrow) = legacy::load_escrow(env, bounty_id) {
+            results.push_back(EscrowWithId { bounty_id, escrow });
+        }
+    }
+    results
+}
+
+fn settled(status: &EscrowStatus) -> bool {
+    matches!(status, EscrowStatus::Released | EscrowStatus::Refunded)
+}
+
+/// Whether `bounty_id` has an escrow that can no longer pay out. Metadata
+/// attached ahead of funding is not settled.
+fn is_settled(env: &Env, bounty_id: u64) -> bool {
+    legacy::load_escrow(env, bounty_id).is_some_and(|escrow| settled(&escrow.status))
+}
+
+fn is_active(env: &Env, bounty_id: u64) -> bool {
+    legacy::load_escrow(env, bounty_id)
+        .map(|escrow| {
+            matches!(
+                escrow.status,
+                EscrowStatus::Locked | EscrowStatus::PartiallyRefunded
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Drop `bounty_id` from the buckets `prev` is in but `next` is not.
+fn unindex(env: &Env, bounty_id: u64, prev: &EscrowMetadata, next: &EscrowMetadata) {
+    if prev.repo_id != next.repo_id {
+        remove_from(env, &MetadataKey::Repo(prev.repo_id), bounty_id);
+    }
+    if prev.bounty_type != next.bounty_type {
+        remove_from(
+            env,
+            &MetadataKey::BountyType(prev.bounty_type.clone()),
+            bounty_id,
+        );
+    }
+    for tag in prev.tags.iter() {
+        if !next.tags.contains(&tag) {
+            remove_from(env, &MetadataKey::Tag(tag), bounty_id);
+        }
+    }
+}
+
+/// Clear the issue pointer `prev` held if `next` moves to another issue.
+fn release_issue(env: &Env, bounty_id: u64, prev: &EscrowMetadata, next: &EscrowMetadata) {
+    if (prev.repo_id, prev.issue_id) != (next.repo_id, next.issue_id) {
+        let issue_key = MetadataKey::Issue(prev.repo_id, prev.issue_id);
+        if env
+            .storage()
+            .persistent()
+            .get::<MetadataKey, u64>(&issue_key)
+            == Some(bounty_id)
+        {
+            env.storage().persistent().remove(&issue_key);
+        }
+    }
+}
+
+/// Add `bounty_id` to the buckets `next` is in but `prev` was not.
+fn index(env: &Env, bounty_id: u64, next: &EscrowMetadata, prev: Option<&EscrowMetadata>) {
+    if prev.is_none_or(|p| p.repo_id != next.repo_id) {
+        add_to(env, &MetadataKey::Repo(next.repo_id), bounty_id);
+    }
+    if prev.is_none_or(|p| p.bounty_type != next.bounty_type) {
+        add_to(
+            env,
+            &MetadataKey::BountyType(next.bounty_type.clone()),
+            bounty_id,
+        );
+    }
+    for tag in next.tags.iter() {
+        if prev.is_none_or(|p| !p.tags.contains(&tag)) {
+            add_to(env, &MetadataKey::Tag(tag), bounty_id);
+        }
+    }
+}
+
+fn bucket(env: &Env, key: &MetadataKey) -> Vec<u64> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+fn add_to(env: &Env, key: &MetadataKey, bounty_id: u64) {
+    let mut ids = bucket(env, key);
+    if !ids.contains(bounty_id) {
+        ids.push_back(bounty_id);
+        env.storage().persistent().set(key, &ids);
+        ttl::extend(env, key);
+    }
+}
+
+fn remove_from(env: &Env, key: &MetadataKey, bounty_id: u64) {
+    let mut ids = bucket(env, key);
+    if let Some(pos) = ids.first_index_of(bounty_id) {
+        ids.remove(pos);
+        env.storage().persistent().set(key, &ids);
+        ttl::extend(env, key);
+    }
+}
@@ -0,0 +1,151 @@
+// ============================================================================
+// Milestone Escrows
+//
+// A milestone escrow is an ordinary `Escrow` whose funds are split into an
+// ordered list of stages, stored alongside it:
+//
+//   MilestoneKey::List(bounty_id)  Vec<Milestone>
+//
+// Each milestone is released, refunded or extended on its own. The parent
+// escrow is kept in step after every change: `remaining_amount` is the sum
+// of pending milestones, `deadline` is the earliest pending deadline, and
+// `status` is derived by `derive_status`. Whole-escrow release and refund
+// entrypoints reject milestone escrows so the two views cannot drift apart;
+// the one exception is dispute resolution, which settles every pending
+// milestone at once.
+// ============================================================================
+
+use crate::{ttl, Error, Escrow, EscrowStatus};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneKey {
+    List(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MilestoneStatus {
+    Pending,
+    Released,
+    Refunded,
+}
+
+/// Milestone as supplied to `lock_funds_with_milestones`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneSpec {
+    /// Gross amount deposited for this stage.
+    pub amount: i128,
+    pub deadline: u64,
+    /// When set, only this address may receive the milestone.
+    pub assignee: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    /// Net amount payable after this stage's share of the lock fee.
+    pub amount: i128,
+    pub deadline: u64,
+    pub assignee: Option<Address>,
+    pub status: MilestoneStatus,
+}
+
+pub(crate) fn get(env: &Env, bounty_id: u64) -> Option<Vec<Milestone>> {
+    env.storage()
+        .persistent()
+        .get(&MilestoneKey::List(bounty_id))
+}
+
+pub(crate) fn save(env: &Env, bounty_id: u64, milestones: &Vec<Milestone>) {
+    let key = MilestoneKey::List(bounty_id);
+    env.storage().persistent().set(&key, milestones);
+    ttl::extend(env, &key);
+}
+
+/// Reject whole-escrow payouts and refunds on a milestone escrow.
+pub(crate) fn reject_whole_escrow(env: &Env, bounty_id: u64) -> Result<(), Error> {
+    if env
+        .storage()
+        .persistent()
+        .has(&MilestoneKey::List(bounty_id))
+    {
+        return Err(Error::EscrowLocked);
+    }
+    Ok(())
+}
+
+/// Turn specs into pending milestones holding `net` in total. The lock fee
+/// is taken from each stage pro rata (floored); the rounding remainder is
+/// left on the last stage so the milestones sum to `net` exactly.
+pub(crate) fn from_specs(
+    env: &Env,
+    specs: &Vec<MilestoneSpec>,
+    gross: i128,
+    net: i128,
+) -> Result<Vec<Milestone>, Error> {
+    let mut milestones = Vec::new(env);
+    let mut allocated: i128 = 0;
+    let last = specs.len() - 1;
+    for (i, spec) in specs.iter().enumerate() {
+        let amount = if i as u32 == last {
+            net - allocated
+        } else if net == gross {
+            spec.amount
+        } else {
+            spec.amount.checked_mul(net).ok_or(Error::InvalidAmount)? / gross
+        };
+        allocated += amount;
+        milestones.push_back(Milestone {
+            amount,
+            deadline: spec.deadline,
+            assignee: spec.assignee,
+            status: MilestoneStatus::Pending,
+        });
+    }
+    Ok(milestones)
+}
+
+/// Escrow status implied by its milestones: `Locked` while any stage is
+/// pending (`PartiallyRefunded` once one has been refunded), then
+/// `Refunded` if every stage was refunded and `Released` otherwise.
+pub(crate) fn derive_status(milestones: &Vec<Milestone>) -> EscrowStatus {
+    let (mut pending, mut released, mut refunded) = (0u32, 0u32, 0u32);
+    for m in milestones.iter() {
+        match m.status {
+            MilestoneStatus::Pending => pending += 1,
+            MilestoneStatus::Released => released += 1,
+            MilestoneStatus::Refunded => refunded += 1,
+        }
+    }
+    if pending > 0 {
+        if refunded > 0 {
+            EscrowStatus::PartiallyRefunded
+        } else {
+            EscrowStatus::Locked
+        }
+    } else if released == 0 {
+        EscrowStatus::Refunded
+    } else {
+        EscrowStatus::Released
+    }
+}
+
+/// Bring the parent escrow in line with its milestones.
+pub(crate) fn sync_escrow(escrow: &mut Escrow, milestones: &Vec<Milestone>) {
+    let mut remaining: i128 = 0;
+    let mut next_deadline: Option<u64> = None;
+    for m in milestones.iter() {
+        if m.status == MilestoneStatus::Pending {
+            remaining += m.amount;
+            next_deadline = Some(next_deadline.map_or(m.deadline, |d| d.min(m.deadline)));
+        }
+    }
+    escrow.remaining_amount = remaining;
+    if let Some(deadline) = next_deadline {
+        escrow.deadline = deadline;
+    }
+    escrow.status = derive_status(milestones);
+}
@@ -15,9 +15,10 @@
 //          - remaining_amount <= amount
 //          - Released => remaining_amount == 0
 //
-// INV-2  (Aggregate-to-Ledger)
-//        Sum of all `remaining_amount` across *active* escrows ==
-//        actual token balance held by the contract.
+// INV-2  (Aggregate-to-Ledger, per asset)
+//        For every asset the contract has accepted, sum of `remaining_amount`
+//        across *active* escrows in that asset == the contract's balance of
+//        that asset.
 //
 // INV-3  (Fee Separation)
 //        If a fee was collected, it was transferred out at the time of
@@ -30,12 +31,12 @@
 //        original amount – remaining_amount.
 //
 // INV-5  (Index Completeness)
-//        Every bounty_id in the EscrowIndex has a corresponding Escrow entry.
+//        Every bounty_id in the creation index has a corresponding Escrow entry.
 //
 // ============================================================================
 
-use crate::{DataKey, Escrow, EscrowStatus};
-use soroban_sdk::{token, Address, Env, Vec};
+use crate::{indexes, keeper, legacy, DataKey, Escrow, EscrowStatus};
+use soroban_sdk::{token, Address, Env, Map, Vec};
 
 /// Full result of a multi-token balance invariant check.
 /// Returned by `check_all_invariants` so callers can inspect what failed.
@@ -44,9 +45,9 @@ use soroban_sdk::{token, Address, Env, Vec};
 pub struct InvariantReport {
     /// True when ALL invariants pass.
     pub healthy: bool,
-    /// Total remaining amount summed across all active escrows.
+    /// Total remaining amount summed across all active escrows, all assets.
     pub sum_remaining: i128,
-    /// Actual token balance of the contract.
+    /// Contract balance summed across all known assets.
     pub token_balance: i128,
     /// Number of escrows that failed per-escrow sanity checks (INV-1).
     pub per_escrow_failures: u32,
@@ -107,50 +108,34 @@ pub(crate) fn check_refund_consistency(escrow: &Escrow) -> bool {
 // INV-2  Aggregate-to-Ledger
 // ---------------------------------------------------------------------------
 
-/// Sum the remaining_amount of all active (Locked or PartiallyRefunded) escrows.
-pub(crate) fn sum_active_escrow_balances(env: &Env) -> i128 {
-    let index: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::EscrowIndex)
-        .unwrap_or(Vec::new(env));
-
-    let mut total: i128 = 0;
-    for bounty_id in index.iter() {
-        if let Some(escrow) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-        {
-            // Only active escrows contribute to the contract balance.
-            if escrow.status == EscrowStatus::Locked
-                || escrow.status == EscrowStatus::PartiallyRefunded
-            {
-                total += escrow.remaining_amount;
-            }
-        }
-    }
-    total
+/// Sum the remaining_amount of all active (Locked or PartiallyRefunded)
+/// escrows denominated in `asset`, plus the lock fees set aside in it for
+/// keeper rewards. The escrow part is the running total kept by `indexes`.
+pub(crate) fn sum_active_escrow_balances(env: &Env, asset: &Address) -> i128 {
+    keeper::pool(env, asset) + indexes::held_total(env, asset)
 }
 
-/// Get the actual token balance held by the contract.
-pub(crate) fn get_contract_token_balance(env: &Env) -> i128 {
-    let token_addr: Address = env.storage().instance().get(&DataKey::Token).unwrap();
-    let client = token::Client::new(env, &token_addr);
+/// Get the contract's balance of `asset`.
+pub(crate) fn get_contract_token_balance(env: &Env, asset: &Address) -> i128 {
+    let client = token::Client::new(env, asset);
     client.balance(&env.current_contract_address())
 }
 
+/// Every asset the contract has ever accepted.
+pub(crate) fn known_assets(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::KnownAssets)
+        .unwrap_or(Vec::new(env))
+}
+
 // ---------------------------------------------------------------------------
 // INV-5  Index Completeness
 // ---------------------------------------------------------------------------
 
-/// Count how many bounty_ids in the global index have no corresponding Escrow.
+/// Count how many bounty_ids in the creation index have no corresponding Escrow.
 pub(crate) fn count_orphaned_index_entries(env: &Env) -> u32 {
-    let index: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::EscrowIndex)
-        .unwrap_or(Vec::new(env));
+    let index = indexes::all_ids(env, &indexes::IndexKind::Created);
 
     let mut orphans: u32 = 0;
     for bounty_id in index.iter() {
@@ -175,19 +160,19 @@ pub(crate) fn check_all_invariants(env: &Env) -> InvariantReport {
     let mut per_escrow_failures: u32 = 0;
     let mut refund_inconsistencies: u32 = 0;
 
-    let index: Vec<u64> = env
-        .storage()
-        .persistent()
-        .get(&DataKey::EscrowIndex)
-        .unwrap_or(Vec::new(env));
+    let index = indexes::all_ids(env, &indexes::IndexKind::Created);
+    // Recounted from the escrows so the running totals are audited too.
+    let mut recounted: Map<Address, i128> = Map::new(env);
 
     // INV-1 + INV-4: Check each escrow
     for bounty_id in index.iter() {
-        if let Some(escrow) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Escrow>(&DataKey::Escrow(bounty_id))
-        {
+        if let Some(escrow) = legacy::load_escrow(env, bounty_id) {
+            if escrow.status == EscrowStatus::Locked
+                || escrow.status == EscrowStatus::PartiallyRefunded
+            {
+                let held = recounted.get(escrow.token.clone()).unwrap_or(0);
+                recounted.set(escrow.token.clone(), held + escrow.remaining_amount);
+            }
             if !check_escrow_sanity(&escrow) {
                 per_escrow_failures += 1;
                 violations.push_back(soroban_sdk::String::from_str(
@@ -205,15 +190,26 @@ pub(crate) fn check_all_invariants(env: &Env) -> InvariantReport {
         }
     }
 
-    // INV-2: Aggregate-to-Ledger
-    let sum_remaining = sum_active_escrow_balances(env);
-    let token_balance = get_contract_token_balance(env);
-
-    if sum_remaining != token_balance {
-        violations.push_back(soroban_sdk::String::from_str(
-            env,
-            "INV-2: Sum of remaining != contract balance",
-        ));
+    // INV-2: Aggregate-to-Ledger, checked independently for each asset
+    let mut sum_remaining: i128 = 0;
+    let mut token_balance: i128 = 0;
+    for asset in known_assets(env).iter() {
+        let asset_sum = keeper::pool(env, &asset) + recounted.get(asset.clone()).unwrap_or(0);
+        let asset_balance = get_contract_token_balance(env, &asset);
+        if asset_sum != sum_active_escrow_balances(env, &asset) {
+            violations.push_back(soroban_sdk::String::from_str(
+                env,
+                "INV-2: Running total != sum of remaining",
+            ));
+        }
+        if asset_sum != asset_balance {
+            violations.push_back(soroban_sdk::String::from_str(
+                env,
+                "INV-2: Sum of remaining != contract balance",
+            ));
+        }
+        sum_remaining += asset_sum;
+        token_balance += asset_balance;
     }
 
     // INV-5: Index Completeness
@@ -255,10 +251,11 @@ pub(crate) fn assert_all_invariants(env: &Env) {
 // most relevant to the specific operation.
 // ---------------------------------------------------------------------------
 
-/// Assert after a lock: aggregate balance must equal token balance.
-pub(crate) fn assert_after_lock(env: &Env) {
-    let sum = sum_active_escrow_balances(env);
-    let actual = get_contract_token_balance(env);
+/// Assert after a lock: aggregate balance in `asset` must equal the
+/// contract's balance of that asset.
+pub(crate) fn assert_after_lock(env: &Env, asset: &Address) {
+    let sum = sum_active_escrow_balances(env, asset);
+    let actual = get_contract_token_balance(env, asset);
     if sum != actual {
         panic!(
             "INV-2 violated after lock: escrow sum ({}) != balance ({})",
@@ -267,10 +264,11 @@ pub(crate) fn assert_after_lock(env: &Env) {
     }
 }
 
-/// Assert after a release/refund: aggregate balance must equal token balance.
-pub(crate) fn assert_after_disbursement(env: &Env) {
-    let sum = sum_active_escrow_balances(env);
-    let actual = get_contract_token_balance(env);
+/// Assert after a release/refund: aggregate balance in `asset` must equal
+/// the contract's balance of that asset.
+pub(crate) fn assert_after_disbursement(env: &Env, asset: &Address) {
+    let sum = sum_active_escrow_balances(env, asset);
+    let actual = get_contract_token_balance(env, asset);
     if sum != actual {
         panic!(
             "INV-2 violated after disbursement: escrow sum ({}) != balance ({})",
@@ -0,0 +1,241 @@
+// Tests for the best-effort batch entrypoints: failing items are skipped and
+// reported, the rest of the batch still goes through.
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env};
+
+struct BatchSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    depositor: Address,
+}
+
+impl<'a> BatchSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        token_admin.mint(&depositor, &100_000);
+
+        Self {
+            env,
+            client,
+            token,
+            depositor,
+        }
+    }
+
+    fn lock_item(&self, bounty_id: u64, depositor: &Address, amount: i128) -> LockFundsItem {
+        LockFundsItem {
+            bounty_id,
+            depositor: depositor.clone(),
+            amount,
+            deadline: 10_000,
+        }
+    }
+
+    fn release_item(&self, bounty_id: u64, contributor: &Address) -> ReleaseFundsItem {
+        ReleaseFundsItem {
+            bounty_id,
+            contributor: contributor.clone(),
+        }
+    }
+
+    /// Lock bounties `1..=n` with 100 each in one atomic batch.
+    fn lock_many(&self, n: u64) {
+        let mut items = soroban_sdk::Vec::new(&self.env);
+        for id in 1..=n {
+            items.push_back(self.lock_item(id, &self.depositor, 100));
+        }
+        self.client.batch_lock_funds(&items);
+    }
+}
+
+fn codes(results: &soroban_sdk::Vec<BatchItemResult>) -> soroban_sdk::Vec<u32> {
+    let mut out = soroban_sdk::Vec::new(results.env());
+    for r in results.iter() {
+        out.push_back(r.error_code);
+    }
+    out
+}
+
+#[test]
+fn test_best_effort_lock_skips_failing_items() {
+    let s = BatchSetup::new();
+    let broke = Address::generate(&s.env);
+    let banned = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &100, &10_000);
+    s.client.set_blacklist(&banned, &true, &None);
+
+    let items = vec![
+        &s.env,
+        s.lock_item(1, &s.depositor, 100), // exists
+        s.lock_item(2, &s.depositor, 200), // ok
+        s.lock_item(3, &s.depositor, 0),   // bad amount
+        s.lock_item(2, &s.depositor, 300), // repeat of item 2
+        s.lock_item(4, &broke, 500),       // no balance
+        s.lock_item(5, &banned, 500),      // blacklisted
+        s.lock_item(6, &s.depositor, 400), // ok
+    ];
+    let results = s.client.batch_lock_funds_best_effort(&items);
+
+    assert_eq!(
+        codes(&results),
+        vec![
+            &s.env,
+            Error::BountyExists as u32,
+            0,
+            Error::InvalidAmount as u32,
+            Error::DuplicateBountyId as u32,
+            Error::InsufficientFunds as u32,
+            Error::ParticipantNotAllowed as u32,
+            0,
+        ]
+    );
+    assert!(results.get(1).unwrap().success);
+    assert_eq!(s.client.get_escrow_info(&2).amount, 200);
+    assert_eq!(s.client.get_escrow_info(&6).amount, 400);
+    assert!(s.client.try_get_escrow_info(&3).is_err());
+    assert_eq!(s.token.balance(&s.client.address), 700);
+    assert_eq!(
+        s.client
+            .query_escrows_by_depositor(&s.depositor, &0, &10)
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_best_effort_release_skips_disputed_bounty() {
+    let s = BatchSetup::new();
+    let contributor = Address::generate(&s.env);
+    s.lock_many(20);
+    s.client
+        .open_dispute(&7, &s.depositor, &contributor, &DisputeReason::Other);
+
+    let mut items = soroban_sdk::Vec::new(&s.env);
+    for id in 1u64..=20 {
+        items.push_back(s.release_item(id, &contributor));
+    }
+    // The atomic batch fails as a whole.
+    let res = s.client.try_batch_release_funds(&items);
+    assert_eq!(res, Err(Ok(Error::DisputeOpen)));
+
+    let results = s.client.batch_release_funds_best_effort(&items);
+    assert_eq!(results.len(), 20);
+    let failed = results.get(6).unwrap();
+    assert!(!failed.success);
+    assert_eq!(failed.bounty_id, 7);
+    assert_eq!(failed.error_code, Error::DisputeOpen as u32);
+    assert_eq!(results.iter().filter(|r| r.success).count(), 19);
+
+    assert_eq!(s.token.balance(&contributor), 1_900);
+    assert_eq!(s.token.balance(&s.client.address), 100);
+    assert_eq!(s.client.get_escrow_info(&7).status, EscrowStatus::Locked);
+}
+
+#[test]
+fn test_best_effort_release_reports_each_failure() {
+    let s = BatchSetup::new();
+    let contributor = Address::generate(&s.env);
+    s.lock_many(3);
+    s.client.release_funds(&3, &contributor);
+
+    let items = vec![
+        &s.env,
+        s.release_item(1, &contributor),
+        s.release_item(1, &contributor),
+        s.release_item(3, &contributor),
+        s.release_item(9, &contributor),
+        s.release_item(2, &contributor),
+    ];
+    let results = s.client.batch_release_funds_best_effort(&items);
+    assert_eq!(
+        codes(&results),
+        vec![
+            &s.env,
+            0,
+            Error::DuplicateBountyId as u32,
+            Error::FundsNotLocked as u32,
+            Error::BountyNotFound as u32,
+            0,
+        ]
+    );
+    assert_eq!(s.token.balance(&contributor), 300);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_best_effort_lock_counts_earlier_items_against_balance() {
+    let s = BatchSetup::new();
+    let funded = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.token.address).mint(&funded, &1_000);
+
+    let items = vec![
+        &s.env,
+        s.lock_item(1, &funded, 600),
+        s.lock_item(2, &funded, 600), // only 400 left after item 1
+        s.lock_item(3, &funded, 400),
+        s.lock_item(4, &funded, 1),
+    ];
+    let results = s.client.batch_lock_funds_best_effort(&items);
+
+    assert_eq!(
+        codes(&results),
+        vec![
+            &s.env,
+            0,
+            Error::InsufficientFunds as u32,
+            0,
+            Error::InsufficientFunds as u32,
+        ]
+    );
+    assert_eq!(s.token.balance(&funded), 0);
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+}
+
+#[test]
+fn test_best_effort_all_items_failing_changes_nothing() {
+    let s = BatchSetup::new();
+    let contributor = Address::generate(&s.env);
+    let items = vec![
+        &s.env,
+        s.release_item(1, &contributor),
+        s.release_item(2, &contributor),
+    ];
+    let results = s.client.batch_release_funds_best_effort(&items);
+    assert!(results.iter().all(|r| !r.success));
+    assert_eq!(s.token.balance(&contributor), 0);
+}
+
+#[test]
+fn test_best_effort_batch_level_errors() {
+    let s = BatchSetup::new();
+    let empty: soroban_sdk::Vec<LockFundsItem> = soroban_sdk::Vec::new(&s.env);
+    let res = s.client.try_batch_lock_funds_best_effort(&empty);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    let mut too_many = soroban_sdk::Vec::new(&s.env);
+    for id in 0..=MAX_BATCH_SIZE as u64 {
+        too_many.push_back(s.release_item(id, &s.depositor));
+    }
+    let res = s.client.try_batch_release_funds_best_effort(&too_many);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    s.client.set_paused(&Some(true), &None, &None, &None);
+    let items = vec![&s.env, s.lock_item(1, &s.depositor, 100)];
+    let res = s.client.try_batch_lock_funds_best_effort(&items);
+    assert_eq!(res, Err(Ok(Error::FundsPaused)));
+}
@@ -1,17 +1,10 @@
-// Tests for blacklist / whitelist functionality.
-//
-// These tests depend on contract methods (`set_blacklist`, `set_whitelist_mode`,
-// `initialize`, and the `ParticipantNotAllowed` error variant) that have not
-// been implemented yet.  They are gated behind `cfg(feature = "access_control")`
-// so they compile-out until the feature lands (tracked in a future issue).
-
-#![cfg(test)]
-#![cfg(feature = "access_control")]
+// Tests for participant screening (blacklist / whitelist-only mode) and the
+// anti-abuse whitelist, which only exempts addresses from rate limiting.
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger, LedgerInfo},
-    token, Address, Env, IntoVal
+    testutils::{Address as _, Events, Ledger, LedgerInfo},
+    token, Address, Env, TryFromVal,
 };
 
 fn create_env() -> Env {
@@ -93,3 +86,120 @@ fn test_removed_from_whitelist_reenables_rate_limit_checks() {
     let second = client.try_lock_funds(&depositor, &22, &100, &deadline);
     assert!(second.is_err());
 }
+
+#[test]
+fn test_blacklisted_depositor_cannot_lock() {
+    let env = create_env();
+    let (client, depositor, token_client) = setup(&env);
+    let deadline = env.ledger().timestamp() + 86_400;
+
+    client.set_blacklist(&depositor, &true, &None);
+    assert!(client.is_blacklisted(&depositor));
+    let res = client.try_lock_funds(&depositor, &31, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    client.set_blacklist(&depositor, &false, &None);
+    client.lock_funds(&depositor, &31, &100, &deadline);
+    assert_eq!(token_client.balance(&client.address), 100);
+}
+
+#[test]
+fn test_blacklisted_recipient_cannot_receive_release() {
+    let env = create_env();
+    let (client, depositor, token_client) = setup(&env);
+    let contributor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 86_400;
+    client.lock_funds(&depositor, &41, &100, &deadline);
+
+    client.set_blacklist(&contributor, &true, &None);
+    let res = client.try_release_funds(&41, &contributor);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+    let res = client.try_partial_release(&41, &contributor, &50);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+    assert_eq!(client.get_escrow_info(&41).status, EscrowStatus::Locked);
+    assert_eq!(token_client.balance(&contributor), 0);
+}
+
+#[test]
+fn test_blacklisted_beneficiary_cannot_use_ticket() {
+    let env = create_env();
+    let (client, depositor, _token) = setup(&env);
+    let beneficiary = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    client.lock_funds(&depositor, &51, &100, &(now + 86_400));
+
+    let ticket_id = client.issue_claim_ticket(&51, &beneficiary, &100, &(now + 3_600));
+    client.set_blacklist(&beneficiary, &true, &None);
+
+    let res = client.try_claim_with_ticket(&ticket_id);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+    let res = client.try_issue_claim_ticket(&51, &beneficiary, &100, &(now + 3_600));
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+}
+
+#[test]
+fn test_whitelist_mode_only_admits_listed_participants() {
+    let env = create_env();
+    let (client, depositor, _token) = setup(&env);
+    let contributor = Address::generate(&env);
+    let deadline = env.ledger().timestamp() + 86_400;
+
+    client.set_whitelist_mode(&true);
+    assert!(client.get_whitelist_mode());
+    let res = client.try_lock_funds(&depositor, &61, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+
+    client.set_participant_whitelist(&depositor, &true);
+    client.lock_funds(&depositor, &61, &100, &deadline);
+
+    // Recipients are screened against the same list.
+    let res = client.try_release_funds(&61, &contributor);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+    client.set_participant_whitelist(&contributor, &true);
+    client.release_funds(&61, &contributor);
+
+    // Turning the mode off admits everyone who is not blacklisted.
+    client.set_whitelist_mode(&false);
+    assert!(client.is_participant_allowed(&Address::generate(&env)));
+}
+
+#[test]
+fn test_blacklist_overrides_whitelist() {
+    let env = create_env();
+    let (client, depositor, _token) = setup(&env);
+
+    client.set_whitelist_mode(&true);
+    client.set_participant_whitelist(&depositor, &true);
+    client.set_blacklist(&depositor, &true, &None);
+
+    assert!(client.is_participant_whitelisted(&depositor));
+    assert!(!client.is_participant_allowed(&depositor));
+    let deadline = env.ledger().timestamp() + 86_400;
+    let res = client.try_lock_funds(&depositor, &71, &100, &deadline);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+}
+
+#[test]
+fn test_list_changes_emit_events() {
+    let env = create_env();
+    let (client, depositor, _token) = setup(&env);
+    let reason = soroban_sdk::String::from_str(&env, "sanctions match");
+
+    client.set_blacklist(&depositor, &true, &Some(reason.clone()));
+    let (_, _, data) = env.events().all().last().unwrap();
+    let event = events::BlacklistUpdated::try_from_val(&env, &data).unwrap();
+    assert_eq!(event.address, depositor);
+    assert!(event.blacklisted);
+    assert_eq!(event.reason, Some(reason));
+
+    client.set_participant_whitelist(&depositor, &true);
+    let (_, _, data) = env.events().all().last().unwrap();
+    let event = events::WhitelistUpdated::try_from_val(&env, &data).unwrap();
+    assert!(event.whitelisted);
+
+    client.set_whitelist_mode(&true);
+    let (_, _, data) = env.events().all().last().unwrap();
+    let event = events::WhitelistModeChanged::try_from_val(&env, &data).unwrap();
+    assert!(event.enabled);
+}
@@ -361,6 +361,9 @@ fn test_property_fuzz_lock_release_refund_invariants() {
     let start = env.ledger().timestamp();
 
     env.mock_all_auths();
+    // Eighty calls that each update the sharded indexes exceed the default
+    // test budget.
+    env.budget().reset_unlimited();
 
     let token_admin = Address::generate(&env);
     let (token, _token_client, token_admin_client) = create_token_contract(&env, &token_admin);
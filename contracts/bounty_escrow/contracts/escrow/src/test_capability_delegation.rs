@@ -0,0 +1,249 @@
+// Tests for capability delegation: attenuated child capabilities, shared
+// budgets along the chain, and cascading revocation.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct DelegationSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    admin: Address,
+    contributor: Address,
+    /// Holder of the capability the admin issues.
+    lead: Address,
+    /// Who `lead` delegates to.
+    helper: Address,
+}
+
+impl<'a> DelegationSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        token_admin.mint(&depositor, &100_000);
+        client.lock_funds(&depositor, &1, &10_000, &50_000);
+
+        Self {
+            contributor: Address::generate(&env),
+            lead: Address::generate(&env),
+            helper: Address::generate(&env),
+            env,
+            client,
+            token,
+            admin,
+        }
+    }
+
+    /// Release capability on bounty 1: 1000 over 3 uses until t=5000.
+    fn issue_root(&self) -> u64 {
+        self.client.issue_capability(
+            &self.admin,
+            &self.lead,
+            &CapabilityAction::Release,
+            &1,
+            &1_000,
+            &5_000,
+            &3,
+        )
+    }
+
+    fn release(&self, holder: &Address, capability_id: u64, amount: i128) -> Result<(), Error> {
+        match self.client.try_release_with_capability(
+            &1,
+            &self.contributor,
+            &amount,
+            holder,
+            &capability_id,
+        ) {
+            Ok(_) => Ok(()),
+            Err(Ok(err)) => Err(err),
+            Err(Err(_)) => panic!("unexpected host error"),
+        }
+    }
+}
+
+#[test]
+fn test_delegated_capability_records_chain() {
+    let s = DelegationSetup::new();
+    let root = s.issue_root();
+    let child = s
+        .client
+        .delegate_capability(&s.lead, &root, &s.helper, &400, &4_000, &2);
+
+    let cap = s.client.get_capability(&child);
+    assert_eq!(cap.holder, s.helper);
+    assert_eq!(cap.owner, s.admin);
+    assert_eq!(cap.action, CapabilityAction::Release);
+    assert_eq!(cap.parent_id, Some(root));
+    assert_eq!(
+        cap.delegation_chain,
+        vec![&s.env, s.admin.clone(), s.lead.clone()]
+    );
+
+    let root_cap = s.client.get_capability(&root);
+    assert_eq!(root_cap.parent_id, None);
+    assert_eq!(root_cap.delegation_chain, vec![&s.env, s.admin.clone()]);
+}
+
+#[test]
+fn test_child_spends_parent_budget() {
+    let s = DelegationSetup::new();
+    let root = s.issue_root();
+    let child = s
+        .client
+        .delegate_capability(&s.lead, &root, &s.helper, &800, &4_000, &2);
+
+    s.release(&s.helper, child, 500).unwrap();
+    assert_eq!(s.token.balance(&s.contributor), 500);
+    let root_cap = s.client.get_capability(&root);
+    assert_eq!(root_cap.remaining_amount, 500);
+    assert_eq!(root_cap.remaining_uses, 2);
+
+    // The lead spends from the shared root budget, which then caps the child.
+    s.release(&s.lead, root, 400).unwrap();
+    assert_eq!(
+        s.release(&s.helper, child, 200),
+        Err(Error::CapabilityAmountExceeded)
+    );
+    s.release(&s.helper, child, 100).unwrap();
+    assert_eq!(s.client.get_capability(&root).remaining_uses, 0);
+}
+
+#[test]
+fn test_delegation_must_narrow_scope() {
+    let s = DelegationSetup::new();
+    let root = s.issue_root();
+
+    let too_much = s
+        .client
+        .try_delegate_capability(&s.lead, &root, &s.helper, &1_001, &4_000, &1);
+    assert_eq!(too_much, Err(Ok(Error::CapabilityExceedsAuthority)));
+    let too_many_uses = s
+        .client
+        .try_delegate_capability(&s.lead, &root, &s.helper, &100, &4_000, &4);
+    assert_eq!(too_many_uses, Err(Ok(Error::CapabilityExceedsAuthority)));
+    let too_late = s
+        .client
+        .try_delegate_capability(&s.lead, &root, &s.helper, &100, &5_001, &1);
+    assert_eq!(too_late, Err(Ok(Error::CapabilityExceedsAuthority)));
+
+    // Only the holder can delegate.
+    let stranger = Address::generate(&s.env);
+    let res = s
+        .client
+        .try_delegate_capability(&stranger, &root, &s.helper, &100, &4_000, &1);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    // Equal scope is allowed.
+    s.client
+        .delegate_capability(&s.lead, &root, &s.helper, &1_000, &5_000, &3);
+}
+
+#[test]
+fn test_delegation_depth_is_bounded() {
+    let s = DelegationSetup::new();
+    let mut holder = s.lead.clone();
+    let mut id = s.issue_root();
+    for _ in 0..MAX_DELEGATION_DEPTH {
+        let next = Address::generate(&s.env);
+        id = s
+            .client
+            .delegate_capability(&holder, &id, &next, &100, &4_000, &1);
+        holder = next;
+    }
+    let res = s.client.try_delegate_capability(
+        &holder,
+        &id,
+        &Address::generate(&s.env),
+        &100,
+        &4_000,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(Error::CapabilityExceedsAuthority)));
+
+    // The deepest capability still works.
+    s.release(&holder, id, 100).unwrap();
+}
+
+#[test]
+fn test_revoking_parent_cascades() {
+    let s = DelegationSetup::new();
+    let root = s.issue_root();
+    let child = s
+        .client
+        .delegate_capability(&s.lead, &root, &s.helper, &400, &4_000, &2);
+    let grandchild_holder = Address::generate(&s.env);
+    let grandchild =
+        s.client
+            .delegate_capability(&s.helper, &child, &grandchild_holder, &100, &4_000, &1);
+
+    s.client.revoke_capability(&s.admin, &root);
+
+    assert!(s.client.get_capability(&child).revoked);
+    assert!(s.client.get_capability(&grandchild).revoked);
+    assert_eq!(
+        s.release(&grandchild_holder, grandchild, 50),
+        Err(Error::CapabilityRevoked)
+    );
+    let res = s.client.try_delegate_capability(
+        &s.helper,
+        &child,
+        &Address::generate(&s.env),
+        &10,
+        &4_000,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(Error::CapabilityRevoked)));
+}
+
+#[test]
+fn test_delegator_can_revoke_its_branch() {
+    let s = DelegationSetup::new();
+    let root = s.issue_root();
+    let child = s
+        .client
+        .delegate_capability(&s.lead, &root, &s.helper, &400, &4_000, &2);
+
+    // The delegated holder cannot revoke upwards.
+    let res = s.client.try_revoke_capability(&s.helper, &root);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    s.client.revoke_capability(&s.lead, &child);
+    assert_eq!(
+        s.release(&s.helper, child, 10),
+        Err(Error::CapabilityRevoked)
+    );
+    // The parent is unaffected.
+    s.release(&s.lead, root, 10).unwrap();
+}
+
+#[test]
+fn test_expired_parent_blocks_child() {
+    let s = DelegationSetup::new();
+    let root = s.issue_root();
+    let child = s
+        .client
+        .delegate_capability(&s.lead, &root, &s.helper, &400, &5_000, &2);
+
+    s.env.ledger().set_timestamp(5_001);
+    assert_eq!(
+        s.release(&s.helper, child, 10),
+        Err(Error::CapabilityExpired)
+    );
+}
@@ -0,0 +1,408 @@
+// Tests for capability scopes wider than one bounty: explicit bounty sets,
+// repositories and bounty types, with one budget shared across the scope.
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, String, Symbol, TryFromVal,
+};
+
+struct ScopeSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    admin: Address,
+    depositor: Address,
+    maintainer: Address,
+    contributor: Address,
+}
+
+impl<'a> ScopeSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        token_admin.mint(&depositor, &100_000);
+        // Several locks per test; skip the anti-abuse cooldown.
+        client.set_whitelist_entry(&depositor, &true);
+
+        Self {
+            maintainer: Address::generate(&env),
+            contributor: Address::generate(&env),
+            env,
+            client,
+            token,
+            admin,
+            depositor,
+        }
+    }
+
+    /// Lock 1000 on `bounty_id` for issue `bounty_id` in `repo_id`.
+    fn lock(&self, bounty_id: u64, repo_id: u64, bounty_type: &str) {
+        let metadata = EscrowMetadata {
+            repo_id,
+            issue_id: bounty_id,
+            bounty_type: String::from_str(&self.env, bounty_type),
+            tags: soroban_sdk::Vec::new(&self.env),
+        };
+        self.client.lock_funds_with_metadata(
+            &self.depositor,
+            &bounty_id,
+            &1_000,
+            &50_000,
+            &metadata,
+        );
+    }
+
+    fn issue(&self, scope: &CapabilityScope, budget: i128) -> u64 {
+        self.client.issue_scoped_capability(
+            &self.admin,
+            &self.maintainer,
+            &CapabilityAction::Release,
+            scope,
+            &budget,
+            &10_000,
+            &10,
+        )
+    }
+
+    fn release(&self, bounty_id: u64, amount: i128, capability_id: u64) -> Result<(), Error> {
+        match self.client.try_release_with_capability(
+            &bounty_id,
+            &self.contributor,
+            &amount,
+            &self.maintainer,
+            &capability_id,
+        ) {
+            Ok(_) => Ok(()),
+            Err(Ok(err)) => Err(err),
+            Err(Err(_)) => panic!("unexpected host error"),
+        }
+    }
+}
+
+#[test]
+fn test_repo_scope_shares_budget_across_bounties() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    s.lock(2, 7, "bug_fix");
+    s.lock(3, 8, "feature");
+    let cap = s.issue(&CapabilityScope::Repo(7), 1_500);
+
+    s.release(1, 1_000, cap).unwrap();
+    s.release(2, 400, cap).unwrap();
+    assert_eq!(s.token.balance(&s.contributor), 1_400);
+    assert_eq!(s.client.get_capability(&cap).remaining_amount, 100);
+
+    // The budget is shared, even though bounty 2 still holds 600.
+    assert_eq!(s.release(2, 200, cap), Err(Error::CapabilityAmountExceeded));
+    // Other repositories are out of scope.
+    assert_eq!(s.release(3, 50, cap), Err(Error::CapabilityActionMismatch));
+}
+
+#[test]
+fn test_repo_scope_covers_bounties_locked_later() {
+    let s = ScopeSetup::new();
+    let cap = s.issue(&CapabilityScope::Repo(7), 500);
+    s.lock(1, 7, "feature");
+
+    s.release(1, 500, cap).unwrap();
+    assert_eq!(s.token.balance(&s.contributor), 500);
+}
+
+#[test]
+fn test_bounty_set_scope() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    s.lock(2, 7, "feature");
+    s.lock(3, 7, "feature");
+    let cap = s.issue(&CapabilityScope::Bounties(vec![&s.env, 1, 3]), 2_000);
+
+    s.release(1, 1_000, cap).unwrap();
+    s.release(3, 1_000, cap).unwrap();
+    assert_eq!(s.release(2, 10, cap), Err(Error::CapabilityActionMismatch));
+    assert_eq!(s.client.get_escrow_info(&3).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_bounty_type_scope() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "documentation");
+    s.lock(2, 8, "documentation");
+    s.lock(3, 7, "feature");
+    let docs = CapabilityScope::BountyType(String::from_str(&s.env, "documentation"));
+    let cap = s.issue(&docs, 5_000);
+
+    s.release(1, 300, cap).unwrap();
+    s.release(2, 300, cap).unwrap();
+    assert_eq!(s.release(3, 300, cap), Err(Error::CapabilityActionMismatch));
+}
+
+#[test]
+fn test_scope_follows_metadata_updates() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    let cap = s.issue(&CapabilityScope::Repo(7), 1_000);
+
+    s.client
+        .update_metadata(&s.admin, &1, &8, &1, &String::from_str(&s.env, "feature"));
+    assert_eq!(s.release(1, 100, cap), Err(Error::CapabilityActionMismatch));
+}
+
+#[test]
+fn test_wide_scope_validation_at_issue() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+
+    // Claims stay tied to a single bounty.
+    let res = s.client.try_issue_scoped_capability(
+        &s.admin,
+        &s.maintainer,
+        &CapabilityAction::Claim,
+        &CapabilityScope::Repo(7),
+        &100,
+        &10_000,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(Error::CapabilityActionMismatch)));
+
+    // Only the admin can grant authority over many escrows.
+    let res = s.client.try_issue_scoped_capability(
+        &s.depositor,
+        &s.maintainer,
+        &CapabilityAction::Release,
+        &CapabilityScope::Repo(7),
+        &100,
+        &10_000,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+    let empty = CapabilityScope::Bounties(soroban_sdk::Vec::new(&s.env));
+    let res = s.client.try_issue_scoped_capability(
+        &s.admin,
+        &s.maintainer,
+        &CapabilityAction::Release,
+        &empty,
+        &100,
+        &10_000,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    let missing = CapabilityScope::Bounties(vec![&s.env, 1, 99]);
+    let res = s.client.try_issue_scoped_capability(
+        &s.admin,
+        &s.maintainer,
+        &CapabilityAction::Release,
+        &missing,
+        &100,
+        &10_000,
+        &1,
+    );
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+#[test]
+fn test_delegated_capability_keeps_scope() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    s.lock(2, 8, "feature");
+    let cap = s.issue(&CapabilityScope::Repo(7), 1_000);
+    let helper = Address::generate(&s.env);
+    let child = s
+        .client
+        .delegate_capability(&s.maintainer, &cap, &helper, &200, &10_000, &1);
+
+    assert_eq!(
+        s.client.get_capability(&child).scope,
+        CapabilityScope::Repo(7)
+    );
+    let res = s
+        .client
+        .try_release_with_capability(&2, &s.contributor, &100, &helper, &child);
+    assert_eq!(res, Err(Ok(Error::CapabilityActionMismatch)));
+    s.client
+        .release_with_capability(&1, &s.contributor, &100, &helper, &child);
+}
+
+#[test]
+fn test_scoped_release_charges_release_fee() {
+    let s = ScopeSetup::new();
+    let fee_recipient = Address::generate(&s.env);
+    s.client.update_fee_config(
+        &Some(0),
+        &Some(500),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    s.lock(1, 7, "feature");
+    s.lock(2, 7, "feature");
+    let cap = s.issue(&CapabilityScope::Repo(7), 2_000);
+
+    s.release(1, 1_000, cap).unwrap();
+    s.release(2, 1_000, cap).unwrap();
+    assert_eq!(s.token.balance(&s.contributor), 1_900);
+    assert_eq!(s.token.balance(&fee_recipient), 100);
+}
+
+#[test]
+fn test_scoped_release_budget_stays_below_multisig_threshold() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    s.lock(2, 7, "feature");
+    let signers = vec![&s.env, Address::generate(&s.env), Address::generate(&s.env)];
+    s.client.update_multisig_config(&1_500, &signers, &2);
+
+    // Each release would stay under the per-escrow threshold, so a shared
+    // budget at or above it would pay out 2000 without approvals.
+    let res = s.client.try_issue_scoped_capability(
+        &s.admin,
+        &s.maintainer,
+        &CapabilityAction::Release,
+        &CapabilityScope::Repo(7),
+        &2_000,
+        &10_000,
+        &2,
+    );
+    assert_eq!(res, Err(Ok(Error::CapabilityExceedsAuthority)));
+
+    // Single-bounty capabilities still go through the per-escrow check.
+    s.client.issue_capability(
+        &s.admin,
+        &s.maintainer,
+        &CapabilityAction::Release,
+        &1,
+        &1_000,
+        &10_000,
+        &1,
+    );
+
+    // Lowering the threshold after issue stops the wide capability and its
+    // delegates.
+    let cap = s.issue(&CapabilityScope::Repo(7), 1_000);
+    let helper = Address::generate(&s.env);
+    let child = s
+        .client
+        .delegate_capability(&s.maintainer, &cap, &helper, &500, &10_000, &1);
+    s.client.update_multisig_config(&800, &signers, &2);
+    assert_eq!(
+        s.release(1, 100, cap),
+        Err(Error::CapabilityExceedsAuthority)
+    );
+    let res = s
+        .client
+        .try_release_with_capability(&2, &s.contributor, &100, &helper, &child);
+    assert_eq!(res, Err(Ok(Error::CapabilityExceedsAuthority)));
+}
+
+#[test]
+fn test_issue_events_keep_bounty_schema() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    s.client.issue_capability(
+        &s.admin,
+        &s.maintainer,
+        &CapabilityAction::Release,
+        &1,
+        &100,
+        &10_000,
+        &1,
+    );
+    let (_, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&s.env, &topics.get(0).unwrap()).unwrap(),
+        symbol_short!("cap_new")
+    );
+    let issued = events::CapabilityIssued::try_from_val(&s.env, &data).unwrap();
+    assert_eq!(issued.bounty_id, 1);
+
+    s.issue(&CapabilityScope::Repo(7), 100);
+    let (_, topics, data) = s.env.events().all().last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&s.env, &topics.get(0).unwrap()).unwrap(),
+        symbol_short!("cap_scope")
+    );
+    let issued = events::CapabilityScopeIssued::try_from_val(&s.env, &data).unwrap();
+    assert_eq!(issued.scope, CapabilityScope::Repo(7));
+}
+
+#[test]
+fn test_capability_stored_before_scopes_still_works() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    let stored = crate::legacy::CapabilityV0 {
+        owner: s.admin.clone(),
+        holder: s.maintainer.clone(),
+        action: CapabilityAction::Release,
+        bounty_id: 1,
+        amount_limit: 600,
+        remaining_amount: 600,
+        expiry: 10_000,
+        remaining_uses: 2,
+        revoked: false,
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Capability(42), &stored);
+    });
+
+    let cap = s.client.get_capability(&42);
+    assert_eq!(cap.scope, CapabilityScope::Bounty(1));
+    assert_eq!(cap.parent_id, None);
+    assert_eq!(cap.delegation_chain, vec![&s.env, s.admin.clone()]);
+
+    s.release(1, 400, 42).unwrap();
+    assert_eq!(s.client.get_capability(&42).remaining_amount, 200);
+    // The release rewrote it in the current layout.
+    assert_eq!(s.client.migrate_capabilities(&vec![&s.env, 42u64]), 0);
+}
+
+#[test]
+fn test_migrate_capabilities_rewrites_delegated_layout() {
+    let s = ScopeSetup::new();
+    s.lock(1, 7, "feature");
+    let stored = crate::legacy::CapabilityV1 {
+        owner: s.admin.clone(),
+        holder: s.maintainer.clone(),
+        action: CapabilityAction::Release,
+        bounty_id: 1,
+        amount_limit: 600,
+        remaining_amount: 600,
+        expiry: 10_000,
+        remaining_uses: 2,
+        revoked: false,
+        parent_id: None,
+        delegation_chain: vec![&s.env, s.admin.clone()],
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Capability(42), &stored);
+    });
+
+    assert_eq!(
+        s.client.migrate_capabilities(&vec![&s.env, 42u64, 43u64]),
+        1
+    );
+    assert_eq!(
+        s.client.get_capability(&42).scope,
+        CapabilityScope::Bounty(1)
+    );
+}
@@ -0,0 +1,292 @@
+// Tests for off-chain signed claim vouchers: ed25519 verification against the
+// registered signer, per-beneficiary nonces and network/contract binding.
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token,
+    xdr::ToXdr,
+    Address, BytesN, Env, String,
+};
+use vouchers::ClaimVoucher;
+
+struct VoucherSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    admin: Address,
+    beneficiary: Address,
+    signing_key: SigningKey,
+}
+
+impl<'a> VoucherSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init_with_network(
+            &admin,
+            &token_id,
+            &String::from_str(&env, "stellar"),
+            &String::from_str(&env, "testnet"),
+        );
+        token_admin.mint(&depositor, &10_000);
+        client.lock_funds(&depositor, &1, &1_000, &50_000);
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        client.set_voucher_signer(&public_key(&env, &signing_key));
+
+        Self {
+            beneficiary: Address::generate(&env),
+            admin,
+            env,
+            client,
+            token,
+            signing_key,
+        }
+    }
+
+    fn voucher(&self, amount: i128, nonce: u64) -> ClaimVoucher {
+        ClaimVoucher {
+            bounty_id: 1,
+            beneficiary: self.beneficiary.clone(),
+            amount,
+            expires_at: 5_000,
+            nonce,
+            network_id: String::from_str(&self.env, "testnet"),
+            contract: self.client.address.clone(),
+        }
+    }
+
+    fn redeem(&self, voucher: &ClaimVoucher) -> Result<(), Error> {
+        let signature = sign(&self.env, &self.signing_key, voucher);
+        match self.client.try_redeem_claim_voucher(voucher, &signature) {
+            Ok(_) => Ok(()),
+            Err(Ok(err)) => Err(err),
+            Err(Err(_)) => panic!("unexpected host error"),
+        }
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn sign(env: &Env, key: &SigningKey, voucher: &ClaimVoucher) -> BytesN<64> {
+    let message = voucher.clone().to_xdr(env).to_alloc_vec();
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_redeem_voucher_pays_beneficiary() {
+    let s = VoucherSetup::new();
+    assert_eq!(
+        s.client.get_voucher_signer(),
+        Some(public_key(&s.env, &s.signing_key))
+    );
+    assert_eq!(s.client.get_voucher_nonce(&s.beneficiary), 0);
+
+    s.redeem(&s.voucher(400, 0)).unwrap();
+    assert_eq!(s.token.balance(&s.beneficiary), 400);
+    assert_eq!(s.client.get_voucher_nonce(&s.beneficiary), 1);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.remaining_amount, 600);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+
+    s.redeem(&s.voucher(600, 1)).unwrap();
+    assert_eq!(s.token.balance(&s.beneficiary), 1_000);
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_voucher_cannot_be_replayed() {
+    let s = VoucherSetup::new();
+    let voucher = s.voucher(100, 0);
+    s.redeem(&voucher).unwrap();
+    assert_eq!(s.redeem(&voucher), Err(Error::TicketAlreadyUsed));
+
+    // Nonces are sequential per beneficiary; skipping ahead is rejected.
+    assert_eq!(s.redeem(&s.voucher(100, 5)), Err(Error::TicketAlreadyUsed));
+    assert_eq!(s.token.balance(&s.beneficiary), 100);
+}
+
+#[test]
+fn test_voucher_nonces_are_per_beneficiary() {
+    let s = VoucherSetup::new();
+    s.redeem(&s.voucher(100, 0)).unwrap();
+
+    let mut other = s.voucher(100, 0);
+    other.beneficiary = Address::generate(&s.env);
+    s.redeem(&other).unwrap();
+    assert_eq!(s.token.balance(&other.beneficiary), 100);
+}
+
+#[test]
+fn test_voucher_bound_to_network_and_contract() {
+    let s = VoucherSetup::new();
+    let mut mainnet = s.voucher(100, 0);
+    mainnet.network_id = String::from_str(&s.env, "mainnet");
+    assert_eq!(s.redeem(&mainnet), Err(Error::Unauthorized));
+
+    let mut elsewhere = s.voucher(100, 0);
+    elsewhere.contract = Address::generate(&s.env);
+    assert_eq!(s.redeem(&elsewhere), Err(Error::Unauthorized));
+
+    // Rejected vouchers do not consume the nonce.
+    s.redeem(&s.voucher(100, 0)).unwrap();
+}
+
+#[test]
+fn test_voucher_expiry() {
+    let s = VoucherSetup::new();
+    let voucher = s.voucher(100, 0);
+    s.env.ledger().set_timestamp(5_001);
+    assert_eq!(s.redeem(&voucher), Err(Error::TicketExpired));
+}
+
+#[test]
+fn test_expired_voucher_skipped_for_the_next_one() {
+    let s = VoucherSetup::new();
+    let mut stale = s.voucher(100, 0);
+    stale.expires_at = 2_000;
+    s.env.ledger().set_timestamp(3_000);
+    assert_eq!(s.redeem(&stale), Err(Error::TicketExpired));
+
+    // The replacement voucher is held back by the unused nonce.
+    let next = s.voucher(100, 1);
+    assert_eq!(s.redeem(&next), Err(Error::TicketAlreadyUsed));
+
+    assert_eq!(
+        s.client
+            .advance_voucher_nonce(&s.beneficiary, &s.beneficiary),
+        1
+    );
+    s.redeem(&next).unwrap();
+    assert_eq!(s.token.balance(&s.beneficiary), 100);
+    assert_eq!(s.redeem(&stale), Err(Error::TicketExpired));
+}
+
+#[test]
+fn test_advance_voucher_nonce_authorization() {
+    let s = VoucherSetup::new();
+    assert_eq!(s.client.advance_voucher_nonce(&s.admin, &s.beneficiary), 1);
+
+    let stranger = Address::generate(&s.env);
+    let res = s
+        .client
+        .try_advance_voucher_nonce(&stranger, &s.beneficiary);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    assert_eq!(s.client.get_voucher_nonce(&s.beneficiary), 1);
+
+    // A skipped nonce cannot be redeemed afterwards.
+    assert_eq!(s.redeem(&s.voucher(100, 0)), Err(Error::TicketAlreadyUsed));
+    s.redeem(&s.voucher(100, 1)).unwrap();
+}
+
+#[test]
+fn test_voucher_with_bad_signature_is_rejected() {
+    let s = VoucherSetup::new();
+    let voucher = s.voucher(100, 0);
+
+    let impostor = SigningKey::from_bytes(&[9u8; 32]);
+    let forged = sign(&s.env, &impostor, &voucher);
+    assert!(s
+        .client
+        .try_redeem_claim_voucher(&voucher, &forged)
+        .is_err());
+
+    // A valid signature does not cover a different amount.
+    let signature = sign(&s.env, &s.signing_key, &voucher);
+    let mut inflated = voucher.clone();
+    inflated.amount = 1_000;
+    assert!(s
+        .client
+        .try_redeem_claim_voucher(&inflated, &signature)
+        .is_err());
+
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+    assert_eq!(s.client.get_voucher_nonce(&s.beneficiary), 0);
+}
+
+#[test]
+fn test_rotating_signer_invalidates_old_vouchers() {
+    let s = VoucherSetup::new();
+    let voucher = s.voucher(100, 0);
+    let old_signature = sign(&s.env, &s.signing_key, &voucher);
+
+    let new_key = SigningKey::from_bytes(&[8u8; 32]);
+    s.client.set_voucher_signer(&public_key(&s.env, &new_key));
+    assert!(s
+        .client
+        .try_redeem_claim_voucher(&voucher, &old_signature)
+        .is_err());
+
+    let signature = sign(&s.env, &new_key, &voucher);
+    s.client.redeem_claim_voucher(&voucher, &signature);
+    assert_eq!(s.token.balance(&s.beneficiary), 100);
+}
+
+#[test]
+fn test_voucher_escrow_checks() {
+    let s = VoucherSetup::new();
+    assert_eq!(
+        s.redeem(&s.voucher(1_001, 0)),
+        Err(Error::InsufficientFunds)
+    );
+
+    let mut missing = s.voucher(100, 0);
+    missing.bounty_id = 99;
+    assert_eq!(s.redeem(&missing), Err(Error::BountyNotFound));
+
+    s.client.set_paused(&None, &Some(true), &None, &None);
+    assert_eq!(s.redeem(&s.voucher(100, 0)), Err(Error::FundsPaused));
+}
+
+#[test]
+fn test_voucher_requires_signer_and_network() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &1_000);
+    let contract_id = env.register_contract(None, BountyEscrowContract);
+    let client = BountyEscrowContractClient::new(&env, &contract_id);
+    client.init(&admin, &token_id);
+    client.lock_funds(&depositor, &1, &1_000, &50_000);
+
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let voucher = ClaimVoucher {
+        bounty_id: 1,
+        beneficiary: Address::generate(&env),
+        amount: 100,
+        expires_at: 5_000,
+        nonce: 0,
+        network_id: String::from_str(&env, "testnet"),
+        contract: contract_id.clone(),
+    };
+    let signature = sign(&env, &key, &voucher);
+
+    // No signer registered.
+    let res = client.try_redeem_claim_voucher(&voucher, &signature);
+    assert_eq!(res, Err(Ok(Error::NotInitialized)));
+
+    // Signer registered, but `init` stores no network id to bind to.
+    client.set_voucher_signer(&public_key(&env, &key));
+    let res = client.try_redeem_claim_voucher(&voucher, &signature);
+    assert_eq!(res, Err(Ok(Error::NotInitialized)));
+}
@@ -0,0 +1,396 @@
+// Tests for crowdfunded escrows: `contribute`, the per-funder ledger and
+// views, and pro-rata refunds on every refund path.
+
+use super::*;
+use milestones::MilestoneSpec;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct CrowdSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    admin: Address,
+    depositor: Address,
+}
+
+impl<'a> CrowdSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        token_admin.mint(&depositor, &1_000_000);
+        // Several locks and top-ups per test; skip the anti-abuse cooldown.
+        client.set_whitelist_entry(&depositor, &true);
+
+        Self {
+            env,
+            client,
+            token,
+            token_admin,
+            admin,
+            depositor,
+        }
+    }
+
+    fn funder(&self) -> Address {
+        let funder = Address::generate(&self.env);
+        self.token_admin.mint(&funder, &1_000_000);
+        self.client.set_whitelist_entry(&funder, &true);
+        funder
+    }
+}
+
+#[test]
+fn test_contribute_tops_up_escrow() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+
+    s.client.contribute(&1, &alice, &500);
+    s.client.contribute(&1, &alice, &250);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.amount, 1_750);
+    assert_eq!(escrow.remaining_amount, 1_750);
+    assert_eq!(escrow.depositor, s.depositor);
+    assert_eq!(s.token.balance(&s.client.address), 1_750);
+
+    let share = s.client.get_funder_share(&1, &alice);
+    assert_eq!(share.contributed, 750);
+    assert_eq!(s.client.get_funders(&1).len(), 2);
+}
+
+#[test]
+fn test_contribute_charges_lock_fee() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    let fee_recipient = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.update_fee_config(
+        &Some(100), // 1% lock fee
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+
+    s.client.contribute(&1, &alice, &1_000);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.amount, 1_990);
+    assert_eq!(escrow.lock_fee, 10);
+    assert_eq!(s.token.balance(&fee_recipient), 10);
+    assert_eq!(s.client.get_funder_share(&1, &alice).contributed, 990);
+}
+
+#[test]
+fn test_deadline_refund_is_pro_rata() {
+    let s = CrowdSetup::new();
+    let (alice, bob) = (s.funder(), s.funder());
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &500);
+    s.client.contribute(&1, &bob, &501);
+
+    // Pay out a third, then let the deadline pass.
+    s.client
+        .partial_release(&1, &Address::generate(&s.env), &667);
+    s.env.ledger().set_timestamp(5_001);
+
+    let before = (
+        s.token.balance(&s.depositor),
+        s.token.balance(&alice),
+        s.token.balance(&bob),
+    );
+    s.client.refund(&1);
+
+    // 1334 left: 1334 * 500 / 2001 = 333, 1334 * 501 / 2001 = 334, the
+    // depositor takes the other 667 including the rounding remainder.
+    assert_eq!(s.token.balance(&alice), before.1 + 333);
+    assert_eq!(s.token.balance(&bob), before.2 + 334);
+    assert_eq!(s.token.balance(&s.depositor), before.0 + 667);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.refund_history.len(), 3);
+}
+
+#[test]
+fn test_contribution_after_release_does_not_cover_it() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    s.client.lock_funds(&s.depositor, &1, &100, &5_000);
+    s.client
+        .partial_release(&1, &Address::generate(&s.env), &60);
+    s.client.contribute(&1, &alice, &100);
+
+    // The depositor's stake is what is left of it, not the 100 locked.
+    let funders = s.client.get_funders(&1);
+    assert_eq!(funders.get(0).unwrap().contributed, 40);
+    assert_eq!(funders.get(1).unwrap().contributed, 100);
+    assert_eq!(funders.get(1).unwrap().refundable, 100);
+
+    s.env.ledger().set_timestamp(5_001);
+    let before = (s.token.balance(&s.depositor), s.token.balance(&alice));
+    s.client.refund(&1);
+    assert_eq!(s.token.balance(&s.depositor), before.0 + 40);
+    assert_eq!(s.token.balance(&alice), before.1 + 100);
+}
+
+#[test]
+fn test_rebase_after_release_keeps_funder_ratio() {
+    let s = CrowdSetup::new();
+    let (alice, bob) = (s.funder(), s.funder());
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &1_000);
+    s.client
+        .partial_release(&1, &Address::generate(&s.env), &1_000);
+    s.client.contribute(&1, &bob, &500);
+
+    let funders = s.client.get_funders(&1);
+    assert_eq!(funders.get(0).unwrap().contributed, 500);
+    assert_eq!(funders.get(1).unwrap().contributed, 500);
+    assert_eq!(funders.get(2).unwrap().contributed, 500);
+    assert_eq!(funders.get(2).unwrap().share_bps, 3_333);
+}
+
+#[test]
+fn test_contribute_rejected_with_pending_claim() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    let contributor = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.set_claim_window(&500);
+    s.client
+        .authorize_claim(&1, &contributor, &DisputeReason::Other);
+
+    assert_eq!(
+        s.client.try_contribute(&1, &alice, &100),
+        Err(Ok(Error::ClaimPending))
+    );
+
+    s.client.claim(&1);
+    assert_eq!(s.token.balance(&contributor), 1_000);
+    assert_eq!(
+        s.client.try_contribute(&1, &alice, &100),
+        Err(Ok(Error::FundsNotLocked))
+    );
+}
+
+#[test]
+fn test_capability_refund_is_pro_rata() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    let holder = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &3_000);
+    let cap = s.client.issue_capability(
+        &s.admin,
+        &holder,
+        &CapabilityAction::Refund,
+        &1,
+        &400,
+        &4_000,
+        &1,
+    );
+
+    let before = (s.token.balance(&s.depositor), s.token.balance(&alice));
+    s.client.refund_with_capability(&1, &400, &holder, &cap);
+    assert_eq!(s.token.balance(&s.depositor), before.0 + 100);
+    assert_eq!(s.token.balance(&alice), before.1 + 300);
+    assert_eq!(s.client.get_escrow_info(&1).refund_history.len(), 2);
+}
+
+#[test]
+fn test_dispute_refund_is_pro_rata() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    let (arbiter, contributor) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.set_arbiters(&vec![&s.env, arbiter.clone()]);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &1_000);
+
+    let id = s
+        .client
+        .open_dispute(&1, &s.depositor, &contributor, &DisputeReason::Other);
+    let before = (s.token.balance(&s.depositor), s.token.balance(&alice));
+    s.client.resolve_dispute(&id, &arbiter, &400);
+
+    assert_eq!(s.token.balance(&contributor), 400);
+    assert_eq!(s.token.balance(&s.depositor), before.0 + 800);
+    assert_eq!(s.token.balance(&alice), before.1 + 800);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_approved_refund_pays_named_recipient() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &1_000);
+
+    let before = s.token.balance(&alice);
+    s.client
+        .approve_refund(&1, &400, &alice, &RefundMode::Partial);
+    s.client.refund(&1);
+
+    assert_eq!(s.token.balance(&alice), before + 400);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.remaining_amount, 1_600);
+    assert_eq!(escrow.refund_history.len(), 1);
+}
+
+#[test]
+fn test_funder_views_report_shares() {
+    let s = CrowdSetup::new();
+    let (alice, stranger) = (s.funder(), Address::generate(&s.env));
+    s.client.lock_funds(&s.depositor, &1, &3_000, &5_000);
+    s.client.contribute(&1, &alice, &1_000);
+
+    let funders = s.client.get_funders(&1);
+    let depositor = funders.get(0).unwrap();
+    assert_eq!(depositor.funder, s.depositor);
+    assert_eq!(depositor.contributed, 3_000);
+    assert_eq!(depositor.share_bps, 7_500);
+    assert_eq!(depositor.refundable, 3_000);
+
+    let share = s.client.get_funder_share(&1, &alice);
+    assert_eq!(share.share_bps, 2_500);
+    assert_eq!(share.refundable, 1_000);
+
+    let none = s.client.get_funder_share(&1, &stranger);
+    assert_eq!(none.contributed, 0);
+    assert_eq!(none.refundable, 0);
+
+    let res = s.client.try_get_funders(&2);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+#[test]
+fn test_funder_found_by_depositor_query() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.lock_funds(&s.depositor, &2, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &100);
+    s.client.contribute(&2, &alice, &100);
+    s.client.contribute(&1, &alice, &100);
+
+    let found = s.client.query_escrows_by_depositor(&alice, &0, &10);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found.get(0).unwrap().bounty_id, 1);
+    assert_eq!(found.get(1).unwrap().bounty_id, 2);
+}
+
+#[test]
+fn test_contribute_rejected_when_not_open() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+
+    let res = s.client.try_contribute(&1, &alice, &100);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    let res = s.client.try_contribute(&1, &alice, &0);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    s.client.release_funds(&1, &Address::generate(&s.env));
+    let res = s.client.try_contribute(&1, &alice, &100);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    let stages = vec![
+        &s.env,
+        MilestoneSpec {
+            amount: 500,
+            deadline: 2_000,
+            assignee: None,
+        },
+    ];
+    s.client
+        .lock_funds_with_milestones(&s.depositor, &2, &stages);
+    let res = s.client.try_contribute(&2, &alice, &100);
+    assert_eq!(res, Err(Ok(Error::EscrowLocked)));
+
+    s.client.lock_funds(&s.depositor, &3, &1_000, &5_000);
+    s.client.set_blacklist(&alice, &true, &None);
+    let res = s.client.try_contribute(&3, &alice, &100);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+}
+
+#[test]
+fn test_funder_limit() {
+    let s = CrowdSetup::new();
+    s.env.budget().reset_unlimited();
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    for _ in 0..crowdfund::MAX_FUNDERS {
+        s.client.contribute(&1, &s.funder(), &1);
+    }
+
+    let res = s.client.try_contribute(&1, &s.funder(), &1);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+    // The depositor and existing funders can still top up.
+    s.client.contribute(&1, &s.depositor, &1);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.amount, 1_000 + crowdfund::MAX_FUNDERS as i128 + 1);
+}
+
+#[test]
+fn test_compaction_unlists_escrow_for_every_funder() {
+    let s = CrowdSetup::new();
+    let alice = s.funder();
+    let contributor = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.lock_funds(&s.depositor, &2, &1_000, &5_000);
+    s.client.contribute(&1, &alice, &500);
+    assert_eq!(
+        s.client.query_escrows_by_depositor(&alice, &0, &10).len(),
+        1
+    );
+
+    s.client.release_funds(&1, &contributor);
+    s.client.archive_escrow_with_mode(&1, &ArchiveMode::Summary);
+
+    let listed = s.client.query_escrows_by_depositor(&s.depositor, &0, &10);
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed.get(0).unwrap().bounty_id, 2);
+    assert_eq!(
+        s.client.query_escrows_by_depositor(&alice, &0, &10).len(),
+        0
+    );
+}
+
+#[test]
+fn test_pro_rata_reports_overflow() {
+    let env = Env::default();
+    let stakes = vec![
+        &env,
+        crowdfund::Contribution {
+            funder: Address::generate(&env),
+            amount: i128::MAX / 2,
+        },
+        crowdfund::Contribution {
+            funder: Address::generate(&env),
+            amount: i128::MAX / 2,
+        },
+    ];
+    assert_eq!(
+        crowdfund::pro_rata(&env, &stakes, 4),
+        Err(Error::InvalidAmount)
+    );
+    assert_eq!(crowdfund::pro_rata(&env, &stakes, 1), Ok(vec![&env, 1, 0]));
+}
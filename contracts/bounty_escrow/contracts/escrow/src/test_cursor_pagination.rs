@@ -0,0 +1,232 @@
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+struct CursorSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    alice: Address,
+    bob: Address,
+    contributor: Address,
+}
+
+impl<'a> CursorSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let contributor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+        token_admin.mint(&alice, &1_000_000);
+        token_admin.mint(&bob, &1_000_000);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+
+        Self {
+            env,
+            client,
+            alice,
+            bob,
+            contributor,
+        }
+    }
+
+    /// Lock and step past the per-depositor cooldown.
+    fn lock(&self, depositor: &Address, bounty_id: u64, amount: i128, deadline: u64) {
+        self.client
+            .lock_funds(depositor, &bounty_id, &amount, &deadline);
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + 61);
+    }
+}
+
+fn open_filter(env: &Env) -> EscrowFilter {
+    EscrowFilter {
+        statuses: Vec::new(env),
+        depositor: None,
+        min_deadline: 0,
+        max_deadline: u64::MAX,
+        min_amount: 0,
+        max_amount: i128::MAX,
+    }
+}
+
+fn page_ids(page: &EscrowPage) -> std::vec::Vec<u64> {
+    page.items.iter().map(|r| r.bounty_id).collect()
+}
+
+#[test]
+fn test_walks_all_pages_in_creation_order() {
+    let s = CursorSetup::new();
+    for id in [5, 3, 9, 1, 7] {
+        s.lock(&s.alice, id, 100, 50_000);
+    }
+
+    let first = s.client.query_escrows(&open_filter(&s.env), &None, &2);
+    assert_eq!(page_ids(&first), [5, 3]);
+    assert!(first.has_more);
+
+    let second = s
+        .client
+        .query_escrows(&open_filter(&s.env), &first.next_cursor, &2);
+    assert_eq!(page_ids(&second), [9, 1]);
+    assert!(second.has_more);
+
+    let last = s
+        .client
+        .query_escrows(&open_filter(&s.env), &second.next_cursor, &2);
+    assert_eq!(page_ids(&last), [7]);
+    assert!(!last.has_more);
+    assert_eq!(last.next_cursor, None);
+}
+
+#[test]
+fn test_has_more_is_false_on_exact_final_page() {
+    let s = CursorSetup::new();
+    s.lock(&s.alice, 1, 100, 50_000);
+    s.lock(&s.alice, 2, 100, 50_000);
+
+    let page = s.client.query_escrows(&open_filter(&s.env), &None, &2);
+    assert_eq!(page_ids(&page), [1, 2]);
+    assert!(!page.has_more);
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_combined_filters() {
+    let s = CursorSetup::new();
+    s.lock(&s.alice, 1, 100, 5_000);
+    s.lock(&s.alice, 2, 800, 6_000);
+    s.lock(&s.bob, 3, 500, 6_000);
+    s.lock(&s.alice, 4, 500, 9_000);
+    s.lock(&s.alice, 5, 400, 7_000);
+    s.client.release_funds(&5, &s.contributor);
+
+    let filter = EscrowFilter {
+        statuses: soroban_sdk::vec![&s.env, EscrowStatus::Locked],
+        depositor: Some(s.alice.clone()),
+        min_deadline: 5_000,
+        max_deadline: 8_000,
+        min_amount: 200,
+        max_amount: 1_000,
+    };
+    let page = s.client.query_escrows(&filter, &None, &10);
+    assert_eq!(page_ids(&page), [2]);
+    assert!(!page.has_more);
+}
+
+#[test]
+fn test_cursor_survives_status_changes_between_pages() {
+    let s = CursorSetup::new();
+    for id in 1..=4 {
+        s.lock(&s.alice, id, 100, 50_000);
+    }
+
+    let first = s.client.query_escrows(&open_filter(&s.env), &None, &2);
+    assert_eq!(page_ids(&first), [1, 2]);
+
+    // Releasing an escrow on the first page must not shift the second.
+    s.client.release_funds(&1, &s.contributor);
+    let second = s
+        .client
+        .query_escrows(&open_filter(&s.env), &first.next_cursor, &2);
+    assert_eq!(page_ids(&second), [3, 4]);
+}
+
+#[test]
+fn test_cursor_rejected_for_different_filter() {
+    let s = CursorSetup::new();
+    for id in 1..=3 {
+        s.lock(&s.alice, id, 100, 50_000);
+    }
+
+    let first = s.client.query_escrows(&open_filter(&s.env), &None, &1);
+    let mut other = open_filter(&s.env);
+    other.statuses = soroban_sdk::vec![&s.env, EscrowStatus::Locked];
+
+    let res = s.client.try_query_escrows(&other, &first.next_cursor, &1);
+    assert_eq!(res, Err(Ok(Error::InvalidCursor)));
+
+    let truncated = first.next_cursor.unwrap().slice(..8);
+    let res = s
+        .client
+        .try_query_escrows(&open_filter(&s.env), &Some(truncated), &1);
+    assert_eq!(res, Err(Ok(Error::InvalidCursor)));
+}
+
+#[test]
+fn test_cursor_rejected_for_unknown_bounty() {
+    let s = CursorSetup::new();
+    s.lock(&s.alice, 1, 100, 50_000);
+    s.lock(&s.alice, 2, 100, 50_000);
+
+    let cursor = s
+        .client
+        .query_escrows(&open_filter(&s.env), &None, &1)
+        .next_cursor
+        .unwrap();
+    let mut forged = soroban_sdk::Bytes::from_array(&s.env, &99u64.to_be_bytes());
+    forged.append(&cursor.slice(8..));
+
+    let res = s
+        .client
+        .try_query_escrows(&open_filter(&s.env), &Some(forged), &1);
+    assert_eq!(res, Err(Ok(Error::InvalidCursor)));
+}
+
+#[test]
+fn test_zero_limit_rejected_and_large_limit_capped() {
+    let s = CursorSetup::new();
+    s.env.budget().reset_unlimited();
+    let res = s.client.try_query_escrows(&open_filter(&s.env), &None, &0);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    for id in 1..=(MAX_PAGE_SIZE as u64 + 5) {
+        s.lock(&s.alice, id, 100, 50_000);
+    }
+    let page = s.client.query_escrows(&open_filter(&s.env), &None, &1_000);
+    assert_eq!(page.items.len(), MAX_PAGE_SIZE);
+    assert!(page.has_more);
+}
+
+#[test]
+fn test_selective_filter_scan_is_bounded() {
+    let s = CursorSetup::new();
+    s.env.budget().reset_unlimited();
+    let released = MAX_QUERY_SCAN as u64 + 10;
+    for id in 1..=released {
+        s.lock(&s.alice, id, 100, 50_000);
+        s.client.release_funds(&id, &s.contributor);
+    }
+    s.lock(&s.alice, 9_999, 100, 50_000);
+
+    let mut filter = open_filter(&s.env);
+    filter.statuses = soroban_sdk::vec![&s.env, EscrowStatus::Locked];
+    // The first call only gets through released history.
+    let first = s.client.query_escrows(&filter, &None, &5);
+    assert_eq!(first.items.len(), 0);
+    assert!(first.has_more);
+    let cursor = first.next_cursor.clone().unwrap();
+    let mut last_scanned = [0u8; 8];
+    cursor.slice(..8).copy_into_slice(&mut last_scanned);
+    assert_eq!(u64::from_be_bytes(last_scanned), MAX_QUERY_SCAN as u64);
+
+    let second = s.client.query_escrows(&filter, &first.next_cursor, &5);
+    assert_eq!(page_ids(&second), [9_999]);
+    assert!(!second.has_more);
+    assert_eq!(second.next_cursor, None);
+}
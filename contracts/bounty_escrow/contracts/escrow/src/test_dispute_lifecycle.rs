@@ -0,0 +1,321 @@
+use super::*;
+use milestones::{MilestoneSpec, MilestoneStatus};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env,
+};
+
+struct DisputeSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    admin: Address,
+    depositor: Address,
+    contributor: Address,
+    arbiter: Address,
+}
+
+impl<'a> DisputeSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let arbiter = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &10_000);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        client.set_arbiters(&vec![&env, arbiter.clone()]);
+        client.lock_funds(&depositor, &1, &1_000, &2_000);
+
+        Self {
+            env,
+            client,
+            token,
+            admin,
+            depositor,
+            contributor,
+            arbiter,
+        }
+    }
+
+    fn open(&self) -> u64 {
+        self.client.open_dispute(
+            &1,
+            &self.depositor,
+            &self.contributor,
+            &DisputeReason::QualityIssue,
+        )
+    }
+}
+
+#[test]
+fn test_open_dispute_blocks_release_and_refund() {
+    let s = DisputeSetup::new();
+    let id = s.open();
+
+    let active = s.client.get_active_dispute(&1).unwrap();
+    assert_eq!(active.dispute_id, id);
+    assert_eq!(active.status, DisputeStatus::Open);
+    assert_eq!(active.outcome, DisputeOutcome::NoActionTaken);
+
+    assert_eq!(
+        s.client.try_release_funds(&1, &s.contributor),
+        Err(Ok(Error::DisputeOpen))
+    );
+    assert_eq!(
+        s.client.try_partial_release(&1, &s.contributor, &100),
+        Err(Ok(Error::DisputeOpen))
+    );
+    s.env.ledger().set_timestamp(3_000);
+    assert_eq!(s.client.try_refund(&1), Err(Ok(Error::DisputeOpen)));
+}
+
+#[test]
+fn test_only_parties_can_open_once() {
+    let s = DisputeSetup::new();
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_open_dispute(&1, &stranger, &s.contributor, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+    // Without an authorized claim the contributor is only a name.
+    assert_eq!(
+        s.client
+            .try_open_dispute(&1, &s.contributor, &s.contributor, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    s.client
+        .open_dispute(&1, &s.admin, &s.contributor, &DisputeReason::Other);
+    assert_eq!(
+        s.client
+            .try_open_dispute(&1, &s.depositor, &s.contributor, &DisputeReason::Other),
+        Err(Ok(Error::DisputeAlreadyOpen))
+    );
+}
+
+#[test]
+fn test_dispute_after_authorize_claim() {
+    let s = DisputeSetup::new();
+    s.client.set_claim_window(&500);
+    s.client
+        .authorize_claim(&1, &s.contributor, &DisputeReason::Other);
+
+    // The contributor is the claim's recipient, not whoever the opener names.
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_open_dispute(&1, &stranger, &stranger, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.client
+            .try_open_dispute(&1, &s.depositor, &stranger, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let id = s.client.open_dispute(
+        &1,
+        &s.contributor,
+        &s.contributor,
+        &DisputeReason::QualityIssue,
+    );
+    assert_eq!(s.client.get_dispute(&id).contributor, s.contributor);
+    assert_eq!(s.client.try_claim(&1), Err(Ok(Error::DisputeOpen)));
+
+    let ticket = s
+        .client
+        .issue_claim_ticket(&1, &s.contributor, &1_000, &1_500);
+    assert_eq!(
+        s.client.try_claim_with_ticket(&ticket),
+        Err(Ok(Error::DisputeOpen))
+    );
+
+    s.client.resolve_dispute(&id, &s.arbiter, &300);
+    assert_eq!(s.token.balance(&s.contributor), 300);
+    assert_eq!(s.token.balance(&s.depositor), 9_700);
+
+    // The ruling voids the earlier claim.
+    assert_eq!(
+        s.client.try_get_pending_claim(&1),
+        Err(Ok(Error::BountyNotFound))
+    );
+    assert_eq!(s.client.try_claim(&1), Err(Ok(Error::BountyNotFound)));
+    assert_eq!(s.token.balance(&s.contributor), 300);
+}
+
+#[test]
+fn test_release_recipient_can_open_dispute() {
+    let s = DisputeSetup::new();
+    let signer = Address::generate(&s.env);
+    s.client
+        .update_multisig_config(&500, &vec![&s.env, signer.clone()], &1);
+    s.client.approve_large_release(&1, &s.contributor, &signer);
+
+    // Approvals name the recipient, but only on their own behalf.
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_open_dispute(&1, &s.contributor, &stranger, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+    let id = s
+        .client
+        .open_dispute(&1, &s.contributor, &s.contributor, &DisputeReason::Other);
+    assert_eq!(s.client.get_dispute(&id).opened_by, s.contributor);
+}
+
+#[test]
+fn test_milestone_assignee_can_open_dispute() {
+    let s = DisputeSetup::new();
+    let stranger = Address::generate(&s.env);
+    let stages = vec![
+        &s.env,
+        MilestoneSpec {
+            amount: 400,
+            deadline: 2_000,
+            assignee: Some(s.contributor.clone()),
+        },
+        MilestoneSpec {
+            amount: 600,
+            deadline: 3_000,
+            assignee: Some(s.contributor.clone()),
+        },
+    ];
+    // Step past the per-depositor lock cooldown.
+    s.env.ledger().set_timestamp(1_061);
+    s.client
+        .lock_funds_with_milestones(&s.depositor, &2, &stages);
+    s.client.release_milestone(&2, &0, &s.contributor);
+
+    // The assignee is the only contributor a dispute can name.
+    assert_eq!(
+        s.client
+            .try_open_dispute(&2, &s.depositor, &stranger, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.client
+            .try_open_dispute(&2, &stranger, &stranger, &DisputeReason::Other),
+        Err(Ok(Error::Unauthorized))
+    );
+    let id = s.client.open_dispute(
+        &2,
+        &s.contributor,
+        &s.contributor,
+        &DisputeReason::QualityIssue,
+    );
+    assert_eq!(
+        s.client.try_release_milestone(&2, &1, &s.contributor),
+        Err(Ok(Error::DisputeOpen))
+    );
+
+    // The ruling settles the pending milestone along with the escrow.
+    s.client.resolve_dispute(&id, &s.arbiter, &0);
+    assert_eq!(s.token.balance(&s.contributor), 400);
+    assert_eq!(s.token.balance(&s.depositor), 8_600);
+    let stages = s.client.get_milestones(&2);
+    assert_eq!(stages.get(0).unwrap().status, MilestoneStatus::Released);
+    assert_eq!(stages.get(1).unwrap().status, MilestoneStatus::Refunded);
+    let escrow = s.client.get_escrow_info(&2);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_submit_evidence() {
+    let s = DisputeSetup::new();
+    let id = s.open();
+    let hash = BytesN::from_array(&s.env, &[7u8; 32]);
+
+    s.client.submit_dispute_evidence(&id, &s.depositor, &hash);
+    s.client.submit_dispute_evidence(&id, &s.contributor, &hash);
+    assert_eq!(s.client.get_dispute(&id).evidence.len(), 2);
+
+    let stranger = Address::generate(&s.env);
+    assert_eq!(
+        s.client.try_submit_dispute_evidence(&id, &stranger, &hash),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_resolve_by_payout() {
+    let s = DisputeSetup::new();
+    let id = s.open();
+
+    let outcome = s.client.resolve_dispute(&id, &s.arbiter, &1_000);
+    assert_eq!(outcome, DisputeOutcome::ResolvedByPayout);
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Released);
+    assert!(s.client.get_active_dispute(&1).is_none());
+}
+
+#[test]
+fn test_resolve_by_refund() {
+    let s = DisputeSetup::new();
+    let id = s.open();
+
+    let outcome = s.client.resolve_dispute(&id, &s.arbiter, &0);
+    assert_eq!(outcome, DisputeOutcome::ResolvedByRefund);
+    assert_eq!(s.token.balance(&s.depositor), 10_000);
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Refunded);
+}
+
+#[test]
+fn test_resolve_by_split_records_outcome() {
+    let s = DisputeSetup::new();
+    let id = s.open();
+
+    let outcome = s.client.resolve_dispute(&id, &s.arbiter, &600);
+    assert_eq!(outcome, DisputeOutcome::ResolvedBySplit);
+    assert_eq!(s.token.balance(&s.contributor), 600);
+    assert_eq!(s.token.balance(&s.depositor), 9_400);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let dispute = s.client.get_dispute(&id);
+    assert_eq!(dispute.status, DisputeStatus::Resolved);
+    assert_eq!(dispute.outcome, DisputeOutcome::ResolvedBySplit);
+    assert_eq!(dispute.resolved_by, Some(s.arbiter.clone()));
+    assert_eq!(dispute.contributor_amount, 600);
+    assert_eq!(dispute.refund_amount, 400);
+    assert_eq!(s.client.get_dispute_history(&1).len(), 1);
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_resolve_requires_arbiter_and_valid_split() {
+    let s = DisputeSetup::new();
+    let id = s.open();
+
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &s.depositor, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &s.arbiter, &1_001),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    s.client.resolve_dispute(&id, &s.arbiter, &500);
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &s.arbiter, &500),
+        Err(Ok(Error::DisputeClosed))
+    );
+    assert_eq!(
+        s.client.try_get_dispute(&99),
+        Err(Ok(Error::DisputeNotFound))
+    );
+}
@@ -78,6 +78,7 @@ impl TestContext {
             repo_id: 1,
             issue_id: bounty_id,
             bounty_type: SorobanString::from_str(&self.env, "bug_fix"),
+            tags: Vec::new(&self.env),
         };
         self.client.update_metadata(
             &self.admin,
@@ -380,6 +381,7 @@ fn test_e2e_upgrade_preserves_escrow_metadata() {
         repo_id: 123,
         issue_id: 456,
         bounty_type: SorobanString::from_str(&ctx.env, "critical_bug"),
+        tags: Vec::new(&ctx.env),
     };
     ctx.client.update_metadata(
         &ctx.admin,
@@ -0,0 +1,345 @@
+// Tests for `batch_refund_expired`: keeper-driven refunds of expired escrows
+// walked from the deadline index, the per-item report, resume cursors and
+// keeper rewards.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+struct SweepSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    depositor: Address,
+    keeper: Address,
+}
+
+impl<'a> SweepSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let keeper = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        token_admin.mint(&depositor, &1_000_000);
+        // Several locks per test; skip the anti-abuse cooldown.
+        client.set_whitelist_entry(&depositor, &true);
+
+        Self {
+            env,
+            client,
+            token,
+            token_admin,
+            depositor,
+            keeper,
+        }
+    }
+
+    fn sweep(&self, limit: u32) -> soroban_sdk::Vec<keeper::SweepItem> {
+        self.client
+            .batch_refund_expired(&self.keeper, &None, &limit)
+            .items
+    }
+}
+
+#[test]
+fn test_sweep_refunds_expired_oldest_first() {
+    let s = SweepSetup::new();
+    s.client.lock_funds(&s.depositor, &1, &100, &3_000);
+    s.client.lock_funds(&s.depositor, &2, &200, &2_000);
+    s.client.lock_funds(&s.depositor, &3, &300, &9_000);
+    s.client.lock_funds(&s.depositor, &4, &400, &2_500);
+    s.client.release_funds(&4, &Address::generate(&s.env));
+
+    s.env.ledger().set_timestamp(3_000);
+    let before = s.token.balance(&s.depositor);
+    let report = s.sweep(10);
+
+    assert_eq!(report.len(), 2);
+    assert_eq!(report.get(0).unwrap().bounty_id, 2);
+    assert_eq!(report.get(1).unwrap().bounty_id, 1);
+    assert!(report.iter().all(|item| item.success));
+    assert_eq!(s.token.balance(&s.depositor), before + 300);
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Refunded);
+    assert_eq!(s.client.get_escrow_info(&3).status, EscrowStatus::Locked);
+
+    // Nothing left to sweep.
+    assert_eq!(s.sweep(10).len(), 0);
+}
+
+#[test]
+fn test_sweep_stops_at_limit() {
+    let s = SweepSetup::new();
+    for id in 1u64..=5 {
+        s.client
+            .lock_funds(&s.depositor, &id, &100, &(1_000 + id * 100));
+    }
+    s.env.ledger().set_timestamp(5_000);
+
+    let report = s.sweep(2);
+    assert_eq!(report.len(), 2);
+    assert_eq!(report.get(1).unwrap().bounty_id, 2);
+    assert_eq!(s.client.get_escrow_info(&3).status, EscrowStatus::Locked);
+
+    assert_eq!(s.sweep(20).len(), 3);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_sweep_reports_skipped_escrows() {
+    let s = SweepSetup::new();
+    let contributor = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &100, &2_000);
+    s.client.lock_funds(&s.depositor, &2, &100, &2_100);
+    s.client.lock_funds(&s.depositor, &3, &100, &2_200);
+    s.client
+        .authorize_claim(&1, &contributor, &DisputeReason::Other);
+    s.client
+        .open_dispute(&2, &s.depositor, &contributor, &DisputeReason::Other);
+
+    s.env.ledger().set_timestamp(3_000);
+    // Skipped escrows are reported and use up the limit like refunds.
+    let report = s.client.batch_refund_expired(&s.keeper, &None, &2);
+    assert_eq!(report.items.len(), 2);
+    let claim = report.items.get(0).unwrap();
+    assert!(!claim.success);
+    assert_eq!(claim.error_code, Error::ClaimPending as u32);
+    assert_eq!(
+        report.items.get(1).unwrap().error_code,
+        Error::DisputeOpen as u32
+    );
+    let cursor = report.next_cursor.clone().unwrap();
+    assert_eq!(keeper::decode_cursor(&cursor), Some((2_100, 2)));
+
+    let report = s.client.batch_refund_expired(&s.keeper, &Some(cursor), &2);
+    assert_eq!(report.items.len(), 1);
+    assert!(report.items.get(0).unwrap().success);
+    assert_eq!(report.next_cursor, None);
+
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Locked);
+    assert_eq!(s.client.get_escrow_info(&2).status, EscrowStatus::Locked);
+    assert_eq!(s.token.balance(&s.client.address), 200);
+}
+
+#[test]
+fn test_sweep_does_not_scan_settled_escrows() {
+    let s = SweepSetup::new();
+    s.env.budget().reset_unlimited();
+    let settled = keeper::MAX_SWEEP_SCAN as u64 + 10;
+    for id in 1..=settled {
+        s.client.lock_funds(&s.depositor, &id, &10, &2_000);
+        s.client.release_funds(&id, &Address::generate(&s.env));
+    }
+    s.client.lock_funds(&s.depositor, &9_999, &10, &2_500);
+
+    s.env.ledger().set_timestamp(3_000);
+    let report = s.client.batch_refund_expired(&s.keeper, &None, &5);
+    assert_eq!(report.items.len(), 1);
+    assert_eq!(report.items.get(0).unwrap().bounty_id, 9_999);
+    assert_eq!(report.next_cursor, None);
+}
+
+#[test]
+fn test_sweep_scan_is_bounded_and_resumable() {
+    let s = SweepSetup::new();
+    s.env.budget().reset_unlimited();
+    let waiting = keeper::MAX_SWEEP_SCAN as u64 + 10;
+    for id in 1..=waiting {
+        s.client.lock_funds(&s.depositor, &id, &10, &2_000);
+        s.client
+            .approve_refund(&id, &1, &s.depositor, &RefundMode::Partial);
+    }
+    s.client.lock_funds(&s.depositor, &9_999, &10, &2_500);
+
+    s.env.ledger().set_timestamp(3_000);
+    // The first call only gets through escrows left to `refund`.
+    let report = s.client.batch_refund_expired(&s.keeper, &None, &5);
+    assert_eq!(report.items.len(), 0);
+    let cursor = report.next_cursor.unwrap();
+    assert_eq!(
+        keeper::decode_cursor(&cursor),
+        Some((2_000, keeper::MAX_SWEEP_SCAN as u64))
+    );
+
+    let report = s.client.batch_refund_expired(&s.keeper, &Some(cursor), &5);
+    assert_eq!(report.items.len(), 1);
+    assert_eq!(report.items.get(0).unwrap().bounty_id, 9_999);
+    assert_eq!(report.next_cursor, None);
+}
+
+#[test]
+fn test_sweep_leaves_approved_refunds_to_refund() {
+    let s = SweepSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &2_000);
+    s.client
+        .approve_refund(&1, &400, &recipient, &RefundMode::Partial);
+
+    s.env.ledger().set_timestamp(3_000);
+    assert_eq!(s.sweep(10).len(), 0);
+    s.client.refund(&1);
+    assert_eq!(s.token.balance(&recipient), 400);
+
+    // Once the approval is used, the rest is swept back to the depositor.
+    let report = s.sweep(10);
+    assert_eq!(report.get(0).unwrap().refunded, 600);
+}
+
+#[test]
+fn test_keeper_reward_paid_from_lock_fee() {
+    let s = SweepSetup::new();
+    let fee_recipient = Address::generate(&s.env);
+    s.client.update_fee_config(
+        &Some(100),
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    s.client.set_keeper_reward(&2_000); // 20% of the lock fee
+    assert_eq!(s.client.get_keeper_reward(), 2_000);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &2_000);
+    s.client.lock_funds(&s.depositor, &2, &1_000, &2_000);
+    // 1% lock fees of 100 and 10; a fifth of each is kept for keepers.
+    assert_eq!(s.token.balance(&fee_recipient), 88);
+    assert_eq!(s.token.balance(&s.client.address), 9_900 + 990 + 22);
+    assert!(s.client.verify_all_invariants());
+
+    s.env.ledger().set_timestamp(3_000);
+    let before = s.token.balance(&s.depositor);
+    let report = s.sweep(10);
+
+    let first = report.get(0).unwrap();
+    assert_eq!(first.refunded, 9_900);
+    assert_eq!(first.keeper_reward, 20);
+    assert_eq!(report.get(1).unwrap().keeper_reward, 2);
+    assert_eq!(s.token.balance(&s.keeper), 22);
+    // The depositor gets back the whole escrowed principal.
+    assert_eq!(s.token.balance(&s.depositor), before + 9_900 + 990);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_keeper_reward_limited_to_pool() {
+    let s = SweepSetup::new();
+    let fee_recipient = Address::generate(&s.env);
+    s.client.update_fee_config(
+        &Some(100),
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    // Locked before the reward was configured: nothing was set aside.
+    s.client.lock_funds(&s.depositor, &1, &10_000, &2_000);
+    s.client.set_keeper_reward(&2_000);
+
+    s.env.ledger().set_timestamp(3_000);
+    let report = s.sweep(10);
+    assert_eq!(report.get(0).unwrap().keeper_reward, 0);
+    assert_eq!(report.get(0).unwrap().refunded, 9_900);
+    assert_eq!(s.token.balance(&s.keeper), 0);
+}
+
+#[test]
+fn test_unused_keeper_pool_goes_to_fee_recipient() {
+    let s = SweepSetup::new();
+    let fee_recipient = Address::generate(&s.env);
+    s.client.update_fee_config(
+        &Some(100),
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    s.client.set_keeper_reward(&2_000);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &2_000);
+    // Released, so the escrow is never swept and its share stays behind.
+    s.client.release_funds(&1, &Address::generate(&s.env));
+    assert_eq!(s.client.get_keeper_pool(&s.token.address), 20);
+
+    assert_eq!(s.client.withdraw_keeper_pool(&s.token.address), 20);
+    assert_eq!(s.token.balance(&fee_recipient), 100);
+    assert_eq!(s.client.get_keeper_pool(&s.token.address), 0);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert!(s.client.verify_all_invariants());
+
+    assert_eq!(s.client.withdraw_keeper_pool(&s.token.address), 0);
+}
+
+#[test]
+fn test_emergency_withdraw_takes_keeper_pool() {
+    let s = SweepSetup::new();
+    let fee_recipient = Address::generate(&s.env);
+    let target = Address::generate(&s.env);
+    s.client.update_fee_config(
+        &Some(100),
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    s.client.set_keeper_reward(&2_000);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &2_000);
+
+    s.client.set_paused(&Some(true), &None, &None, &None);
+    s.client.emergency_withdraw(&target);
+    assert_eq!(s.token.balance(&target), 9_900 + 20);
+    assert!(s.client.verify_all_invariants());
+
+    // The token can be locked again once the contract is unpaused.
+    s.client.set_paused(&Some(false), &None, &None, &None);
+    s.client.lock_funds(&s.depositor, &2, &1_000, &2_000);
+    assert_eq!(s.token.balance(&s.client.address), 990 + 2);
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_sweep_refunds_crowdfunded_escrow_pro_rata() {
+    let s = SweepSetup::new();
+    let funder = Address::generate(&s.env);
+    s.token_admin.mint(&funder, &1_000);
+    s.client.lock_funds(&s.depositor, &1, &3_000, &2_000);
+    s.client.contribute(&1, &funder, &1_000);
+
+    s.env.ledger().set_timestamp(3_000);
+    let before = s.token.balance(&s.depositor);
+    s.sweep(10);
+
+    assert_eq!(s.token.balance(&funder), 1_000);
+    assert_eq!(s.token.balance(&s.depositor), before + 3_000);
+    assert_eq!(s.client.get_escrow_info(&1).refund_history.len(), 2);
+}
+
+#[test]
+fn test_sweep_validation() {
+    let s = SweepSetup::new();
+    let res = s.client.try_batch_refund_expired(&s.keeper, &None, &0);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+    let res = s
+        .client
+        .try_batch_refund_expired(&s.keeper, &None, &(MAX_BATCH_SIZE + 1));
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+    let bad = soroban_sdk::Bytes::from_array(&s.env, &[1, 2, 3]);
+    let res = s
+        .client
+        .try_batch_refund_expired(&s.keeper, &Some(bad), &10);
+    assert_eq!(res, Err(Ok(Error::InvalidCursor)));
+
+    let res = s.client.try_set_keeper_reward(&(MAX_FEE_RATE as u32 + 1));
+    assert_eq!(res, Err(Ok(Error::InvalidFeeRate)));
+
+    s.client.set_paused(&None, &None, &Some(true), &None);
+    let res = s.client.try_batch_refund_expired(&s.keeper, &None, &10);
+    assert_eq!(res, Err(Ok(Error::FundsPaused)));
+}
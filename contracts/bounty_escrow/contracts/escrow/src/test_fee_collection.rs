@@ -0,0 +1,337 @@
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, String, Symbol, TryFromVal,
+};
+
+struct FeeSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    admin: Address,
+    fee_recipient: Address,
+    depositor: Address,
+    contributor: Address,
+}
+
+impl<'a> FeeSetup<'a> {
+    fn new(lock_fee_rate: i128, release_fee_rate: i128) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let fee_recipient = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        client.update_fee_config(
+            &Some(lock_fee_rate),
+            &Some(release_fee_rate),
+            &Some(fee_recipient.clone()),
+            &Some(true),
+        );
+        token_admin.mint(&depositor, &1_000_000);
+
+        Self {
+            env,
+            client,
+            token,
+            token_admin,
+            admin,
+            fee_recipient,
+            depositor,
+            contributor,
+        }
+    }
+}
+
+#[test]
+fn test_lock_fee_routed_to_recipient() {
+    let s = FeeSetup::new(250, 0);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.lock_fee, 250);
+    assert_eq!(escrow.amount, 9_750);
+    assert_eq!(escrow.remaining_amount, 9_750);
+    assert_eq!(s.token.balance(&s.fee_recipient), 250);
+    assert_eq!(s.token.balance(&s.client.address), 9_750);
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_release_fee_routed_to_recipient() {
+    let s = FeeSetup::new(0, 300);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    s.client.release_funds(&1, &s.contributor);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.release_fee, 300);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(s.token.balance(&s.contributor), 9_700);
+    assert_eq!(s.token.balance(&s.fee_recipient), 300);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_capability_release_charges_release_fee() {
+    let s = FeeSetup::new(0, 300);
+    let holder = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    let cap = s.client.issue_capability(
+        &s.admin,
+        &holder,
+        &CapabilityAction::Release,
+        &1,
+        &10_000,
+        &4_000,
+        &1,
+    );
+    s.client
+        .release_with_capability(&1, &s.contributor, &4_000, &holder, &cap);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.release_fee, 120);
+    assert_eq!(escrow.remaining_amount, 6_000);
+    assert_eq!(s.token.balance(&s.contributor), 3_880);
+    assert_eq!(s.token.balance(&s.fee_recipient), 120);
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_claim_paths_charge_release_fee() {
+    let s = FeeSetup::new(0, 300);
+    let holder = Address::generate(&s.env);
+    s.client.set_claim_window(&500);
+    s.client.set_whitelist_entry(&s.depositor, &true);
+
+    // Claimed by the recipient.
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    s.client
+        .authorize_claim(&1, &s.contributor, &DisputeReason::Other);
+    s.client.claim(&1);
+    assert_eq!(s.token.balance(&s.contributor), 9_700);
+
+    // Claimed through a capability.
+    s.client.lock_funds(&s.depositor, &2, &10_000, &5_000);
+    s.client
+        .authorize_claim(&2, &s.contributor, &DisputeReason::Other);
+    let cap = s.client.issue_capability(
+        &s.contributor,
+        &holder,
+        &CapabilityAction::Claim,
+        &2,
+        &10_000,
+        &4_000,
+        &1,
+    );
+    s.client.claim_with_capability(&2, &holder, &cap);
+    assert_eq!(s.token.balance(&s.contributor), 19_400);
+
+    // Claimed with a ticket.
+    s.client.lock_funds(&s.depositor, &3, &10_000, &5_000);
+    let ticket = s
+        .client
+        .issue_claim_ticket(&3, &s.contributor, &10_000, &4_000);
+    s.client.claim_with_ticket(&ticket);
+    assert_eq!(s.token.balance(&s.contributor), 29_100);
+
+    assert_eq!(s.token.balance(&s.fee_recipient), 900);
+    for id in 1..=3 {
+        let escrow = s.client.get_escrow_info(&id);
+        assert_eq!(escrow.release_fee, 300);
+        assert_eq!(escrow.remaining_amount, 0);
+        assert_eq!(escrow.status, EscrowStatus::Released);
+    }
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_dispute_payout_charges_release_fee() {
+    let s = FeeSetup::new(0, 300);
+    let arbiter = Address::generate(&s.env);
+    s.client.set_arbiters(&vec![&s.env, arbiter.clone()]);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    let id = s
+        .client
+        .open_dispute(&1, &s.depositor, &s.contributor, &DisputeReason::Other);
+    s.client.resolve_dispute(&id, &arbiter, &4_000);
+
+    assert_eq!(s.client.get_escrow_info(&1).release_fee, 120);
+    assert_eq!(s.token.balance(&s.contributor), 3_880);
+    assert_eq!(s.token.balance(&s.fee_recipient), 120);
+    assert_eq!(s.token.balance(&s.depositor), 996_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let mut released = None;
+    let mut fee_topics = 0;
+    for (contract, topics, data) in s.env.events().all().iter() {
+        if contract != s.client.address {
+            continue;
+        }
+        let topic = Symbol::try_from_val(&s.env, &topics.get(0).unwrap());
+        if topic == Ok(symbol_short!("f_rel")) {
+            released = Some(events::FundsReleased::try_from_val(&s.env, &data).unwrap());
+        } else if topic == Ok(symbol_short!("fee")) {
+            assert_eq!(topics.len(), 1);
+            let fee = events::FeeCollected::try_from_val(&s.env, &data).unwrap();
+            assert_eq!(fee.bounty_id, 1);
+            fee_topics += 1;
+        }
+    }
+    let released = released.unwrap();
+    assert_eq!(released.recipient, s.contributor);
+    assert_eq!(released.amount, 3_880);
+    assert_eq!(released.gross_amount, 4_000);
+    assert_eq!(released.fee_amount, 120);
+    assert_eq!(fee_topics, 1);
+}
+
+#[test]
+fn test_renewal_and_next_cycle_charge_lock_fee() {
+    let s = FeeSetup::new(100, 0);
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    s.client.renew_escrow(&1, &6_000, &5_000);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.amount, 9_900 + 4_950);
+    assert_eq!(escrow.remaining_amount, 9_900 + 4_950);
+    assert_eq!(escrow.lock_fee, 150);
+    assert_eq!(s.token.balance(&s.fee_recipient), 150);
+
+    s.client.release_funds(&1, &s.contributor);
+    s.client.create_next_cycle(&1, &2, &10_000, &8_000);
+
+    let next = s.client.get_escrow_info(&2);
+    assert_eq!(next.amount, 9_900);
+    assert_eq!(next.remaining_amount, 9_900);
+    assert_eq!(next.lock_fee, 100);
+    assert_eq!(s.token.balance(&s.fee_recipient), 250);
+    assert_eq!(s.token.balance(&s.client.address), 9_900);
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_fee_rounds_down_in_payers_favour() {
+    // 333 * 1% = 3.33 -> 3
+    let s = FeeSetup::new(100, 100);
+    s.client.lock_funds(&s.depositor, &1, &333, &5_000);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.lock_fee, 3);
+    assert_eq!(escrow.amount, 330);
+
+    s.client.partial_release(&1, &s.contributor, &199);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.release_fee, 1);
+    assert_eq!(escrow.remaining_amount, 131);
+    assert_eq!(s.token.balance(&s.contributor), 198);
+    assert_eq!(s.token.balance(&s.fee_recipient), 4);
+    assert_eq!(s.token.balance(&s.client.address), 131);
+    assert!(s.client.verify_all_invariants());
+}
+
+#[test]
+fn test_release_after_partial_pays_only_remaining() {
+    let s = FeeSetup::new(0, 0);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &5_000);
+    s.client.partial_release(&1, &s.contributor, &400);
+    s.client.release_funds(&1, &s.contributor);
+
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_fees_disabled_charges_nothing() {
+    let s = FeeSetup::new(500, 500);
+    s.client
+        .update_fee_config(&None, &None, &None, &Some(false));
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    s.client.release_funds(&1, &s.contributor);
+
+    assert_eq!(s.token.balance(&s.contributor), 10_000);
+    assert_eq!(s.token.balance(&s.fee_recipient), 0);
+}
+
+#[test]
+fn test_promotion_lowers_charged_fee() {
+    let s = FeeSetup::new(500, 500);
+    s.client.create_promotional_period(
+        &String::from_str(&s.env, "launch"),
+        &1_000,
+        &2_000,
+        &0,
+        &100,
+    );
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    s.client.release_funds(&1, &s.contributor);
+
+    assert_eq!(s.client.get_escrow_info(&1).lock_fee, 0);
+    assert_eq!(s.token.balance(&s.contributor), 9_900);
+    assert_eq!(s.token.balance(&s.fee_recipient), 100);
+}
+
+#[test]
+fn test_batch_lock_and_release_charge_fees_per_item() {
+    let s = FeeSetup::new(100, 200);
+    let other = Address::generate(&s.env);
+    s.token_admin.mint(&other, &1_000_000);
+
+    let items = vec![
+        &s.env,
+        LockFundsItem {
+            bounty_id: 1,
+            depositor: s.depositor.clone(),
+            amount: 10_000,
+            deadline: 5_000,
+        },
+        LockFundsItem {
+            bounty_id: 2,
+            depositor: other.clone(),
+            amount: 20_000,
+            deadline: 5_000,
+        },
+    ];
+    s.client.batch_lock_funds(&items);
+    assert_eq!(s.client.get_escrow_info(&1).amount, 9_900);
+    assert_eq!(s.client.get_escrow_info(&2).amount, 19_800);
+    assert_eq!(s.token.balance(&s.fee_recipient), 300);
+
+    let releases = vec![
+        &s.env,
+        ReleaseFundsItem {
+            bounty_id: 1,
+            contributor: s.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 2,
+            contributor: s.contributor.clone(),
+        },
+    ];
+    s.client.batch_release_funds(&releases);
+    // 9_900 * 2% = 198, 19_800 * 2% = 396
+    assert_eq!(s.token.balance(&s.contributor), 9_702 + 19_404);
+    assert_eq!(s.token.balance(&s.fee_recipient), 300 + 198 + 396);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_simulate_lock_reports_net_remaining() {
+    let s = FeeSetup::new(1_000, 0);
+    let result = s.client.simulate_lock(&s.depositor, &1, &10_000, &5_000);
+    assert!(result.success);
+    assert_eq!(result.amount, 10_000);
+    assert_eq!(result.remaining_amount, 9_000);
+}
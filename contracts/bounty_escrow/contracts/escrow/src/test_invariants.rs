@@ -191,6 +191,9 @@ fn test_invariant_checker_catches_negative_amount() {
         status: EscrowStatus::Locked,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -213,6 +216,9 @@ fn test_invariant_checker_catches_negative_remaining_amount() {
         status: EscrowStatus::Locked,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -235,6 +241,9 @@ fn test_invariant_checker_catches_remaining_amount_exceeds_amount() {
         status: EscrowStatus::Locked,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -257,6 +266,9 @@ fn test_invariant_checker_catches_released_with_nonzero_remaining() {
         status: EscrowStatus::Released,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -278,6 +290,9 @@ fn test_invariant_checker_allows_valid_edge_cases() {
         status: EscrowStatus::Released,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -292,6 +307,9 @@ fn test_invariant_checker_allows_valid_edge_cases() {
         status: EscrowStatus::Locked,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -306,6 +324,9 @@ fn test_invariant_checker_allows_valid_edge_cases() {
         status: EscrowStatus::Released,
         deadline: env.ledger().timestamp() + 1000,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     env.as_contract(&client.address, || {
@@ -334,6 +355,9 @@ fn test_invariant_checker_partial_refund_state() {
         status: EscrowStatus::Locked,
         deadline,
         refund_history: vec![&env],
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&env),
     };
 
     // This should pass invariants
@@ -0,0 +1,175 @@
+use super::*;
+use crate::legacy::{EscrowV0, EscrowV1};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, Map, Symbol, Val,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    token_id: Address,
+    depositor: Address,
+    contributor: Address,
+}
+
+impl<'a> Setup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &1_000_000);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        client.set_whitelist_entry(&depositor, &true);
+
+        Self {
+            env,
+            client,
+            token,
+            token_id,
+            depositor,
+            contributor,
+        }
+    }
+
+    /// Lock `amount` and overwrite the stored record with the pre-fee
+    /// layout, as an escrow written by an earlier contract version.
+    fn lock_v0(&self, bounty_id: u64, amount: i128) {
+        self.client
+            .lock_funds(&self.depositor, &bounty_id, &amount, &5_000);
+        let v0 = EscrowV0 {
+            depositor: self.depositor.clone(),
+            amount,
+            remaining_amount: amount,
+            status: EscrowStatus::Locked,
+            deadline: 5_000,
+            refund_history: vec![&self.env],
+        };
+        self.env.as_contract(&self.client.address, || {
+            self.env
+                .storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &v0);
+        });
+    }
+
+    /// Lock `amount` and overwrite the stored record with the layout that
+    /// had fees but no per-escrow token.
+    fn lock_v1(&self, bounty_id: u64, amount: i128, lock_fee: i128) {
+        self.client
+            .lock_funds(&self.depositor, &bounty_id, &amount, &5_000);
+        let v1 = EscrowV1 {
+            depositor: self.depositor.clone(),
+            amount,
+            remaining_amount: amount,
+            status: EscrowStatus::Locked,
+            deadline: 5_000,
+            refund_history: vec![&self.env],
+            lock_fee,
+            release_fee: 0,
+        };
+        self.env.as_contract(&self.client.address, || {
+            self.env
+                .storage()
+                .persistent()
+                .set(&DataKey::Escrow(bounty_id), &v1);
+        });
+    }
+
+    fn stored_fields(&self, bounty_id: u64) -> Map<Symbol, Val> {
+        self.env.as_contract(&self.client.address, || {
+            self.env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(bounty_id))
+                .unwrap()
+        })
+    }
+}
+
+#[test]
+fn test_v0_escrow_reads_with_defaults() {
+    let s = Setup::new();
+    s.lock_v0(1, 10_000);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.amount, 10_000);
+    assert_eq!(escrow.remaining_amount, 10_000);
+    assert_eq!(escrow.lock_fee, 0);
+    assert_eq!(escrow.release_fee, 0);
+    assert_eq!(escrow.token, s.token_id);
+}
+
+#[test]
+fn test_v0_escrow_can_be_released() {
+    let s = Setup::new();
+    s.lock_v0(1, 10_000);
+
+    s.client.release_funds(&1, &s.contributor);
+    assert_eq!(s.token.balance(&s.contributor), 10_000);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.remaining_amount, 0);
+}
+
+#[test]
+fn test_v1_escrow_keeps_fees_and_takes_default_token() {
+    let s = Setup::new();
+    s.lock_v1(1, 10_000, 0);
+    s.lock_v1(2, 10_000, 125);
+
+    let escrow = s.client.get_escrow_info(&2);
+    assert_eq!(escrow.lock_fee, 125);
+    assert_eq!(escrow.release_fee, 0);
+    assert_eq!(escrow.token, s.token_id);
+
+    s.client.release_funds(&1, &s.contributor);
+    assert_eq!(s.token.balance(&s.contributor), 10_000);
+
+    assert_eq!(s.client.migrate_escrows(&vec![&s.env, 2u64]), 1);
+    assert_eq!(s.client.get_escrow_info(&2).lock_fee, 125);
+}
+
+#[test]
+fn test_migrate_escrows_rewrites_old_records() {
+    let s = Setup::new();
+    s.lock_v0(1, 10_000);
+    s.client.lock_funds(&s.depositor, &2, &5_000, &5_000);
+    assert!(!s
+        .stored_fields(1)
+        .contains_key(Symbol::new(&s.env, "token")));
+
+    let migrated = s.client.migrate_escrows(&vec![&s.env, 1u64, 2u64, 99u64]);
+    assert_eq!(migrated, 1);
+    assert!(s
+        .stored_fields(1)
+        .contains_key(Symbol::new(&s.env, "token")));
+    assert_eq!(s.client.get_escrow_info(&1).amount, 10_000);
+
+    // Already current: nothing left to rewrite.
+    assert_eq!(s.client.migrate_escrows(&vec![&s.env, 1u64]), 0);
+}
+
+#[test]
+fn test_migrate_escrows_rejects_oversized_batch() {
+    let s = Setup::new();
+    let mut ids = vec![&s.env];
+    for id in 0..=MAX_BATCH_SIZE as u64 {
+        ids.push_back(id);
+    }
+    let result = s.client.try_migrate_escrows(&ids);
+    assert_eq!(result, Err(Ok(Error::InvalidBatchSize)));
+}
@@ -1,378 +1,429 @@
-// Tests for metadata tagging functionality.
-//
-// Most tests in this module are gated behind `cfg(feature = "metadata_tagging")`
-// because the contract does not yet expose the relevant types (`EscrowMetadata`,
-// `SdkVec`, `lock_funds_with_metadata`, etc.).  The feature gate compiles them
-// out until the feature is implemented (tracked in Issue #63).
-
-#[cfg(test)]
-#[cfg(feature = "metadata_tagging")]
-mod metadata_tagging_tests {
-    use super::super::*;
-    use soroban_sdk::{testutils::Address as _, token, Address, Env, String, Vec as SdkVec};
-
-    fn create_token(
-        env: &Env,
-        admin: &Address,
-    ) -> (token::Client<'static>, token::StellarAssetClient<'static>) {
-        let addr = env
-            .register_stellar_asset_contract_v2(admin.clone())
-            .address();
-        (
-            token::Client::new(env, &addr),
-            token::StellarAssetClient::new(env, &addr),
-        )
-    }
-
-    fn create_escrow(env: &Env) -> BountyEscrowContractClient<'static> {
-        let id = env.register_contract(None, BountyEscrowContract);
-        BountyEscrowContractClient::new(env, &id)
-    }
-
-    struct Setup {
-        env: Env,
-        _admin: Address,
-        depositor: Address,
-        escrow: BountyEscrowContractClient<'static>,
-        token: token::Client<'static>,
+// Tests for metadata tagging (Issue #63): atomic lock-with-metadata, tag,
+// repo and bounty-type indexes, and the one-active-escrow-per-issue guard.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, String, Vec as SdkVec,
+};
+
+fn create_token(
+    env: &Env,
+    admin: &Address,
+) -> (token::Client<'static>, token::StellarAssetClient<'static>) {
+    let addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    (
+        token::Client::new(env, &addr),
+        token::StellarAssetClient::new(env, &addr),
+    )
+}
+
+fn create_escrow(env: &Env) -> BountyEscrowContractClient<'static> {
+    let id = env.register_contract(None, BountyEscrowContract);
+    BountyEscrowContractClient::new(env, &id)
+}
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    depositor: Address,
+    escrow: BountyEscrowContractClient<'static>,
+    token: token::Client<'static>,
+}
+
+impl Setup {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let (token, token_admin) = create_token(&env, &admin);
+        let escrow = create_escrow(&env);
+        escrow.init(&admin, &token.address);
+        token_admin.mint(&depositor, &10_000_000);
+        Setup {
+            env,
+            admin,
+            depositor,
+            escrow,
+            token,
+        }
     }
 
-    impl Setup {
-        fn new() -> Self {
-            let env = Env::default();
-            env.mock_all_auths();
-            let admin = Address::generate(&env);
-            let depositor = Address::generate(&env);
-            let (token, token_admin) = create_token(&env, &admin);
-            let escrow = create_escrow(&env);
-            escrow.init(&admin, &token.address);
-            token_admin.mint(&depositor, &10_000_000);
-            Setup {
-                env,
-                _admin: admin,
-                depositor,
-                escrow,
-                token,
-            }
+    fn metadata(
+        &self,
+        repo_id: u64,
+        issue_id: u64,
+        bounty_type: &str,
+        tags: &[&str],
+    ) -> EscrowMetadata {
+        let mut tag_vec = SdkVec::new(&self.env);
+        for tag in tags {
+            tag_vec.push_back(String::from_str(&self.env, tag));
+        }
+        EscrowMetadata {
+            repo_id,
+            issue_id,
+            bounty_type: String::from_str(&self.env, bounty_type),
+            tags: tag_vec,
         }
     }
+}
+
+// ============================================================================
+// Test 1: Basic Metadata Storage and Retrieval
+// ============================================================================
+
+#[test]
+fn test_metadata_set_on_creation() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let metadata = s.metadata(123, 7, "bug_fix", &["rust", "smart-contract"]);
+
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &100, &5000, &deadline, &metadata);
+
+    assert_eq!(s.escrow.get_metadata(&100), metadata);
+    assert_eq!(s.escrow.get_escrow_info(&100).amount, 5000);
+    assert_eq!(s.token.balance(&s.escrow.address), 5000);
+}
+
+#[test]
+fn test_metadata_update_moves_indexes() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 7200;
+    let metadata = s.metadata(1, 456, "feature", &["rust"]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &101, &3000, &deadline, &metadata);
+
+    let docs = String::from_str(&s.env, "documentation");
+    s.escrow.update_metadata(&s.admin, &101, &2, &456, &docs);
+
+    // update_metadata keeps existing tags.
+    let retrieved = s.escrow.get_metadata(&101);
+    assert_eq!(retrieved.bounty_type, docs);
+    assert_eq!(retrieved.tags.len(), 1);
+
+    assert_eq!(s.escrow.query_escrows_by_repo(&1, &0, &20).len(), 0);
+    assert_eq!(s.escrow.query_escrows_by_repo(&2, &0, &20).len(), 1);
+    let feature = String::from_str(&s.env, "feature");
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_bounty_type(&feature, &0, &20)
+            .len(),
+        0
+    );
+    assert_eq!(
+        s.escrow.query_escrows_by_bounty_type(&docs, &0, &20).len(),
+        1
+    );
+
+    let mut tags = SdkVec::new(&s.env);
+    tags.push_back(String::from_str(&s.env, "good-first-issue"));
+    s.escrow.update_metadata_tags(&101, &tags);
+    let rust = String::from_str(&s.env, "rust");
+    assert_eq!(s.escrow.query_escrows_by_tag(&rust, &0, &20).len(), 0);
+    let gfi = String::from_str(&s.env, "good-first-issue");
+    assert_eq!(s.escrow.query_escrows_by_tag(&gfi, &0, &20).len(), 1);
+}
+
+// ============================================================================
+// Test 2: Metadata Persistence Across Lifecycle
+// ============================================================================
+
+#[test]
+fn test_metadata_persistence_across_lifecycle() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let metadata = s.metadata(9, 9, "feature", &["rust"]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &100, &5000, &deadline, &metadata);
+
+    s.escrow.release_funds(&100, &Address::generate(&s.env));
+
+    // Settled escrows keep their metadata but leave the query buckets.
+    assert_eq!(s.escrow.get_metadata(&100), metadata);
+    assert_eq!(s.escrow.query_escrows_by_repo(&9, &0, &20).len(), 0);
+    let feature = String::from_str(&s.env, "feature");
+    assert_eq!(
+        s.escrow
+            .query_escrows_by_bounty_type(&feature, &0, &20)
+            .len(),
+        0
+    );
+    let rust = String::from_str(&s.env, "rust");
+    assert_eq!(s.escrow.query_escrows_by_tag(&rust, &0, &20).len(), 0);
+
+    // Re-tagging a settled escrow does not put it back.
+    let mut tags = SdkVec::new(&s.env);
+    tags.push_back(String::from_str(&s.env, "wasm"));
+    s.escrow.update_metadata_tags(&100, &tags);
+    assert_eq!(s.escrow.get_metadata(&100).tags, tags);
+    let wasm = String::from_str(&s.env, "wasm");
+    assert_eq!(s.escrow.query_escrows_by_tag(&wasm, &0, &20).len(), 0);
+}
+
+#[test]
+fn test_refunded_escrow_leaves_query_buckets() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let metadata = s.metadata(9, 1, "feature", &["rust"]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &1, &1000, &deadline, &metadata);
+    let metadata = s.metadata(9, 2, "feature", &["rust"]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &2, &1000, &deadline, &metadata);
+
+    s.env.ledger().set_timestamp(deadline + 1);
+    s.escrow.refund(&1);
+
+    let results = s.escrow.query_escrows_by_repo(&9, &0, &20);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get(0).unwrap().bounty_id, 2);
+}
+
+#[test]
+fn test_metadata_written_before_tags_still_reads() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    s.escrow.lock_funds(&s.depositor, &1, &1000, &deadline);
+    let bug_fix = String::from_str(&s.env, "bug_fix");
+    s.env.as_contract(&s.escrow.address, || {
+        s.env.storage().persistent().set(
+            &DataKey::Metadata(1),
+            &metadata::StoredMetadata {
+                repo_id: 5,
+                issue_id: 6,
+                bounty_type: bug_fix.clone(),
+            },
+        );
+    });
 
-    // ============================================================================
-    // Test 1: Basic Metadata Storage and Retrieval
-    // ============================================================================
-
-    #[test]
-    #[ignore = "Metadata functionality to be implemented - Issue #63"]
-    fn test_metadata_set_on_creation() {
-        let s = Setup::new();
-        let bounty_id = 100u64;
-        let amount = 5000i128;
-        let deadline = s.env.ledger().timestamp() + 3600;
-
-        let repo_id = String::from_str(&s.env, "stellar/soroban-examples");
-        let issue_id = String::from_str(&s.env, "123");
-        let bounty_type = String::from_str(&s.env, "bug_fix");
-        let mut tags = SdkVec::new(&s.env);
-        tags.push_back(String::from_str(&s.env, "rust"));
-        tags.push_back(String::from_str(&s.env, "smart-contract"));
-
-        let metadata = EscrowMetadata {
-            repo_id: Some(repo_id.clone()),
-            issue_id: Some(issue_id.clone()),
-            bounty_type: Some(bounty_type.clone()),
-            tags: tags.clone(),
-            custom_fields: SdkVec::new(&s.env),
-        };
+    let retrieved = s.escrow.get_metadata(&1);
+    assert_eq!(retrieved.repo_id, 5);
+    assert_eq!(retrieved.bounty_type, bug_fix);
+    assert_eq!(retrieved.tags.len(), 0);
 
-        s.escrow
-            .lock_funds_with_metadata(&s.depositor, &bounty_id, &amount, &deadline, &metadata);
+    s.escrow.update_metadata(&s.admin, &1, &5, &7, &bug_fix);
+    assert_eq!(s.escrow.get_metadata(&1).issue_id, 7);
+}
 
-        let retrieved_metadata = s.escrow.get_escrow_metadata(&bounty_id);
-        assert_eq!(retrieved_metadata.repo_id, Some(repo_id));
-        assert_eq!(retrieved_metadata.issue_id, Some(issue_id));
-        assert_eq!(retrieved_metadata.bounty_type, Some(bounty_type));
-        assert_eq!(retrieved_metadata.tags.len(), 2);
-    }
+// ============================================================================
+// Test 3: Query by Metadata Fields
+// ============================================================================
 
-    #[test]
-    #[ignore = "Metadata functionality to be implemented - Issue #63"]
-    fn test_metadata_update() {
-        let s = Setup::new();
-        let bounty_id = 101u64;
-        let amount = 3000i128;
-        let deadline = s.env.ledger().timestamp() + 7200;
-
-        let initial_metadata = EscrowMetadata {
-            repo_id: Some(String::from_str(&s.env, "stellar/rs-soroban-sdk")),
-            issue_id: Some(String::from_str(&s.env, "456")),
-            bounty_type: Some(String::from_str(&s.env, "feature")),
-            tags: SdkVec::new(&s.env),
-            custom_fields: SdkVec::new(&s.env),
-        };
+#[test]
+fn test_query_by_repo_id() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
 
+    for i in 1u64..=10 {
+        let repo_id = if i <= 5 { 100 } else { 200 };
+        let metadata = s.metadata(repo_id, i, "feature", &[]);
         s.escrow.lock_funds_with_metadata(
             &s.depositor,
-            &bounty_id,
-            &amount,
+            &i,
+            &(i as i128 * 1000),
             &deadline,
-            &initial_metadata,
-        );
-
-        let mut updated_tags = SdkVec::new(&s.env);
-        updated_tags.push_back(String::from_str(&s.env, "documentation"));
-
-        let updated_metadata = EscrowMetadata {
-            repo_id: Some(String::from_str(&s.env, "stellar/rs-soroban-sdk")),
-            issue_id: Some(String::from_str(&s.env, "456")),
-            bounty_type: Some(String::from_str(&s.env, "documentation")),
-            tags: updated_tags.clone(),
-            custom_fields: SdkVec::new(&s.env),
-        };
-
-        s.escrow
-            .update_escrow_metadata(&bounty_id, &updated_metadata);
-
-        let retrieved = s.escrow.get_escrow_metadata(&bounty_id);
-        assert_eq!(
-            retrieved.bounty_type,
-            Some(String::from_str(&s.env, "documentation"))
+            &metadata,
         );
     }
 
-    // ============================================================================
-    // Test 2: Metadata Persistence Across Lifecycle
-    // ============================================================================
-
-    #[test]
-    fn test_metadata_persistence_across_lifecycle() {
-        let s = Setup::new();
-        let bounty_id = 100u64;
-        let amount = 5000i128;
-        let dl = s.env.ledger().timestamp() + 3600;
-
-        s.escrow.lock_funds(&s.depositor, &bounty_id, &amount, &dl);
-
-        let info = s.escrow.get_escrow_info(&bounty_id);
-        assert_eq!(info.amount, amount);
-        assert_eq!(info.status, EscrowStatus::Locked);
+    let results = s.escrow.query_escrows_by_repo(&100, &0, &20);
+    assert_eq!(results.len(), 5);
+
+    // Pages follow the order metadata was attached.
+    let page = s.escrow.query_escrows_by_repo(&200, &2, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().bounty_id, 8);
+    assert_eq!(page.get(1).unwrap().bounty_id, 9);
+}
+
+#[test]
+fn test_query_by_bounty_type() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let types = [
+        "bug_fix",
+        "feature",
+        "bug_fix",
+        "documentation",
+        "bug_fix",
+        "feature",
+    ];
+
+    for (i, bounty_type) in types.iter().enumerate() {
+        let bounty_id = (i + 1) as u64;
+        let metadata = s.metadata(1, bounty_id, bounty_type, &[]);
+        s.escrow
+            .lock_funds_with_metadata(&s.depositor, &bounty_id, &1000, &deadline, &metadata);
     }
 
-    // ============================================================================
-    // Test 3: Query by Metadata Fields
-    // ============================================================================
-
-    #[test]
-    #[ignore = "Metadata query functionality to be implemented - Issue #63"]
-    fn test_query_by_repo_id() {
-        let s = Setup::new();
-        let deadline = s.env.ledger().timestamp() + 3600;
-
-        for i in 1u64..=10 {
-            let repo_id = if i <= 5 {
-                String::from_str(&s.env, "stellar/soroban-examples")
-            } else {
-                String::from_str(&s.env, "stellar/rs-soroban-sdk")
-            };
-
-            let metadata = EscrowMetadata {
-                repo_id: Some(repo_id),
-                issue_id: Some(String::from_str(&s.env, &i.to_string())),
-                bounty_type: Some(String::from_str(&s.env, "feature")),
-                tags: SdkVec::new(&s.env),
-                custom_fields: SdkVec::new(&s.env),
-            };
-
-            s.escrow.lock_funds_with_metadata(
-                &s.depositor,
-                &i,
-                &(i as i128 * 1000),
-                &deadline,
-                &metadata,
-            );
+    let bug_fixes =
+        s.escrow
+            .query_escrows_by_bounty_type(&String::from_str(&s.env, "bug_fix"), &0, &20);
+    assert_eq!(bug_fixes.len(), 3);
+}
+
+#[test]
+fn test_query_by_tags() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+
+    for i in 1u64..=8 {
+        let mut tags: [&str; 2] = [""; 2];
+        let mut n = 0;
+        if i % 2 == 0 {
+            tags[n] = "rust";
+            n += 1;
         }
-
-        let results = s.escrow.query_escrows_by_repo_id(
-            &String::from_str(&s.env, "stellar/soroban-examples"),
-            &0,
-            &20,
-        );
-
-        assert_eq!(results.len(), 5);
-    }
-
-    #[test]
-    #[ignore = "Metadata query functionality to be implemented - Issue #63"]
-    fn test_query_by_bounty_type() {
-        let s = Setup::new();
-        let deadline = s.env.ledger().timestamp() + 3600;
-
-        let types = vec![
-            "bug_fix",
-            "feature",
-            "bug_fix",
-            "documentation",
-            "bug_fix",
-            "feature",
-        ];
-
-        for (i, bounty_type) in types.iter().enumerate() {
-            let metadata = EscrowMetadata {
-                repo_id: Some(String::from_str(&s.env, "stellar/test")),
-                issue_id: Some(String::from_str(&s.env, &(i + 1).to_string())),
-                bounty_type: Some(String::from_str(&s.env, bounty_type)),
-                tags: SdkVec::new(&s.env),
-                custom_fields: SdkVec::new(&s.env),
-            };
-
-            s.escrow.lock_funds_with_metadata(
-                &s.depositor,
-                &((i + 1) as u64),
-                &1000,
-                &deadline,
-                &metadata,
-            );
+        if i % 3 == 0 {
+            tags[n] = "beginner-friendly";
+            n += 1;
         }
-
-        let bug_fixes =
-            s.escrow
-                .query_escrows_by_bounty_type(&String::from_str(&s.env, "bug_fix"), &0, &20);
-        assert_eq!(bug_fixes.len(), 3);
+        let metadata = s.metadata(1, i, "feature", &tags[..n]);
+        s.escrow
+            .lock_funds_with_metadata(&s.depositor, &i, &1000, &deadline, &metadata);
     }
 
-    #[test]
-    #[ignore = "Metadata query functionality to be implemented - Issue #63"]
-    fn test_query_by_tags() {
-        let s = Setup::new();
-        let deadline = s.env.ledger().timestamp() + 3600;
-
-        for i in 1u64..=8 {
-            let mut tags = SdkVec::new(&s.env);
-
-            if i % 2 == 0 {
-                tags.push_back(String::from_str(&s.env, "rust"));
-            }
-            if i % 3 == 0 {
-                tags.push_back(String::from_str(&s.env, "beginner-friendly"));
-            }
-
-            let metadata = EscrowMetadata {
-                repo_id: Some(String::from_str(&s.env, "stellar/test")),
-                issue_id: Some(String::from_str(&s.env, &i.to_string())),
-                bounty_type: Some(String::from_str(&s.env, "feature")),
-                tags,
-                custom_fields: SdkVec::new(&s.env),
-            };
-
-            s.escrow
-                .lock_funds_with_metadata(&s.depositor, &i, &1000, &deadline, &metadata);
-        }
-
-        let rust_bounties =
-            s.escrow
-                .query_escrows_by_tag(&String::from_str(&s.env, "rust"), &0, &20);
-        assert_eq!(rust_bounties.len(), 4); // 2, 4, 6, 8
+    let rust = s
+        .escrow
+        .query_escrows_by_tag(&String::from_str(&s.env, "rust"), &0, &20);
+    assert_eq!(rust.len(), 4); // 2, 4, 6, 8
+    let beginner =
+        s.escrow
+            .query_escrows_by_tag(&String::from_str(&s.env, "beginner-friendly"), &0, &20);
+    assert_eq!(beginner.len(), 2); // 3, 6
+}
+
+#[test]
+fn test_query_page_size_is_capped() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let count = MAX_PAGE_SIZE as u64 + 5;
+    s.env.budget().reset_unlimited();
+    for i in 1..=count {
+        let metadata = s.metadata(7, i, "feature", &["rust"]);
+        s.escrow
+            .lock_funds_with_metadata(&s.depositor, &i, &10, &deadline, &metadata);
     }
 
-    // ============================================================================
-    // Test 4: Query Filters on Large Dataset
-    // ============================================================================
-
-    #[test]
-    fn test_query_filters_on_large_dataset() {
-        let s = Setup::new();
-        let dl_base = s.env.ledger().timestamp();
-
-        for i in 1u64..=15 {
-            let amount = (i as i128) * 1000;
-            let deadline = dl_base + (i * 100);
-            s.escrow.lock_funds(&s.depositor, &i, &amount, &deadline);
-        }
-
-        let amount_results = s.escrow.query_escrows_by_amount(&5000, &10000, &0, &20);
-        assert_eq!(amount_results.len(), 6);
-
-        let dl_results =
-            s.escrow
-                .query_escrows_by_deadline(&(dl_base + 300), &(dl_base + 700), &0, &20);
-        assert_eq!(dl_results.len(), 5);
+    let page = s.escrow.query_escrows_by_repo(&7, &0, &u32::MAX);
+    assert_eq!(page.len(), MAX_PAGE_SIZE);
+    let rest = s
+        .escrow
+        .query_escrows_by_repo(&7, &MAX_PAGE_SIZE, &u32::MAX);
+    assert_eq!(rest.len(), 5);
+    let rust = String::from_str(&s.env, "rust");
+    assert_eq!(
+        s.escrow.query_escrows_by_tag(&rust, &0, &u32::MAX).len(),
+        MAX_PAGE_SIZE
+    );
+}
+
+// ============================================================================
+// Test 4: Query Filters on Large Dataset
+// ============================================================================
+
+#[test]
+fn test_query_filters_on_large_dataset() {
+    let s = Setup::new();
+    let dl_base = s.env.ledger().timestamp();
+
+    for i in 1u64..=15 {
+        let amount = (i as i128) * 1000;
+        let deadline = dl_base + (i * 100);
+        s.escrow.lock_funds(&s.depositor, &i, &amount, &deadline);
     }
 
-    // ============================================================================
-    // Test 5: Serialization Format for Off-Chain Consumers
-    // ============================================================================
-
-    #[test]
-    #[ignore = "Metadata functionality to be implemented - Issue #63"]
-    fn test_metadata_serialization_format() {
-        let s = Setup::new();
-        let bounty_id = 300u64;
-        let amount = 1500i128;
-        let deadline = s.env.ledger().timestamp() + 3600;
-
-        let mut tags = SdkVec::new(&s.env);
-        tags.push_back(String::from_str(&s.env, "rust"));
-        tags.push_back(String::from_str(&s.env, "smart-contract"));
-
-        let mut custom_fields = SdkVec::new(&s.env);
-        custom_fields.push_back((
-            String::from_str(&s.env, "priority"),
-            String::from_str(&s.env, "high"),
-        ));
-
-        let metadata = EscrowMetadata {
-            repo_id: Some(String::from_str(&s.env, "stellar/soroban-examples")),
-            issue_id: Some(String::from_str(&s.env, "42")),
-            bounty_type: Some(String::from_str(&s.env, "bug_fix")),
-            tags,
-            custom_fields,
-        };
+    let amount_results = s.escrow.query_escrows_by_amount(&5000, &10000, &0, &20);
+    assert_eq!(amount_results.len(), 6);
 
+    let dl_results =
         s.escrow
-            .lock_funds_with_metadata(&s.depositor, &bounty_id, &amount, &deadline, &metadata);
-
-        let retrieved = s.escrow.get_escrow_metadata(&bounty_id);
-
-        assert!(retrieved.repo_id.is_some());
-        assert!(retrieved.issue_id.is_some());
-        assert!(retrieved.bounty_type.is_some());
-        assert_eq!(retrieved.tags.len(), 2);
-        assert_eq!(retrieved.custom_fields.len(), 1);
-    }
-
-    // ============================================================================
-    // Test 6: Empty and Edge Case Metadata
-    // ============================================================================
-
-    #[test]
-    #[ignore = "Metadata functionality to be implemented - Issue #63"]
-    fn test_empty_metadata() {
-        let s = Setup::new();
-        let bounty_id = 400u64;
-        let amount = 1000i128;
-        let deadline = s.env.ledger().timestamp() + 3600;
-
-        let metadata = EscrowMetadata {
-            repo_id: None,
-            issue_id: None,
-            bounty_type: None,
-            tags: SdkVec::new(&s.env),
-            custom_fields: SdkVec::new(&s.env),
-        };
-
+            .query_escrows_by_deadline(&(dl_base + 300), &(dl_base + 700), &0, &20);
+    assert_eq!(dl_results.len(), 5);
+}
+
+// ============================================================================
+// Test 5: Tag Validation
+// ============================================================================
+
+#[test]
+fn test_duplicate_tags_are_collapsed() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let metadata = s.metadata(1, 1, "feature", &["rust", "rust", "wasm"]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &1, &1000, &deadline, &metadata);
+
+    assert_eq!(s.escrow.get_metadata(&1).tags.len(), 2);
+    let rust = String::from_str(&s.env, "rust");
+    assert_eq!(s.escrow.query_escrows_by_tag(&rust, &0, &20).len(), 1);
+}
+
+#[test]
+fn test_too_many_tags_rejected_without_locking() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let tags = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k"];
+    let metadata = s.metadata(1, 1, "feature", &tags);
+
+    let res = s
+        .escrow
+        .try_lock_funds_with_metadata(&s.depositor, &1, &1000, &deadline, &metadata);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+    assert!(s.escrow.try_get_escrow_info(&1).is_err());
+    assert_eq!(s.token.balance(&s.escrow.address), 0);
+}
+
+// ============================================================================
+// Test 6: One Active Escrow per Issue
+// ============================================================================
+
+#[test]
+fn test_duplicate_issue_rejected_while_active() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let metadata = s.metadata(42, 7, "bug_fix", &[]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &1, &1000, &deadline, &metadata);
+
+    let res = s
+        .escrow
+        .try_lock_funds_with_metadata(&s.depositor, &2, &1000, &deadline, &metadata);
+    assert_eq!(res, Err(Ok(Error::BountyExists)));
+    assert!(s.escrow.try_get_escrow_info(&2).is_err());
+    assert_eq!(s.token.balance(&s.escrow.address), 1000);
+
+    // Pointing another escrow at the issue through update_metadata is
+    // rejected the same way.
+    s.escrow.lock_funds(&s.depositor, &3, &1000, &deadline);
+    let res =
         s.escrow
-            .lock_funds_with_metadata(&s.depositor, &bounty_id, &amount, &deadline, &metadata);
-
-        let retrieved = s.escrow.get_escrow_metadata(&bounty_id);
-        assert!(retrieved.repo_id.is_none());
-        assert!(retrieved.issue_id.is_none());
-        assert!(retrieved.bounty_type.is_none());
-        assert_eq!(retrieved.tags.len(), 0);
-    }
-
-    #[test]
-    #[ignore = "Tagging functionality not yet implemented in contract"]
-    fn test_tagging_logic_verification() {
-        // This test is for future metadata tagging functionality
-        // Currently the contract doesn't support metadata/tagging
-    }
-} // end of metadata_tagging_tests module
+            .try_update_metadata(&s.admin, &3, &42, &7, &String::from_str(&s.env, "bug_fix"));
+    assert_eq!(res, Err(Ok(Error::BountyExists)));
+
+    // Same issue in another repo is a different issue.
+    let other_repo = s.metadata(43, 7, "bug_fix", &[]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &4, &1000, &deadline, &other_repo);
+}
+
+#[test]
+fn test_issue_can_be_funded_again_after_settlement() {
+    let s = Setup::new();
+    let deadline = s.env.ledger().timestamp() + 3600;
+    let metadata = s.metadata(42, 7, "bug_fix", &[]);
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &1, &1000, &deadline, &metadata);
+    s.escrow.release_funds(&1, &Address::generate(&s.env));
+
+    s.escrow
+        .lock_funds_with_metadata(&s.depositor, &2, &1000, &deadline, &metadata);
+    let results = s.escrow.query_escrows_by_repo(&42, &0, &20);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results.get(0).unwrap().bounty_id, 2);
+}
@@ -0,0 +1,256 @@
+// Tests for milestone escrows: staged lock, per-milestone release, refund
+// and extension, and the escrow status derived from milestone states.
+
+use super::*;
+use milestones::{MilestoneSpec, MilestoneStatus};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct MilestoneSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    depositor: Address,
+}
+
+impl<'a> MilestoneSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        token_admin.mint(&depositor, &1_000_000);
+
+        Self {
+            env,
+            client,
+            token,
+            depositor,
+        }
+    }
+
+    fn spec(&self, amount: i128, deadline: u64, assignee: Option<Address>) -> MilestoneSpec {
+        MilestoneSpec {
+            amount,
+            deadline,
+            assignee,
+        }
+    }
+
+    /// Three stages of 1000 / 2000 / 3000 due at t=2000 / 3000 / 4000.
+    fn lock_three(&self, bounty_id: u64) {
+        let stages = vec![
+            &self.env,
+            self.spec(1_000, 2_000, None),
+            self.spec(2_000, 3_000, None),
+            self.spec(3_000, 4_000, None),
+        ];
+        self.client
+            .lock_funds_with_milestones(&self.depositor, &bounty_id, &stages);
+    }
+}
+
+#[test]
+fn test_lock_with_milestones_sets_totals_and_next_deadline() {
+    let s = MilestoneSetup::new();
+    s.lock_three(1);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.amount, 6_000);
+    assert_eq!(escrow.remaining_amount, 6_000);
+    assert_eq!(escrow.deadline, 2_000);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(s.token.balance(&s.client.address), 6_000);
+
+    let stages = s.client.get_milestones(&1);
+    assert_eq!(stages.len(), 3);
+    assert!(stages.iter().all(|m| m.status == MilestoneStatus::Pending));
+}
+
+#[test]
+fn test_release_milestones_in_stages() {
+    let s = MilestoneSetup::new();
+    let contributor = Address::generate(&s.env);
+    s.lock_three(1);
+
+    s.client.release_milestone(&1, &0, &contributor);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Locked);
+    assert_eq!(escrow.remaining_amount, 5_000);
+    assert_eq!(escrow.deadline, 3_000);
+    assert_eq!(s.token.balance(&contributor), 1_000);
+
+    // Stages need not be released in order.
+    s.client.release_milestone(&1, &2, &contributor);
+    s.client.release_milestone(&1, &1, &contributor);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(s.token.balance(&contributor), 6_000);
+
+    let res = s.client.try_release_milestone(&1, &1, &contributor);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+}
+
+#[test]
+fn test_assignee_restricts_recipient() {
+    let s = MilestoneSetup::new();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let stages = vec![&s.env, s.spec(500, 2_000, Some(alice.clone()))];
+    s.client
+        .lock_funds_with_milestones(&s.depositor, &1, &stages);
+
+    let res = s.client.try_release_milestone(&1, &0, &bob);
+    assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    s.client.release_milestone(&1, &0, &alice);
+    assert_eq!(s.token.balance(&alice), 500);
+}
+
+#[test]
+fn test_refund_only_expired_unreleased_milestones() {
+    let s = MilestoneSetup::new();
+    let contributor = Address::generate(&s.env);
+    s.lock_three(1);
+    s.client.release_milestone(&1, &0, &contributor);
+
+    // Second stage not yet due.
+    s.env.ledger().set_timestamp(2_500);
+    let res = s.client.try_refund_milestone(&1, &1);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+    // First stage already released.
+    let res = s.client.try_refund_milestone(&1, &0);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    s.env.ledger().set_timestamp(3_000);
+    let before = s.token.balance(&s.depositor);
+    s.client.refund_milestone(&1, &1);
+    assert_eq!(s.token.balance(&s.depositor), before + 2_000);
+
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::PartiallyRefunded);
+    assert_eq!(escrow.remaining_amount, 3_000);
+    assert_eq!(escrow.refund_history.len(), 1);
+    assert_eq!(
+        s.client.get_milestones(&1).get(1).unwrap().status,
+        MilestoneStatus::Refunded
+    );
+
+    // The last stage can still be released after a refund.
+    s.client.release_milestone(&1, &2, &contributor);
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_all_refunded_derives_refunded() {
+    let s = MilestoneSetup::new();
+    s.lock_three(1);
+    s.env.ledger().set_timestamp(5_000);
+    for i in 0..3u32 {
+        s.client.refund_milestone(&1, &i);
+    }
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_extend_milestone_defers_refund() {
+    let s = MilestoneSetup::new();
+    s.lock_three(1);
+
+    let res = s.client.try_extend_milestone(&1, &0, &1_500);
+    assert_eq!(res, Err(Ok(Error::InvalidDeadline)));
+    s.client.extend_milestone(&1, &0, &3_500);
+
+    // The escrow deadline follows the earliest pending stage.
+    assert_eq!(s.client.get_escrow_info(&1).deadline, 3_000);
+    s.env.ledger().set_timestamp(3_200);
+    let res = s.client.try_refund_milestone(&1, &0);
+    assert_eq!(res, Err(Ok(Error::DeadlineNotPassed)));
+    s.client.refund_milestone(&1, &1);
+}
+
+#[test]
+fn test_lock_fee_is_spread_over_milestones() {
+    let s = MilestoneSetup::new();
+    let fee_recipient = Address::generate(&s.env);
+    s.client.update_fee_config(
+        &Some(100), // 1% lock fee
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    let stages = vec![
+        &s.env,
+        s.spec(1_050, 2_000, None),
+        s.spec(2_050, 3_000, None),
+    ];
+    s.client
+        .lock_funds_with_milestones(&s.depositor, &1, &stages);
+
+    let escrow = s.client.get_escrow_info(&1);
+    let stages = s.client.get_milestones(&1);
+    // 1% of 3100 is 31; 1050 * 3069 / 3100 floors to 1039.
+    assert_eq!(escrow.amount, 3_069);
+    assert_eq!(stages.get(0).unwrap().amount, 1_039);
+    assert_eq!(stages.get(1).unwrap().amount, 2_030);
+}
+
+#[test]
+fn test_whole_escrow_operations_rejected() {
+    let s = MilestoneSetup::new();
+    let contributor = Address::generate(&s.env);
+    s.lock_three(1);
+
+    let res = s.client.try_release_funds(&1, &contributor);
+    assert_eq!(res, Err(Ok(Error::EscrowLocked)));
+    let res = s.client.try_partial_release(&1, &contributor, &100);
+    assert_eq!(res, Err(Ok(Error::EscrowLocked)));
+    s.env.ledger().set_timestamp(10_000);
+    let res = s.client.try_refund(&1);
+    assert_eq!(res, Err(Ok(Error::EscrowLocked)));
+    assert_eq!(s.token.balance(&s.client.address), 6_000);
+}
+
+#[test]
+fn test_lock_with_milestones_validation() {
+    let s = MilestoneSetup::new();
+    let empty: soroban_sdk::Vec<MilestoneSpec> = soroban_sdk::Vec::new(&s.env);
+    let res = s
+        .client
+        .try_lock_funds_with_milestones(&s.depositor, &1, &empty);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    let zero = vec![&s.env, s.spec(100, 2_000, None), s.spec(0, 3_000, None)];
+    let res = s
+        .client
+        .try_lock_funds_with_milestones(&s.depositor, &1, &zero);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    s.lock_three(1);
+    let again = vec![&s.env, s.spec(100, 2_000, None)];
+    let res = s
+        .client
+        .try_lock_funds_with_milestones(&s.depositor, &1, &again);
+    assert_eq!(res, Err(Ok(Error::BountyExists)));
+
+    let res = s
+        .client
+        .try_release_milestone(&1, &3, &Address::generate(&s.env));
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
@@ -8,10 +8,9 @@
 // two separate contract instances — one per token. This mirrors production usage.
 //
 // Fee accounting note: `FeeConfig` is stored per-instance and is independent
-// across contract instances. The `lock_funds` function transfers the full `amount`
-// to the contract, and `release_funds` transfers the full stored `escrow.amount`
-// to the contributor. Fee configuration is a per-instance setting that governs
-// which rate applies when fee collection is triggered.
+// across contract instances. When fees are enabled, `lock_funds` and
+// `release_funds` deduct the instance's rate and route it to that instance's
+// `fee_recipient`; with fees disabled the full amount moves through untouched.
 //
 // The tests here verify:
 //   1. Fee configuration is independent per contract instance.
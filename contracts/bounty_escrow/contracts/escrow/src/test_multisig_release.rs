@@ -0,0 +1,311 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct MultisigSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    admin: Address,
+    signers: Vec<Address>,
+    contributor: Address,
+}
+
+impl<'a> MultisigSetup<'a> {
+    /// Threshold 1_000, two of three signers required.
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let contributor = Address::generate(&env);
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+
+        let signers = vec![
+            &env,
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        client.update_multisig_config(&1_000, &signers, &2);
+
+        Self {
+            env,
+            client,
+            token,
+            token_admin,
+            admin,
+            signers,
+            contributor,
+        }
+    }
+
+    fn lock(&self, bounty_id: u64, amount: i128) {
+        let depositor = Address::generate(&self.env);
+        self.token_admin.mint(&depositor, &amount);
+        self.client
+            .lock_funds(&depositor, &bounty_id, &amount, &10_000);
+    }
+
+    fn approve(&self, bounty_id: u64, contributor: &Address, signer_idx: u32) {
+        self.client.approve_large_release(
+            &bounty_id,
+            contributor,
+            &self.signers.get(signer_idx).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn test_release_below_threshold_needs_no_approval() {
+    let s = MultisigSetup::new();
+    s.lock(1, 999);
+    s.client.release_funds(&1, &s.contributor);
+    assert_eq!(s.token.balance(&s.contributor), 999);
+}
+
+#[test]
+fn test_release_at_threshold_requires_approvals() {
+    let s = MultisigSetup::new();
+    s.lock(1, 1_000);
+    assert_eq!(
+        s.client.try_release_funds(&1, &s.contributor),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+
+    s.approve(1, &s.contributor, 0);
+    assert_eq!(
+        s.client.try_release_funds(&1, &s.contributor),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+
+    // Re-approving with the same signer does not count twice.
+    s.approve(1, &s.contributor, 0);
+    assert_eq!(
+        s.client.try_release_funds(&1, &s.contributor),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+
+    s.approve(1, &s.contributor, 1);
+    s.client.release_funds(&1, &s.contributor);
+    assert_eq!(s.token.balance(&s.contributor), 1_000);
+}
+
+#[test]
+fn test_approval_bound_to_contributor() {
+    let s = MultisigSetup::new();
+    let other = Address::generate(&s.env);
+    s.lock(1, 5_000);
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+
+    assert_eq!(
+        s.client.try_release_funds(&1, &other),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+
+    // Approving a different contributor discards the earlier approvals.
+    s.approve(1, &other, 2);
+    assert_eq!(
+        s.client.try_release_funds(&1, &s.contributor),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+}
+
+#[test]
+fn test_partial_release_consumes_approval() {
+    let s = MultisigSetup::new();
+    s.lock(1, 5_000);
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+
+    s.client.partial_release(&1, &s.contributor, &2_000);
+    assert_eq!(s.token.balance(&s.contributor), 2_000);
+
+    // The approval was spent; the next large payout needs a fresh round.
+    assert_eq!(
+        s.client.try_partial_release(&1, &s.contributor, &2_000),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    // Small payouts below the threshold still go through.
+    s.client.partial_release(&1, &s.contributor, &500);
+    assert_eq!(s.token.balance(&s.contributor), 2_500);
+}
+
+#[test]
+fn test_removed_signer_approval_not_counted() {
+    let s = MultisigSetup::new();
+    s.lock(1, 5_000);
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+
+    let remaining = vec![&s.env, s.signers.get(1).unwrap(), s.signers.get(2).unwrap()];
+    s.client.update_multisig_config(&1_000, &remaining, &2);
+    assert_eq!(
+        s.client.try_release_funds(&1, &s.contributor),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+}
+
+#[test]
+fn test_batch_release_checks_every_item() {
+    let s = MultisigSetup::new();
+    s.lock(1, 500);
+    s.lock(2, 3_000);
+
+    let items = vec![
+        &s.env,
+        ReleaseFundsItem {
+            bounty_id: 1,
+            contributor: s.contributor.clone(),
+        },
+        ReleaseFundsItem {
+            bounty_id: 2,
+            contributor: s.contributor.clone(),
+        },
+    ];
+    assert_eq!(
+        s.client.try_batch_release_funds(&items),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    assert_eq!(s.token.balance(&s.contributor), 0);
+
+    s.approve(2, &s.contributor, 0);
+    s.approve(2, &s.contributor, 2);
+    assert_eq!(s.client.batch_release_funds(&items), 2);
+    assert_eq!(s.token.balance(&s.contributor), 3_500);
+}
+
+#[test]
+fn test_partial_releases_count_toward_threshold() {
+    let s = MultisigSetup::new();
+    s.lock(1, 5_000);
+
+    s.client.partial_release(&1, &s.contributor, &600);
+    // 600 + 600 reaches the 1_000 threshold.
+    assert_eq!(
+        s.client.try_partial_release(&1, &s.contributor, &600),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+    s.client.partial_release(&1, &s.contributor, &600);
+    // Only what was released since the approval counts.
+    s.client.partial_release(&1, &s.contributor, &999);
+    assert_eq!(
+        s.client.try_partial_release(&1, &s.contributor, &1),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    assert_eq!(s.token.balance(&s.contributor), 2_199);
+}
+
+#[test]
+fn test_claim_requires_approval_at_authorize_and_claim() {
+    let s = MultisigSetup::new();
+    s.lock(1, 2_000);
+    s.client.set_claim_window(&500);
+
+    assert_eq!(
+        s.client
+            .try_authorize_claim(&1, &s.contributor, &DisputeReason::Other),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+    s.client
+        .authorize_claim(&1, &s.contributor, &DisputeReason::Other);
+
+    // Losing the quorum before the claim is paid blocks it.
+    let remaining = vec![&s.env, s.signers.get(1).unwrap(), s.signers.get(2).unwrap()];
+    s.client.update_multisig_config(&1_000, &remaining, &2);
+    assert_eq!(
+        s.client.try_claim(&1),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    s.approve(1, &s.contributor, 2);
+    s.client.claim(&1);
+    assert_eq!(s.token.balance(&s.contributor), 2_000);
+}
+
+#[test]
+fn test_ticket_requires_approval() {
+    let s = MultisigSetup::new();
+    s.lock(1, 2_000);
+
+    assert_eq!(
+        s.client
+            .try_issue_claim_ticket(&1, &s.contributor, &1_500, &5_000),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+    let ticket = s
+        .client
+        .issue_claim_ticket(&1, &s.contributor, &1_500, &5_000);
+    s.client.claim_with_ticket(&ticket);
+    assert_eq!(s.token.balance(&s.contributor), 1_500);
+}
+
+#[test]
+fn test_capability_release_requires_approval() {
+    let s = MultisigSetup::new();
+    s.lock(1, 2_000);
+    let holder = Address::generate(&s.env);
+    let cap = s.client.issue_capability(
+        &s.admin,
+        &holder,
+        &CapabilityAction::Release,
+        &1,
+        &2_000,
+        &5_000,
+        &2,
+    );
+
+    s.client
+        .release_with_capability(&1, &s.contributor, &900, &holder, &cap);
+    assert_eq!(
+        s.client
+            .try_release_with_capability(&1, &s.contributor, &900, &holder, &cap),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    s.approve(1, &s.contributor, 0);
+    s.approve(1, &s.contributor, 1);
+    s.client
+        .release_with_capability(&1, &s.contributor, &900, &holder, &cap);
+    assert_eq!(s.token.balance(&s.contributor), 1_800);
+}
+
+#[test]
+fn test_dispute_payout_requires_approval() {
+    let s = MultisigSetup::new();
+    let depositor = Address::generate(&s.env);
+    let arbiter = Address::generate(&s.env);
+    s.token_admin.mint(&depositor, &2_000);
+    s.client.lock_funds(&depositor, &1, &2_000, &10_000);
+    s.client.set_arbiters(&vec![&s.env, arbiter.clone()]);
+    let id = s
+        .client
+        .open_dispute(&1, &depositor, &s.contributor, &DisputeReason::Other);
+
+    assert_eq!(
+        s.client.try_resolve_dispute(&id, &arbiter, &1_500),
+        Err(Ok(Error::InsufficientApprovals))
+    );
+    // A ruling that pays the contributor little needs no sign-off.
+    s.client.resolve_dispute(&id, &arbiter, &500);
+    assert_eq!(s.token.balance(&s.contributor), 500);
+    assert_eq!(s.token.balance(&depositor), 1_500);
+}
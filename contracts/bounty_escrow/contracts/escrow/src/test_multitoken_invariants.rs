@@ -89,6 +89,9 @@ fn test_inv1_healthy_escrow_passes() {
         status: EscrowStatus::Locked,
         deadline: 999,
         refund_history: soroban_sdk::Vec::new(&Env::default()),
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&Env::default()),
     };
     assert!(multitoken_invariants::check_escrow_sanity(&escrow));
 }
@@ -102,6 +105,9 @@ fn test_inv1_negative_amount_fails() {
         status: EscrowStatus::Locked,
         deadline: 999,
         refund_history: soroban_sdk::Vec::new(&Env::default()),
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&Env::default()),
     };
     assert!(!multitoken_invariants::check_escrow_sanity(&escrow));
 }
@@ -115,6 +121,9 @@ fn test_inv1_remaining_exceeds_amount_fails() {
         status: EscrowStatus::Locked,
         deadline: 999,
         refund_history: soroban_sdk::Vec::new(&Env::default()),
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&Env::default()),
     };
     assert!(!multitoken_invariants::check_escrow_sanity(&escrow));
 }
@@ -128,6 +137,9 @@ fn test_inv1_released_with_nonzero_remaining_fails() {
         status: EscrowStatus::Released,
         deadline: 999,
         refund_history: soroban_sdk::Vec::new(&Env::default()),
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&Env::default()),
     };
     assert!(!multitoken_invariants::check_escrow_sanity(&escrow));
 }
@@ -141,6 +153,9 @@ fn test_inv1_refunded_with_nonzero_remaining_fails() {
         status: EscrowStatus::Refunded,
         deadline: 999,
         refund_history: soroban_sdk::Vec::new(&Env::default()),
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&Env::default()),
     };
     assert!(!multitoken_invariants::check_escrow_sanity(&escrow));
 }
@@ -161,8 +176,8 @@ fn test_inv2_single_lock_invariant_holds() {
 
     // Double-check manually
     s.env.as_contract(&s.escrow.address, || {
-        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env);
-        let balance = multitoken_invariants::get_contract_token_balance(&s.env);
+        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address);
+        let balance = multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address);
         assert_eq!(sum, balance);
         assert_eq!(sum, 1_000);
     });
@@ -180,8 +195,8 @@ fn test_inv2_multiple_locks_invariant_holds() {
     assert!(s.escrow.verify_all_invariants());
 
     s.env.as_contract(&s.escrow.address, || {
-        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env);
-        let balance = multitoken_invariants::get_contract_token_balance(&s.env);
+        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address);
+        let balance = multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address);
         assert_eq!(sum, 6_000);
         assert_eq!(sum, balance);
     });
@@ -201,8 +216,8 @@ fn test_inv2_lock_then_release_invariant_holds() {
     assert!(s.escrow.verify_all_invariants());
 
     s.env.as_contract(&s.escrow.address, || {
-        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env);
-        let balance = multitoken_invariants::get_contract_token_balance(&s.env);
+        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address);
+        let balance = multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address);
         // Only bounty 2 is still active
         assert_eq!(sum, 2_000);
         assert_eq!(sum, balance);
@@ -226,8 +241,8 @@ fn test_inv2_lock_then_refund_invariant_holds() {
     assert!(s.escrow.verify_all_invariants());
 
     s.env.as_contract(&s.escrow.address, || {
-        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env);
-        let balance = multitoken_invariants::get_contract_token_balance(&s.env);
+        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address);
+        let balance = multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address);
         assert_eq!(sum, 2_000);
         assert_eq!(sum, balance);
     });
@@ -247,8 +262,8 @@ fn test_inv2_all_released_contract_empty() {
     assert!(s.escrow.verify_all_invariants());
 
     s.env.as_contract(&s.escrow.address, || {
-        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env);
-        let balance = multitoken_invariants::get_contract_token_balance(&s.env);
+        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address);
+        let balance = multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address);
         assert_eq!(sum, 0);
         assert_eq!(balance, 0);
     });
@@ -268,8 +283,8 @@ fn test_inv2_partial_release_invariant_holds() {
     assert!(s.escrow.verify_all_invariants());
 
     s.env.as_contract(&s.escrow.address, || {
-        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env);
-        let balance = multitoken_invariants::get_contract_token_balance(&s.env);
+        let sum = multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address);
+        let balance = multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address);
         assert_eq!(sum, 7_000);
         assert_eq!(sum, balance);
     });
@@ -313,6 +328,9 @@ fn test_inv4_no_refund_history_is_consistent() {
         status: EscrowStatus::Locked,
         deadline: 999,
         refund_history: soroban_sdk::Vec::new(&Env::default()),
+        lock_fee: 0,
+        release_fee: 0,
+        token: Address::generate(&Env::default()),
     };
     assert!(multitoken_invariants::check_refund_consistency(&escrow));
 }
@@ -342,19 +360,14 @@ fn test_inv5_tampered_index_detects_orphan() {
 
     s.escrow.lock_funds(&s.depositor, &1_u64, &1_000, &deadline);
 
-    // Tamper: add a fake bounty ID to the index without a backing escrow
+    // Tamper: index a fake bounty ID, then remove its backing escrow
+    s.escrow
+        .lock_funds(&s.depositor, &999_u64, &1_000, &deadline);
     s.env.as_contract(&s.escrow.address, || {
-        let mut index: soroban_sdk::Vec<u64> = s
-            .env
-            .storage()
-            .persistent()
-            .get(&DataKey::EscrowIndex)
-            .unwrap_or(soroban_sdk::Vec::new(&s.env));
-        index.push_back(999_u64); // no Escrow(999) exists
         s.env
             .storage()
             .persistent()
-            .set(&DataKey::EscrowIndex, &index);
+            .remove(&DataKey::Escrow(999_u64));
     });
 
     s.env.as_contract(&s.escrow.address, || {
@@ -500,3 +513,125 @@ fn test_invariant_maintained_through_full_lifecycle() {
         assert_eq!(report.token_balance, 7_000);
     });
 }
+
+// ===========================================================================
+// Per-Escrow Assets (INV-2 checked independently for each asset)
+// ===========================================================================
+
+impl<'a> InvSetup<'a> {
+    /// Register and allow a second asset, funding the depositor with it.
+    fn second_asset(&self) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+        let (token, token_admin) = create_token(&self.env, &self.admin);
+        self.escrow.set_asset_allowed(&token.address, &true);
+        token_admin.mint(&self.depositor, &100_000_000);
+        (token, token_admin)
+    }
+}
+
+#[test]
+fn test_lock_in_second_asset_keeps_balances_separate() {
+    let s = InvSetup::new();
+    let (usdc, _) = s.second_asset();
+    let deadline = s.env.ledger().timestamp() + 5_000;
+
+    s.escrow.lock_funds(&s.depositor, &1_u64, &1_000, &deadline);
+    s.escrow
+        .lock_funds_with_asset(&s.depositor, &2_u64, &3_000, &deadline, &usdc.address);
+
+    assert_eq!(s.escrow.get_escrow_info(&1_u64).token, s.token.address);
+    assert_eq!(s.escrow.get_escrow_info(&2_u64).token, usdc.address);
+    assert_eq!(s.escrow.get_balance(), 1_000);
+    assert_eq!(s.escrow.get_asset_balance(&usdc.address), 3_000);
+    assert!(s.escrow.verify_all_invariants());
+
+    s.escrow.release_funds(&2_u64, &s.contributor);
+    assert_eq!(usdc.balance(&s.contributor), 3_000);
+    assert_eq!(s.token.balance(&s.contributor), 0);
+    assert_eq!(s.escrow.get_balance(), 1_000);
+    assert!(s.escrow.verify_all_invariants());
+}
+
+#[test]
+fn test_refund_and_fees_use_escrow_asset() {
+    let s = InvSetup::new();
+    let (usdc, _) = s.second_asset();
+    let fee_recipient = Address::generate(&s.env);
+    s.escrow.update_fee_config(
+        &Some(100),
+        &Some(0),
+        &Some(fee_recipient.clone()),
+        &Some(true),
+    );
+    let deadline = s.env.ledger().timestamp() + 1_000;
+
+    s.escrow
+        .lock_funds_with_asset(&s.depositor, &1_u64, &10_000, &deadline, &usdc.address);
+    assert_eq!(usdc.balance(&fee_recipient), 100);
+    assert_eq!(s.token.balance(&fee_recipient), 0);
+
+    s.env.ledger().set_timestamp(deadline + 1);
+    s.escrow.refund(&1_u64);
+    assert_eq!(usdc.balance(&s.depositor), 100_000_000 - 100);
+    assert_eq!(s.escrow.get_asset_balance(&usdc.address), 0);
+    assert!(s.escrow.verify_all_invariants());
+}
+
+#[test]
+fn test_lock_rejects_asset_not_on_allow_list() {
+    let s = InvSetup::new();
+    let (other, other_admin) = create_token(&s.env, &s.admin);
+    other_admin.mint(&s.depositor, &10_000);
+    let deadline = s.env.ledger().timestamp() + 1_000;
+
+    assert_eq!(
+        s.escrow
+            .try_lock_funds_with_asset(&s.depositor, &1_u64, &1_000, &deadline, &other.address),
+        Err(Ok(Error::AssetNotAllowed))
+    );
+}
+
+#[test]
+fn test_disallowed_asset_blocks_new_locks_only() {
+    let s = InvSetup::new();
+    let (usdc, _) = s.second_asset();
+    let deadline = s.env.ledger().timestamp() + 5_000;
+
+    s.escrow
+        .lock_funds_with_asset(&s.depositor, &1_u64, &2_000, &deadline, &usdc.address);
+    s.escrow.set_asset_allowed(&usdc.address, &false);
+    assert_eq!(s.escrow.get_allowed_assets().len(), 1);
+
+    assert_eq!(
+        s.escrow
+            .try_lock_funds_with_asset(&s.depositor, &2_u64, &2_000, &deadline, &usdc.address),
+        Err(Ok(Error::AssetNotAllowed))
+    );
+
+    // The existing escrow can still be paid out and stays covered by INV-2.
+    s.escrow.release_funds(&1_u64, &s.contributor);
+    assert_eq!(usdc.balance(&s.contributor), 2_000);
+    assert!(s.escrow.verify_all_invariants());
+}
+
+#[test]
+fn test_inv2_detects_mismatch_in_one_asset() {
+    let s = InvSetup::new();
+    let (usdc, usdc_admin) = s.second_asset();
+    let deadline = s.env.ledger().timestamp() + 5_000;
+
+    s.escrow.lock_funds(&s.depositor, &1_u64, &1_000, &deadline);
+    s.escrow
+        .lock_funds_with_asset(&s.depositor, &2_u64, &1_000, &deadline, &usdc.address);
+    assert!(s.escrow.verify_all_invariants());
+
+    // Stray USDC sent straight to the contract breaks INV-2 for that asset
+    // even though the default token is still balanced.
+    usdc_admin.mint(&s.escrow.address, &1);
+    assert!(!s.escrow.verify_all_invariants());
+    s.env.as_contract(&s.escrow.address, || {
+        assert_eq!(
+            multitoken_invariants::sum_active_escrow_balances(&s.env, &s.token.address),
+            multitoken_invariants::get_contract_token_balance(&s.env, &s.token.address)
+        );
+    });
+}
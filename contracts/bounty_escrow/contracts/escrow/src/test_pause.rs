@@ -480,13 +480,13 @@ fn test_rbac_admin_emergency_withdraw_requires_paused_state() {
     escrow_client.emergency_withdraw(&target);
 }
 
-/// emergency_withdraw emits the correct event with admin address and amount.
+/// emergency_withdraw emits the correct event with admin address, asset and amount.
 #[test]
 fn test_rbac_emergency_withdraw_emits_event() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (admin, _, _token_client, escrow_client) = setup_rbac_env(&env);
+    let (admin, _, token_client, escrow_client) = setup_rbac_env(&env);
     let target = Address::generate(&env);
 
     escrow_client.set_paused(&Some(true), &None, &None, &None);
@@ -505,6 +505,7 @@ fn test_rbac_emergency_withdraw_emits_event() {
                 events::EmergencyWithdrawEvent {
                     admin: admin.clone(),
                     recipient: target.clone(),
+                    token: token_client.address.clone(),
                     amount: 500i128,
                     timestamp: env.ledger().timestamp(),
                 }
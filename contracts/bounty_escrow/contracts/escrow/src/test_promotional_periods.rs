@@ -0,0 +1,195 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    fee_recipient: Address,
+    depositor: Address,
+    token_admin: token::StellarAssetClient<'a>,
+}
+
+impl<'a> Setup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let fee_recipient = Address::generate(&env);
+        let depositor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        client.update_fee_config(
+            &Some(500),
+            &Some(300),
+            &Some(fee_recipient.clone()),
+            &Some(true),
+        );
+
+        Self {
+            env,
+            client,
+            fee_recipient,
+            depositor,
+            token_admin,
+        }
+    }
+
+    fn name(&self) -> String {
+        String::from_str(&self.env, "fee-free week")
+    }
+}
+
+#[test]
+fn test_create_promotion_stores_and_lists() {
+    let s = Setup::new();
+    let id = s
+        .client
+        .create_promotional_period(&s.name(), &2_000, &3_000, &0, &0);
+    assert_eq!(id, 1);
+
+    let promo = s.client.get_promotional_period(&id);
+    assert_eq!(promo.start_time, 2_000);
+    assert_eq!(promo.end_time, 3_000);
+    assert!(promo.enabled);
+    assert!(!promo.activated);
+
+    assert_eq!(s.client.get_promotional_periods(&0, &10).len(), 1);
+    assert_eq!(s.client.get_active_promotions().len(), 1);
+}
+
+#[test]
+fn test_effective_rate_uses_lowest_active_promotion() {
+    let s = Setup::new();
+    s.client
+        .create_promotional_period(&s.name(), &2_000, &3_000, &100, &400);
+    s.client
+        .create_promotional_period(&s.name(), &2_500, &3_000, &200, &0);
+
+    // Before any window opens the FeeConfig rates apply.
+    assert_eq!(s.client.get_effective_fee_rates(), (500, 300));
+
+    s.env.ledger().set_timestamp(2_000);
+    // Promotional release rate above the config rate is ignored.
+    assert_eq!(s.client.get_effective_fee_rates(), (100, 300));
+
+    s.env.ledger().set_timestamp(2_600);
+    assert_eq!(s.client.get_effective_fee_rates(), (100, 0));
+
+    // end_time is exclusive
+    s.env.ledger().set_timestamp(3_000);
+    assert_eq!(s.client.get_effective_fee_rates(), (500, 300));
+}
+
+#[test]
+fn test_disabled_promotion_does_not_apply() {
+    let s = Setup::new();
+    let id = s
+        .client
+        .create_promotional_period(&s.name(), &1_000, &3_000, &0, &0);
+    assert_eq!(s.client.get_effective_fee_rates(), (0, 0));
+
+    s.client.set_promotional_period_enabled(&id, &false);
+    assert_eq!(s.client.get_effective_fee_rates(), (500, 300));
+
+    s.client.set_promotional_period_enabled(&id, &true);
+    assert_eq!(s.client.get_effective_fee_rates(), (0, 0));
+}
+
+#[test]
+fn test_fees_disabled_ignores_promotions() {
+    let s = Setup::new();
+    s.client
+        .update_fee_config(&None, &None, &None, &Some(false));
+    s.client
+        .create_promotional_period(&s.name(), &1_000, &3_000, &100, &100);
+    assert_eq!(s.client.get_effective_fee_rates(), (0, 0));
+}
+
+#[test]
+fn test_update_promotion_window_and_rates() {
+    let s = Setup::new();
+    let id = s
+        .client
+        .create_promotional_period(&s.name(), &2_000, &3_000, &100, &100);
+    s.client
+        .update_promotional_period(&id, &Some(1_000), &None, &Some(0), &None);
+
+    let promo = s.client.get_promotional_period(&id);
+    assert_eq!(promo.start_time, 1_000);
+    assert_eq!(promo.lock_fee_rate, 0);
+    assert_eq!(promo.release_fee_rate, 100);
+    assert_eq!(s.client.get_effective_fee_rates(), (0, 100));
+}
+
+#[test]
+fn test_invalid_promotions_rejected() {
+    let s = Setup::new();
+    assert_eq!(
+        s.client
+            .try_create_promotional_period(&s.name(), &3_000, &2_000, &0, &0),
+        Err(Ok(Error::InvalidPromotionPeriod))
+    );
+    assert_eq!(
+        s.client
+            .try_create_promotional_period(&s.name(), &0, &500, &0, &0),
+        Err(Ok(Error::InvalidPromotionPeriod))
+    );
+    assert_eq!(
+        s.client
+            .try_create_promotional_period(&s.name(), &2_000, &3_000, &(MAX_FEE_RATE + 1), &0),
+        Err(Ok(Error::InvalidFeeRate))
+    );
+    assert_eq!(
+        s.client.try_set_promotional_period_enabled(&42, &false),
+        Err(Ok(Error::PromotionNotFound))
+    );
+}
+
+#[test]
+fn test_promotion_limit_enforced() {
+    let s = Setup::new();
+    for _ in 0..MAX_ACTIVE_PROMOTIONS {
+        s.client
+            .create_promotional_period(&s.name(), &2_000, &3_000, &0, &0);
+    }
+    assert_eq!(
+        s.client
+            .try_create_promotional_period(&s.name(), &2_000, &3_000, &0, &0),
+        Err(Ok(Error::TooManyPromotions))
+    );
+}
+
+#[test]
+fn test_lock_activates_then_expires_promotion() {
+    let s = Setup::new();
+    let id = s
+        .client
+        .create_promotional_period(&s.name(), &2_000, &3_000, &0, &0);
+    s.token_admin.mint(&s.depositor, &10_000);
+
+    s.env.ledger().set_timestamp(2_000);
+    s.client.lock_funds(&s.depositor, &1, &1_000, &10_000);
+    assert!(s.client.get_promotional_period(&id).activated);
+
+    s.env.ledger().set_timestamp(3_000);
+    s.client.lock_funds(&s.depositor, &2, &1_000, &10_000);
+    assert_eq!(s.client.get_active_promotions().len(), 0);
+    // Expired promotions are still listed in the full history.
+    assert_eq!(s.client.get_promotional_periods(&0, &10).len(), 1);
+    assert_eq!(
+        s.client.get_fee_config().fee_recipient,
+        s.fee_recipient.clone()
+    );
+}
@@ -0,0 +1,251 @@
+// Tests for `release_split`: paying co-authors of a bounty in one call.
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, Symbol, TryFromVal,
+};
+
+struct SplitSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    fee_recipient: Address,
+    depositor: Address,
+}
+
+impl<'a> SplitSetup<'a> {
+    fn new(release_fee_rate: i128) -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let fee_recipient = Address::generate(&env);
+        let depositor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token = token::Client::new(&env, &token_id);
+        let token_admin = token::StellarAssetClient::new(&env, &token_id);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+        client.update_fee_config(
+            &Some(0),
+            &Some(release_fee_rate),
+            &Some(fee_recipient.clone()),
+            &Some(true),
+        );
+        token_admin.mint(&depositor, &1_000_000);
+
+        Self {
+            env,
+            client,
+            token,
+            fee_recipient,
+            depositor,
+        }
+    }
+}
+
+#[test]
+fn test_split_pays_each_share_and_releases() {
+    let s = SplitSetup::new(0);
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+
+    let shares = vec![&s.env, (a.clone(), 6_000u32), (b.clone(), 4_000u32)];
+    s.client.release_split(&1, &shares, &a);
+
+    assert_eq!(s.token.balance(&a), 6_000);
+    assert_eq!(s.token.balance(&b), 4_000);
+    let escrow = s.client.get_escrow_info(&1);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.remaining_amount, 0);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_split_remainder_goes_to_designated_shareholder() {
+    let s = SplitSetup::new(0);
+    let a = Address::generate(&s.env);
+    let b = Address::generate(&s.env);
+    let c = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &100, &5_000);
+
+    // 3333 bps of 100 floors to 33 each; the 1 left over goes to `b`.
+    let shares = vec![
+        &s.env,
+        (a.clone(), 3_333u32),
+        (b.clone(), 3_333u32),
+        (c.clone(), 3_334u32),
+    ];
+    s.client.release_split(&1, &shares, &b);
+
+    assert_eq!(s.token.balance(&a), 33);
+    assert_eq!(s.token.balance(&b), 34);
+    assert_eq!(s.token.balance(&c), 33);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_split_remainder_to_outside_party() {
+    let s = SplitSetup::new(0);
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    let maintainer = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &101, &5_000);
+
+    let shares = vec![&s.env, (a.clone(), 5_000u32), (b.clone(), 5_000u32)];
+    s.client.release_split(&1, &shares, &maintainer);
+
+    assert_eq!(s.token.balance(&a), 50);
+    assert_eq!(s.token.balance(&b), 50);
+    assert_eq!(s.token.balance(&maintainer), 1);
+}
+
+#[test]
+fn test_split_uses_remaining_after_partial_release() {
+    let s = SplitSetup::new(0);
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    s.client.partial_release(&1, &a, &2_000);
+
+    let shares = vec![&s.env, (a.clone(), 5_000u32), (b.clone(), 5_000u32)];
+    s.client.release_split(&1, &shares, &a);
+
+    assert_eq!(s.token.balance(&a), 6_000);
+    assert_eq!(s.token.balance(&b), 4_000);
+}
+
+#[test]
+fn test_split_applies_release_fee_per_share() {
+    let s = SplitSetup::new(100); // 1%
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+
+    let shares = vec![&s.env, (a.clone(), 7_500u32), (b.clone(), 2_500u32)];
+    s.client.release_split(&1, &shares, &a);
+
+    assert_eq!(s.token.balance(&a), 7_425);
+    assert_eq!(s.token.balance(&b), 2_475);
+    assert_eq!(s.token.balance(&s.fee_recipient), 100);
+    assert_eq!(s.client.get_escrow_info(&1).release_fee, 100);
+}
+
+#[test]
+fn test_split_emits_one_event_per_share() {
+    let s = SplitSetup::new(0);
+    let a = Address::generate(&s.env);
+    let b = Address::generate(&s.env);
+    let c = Address::generate(&s.env);
+    s.client.lock_funds(&s.depositor, &1, &9_000, &5_000);
+
+    let shares = vec![
+        &s.env,
+        (a.clone(), 5_000u32),
+        (b.clone(), 3_000u32),
+        (c.clone(), 2_000u32),
+    ];
+    s.client.release_split(&1, &shares, &a);
+
+    let mut released = soroban_sdk::Vec::new(&s.env);
+    for (contract, topics, data) in s.env.events().all().iter() {
+        if contract != s.client.address {
+            continue;
+        }
+        let topic = Symbol::try_from_val(&s.env, &topics.get(0).unwrap());
+        if topic == Ok(symbol_short!("f_rel")) {
+            let event = events::FundsReleased::try_from_val(&s.env, &data).unwrap();
+            released.push_back((event.recipient, event.amount));
+        }
+    }
+    assert_eq!(
+        released,
+        vec![&s.env, (a, 4_500i128), (b, 2_700i128), (c, 1_800i128)]
+    );
+}
+
+#[test]
+fn test_split_rejects_invalid_shares() {
+    let s = SplitSetup::new(0);
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+
+    let short = vec![&s.env, (a.clone(), 5_000u32), (b.clone(), 4_999u32)];
+    let res = s.client.try_release_split(&1, &short, &a);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let zero = vec![&s.env, (a.clone(), 10_000u32), (b.clone(), 0u32)];
+    let res = s.client.try_release_split(&1, &zero, &a);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let twice = vec![&s.env, (a.clone(), 5_000u32), (a.clone(), 5_000u32)];
+    let res = s.client.try_release_split(&1, &twice, &a);
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    let empty: soroban_sdk::Vec<(Address, u32)> = soroban_sdk::Vec::new(&s.env);
+    let res = s.client.try_release_split(&1, &empty, &a);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Locked);
+    assert_eq!(s.token.balance(&s.client.address), 10_000);
+}
+
+#[test]
+fn test_split_rejects_blacklisted_and_settled() {
+    let s = SplitSetup::new(0);
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+    let shares = vec![&s.env, (a.clone(), 5_000u32), (b.clone(), 5_000u32)];
+
+    s.client.set_blacklist(&b, &true, &None);
+    let res = s.client.try_release_split(&1, &shares, &a);
+    assert_eq!(res, Err(Ok(Error::ParticipantNotAllowed)));
+    s.client.set_blacklist(&b, &false, &None);
+
+    s.client.release_split(&1, &shares, &a);
+    let res = s.client.try_release_split(&1, &shares, &a);
+    assert_eq!(res, Err(Ok(Error::FundsNotLocked)));
+
+    let res = s.client.try_release_split(&2, &shares, &a);
+    assert_eq!(res, Err(Ok(Error::BountyNotFound)));
+}
+
+#[test]
+fn test_split_multisig_approval_covers_exact_shares() {
+    let s = SplitSetup::new(0);
+    let (a, b) = (Address::generate(&s.env), Address::generate(&s.env));
+    let signers = [Address::generate(&s.env), Address::generate(&s.env)];
+    s.client.update_multisig_config(
+        &5_000,
+        &vec![&s.env, signers[0].clone(), signers[1].clone()],
+        &2,
+    );
+    s.client.lock_funds(&s.depositor, &1, &10_000, &5_000);
+
+    let approved = vec![&s.env, (a.clone(), 5_000u32), (b.clone(), 5_000u32)];
+    let skewed = vec![&s.env, (a.clone(), 9_000u32), (b.clone(), 1_000u32)];
+    for signer in signers.iter() {
+        s.client.approve_split_release(&1, &approved, &a, signer);
+    }
+
+    // Same remainder holder, different shares.
+    let res = s.client.try_release_split(&1, &skewed, &a);
+    assert_eq!(res, Err(Ok(Error::InsufficientApprovals)));
+    let res = s.client.try_release_split(&1, &approved, &b);
+    assert_eq!(res, Err(Ok(Error::InsufficientApprovals)));
+
+    // Approving another split starts over.
+    s.client.approve_split_release(&1, &skewed, &a, &signers[0]);
+    let res = s.client.try_release_split(&1, &skewed, &a);
+    assert_eq!(res, Err(Ok(Error::InsufficientApprovals)));
+    s.client.approve_split_release(&1, &skewed, &a, &signers[1]);
+
+    s.client.release_split(&1, &skewed, &a);
+    assert_eq!(s.token.balance(&a), 9_000);
+    assert_eq!(s.token.balance(&b), 1_000);
+}
@@ -0,0 +1,305 @@
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct IndexSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    depositor: Address,
+    contributor: Address,
+}
+
+impl<'a> IndexSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &1_000_000);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+
+        Self {
+            env,
+            client,
+            depositor,
+            contributor,
+        }
+    }
+
+    /// Lock and step past the per-depositor cooldown.
+    fn lock(&self, bounty_id: u64, amount: i128, deadline: u64) {
+        self.client
+            .lock_funds(&self.depositor, &bounty_id, &amount, &deadline);
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + 61);
+    }
+}
+
+fn ids(results: &Vec<EscrowWithId>) -> std::vec::Vec<u64> {
+    results.iter().map(|r| r.bounty_id).collect()
+}
+
+#[test]
+fn test_status_index_follows_transitions() {
+    let s = IndexSetup::new();
+    s.lock(1, 100, 50_000);
+    s.lock(2, 200, 50_000);
+    s.lock(3, 300, 2_000);
+
+    s.client.release_funds(&2, &s.contributor);
+    s.env.ledger().set_timestamp(2_000);
+    s.client.refund(&3);
+
+    let locked = s
+        .client
+        .get_escrow_ids_by_status(&EscrowStatus::Locked, &0, &10);
+    assert_eq!(locked.len(), 1);
+    assert_eq!(locked.get(0).unwrap(), 1);
+
+    let released = s
+        .client
+        .query_escrows_by_status(&EscrowStatus::Released, &0, &10);
+    assert_eq!(ids(&released), [2]);
+    let refunded = s
+        .client
+        .query_escrows_by_status(&EscrowStatus::Refunded, &0, &10);
+    assert_eq!(ids(&refunded), [3]);
+}
+
+#[test]
+fn test_status_index_pagination() {
+    let s = IndexSetup::new();
+    for id in 1..=5 {
+        s.lock(id, 100, 50_000);
+    }
+
+    let page = s
+        .client
+        .get_escrow_ids_by_status(&EscrowStatus::Locked, &1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap(), 2);
+    assert_eq!(page.get(1).unwrap(), 3);
+
+    let tail = s
+        .client
+        .query_escrows_by_status(&EscrowStatus::Locked, &4, &10);
+    assert_eq!(ids(&tail), [5]);
+    let past_end = s
+        .client
+        .get_escrow_ids_by_status(&EscrowStatus::Locked, &9, &10);
+    assert_eq!(past_end.len(), 0);
+}
+
+#[test]
+fn test_deadline_index_is_sorted_and_bounded() {
+    let s = IndexSetup::new();
+    s.lock(1, 100, 9_000);
+    s.lock(2, 100, 5_000);
+    s.lock(3, 100, 7_000);
+    s.lock(4, 100, 5_000);
+
+    let all = s.client.query_escrows_by_deadline(&0, &u64::MAX, &0, &10);
+    assert_eq!(ids(&all), [2, 4, 3, 1]);
+
+    // Bounds are inclusive on both ends.
+    let window = s.client.query_escrows_by_deadline(&5_000, &7_000, &0, &10);
+    assert_eq!(ids(&window), [2, 4, 3]);
+
+    let page = s.client.query_escrows_by_deadline(&5_000, &9_000, &1, &2);
+    assert_eq!(ids(&page), [4, 3]);
+}
+
+#[test]
+fn test_amount_index_is_sorted_and_bounded() {
+    let s = IndexSetup::new();
+    s.lock(1, 500, 50_000);
+    s.lock(2, 100, 50_000);
+    s.lock(3, 300, 50_000);
+
+    let all = s.client.query_escrows_by_amount(&0, &i128::MAX, &0, &10);
+    assert_eq!(ids(&all), [2, 3, 1]);
+
+    let window = s.client.query_escrows_by_amount(&100, &300, &0, &10);
+    assert_eq!(ids(&window), [2, 3]);
+
+    let empty = s.client.query_escrows_by_amount(&301, &499, &0, &10);
+    assert_eq!(empty.len(), 0);
+    let inverted = s.client.query_escrows_by_amount(&500, &100, &0, &10);
+    assert_eq!(inverted.len(), 0);
+}
+
+#[test]
+fn test_renewal_moves_deadline_and_amount_entries() {
+    let s = IndexSetup::new();
+    s.lock(1, 100, 5_000);
+    s.lock(2, 200, 6_000);
+
+    s.client.renew_escrow(&1, &8_000, &400);
+
+    let by_deadline = s.client.query_escrows_by_deadline(&0, &u64::MAX, &0, &10);
+    assert_eq!(ids(&by_deadline), [2, 1]);
+    assert_eq!(
+        s.client
+            .query_escrows_by_deadline(&5_000, &5_000, &0, &10)
+            .len(),
+        0
+    );
+
+    let by_amount = s.client.query_escrows_by_amount(&0, &i128::MAX, &0, &10);
+    assert_eq!(ids(&by_amount), [2, 1]);
+    assert_eq!(by_amount.get(1).unwrap().escrow.amount, 500);
+}
+
+#[test]
+fn test_aggregate_stats_track_every_write() {
+    let s = IndexSetup::new();
+    s.lock(1, 100, 50_000);
+    s.lock(2, 200, 50_000);
+    s.lock(3, 300, 2_000);
+
+    let stats = s.client.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 600);
+    assert_eq!(stats.count_locked, 3);
+
+    s.client.release_funds(&1, &s.contributor);
+    s.env.ledger().set_timestamp(2_000);
+    s.client.refund(&3);
+
+    let stats = s.client.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 200);
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.total_released, 100);
+    assert_eq!(stats.count_released, 1);
+    assert_eq!(stats.total_refunded, 300);
+    assert_eq!(stats.count_refunded, 1);
+}
+
+#[test]
+fn test_failed_write_leaves_indexes_untouched() {
+    let s = IndexSetup::new();
+    s.lock(1, 100, 50_000);
+
+    // Refund before the deadline is rejected and must not move the escrow.
+    assert!(s.client.try_refund(&1).is_err());
+
+    let stats = s.client.get_aggregate_stats();
+    assert_eq!(stats.count_locked, 1);
+    assert_eq!(stats.count_refunded, 0);
+    assert_eq!(
+        s.client
+            .get_escrow_ids_by_status(&EscrowStatus::Locked, &0, &10)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_indexes_split_into_bounded_shards() {
+    let s = IndexSetup::new();
+    s.env.budget().reset_unlimited();
+    // Falling deadlines insert every entry at the front of the index.
+    for id in 1..=150u64 {
+        s.lock(id, id as i128, 1_000_000 - id);
+    }
+
+    let page = s.client.query_escrows_by_deadline(&0, &u64::MAX, &100, &3);
+    assert_eq!(ids(&page), [50, 49, 48]);
+    let page = s.client.query_escrows_by_amount(&60, &200, &70, &5);
+    assert_eq!(ids(&page), [130, 131, 132, 133, 134]);
+    let page = s
+        .client
+        .get_escrow_ids_by_status(&EscrowStatus::Locked, &145, &10);
+    assert_eq!(page, vec![&s.env, 146, 147, 148, 149, 150]);
+
+    for id in 1..=140u64 {
+        s.client.release_funds(&id, &s.contributor);
+    }
+    let locked = s
+        .client
+        .get_escrow_ids_by_status(&EscrowStatus::Locked, &0, &20);
+    assert_eq!(locked.len(), 10);
+    assert_eq!(locked.get(0).unwrap(), 141);
+
+    s.env.as_contract(&s.client.address, || {
+        for kind in [
+            indexes::IndexKind::Deadline,
+            indexes::IndexKind::Status(EscrowStatus::Released),
+            indexes::IndexKind::Status(EscrowStatus::Locked),
+        ] {
+            let dir: indexes::ShardDirectory = s
+                .env
+                .storage()
+                .persistent()
+                .get(&indexes::IndexKey::Directory(kind.clone()))
+                .unwrap();
+            for shard in dir.shards.iter() {
+                let entries: indexes::OrderedIndex = s
+                    .env
+                    .storage()
+                    .persistent()
+                    .get(&indexes::IndexKey::Shard(kind.clone(), shard))
+                    .unwrap();
+                assert!(entries.ids.len() <= indexes::MAX_SHARD_LEN);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_backfill_indexes_pre_upgrade_escrows() {
+    let s = IndexSetup::new();
+    s.lock(1, 100, 50_000);
+    // An escrow written before the indexes existed.
+    let old = crate::legacy::EscrowV0 {
+        depositor: s.depositor.clone(),
+        amount: 700,
+        remaining_amount: 700,
+        status: EscrowStatus::Locked,
+        deadline: 40_000,
+        refund_history: vec![&s.env],
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().set(&DataKey::Escrow(7), &old);
+    });
+    assert_eq!(
+        s.client
+            .get_escrow_ids_by_status(&EscrowStatus::Locked, &0, &10)
+            .len(),
+        1
+    );
+
+    assert_eq!(s.client.backfill_indexes(&vec![&s.env, 7, 1, 99]), 1);
+    let locked = s
+        .client
+        .query_escrows_by_status(&EscrowStatus::Locked, &0, &10);
+    assert_eq!(ids(&locked), [1, 7]);
+    let by_deadline = s.client.query_escrows_by_deadline(&0, &u64::MAX, &0, &10);
+    assert_eq!(ids(&by_deadline), [7, 1]);
+    let stats = s.client.get_aggregate_stats();
+    assert_eq!(stats.total_locked, 800);
+    assert_eq!(stats.count_locked, 2);
+
+    // Already indexed: nothing changes on a second run.
+    assert_eq!(s.client.backfill_indexes(&vec![&s.env, 7]), 0);
+    assert_eq!(s.client.get_aggregate_stats().count_locked, 2);
+
+    let too_many = Vec::from_array(&s.env, [0u64; MAX_BATCH_SIZE as usize + 1]);
+    assert_eq!(
+        s.client.try_backfill_indexes(&too_many),
+        Err(Ok(Error::InvalidBatchSize))
+    );
+}
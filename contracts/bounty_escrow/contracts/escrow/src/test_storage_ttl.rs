@@ -0,0 +1,230 @@
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    token, vec, Address, Env,
+};
+
+struct TtlSetup<'a> {
+    env: Env,
+    client: BountyEscrowContractClient<'a>,
+    depositor: Address,
+    contributor: Address,
+}
+
+impl<'a> TtlSetup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let admin = Address::generate(&env);
+        let depositor = Address::generate(&env);
+        let contributor = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&depositor, &1_000_000);
+
+        let contract_id = env.register_contract(None, BountyEscrowContract);
+        let client = BountyEscrowContractClient::new(&env, &contract_id);
+        client.init(&admin, &token_id);
+
+        Self {
+            env,
+            client,
+            depositor,
+            contributor,
+        }
+    }
+
+    /// Lock and step past the per-depositor cooldown.
+    fn lock(&self, bounty_id: u64, amount: i128) {
+        self.client
+            .lock_funds(&self.depositor, &bounty_id, &amount, &50_000);
+        let now = self.env.ledger().timestamp();
+        self.env.ledger().set_timestamp(now + 61);
+    }
+
+    fn escrow_ttl(&self, bounty_id: u64) -> u32 {
+        self.env.as_contract(&self.client.address, || {
+            self.env
+                .storage()
+                .persistent()
+                .get_ttl(&DataKey::Escrow(bounty_id))
+        })
+    }
+
+    fn advance_ledgers(&self, ledgers: u32) {
+        let seq = self.env.ledger().sequence();
+        self.env.ledger().set_sequence_number(seq + ledgers);
+    }
+}
+
+#[test]
+fn test_lock_extends_escrow_ttl() {
+    let s = TtlSetup::new();
+    s.lock(1, 1_000);
+    assert_eq!(s.escrow_ttl(1), ttl::TTL_EXTEND_TO);
+
+    let expiry = s
+        .client
+        .get_escrows_nearing_expiry(&ttl::TTL_EXTEND_TO, &0, &10)
+        .items;
+    assert_eq!(expiry.len(), 1);
+    assert_eq!(
+        expiry.get(0).unwrap().live_until_ledger,
+        s.env.ledger().sequence() + ttl::TTL_EXTEND_TO
+    );
+}
+
+#[test]
+fn test_bump_escrows_restores_full_ttl() {
+    let s = TtlSetup::new();
+    s.lock(1, 1_000);
+    s.lock(2, 1_000);
+    s.advance_ledgers(1_000);
+    assert_eq!(s.escrow_ttl(1), ttl::TTL_EXTEND_TO - 1_000);
+
+    // Unknown ids are skipped rather than failing the batch.
+    let bumped = s.client.bump_escrows(&vec![&s.env, 1u64, 2u64, 99u64]);
+    assert_eq!(bumped, 2);
+    assert_eq!(s.escrow_ttl(1), ttl::TTL_EXTEND_TO);
+    assert_eq!(s.escrow_ttl(2), ttl::TTL_EXTEND_TO);
+}
+
+#[test]
+fn test_bump_escrows_rejects_oversized_batch() {
+    let s = TtlSetup::new();
+    let mut ids = Vec::new(&s.env);
+    for id in 0..=MAX_BATCH_SIZE as u64 {
+        ids.push_back(id);
+    }
+    let res = s.client.try_bump_escrows(&ids);
+    assert_eq!(res, Err(Ok(Error::InvalidBatchSize)));
+}
+
+#[test]
+fn test_nearing_expiry_respects_window() {
+    let s = TtlSetup::new();
+    s.lock(1, 1_000);
+    s.advance_ledgers(1_000);
+    s.lock(2, 1_000);
+
+    // Escrow 1 expires 1_000 ledgers before escrow 2.
+    let window = ttl::TTL_EXTEND_TO - 500;
+    let expiring = s.client.get_escrows_nearing_expiry(&window, &0, &10);
+    assert_eq!(expiring.items.len(), 1);
+    assert_eq!(expiring.items.get(0).unwrap().bounty_id, 1);
+    assert_eq!(expiring.next_start, None);
+
+    s.client.bump_escrows(&vec![&s.env, 1u64]);
+    let expiring = s.client.get_escrows_nearing_expiry(&window, &0, &10);
+    assert_eq!(expiring.items.len(), 0);
+}
+
+#[test]
+fn test_nearing_expiry_scan_is_bounded_and_resumable() {
+    let s = TtlSetup::new();
+    s.env.budget().reset_unlimited();
+    let total = MAX_QUERY_SCAN as u64 + 5;
+    for id in 1..=total {
+        s.lock(id, 100);
+    }
+    // Only the last five escrows are left unbumped.
+    s.advance_ledgers(1_000);
+    for first in (1..=MAX_QUERY_SCAN as u64).step_by(MAX_BATCH_SIZE as usize) {
+        let mut ids = Vec::new(&s.env);
+        for id in first..first + MAX_BATCH_SIZE as u64 {
+            ids.push_back(id);
+        }
+        s.client.bump_escrows(&ids);
+    }
+    let window = ttl::TTL_EXTEND_TO - 500;
+
+    // The first call only gets through the bumped escrows.
+    let first = s.client.get_escrows_nearing_expiry(&window, &0, &10);
+    assert_eq!(first.items.len(), 0);
+    let start = first.next_start.unwrap();
+
+    let second = s.client.get_escrows_nearing_expiry(&window, &start, &2);
+    let ids: std::vec::Vec<u64> = second.items.iter().map(|e| e.bounty_id).collect();
+    assert_eq!(ids, [total - 4, total - 3]);
+
+    let last = s
+        .client
+        .get_escrows_nearing_expiry(&window, &second.next_start.unwrap(), &10);
+    let ids: std::vec::Vec<u64> = last.items.iter().map(|e| e.bounty_id).collect();
+    assert_eq!(ids, [total - 2, total - 1, total]);
+    assert_eq!(last.next_start, None);
+}
+
+#[test]
+fn test_archive_to_temporary_storage() {
+    let s = TtlSetup::new();
+    s.lock(1, 1_000);
+    s.client.release_funds(&1, &s.contributor);
+    let stats_before = s.client.get_aggregate_stats();
+
+    s.client
+        .archive_escrow_with_mode(&1, &ArchiveMode::Temporary);
+
+    let persistent = s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().has(&DataKey::Escrow(1))
+    });
+    assert!(!persistent);
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Released);
+    assert_eq!(
+        s.client
+            .get_escrow_ids_by_status(&EscrowStatus::Released, &0, &10)
+            .len(),
+        0
+    );
+    assert_eq!(s.client.get_aggregate_stats(), stats_before);
+    assert_eq!(
+        s.client
+            .query_escrows_by_depositor(&s.depositor, &0, &10)
+            .len(),
+        0
+    );
+
+    // The id stays retired even though no persistent record remains.
+    let res = s.client.try_lock_funds(&s.depositor, &1, &1_000, &50_000);
+    assert_eq!(res, Err(Ok(Error::BountyExists)));
+}
+
+#[test]
+fn test_archive_to_summary() {
+    let s = TtlSetup::new();
+    s.lock(1, 1_000);
+    s.client.release_funds(&1, &s.contributor);
+
+    s.client.archive_escrow(&1);
+    s.client.archive_escrow_with_mode(&1, &ArchiveMode::Summary);
+
+    assert_eq!(
+        s.client.try_get_escrow_info(&1),
+        Err(Ok(Error::BountyNotFound))
+    );
+    let summary = s.client.get_escrow_summary(&1);
+    assert_eq!(summary.depositor, s.depositor);
+    assert_eq!(summary.amount, 1_000);
+    assert_eq!(summary.status, EscrowStatus::Released);
+    assert!(summary.completed_at > 0);
+
+    // Repeating the archive on a compacted escrow is a no-op.
+    s.client.archive_escrow_with_mode(&1, &ArchiveMode::Summary);
+}
+
+#[test]
+fn test_archive_rejects_active_escrow() {
+    let s = TtlSetup::new();
+    s.lock(1, 1_000);
+    let res = s
+        .client
+        .try_archive_escrow_with_mode(&1, &ArchiveMode::Temporary);
+    assert_eq!(res, Err(Ok(Error::ArchiveCooldownNotElapsed)));
+    assert_eq!(s.client.get_escrow_info(&1).status, EscrowStatus::Locked);
+}
@@ -42,6 +42,14 @@ pub fn split_amount(amount: i128, fee_rate: i128) -> (i128, i128) {
     (fee, amount - fee)
 }
 
+/// Portion of `amount` owed to a `share_bps` share, floored.
+///
+/// Summing floored shares can fall short of `amount` by at most one unit per
+/// share; callers decide who receives that remainder. Returns 0 on overflow.
+pub fn calculate_share(amount: i128, share_bps: i128) -> i128 {
+    calculate_fee(amount, share_bps)
+}
+
 /// Scale `amount` from `from_decimals` to `to_decimals`.
 ///
 /// Uses floor rounding when scaling down. Returns `None` on overflow.
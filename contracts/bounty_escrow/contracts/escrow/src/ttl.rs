@@ -0,0 +1,77 @@
+// ============================================================================
+// Storage TTL Policy
+//
+// Persistent entries are bumped whenever they are written ("bump on touch"):
+// once an entry's remaining TTL drops below `TTL_THRESHOLD` it is extended to
+// `TTL_EXTEND_TO`. Escrows additionally record the ledger their entry lives
+// until under `DataKey::EscrowExpiry`, since a contract cannot read its own
+// TTL; `get_escrows_nearing_expiry` reports from that record and keepers
+// call `bump_escrows` to keep long-running bounties live.
+//
+// An entry that does get archived by the network is not lost: it can be
+// brought back with a `RestoreFootprint` operation before the next access.
+// ============================================================================
+
+use crate::{indexes::IndexKey, DataKey};
+use soroban_sdk::{Env, IntoVal, Val};
+
+/// Ledgers per day at a ~5 second close time.
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Remaining TTL below which a touched entry is extended.
+pub(crate) const TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+
+/// TTL a touched entry is extended to.
+pub(crate) const TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+/// TTL given to compacted escrows moved to temporary storage.
+pub(crate) const ARCHIVE_TEMPORARY_TTL: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Extend a persistent entry that was just written.
+pub(crate) fn extend<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Extend the contract instance (config, admin, counters).
+pub(crate) fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Extend an escrow record if its tracked expiry is within the threshold.
+pub(crate) fn touch_escrow(env: &Env, bounty_id: u64) {
+    let live_until: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::EscrowExpiry(bounty_id))
+        .unwrap_or(0);
+    if live_until <= env.ledger().sequence().saturating_add(TTL_THRESHOLD) {
+        bump_escrow(env, bounty_id);
+    }
+}
+
+/// Extend an escrow record to the full target and record its new expiry.
+pub(crate) fn bump_escrow(env: &Env, bounty_id: u64) {
+    // Extend unconditionally to the full target so the recorded expiry is
+    // a lower bound even if the entry was last bumped outside the contract.
+    env.storage().persistent().extend_ttl(
+        &DataKey::Escrow(bounty_id),
+        TTL_EXTEND_TO,
+        TTL_EXTEND_TO,
+    );
+    let sequence_key = IndexKey::Sequence(bounty_id);
+    if env.storage().persistent().has(&sequence_key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(&sequence_key, TTL_EXTEND_TO, TTL_EXTEND_TO);
+    }
+    let expiry_key = DataKey::EscrowExpiry(bounty_id);
+    let live_until = env.ledger().sequence().saturating_add(TTL_EXTEND_TO);
+    env.storage().persistent().set(&expiry_key, &live_until);
+    env.storage()
+        .persistent()
+        .extend_ttl(&expiry_key, TTL_EXTEND_TO, TTL_EXTEND_TO);
+}
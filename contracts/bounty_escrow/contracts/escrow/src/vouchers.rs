@@ -0,0 +1,113 @@
+// ============================================================================
+// Off-chain Claim Vouchers
+//
+// The backend signs a `ClaimVoucher` with an ed25519 key instead of sending
+// an `issue_claim_ticket` transaction per winner; the beneficiary redeems it
+// with `redeem_claim_voucher`. The admin registers the public key, stored in
+// instance storage:
+//
+//   VoucherKey::Signer  BytesN<32>  ed25519 public key vouchers must verify against
+//
+// The signed message is the XDR encoding of the voucher. It names the
+// network id stored by `init_with_network` and this contract's address, so a
+// voucher cannot be replayed on another network or another deployment.
+// Replay on this contract is prevented by `grainlify_core::nonce`: each
+// beneficiary has a sequential nonce in the VOUCHER_DOMAIN domain, and a
+// voucher is only valid for the beneficiary's current nonce. A voucher that
+// expires unredeemed would hold back every later one, so the beneficiary or
+// the admin can skip its nonce with `advance_voucher_nonce`.
+// ============================================================================
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, String, Symbol};
+
+use crate::{DataKey, Error};
+
+/// Nonce domain for claim vouchers, kept apart from other signed operations.
+pub const VOUCHER_DOMAIN: Symbol = symbol_short!("voucher");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VoucherKey {
+    Signer,
+}
+
+/// Payout authorization signed off-chain by the voucher signer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimVoucher {
+    pub bounty_id: u64,
+    pub beneficiary: Address,
+    /// Gross amount released from the escrow; the release fee applies.
+    pub amount: i128,
+    /// Last timestamp at which the voucher can be redeemed.
+    pub expires_at: u64,
+    /// Must equal `get_voucher_nonce(beneficiary)` at redemption.
+    pub nonce: u64,
+    pub network_id: String,
+    /// The escrow contract the voucher is valid on.
+    pub contract: Address,
+}
+
+pub(crate) fn signer(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&VoucherKey::Signer)
+}
+
+pub(crate) fn set_signer(env: &Env, public_key: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set(&VoucherKey::Signer, public_key);
+}
+
+pub(crate) fn next_nonce(env: &Env, beneficiary: &Address) -> u64 {
+    grainlify_core::nonce::get_nonce_with_domain(env, beneficiary, VOUCHER_DOMAIN)
+}
+
+/// Consume the beneficiary's current nonce without redeeming anything.
+/// Returns the nonce the next voucher must carry.
+pub(crate) fn skip_nonce(env: &Env, beneficiary: &Address) -> u64 {
+    let nonce = next_nonce(env, beneficiary);
+    grainlify_core::nonce::validate_and_increment_nonce_with_domain(
+        env,
+        beneficiary,
+        VOUCHER_DOMAIN,
+        nonce,
+    )
+    .expect("current nonce is always valid");
+    nonce + 1
+}
+
+/// Check everything about `voucher` except the escrow it pays out of, and
+/// consume the beneficiary's nonce.
+///
+/// A signature that does not verify traps in the host rather than returning
+/// an error, as `ed25519_verify` does not report failure any other way.
+pub(crate) fn verify_and_consume(
+    env: &Env,
+    voucher: &ClaimVoucher,
+    signature: &BytesN<64>,
+) -> Result<(), Error> {
+    let public_key = signer(env).ok_or(Error::NotInitialized)?;
+    let network_id: String = env
+        .storage()
+        .instance()
+        .get(&DataKey::NetworkId)
+        .ok_or(Error::NotInitialized)?;
+    if voucher.network_id != network_id || voucher.contract != env.current_contract_address() {
+        return Err(Error::Unauthorized);
+    }
+    if env.ledger().timestamp() > voucher.expires_at {
+        return Err(Error::TicketExpired);
+    }
+
+    voucher.beneficiary.require_auth();
+    env.crypto()
+        .ed25519_verify(&public_key, &voucher.clone().to_xdr(env), signature);
+
+    grainlify_core::nonce::validate_and_increment_nonce_with_domain(
+        env,
+        &voucher.beneficiary,
+        VOUCHER_DOMAIN,
+        voucher.nonce,
+    )
+    .map_err(|_| Error::TicketAlreadyUsed)
+}
@@ -153,6 +153,7 @@
 #![no_std]
 
 pub mod asset;
+pub mod nonce;
 mod commit_reveal;
 mod governance;
 mod multisig;
@@ -2,23 +2,33 @@
 // FILE: contracts/program-escrow/src/claim_period.rs
 //
 // This module implements claim period support for Issue #66.
+// The functions are exposed as `ProgramEscrowContract` entrypoints.
 //
-// To integrate:
-// - Add `mod claim_period;` in lib.rs
-// - Expose the relevant functions inside the `ProgramEscrowContract` impl block
-//
-// The required DataKey variants are already defined in lib.rs:
+// Storage (claims are persistent, the claim window is instance):
 //
 //   DataKey::PendingClaim(String, u64)
-//     → Maps (program_id, schedule_id) to a ClaimRecord
+//     → Maps (program_id, claim_id) to a ClaimRecord
 //
 //   DataKey::ClaimWindow
 //     → Stores the global claim window duration (in seconds)
 //
+//   ClaimIndexKey::ProgramPending(program_id)
+//     → Vec<u64> of the program's claims still in `Pending`
+//
+//   ClaimIndexKey::RecipientPending(recipient)
+//     → Vec<(String, u64)> of the recipient's claims still in `Pending`
+//
+// Creating a claim reserves its amount out of `remaining_balance`. A claim
+// that passes its deadline unexecuted is marked `Expired` and the amount is
+// returned to `remaining_balance` the next time the program's claims are
+// swept. `create_pending_claim` and every payout check the oldest
+// `AUTO_EXPIRE_SCAN` pending claims, so their cost does not grow with the
+// backlog; `expire_claims` checks up to `MAX_EXPIRE_SCAN` from a given
+// position in the pending list. The pending listings are paged the same way.
 // ============================================================
 
-use crate::{DataKey, ProgramData, PROGRAM_DATA};
-use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+use crate::{payout_log, program_storage, DataKey, ProgramData, ProgramEscrowError};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
 
 /// The status of a pending claim record.
 #[contracttype]
@@ -27,6 +37,15 @@ pub enum ClaimStatus {
     Pending,
     Completed,
     Cancelled,
+    /// The deadline passed; the amount went back to the program balance.
+    Expired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ClaimIndexKey {
+    ProgramPending(String),
+    RecipientPending(Address),
 }
 
 /// Created when an admin approves a payout.
@@ -48,6 +67,14 @@ pub struct ClaimRecord {
 const CLAIM_CREATED: Symbol = symbol_short!("ClmCrtd");
 const CLAIM_EXECUTED: Symbol = symbol_short!("ClmExec");
 const CLAIM_CANCELLED: Symbol = symbol_short!("ClmCncl");
+const CLAIM_EXPIRED: Symbol = symbol_short!("ClmExpd");
+
+/// Pending claims checked by the sweep that runs on claim creation and on
+/// payouts.
+pub const AUTO_EXPIRE_SCAN: u32 = 10;
+
+/// Pending claims one `expire_claims` call checks at most.
+pub const MAX_EXPIRE_SCAN: u32 = 50;
 
 // Storage key for auto-incrementing claim IDs
 const NEXT_CLAIM_ID: Symbol = symbol_short!("NxtClmId");
@@ -62,21 +89,90 @@ fn next_claim_id(env: &Env) -> u64 {
     id
 }
 
-fn get_program(env: &Env) -> ProgramData {
+fn get_program(env: &Env, program_id: &String) -> Result<ProgramData, ProgramEscrowError> {
     env.storage()
-        .instance()
-        .get(&PROGRAM_DATA)
-        .unwrap_or_else(|| panic!("Program not initialized"))
+        .persistent()
+        .get(&DataKey::Program(program_id.clone()))
+        .ok_or(ProgramEscrowError::ProgramNotFound)
 }
 
 fn save_program(env: &Env, data: &ProgramData) {
-    env.storage().instance().set(&PROGRAM_DATA, data);
+    program_storage::save(env, &DataKey::Program(data.program_id.clone()), data);
 }
 
 fn claim_key(program_id: &String, claim_id: u64) -> DataKey {
     DataKey::PendingClaim(program_id.clone(), claim_id)
 }
 
+fn program_pending(env: &Env, program_id: &String) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&ClaimIndexKey::ProgramPending(program_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn recipient_pending(env: &Env, recipient: &Address) -> Vec<(String, u64)> {
+    env.storage()
+        .persistent()
+        .get(&ClaimIndexKey::RecipientPending(recipient.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn add_pending(env: &Env, record: &ClaimRecord) {
+    let mut by_program = program_pending(env, &record.program_id);
+    by_program.push_back(record.claim_id);
+    program_storage::save(
+        env,
+        &ClaimIndexKey::ProgramPending(record.program_id.clone()),
+        &by_program,
+    );
+
+    let mut by_recipient = recipient_pending(env, &record.recipient);
+    by_recipient.push_back((record.program_id.clone(), record.claim_id));
+    program_storage::save(
+        env,
+        &ClaimIndexKey::RecipientPending(record.recipient.clone()),
+        &by_recipient,
+    );
+}
+
+/// Drop a claim that left `Pending` from both indexes.
+fn remove_pending(env: &Env, record: &ClaimRecord) {
+    let mut by_program = program_pending(env, &record.program_id);
+    if let Some(i) = by_program.first_index_of(record.claim_id) {
+        by_program.remove(i);
+        program_storage::save(
+            env,
+            &ClaimIndexKey::ProgramPending(record.program_id.clone()),
+            &by_program,
+        );
+    }
+
+    let mut by_recipient = recipient_pending(env, &record.recipient);
+    if let Some(i) = by_recipient.first_index_of((record.program_id.clone(), record.claim_id)) {
+        by_recipient.remove(i);
+        program_storage::save(
+            env,
+            &ClaimIndexKey::RecipientPending(record.recipient.clone()),
+            &by_recipient,
+        );
+    }
+}
+
+/// The `limit` entries of a pending list at `offset`, with `limit` capped at
+/// `payout_log::MAX_PAGE_SIZE`.
+fn page<T>(pending: &Vec<T>, offset: u32, limit: u32) -> Vec<T> {
+    let start = offset.min(pending.len());
+    let end = start
+        .saturating_add(limit.min(payout_log::MAX_PAGE_SIZE))
+        .min(pending.len());
+    pending.slice(start..end)
+}
+
+fn save_claim(env: &Env, record: &ClaimRecord) {
+    program_storage::save(env, &claim_key(&record.program_id, record.claim_id), record);
+}
+
 // ── Public functions ─────────────────────────────────────────
 // These functions should be called from the ProgramEscrowContract impl.
 
@@ -93,22 +189,26 @@ pub fn create_pending_claim(
     recipient: &Address,
     amount: i128,
     claim_deadline: u64,
-) -> u64 {
-    let mut program = get_program(env);
+) -> Result<u64, ProgramEscrowError> {
+    let mut program = get_program(env, program_id)?;
 
     // Only the authorized payout key can create a claim.
 
     program.authorized_payout_key.require_auth();
 
+    // Return lapsed reservations before checking the balance.
+    release_expired(env, &mut program, 0, AUTO_EXPIRE_SCAN)?;
+
     if amount <= 0 {
-        panic!("Amount must be greater than zero");
+        return Err(ProgramEscrowError::InvalidAmount);
     }
     if amount > program.remaining_balance {
-        panic!("Insufficient escrow balance");
+        return Err(ProgramEscrowError::InsufficientBalance);
     }
     if claim_deadline <= env.ledger().timestamp() {
-        panic!("Claim deadline must be in the future");
+        return Err(ProgramEscrowError::InvalidClaimDeadline);
     }
+    crate::multisig::require_approval(env, program_id, recipient, amount)?;
     // Reserve the funds (deduct from remaining balance)
     program.remaining_balance -= amount;
     save_program(env, &program);
@@ -126,9 +226,8 @@ pub fn create_pending_claim(
         status: ClaimStatus::Pending,
     };
 
-    env.storage()
-        .persistent()
-        .set(&claim_key(program_id, claim_id), &record);
+    save_claim(env, &record);
+    add_pending(env, &record);
 
     env.events().publish(
         (CLAIM_CREATED,),
@@ -141,14 +240,19 @@ pub fn create_pending_claim(
         ),
     );
 
-    claim_id
+    Ok(claim_id)
 }
 
 // Executes (redeems) a pending claim before its deadline.
 //
 // Transfers the reserved escrowed funds to the recipient.
 
-pub fn execute_claim(env: &Env, program_id: &String, claim_id: u64, caller: &Address) {
+pub fn execute_claim(
+    env: &Env,
+    program_id: &String,
+    claim_id: u64,
+    caller: &Address,
+) -> Result<(), ProgramEscrowError> {
     caller.require_auth();
 
     let key = claim_key(program_id, claim_id);
@@ -156,25 +260,25 @@ pub fn execute_claim(env: &Env, program_id: &String, claim_id: u64, caller: &Add
         .storage()
         .persistent()
         .get(&key)
-        .unwrap_or_else(|| panic!("Claim not found"));
+        .ok_or(ProgramEscrowError::ClaimNotFound)?;
     // only the designated recipient can execute their own claim
     if record.recipient != *caller {
-        panic!("Unauthorized: only the claim recipient can execute this claim");
+        return Err(ProgramEscrowError::Unauthorized);
     }
 
     // checks if is still pending.
     match record.status {
         ClaimStatus::Pending => {}
-        _ => panic!("ClaimAlreadyProcessed"),
+        _ => return Err(ProgramEscrowError::ClaimAlreadyProcessed),
     }
 
     // checks if claim deadline has not expired
     if env.ledger().timestamp() > record.claim_deadline {
-        panic!("ClaimExpired");
+        return Err(ProgramEscrowError::ClaimExpired);
     }
 
     // transfer funds to recipient
-    let program = get_program(env);
+    let mut program = get_program(env, program_id)?;
     let token_client = soroban_sdk::token::Client::new(env, &program.token_address);
     token_client.transfer(
         &env.current_contract_address(),
@@ -182,9 +286,14 @@ pub fn execute_claim(env: &Env, program_id: &String, claim_id: u64, caller: &Add
         &record.amount,
     );
 
+    // the reserved amount already left remaining_balance; record the payout.
+    crate::payout_log::append(env, &mut program, &record.recipient, record.amount);
+    save_program(env, &program);
+
     // marks the claim as completed and persist the update.
     record.status = ClaimStatus::Completed;
-    env.storage().persistent().set(&key, &record);
+    save_claim(env, &record);
+    remove_pending(env, &record);
 
     env.events().publish(
         (CLAIM_EXECUTED,),
@@ -195,18 +304,24 @@ pub fn execute_claim(env: &Env, program_id: &String, claim_id: u64, caller: &Add
             record.amount,
         ),
     );
+    Ok(())
 }
 /// Admin cancels a claim pending or expired and returns reserved funds to escrow.
-pub fn cancel_claim(env: &Env, program_id: &String, claim_id: u64, admin: &Address) {
+pub fn cancel_claim(
+    env: &Env,
+    program_id: &String,
+    claim_id: u64,
+    admin: &Address,
+) -> Result<(), ProgramEscrowError> {
     // Only contract admin can cancel
     let stored_admin: Address = env
         .storage()
         .instance()
         .get(&DataKey::Admin)
-        .unwrap_or_else(|| panic!("Not initialized"));
+        .ok_or(ProgramEscrowError::NotInitialized)?;
 
     if *admin != stored_admin {
-        panic!("Unauthorized: only admin can cancel claims");
+        return Err(ProgramEscrowError::Unauthorized);
     }
     admin.require_auth();
 
@@ -215,21 +330,25 @@ pub fn cancel_claim(env: &Env, program_id: &String, claim_id: u64, admin: &Addre
         .storage()
         .persistent()
         .get(&key)
-        .unwrap_or_else(|| panic!("Claim not found"));
+        .ok_or(ProgramEscrowError::ClaimNotFound)?;
 
     // can only cancel Pending claims (completed claims are final)
     match record.status {
         ClaimStatus::Pending => {}
-        _ => panic!("ClaimAlreadyProcessed"),
+        _ => return Err(ProgramEscrowError::ClaimAlreadyProcessed),
     }
     // return reserved funds to escrow balance
-    let mut program = get_program(env);
-    program.remaining_balance += record.amount;
+    let mut program = get_program(env, program_id)?;
+    program.remaining_balance = program
+        .remaining_balance
+        .checked_add(record.amount)
+        .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
     save_program(env, &program);
 
     // mark claim as cancelled
     record.status = ClaimStatus::Cancelled;
-    env.storage().persistent().set(&key, &record);
+    save_claim(env, &record);
+    remove_pending(env, &record);
 
     env.events().publish(
         (CLAIM_CANCELLED,),
@@ -240,33 +359,164 @@ pub fn cancel_claim(env: &Env, program_id: &String, claim_id: u64, admin: &Addre
             record.amount,
         ),
     );
+    Ok(())
+}
+
+/// Marks those of the `max_checked` pending claims from position `offset`
+/// of the program's pending list whose deadline has passed as `Expired`
+/// and returns their amounts to `program.remaining_balance`.
+///
+/// The caller saves `program`. Returns the number of claims expired.
+pub fn release_expired(
+    env: &Env,
+    program: &mut ProgramData,
+    offset: u32,
+    max_checked: u32,
+) -> Result<u32, ProgramEscrowError> {
+    let now = env.ledger().timestamp();
+    let mut expired = 0u32;
+    let pending = program_pending(env, &program.program_id);
+    let start = offset.min(pending.len());
+    let end = start.saturating_add(max_checked).min(pending.len());
+    for claim_id in pending.slice(start..end).iter() {
+        let mut record: ClaimRecord = match env
+            .storage()
+            .persistent()
+            .get(&claim_key(&program.program_id, claim_id))
+        {
+            Some(record) => record,
+            None => continue,
+        };
+        if record.status != ClaimStatus::Pending || now <= record.claim_deadline {
+            continue;
+        }
+
+        program.remaining_balance = program
+            .remaining_balance
+            .checked_add(record.amount)
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
+        record.status = ClaimStatus::Expired;
+        save_claim(env, &record);
+        remove_pending(env, &record);
+        expired += 1;
+
+        env.events().publish(
+            (CLAIM_EXPIRED,),
+            (
+                record.program_id.clone(),
+                claim_id,
+                record.recipient.clone(),
+                record.amount,
+            ),
+        );
+    }
+    Ok(expired)
+}
+
+/// Sweeps expired claims of `program_id` back into its balance, checking
+/// at most `limit` (capped at `MAX_EXPIRE_SCAN`) pending claims from
+/// position `offset`. Expired claims leave the pending list, so the next
+/// unchecked claim is at `offset + limit - expired`.
+pub fn expire_claims(
+    env: &Env,
+    program_id: &String,
+    offset: u32,
+    limit: u32,
+) -> Result<u32, ProgramEscrowError> {
+    let mut program = get_program(env, program_id)?;
+    let expired = release_expired(env, &mut program, offset, limit.min(MAX_EXPIRE_SCAN))?;
+    if expired > 0 {
+        save_program(env, &program);
+    }
+    Ok(expired)
+}
+
+/// Claims of `program_id` that can still be executed, oldest first, from
+/// the `limit` entries (capped at `payout_log::MAX_PAGE_SIZE`) at position
+/// `offset` of the pending list. Claims past their deadline but not yet
+/// swept are left out, so a page can come back short.
+pub fn get_pending_claims(
+    env: &Env,
+    program_id: &String,
+    offset: u32,
+    limit: u32,
+) -> Vec<ClaimRecord> {
+    let now = env.ledger().timestamp();
+    let mut claims = Vec::new(env);
+    let pending = program_pending(env, program_id);
+    for claim_id in page(&pending, offset, limit).iter() {
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<_, ClaimRecord>(&claim_key(program_id, claim_id))
+        {
+            if now <= record.claim_deadline {
+                claims.push_back(record);
+            }
+        }
+    }
+    claims
+}
+
+/// Claims payable to `recipient` that can still be executed, across all
+/// programs, oldest first. Paged like `get_pending_claims`.
+pub fn get_pending_claims_by_recipient(
+    env: &Env,
+    recipient: &Address,
+    offset: u32,
+    limit: u32,
+) -> Vec<ClaimRecord> {
+    let now = env.ledger().timestamp();
+    let mut claims = Vec::new(env);
+    let pending = recipient_pending(env, recipient);
+    for (program_id, claim_id) in page(&pending, offset, limit).iter() {
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<_, ClaimRecord>(&claim_key(&program_id, claim_id))
+        {
+            if now <= record.claim_deadline {
+                claims.push_back(record);
+            }
+        }
+    }
+    claims
 }
 
 /// Returns a claim record by its ID.
 ///
-/// Panics if the claim does not exist.
-pub fn get_claim(env: &Env, program_id: &String, claim_id: u64) -> ClaimRecord {
+/// Returns `ClaimNotFound` if the claim does not exist.
+pub fn get_claim(
+    env: &Env,
+    program_id: &String,
+    claim_id: u64,
+) -> Result<ClaimRecord, ProgramEscrowError> {
     env.storage()
         .persistent()
         .get(&claim_key(program_id, claim_id))
-        .unwrap_or_else(|| panic!("Claim not found"))
+        .ok_or(ProgramEscrowError::ClaimNotFound)
 }
 
 /// Set the global default claim window in seconds.
 /// Admin only.
-pub fn set_claim_window(env: &Env, admin: &Address, window_seconds: u64) {
+pub fn set_claim_window(
+    env: &Env,
+    admin: &Address,
+    window_seconds: u64,
+) -> Result<(), ProgramEscrowError> {
     let stored_admin: Address = env
         .storage()
         .instance()
         .get(&DataKey::Admin)
-        .unwrap_or_else(|| panic!("Not initialized"));
+        .ok_or(ProgramEscrowError::NotInitialized)?;
     if *admin != stored_admin {
-        panic!("Unauthorized");
+        return Err(ProgramEscrowError::Unauthorized);
     }
     admin.require_auth();
     env.storage()
         .instance()
         .set(&DataKey::ClaimWindow, &window_seconds);
+    Ok(())
 }
 
 /// Returns the global default claim window in seconds (default: 86400 = 24h).
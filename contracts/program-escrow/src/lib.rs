@@ -1,3 +1,4 @@
+#![no_std]
 //! # Program Escrow Smart Contract
 //!
 //! A secure escrow system for managing hackathon and program prize pools on Stellar.
@@ -61,7 +62,7 @@
 //! │  │  - total_funds                           │                  │
 //! │  │  - remaining_balance                     │                  │
 //! │  │  - authorized_payout_key                 │                  │
-//! │  │  - payout_history: [PayoutRecord]        │                  │
+//! │  │  - payout_count, total_paid_out          │                  │
 //! │  │  - token_address                         │                  │
 //! │  └──────────────────────────────────────────┘                  │
 //! └─────────────────────────────────────────────────────────────────┘
@@ -96,12 +97,15 @@
 //! let program = escrow_client.init_program(
 //!     &program_id,
 //!     &backend,
-//!     &usdc_token
+//!     &usdc_token,
+//!     &organizer,
+//!     &None, // no initial liquidity
+//!     &None, // no reference hash
 //! );
 //!
 //! // 2. Lock prize pool (10,000 USDC)
 //! let prize_pool = 10_000_0000000; // 10,000 USDC (7 decimals)
-//! escrow_client.lock_program_funds(&prize_pool);
+//! escrow_client.lock_program_funds(&program_id, &prize_pool);
 //!
 //! // 3. After hackathon, distribute prizes
 //! let winners = vec![
@@ -118,7 +122,7 @@
 //!     2_000_0000000,  // 3rd place: 2,000 USDC
 //! ];
 //!
-//! escrow_client.batch_payout(&winners, &prizes);
+//! escrow_client.batch_payout(&program_id, &winners, &prizes);
 //! ```
 //!
 //! ## Event System
@@ -139,8 +143,8 @@
 //! 6. **Token Approval**: Ensure contract has token allowance before locking funds
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env, String, Symbol,
-    Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, vec, Address, Env,
+    String, Symbol, Vec,
 };
 
 // Event types — canonical topic symbols aligned with backend analytics schemas.
@@ -152,30 +156,118 @@ const PAYOUT: Symbol = symbol_short!("Payout");
 const DEPENDENCY_CREATED: Symbol = symbol_short!("dep_add");
 const DEPENDENCY_CLEARED: Symbol = symbol_short!("dep_clr");
 const DEPENDENCY_STATUS_UPDATED: Symbol = symbol_short!("dep_sts");
+const PAUSE_STATE_CHANGED: Symbol = symbol_short!("PauseSt");
+const PROGRAM_REGISTERED: Symbol = symbol_short!("ProgRgd");
+const EMERGENCY_WITHDRAWN: Symbol = symbol_short!("em_wtd");
+const EVENT_VERSION_V2: u32 = 2;
+
+// Event symbols for program release schedules
+const PROG_SCHEDULE_CREATED: Symbol = symbol_short!("prg_sch_c");
+const PROG_SCHEDULE_RELEASED: Symbol = symbol_short!("prg_sch_r");
 
 // Storage keys
-const PROGRAM_DATA: Symbol = symbol_short!("ProgData");
 const FEE_CONFIG: Symbol = symbol_short!("FeeCfg");
+/// Storage key for the program registry (list of all program IDs)
+pub(crate) const PROGRAM_REGISTRY: Symbol = symbol_short!("ProgReg");
+const CONFIG_SNAPSHOT_LIMIT: u32 = 20;
+
+/// Maximum number of programs registered by one `batch_initialize_programs` call.
+const MAX_BATCH_SIZE: u32 = 100;
 
 // Fee rate is stored in basis points (1 basis point = 0.01%)
 // Example: 100 basis points = 1%, 1000 basis points = 10%
-const BASIS_POINTS: i128 = 10_000;
 const MAX_FEE_RATE: i128 = 1_000; // Maximum 10% fee
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FeeConfig {
-    pub lock_fee_rate: i128,      // Fee rate for lock operations (basis points)
-    pub payout_fee_rate: i128,     // Fee rate for payout operations (basis points)
-    pub fee_recipient: Address,    // Address to receive fees
-    pub fee_enabled: bool,         // Global fee enable/disable flag
+    pub lock_fee_rate: i128,    // Fee rate for lock operations (basis points)
+    pub payout_fee_rate: i128,  // Fee rate for payout operations (basis points)
+    pub fee_recipient: Address, // Address to receive fees
+    pub fee_enabled: bool,      // Global fee enable/disable flag
 }
 
-
+/// Errors returned by `ProgramEscrowContract` entrypoints.
+///
+/// Codes are part of the contract's public interface: a failed invocation
+/// surfaces as `Error(Contract, #code)` and indexers key on the number, so
+/// existing codes must never be renumbered or reused. See the README for
+/// the full table.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProgramEscrowError {
+    /// The contract or program has already been initialized
+    AlreadyInitialized = 1,
+    /// The contract has not been initialized (no program data or admin)
+    NotInitialized = 2,
+    /// No program is registered under the given id
+    ProgramNotFound = 3,
+    /// Caller is not allowed to perform this operation
+    Unauthorized = 4,
+    /// Amount is zero or negative
+    InvalidAmount = 5,
+    /// Remaining program balance does not cover the operation
+    InsufficientBalance = 6,
+    /// Funds are paused, or the contract is paused
+    FundsPaused = 7,
+    /// Operation requires the contract to be paused
+    NotPaused = 8,
+    /// Recipients and amounts vectors differ in length
+    BatchLengthMismatch = 9,
+    /// Batch contains no items
+    EmptyBatch = 10,
+    /// An amount or counter calculation overflowed
+    ArithmeticOverflow = 11,
+    /// No release schedule exists with the given id
+    ScheduleNotFound = 12,
+    /// Release schedule has already been released
+    ScheduleAlreadyReleased = 13,
+    /// Release schedule timestamp has not been reached yet
+    ScheduleNotDue = 14,
+    /// Fee rate is negative or above `MAX_FEE_RATE`
+    InvalidFeeRate = 15,
+    /// No configuration snapshot exists with the given id
+    SnapshotNotFound = 16,
+    /// A program dependency has not completed yet
+    DependencyNotSatisfied = 17,
+    /// A program dependency has failed
+    DependencyFailed = 18,
+    /// Dependency is empty, self-referential, duplicated or unregistered
+    InvalidDependency = 19,
+    /// Dependency graph would contain a cycle
+    DependencyCycle = 20,
+    /// No pending claim exists with the given id
+    ClaimNotFound = 21,
+    /// Claim has already been executed, cancelled or expired
+    ClaimAlreadyProcessed = 22,
+    /// Claim deadline has passed
+    ClaimExpired = 23,
+    /// Claim deadline is not in the future
+    InvalidClaimDeadline = 24,
+    /// Batch holds more than `MAX_BATCH_SIZE` items
+    BatchTooLarge = 25,
+    /// Program id is empty
+    InvalidProgramId = 26,
+    /// Payout needs more multisig approvals than it has
+    InsufficientApprovals = 27,
+    /// Multisig approval for the payout has expired
+    ApprovalExpired = 28,
+    /// Multisig threshold, signers or signature count are invalid
+    InvalidMultisigConfig = 29,
+    /// Vesting duration is zero, or cliff or period exceed the duration
+    InvalidVestingSchedule = 30,
+    /// No vesting schedule exists with the given id
+    VestingNotFound = 31,
+    /// Nothing has vested beyond what was already claimed
+    NothingVested = 32,
+    /// Vesting schedule has already been revoked
+    VestingAlreadyRevoked = 33,
+}
 
 // ==================== MONITORING MODULE ====================
 mod monitoring {
-    use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+    use soroban_sdk::{contracttype, Address, Env, String, Symbol};
 
     // Storage keys
     const OPERATION_COUNT: &str = "op_count";
@@ -255,7 +347,10 @@ mod monitoring {
         }
 
         env.events().publish(
-            (Symbol::new(env, "program_escrow"), Symbol::new(env, "monitoring")),
+            (
+                Symbol::new(env, "program_escrow"),
+                Symbol::new(env, "monitoring"),
+            ),
             OperationMetric {
                 operation,
                 caller,
@@ -267,8 +362,25 @@ mod monitoring {
 
     // Emit performance metric
     pub fn emit_performance(env: &Env, function: Symbol, duration: u64) {
+        let count_key = (Symbol::new(env, "perf_cnt"), function.clone());
+        let time_key = (Symbol::new(env, "perf_time"), function.clone());
+        let last_key = (Symbol::new(env, "perf_last"), function.clone());
+
+        let count: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let total: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        env.storage()
+            .persistent()
+            .set(&time_key, &total.saturating_add(duration));
+        env.storage()
+            .persistent()
+            .set(&last_key, &env.ledger().timestamp());
+
         env.events().publish(
-            (Symbol::new(env, "program_escrow"), Symbol::new(env, "performance")),
+            (
+                Symbol::new(env, "program_escrow"),
+                Symbol::new(env, "performance"),
+            ),
             PerformanceMetric {
                 function,
                 duration,
@@ -276,104 +388,77 @@ mod monitoring {
             },
         );
     }
-}
-
-// ── Step 1: Add module declarations near the top of lib.rs ──────────────
-// (after `mod anti_abuse;` and before the contract struct)
-
-mod claim_period;
-pub mod token_math;
-pub use claim_period::{ClaimRecord, ClaimStatus};
-mod error_recovery;
-mod reentrancy_guard;
-#[cfg(test)]
-mod test_claim_period_expiry_cancellation;
-#[cfg(test)]
-mod test_token_math;
-
-// Storage keys
-const PROGRAM_DATA: Symbol = symbol_short!("ProgData");
-const FEE_CONFIG: Symbol = symbol_short!("FeeCfg");
-const CONFIG_SNAPSHOT_LIMIT: u32 = 20;
-
-// Fee rate is stored in basis points (1 basis point = 0.01%)
-// Example: 100 basis points = 1%, 1000 basis points = 10%
-const BASIS_POINTS: i128 = 10_000;
-const MAX_FEE_RATE: i128 = 1_000; // Maximum 10% fee
-
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct FeeConfig {
-    pub lock_fee_rate: i128,    // Fee rate for lock operations (basis points)
-    pub payout_fee_rate: i128,  // Fee rate for payout operations (basis points)
-    pub fee_recipient: Address, // Address to receive fees
-    pub fee_enabled: bool,      // Global fee enable/disable flag
-}
-#[cfg(any())]
-mod reentrancy_tests;
-#[cfg(test)]
-mod test_dispute_resolution;
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ConfigSnapshot {
-    pub id: u64,
-    pub timestamp: u64,
-    pub fee_config: FeeConfig,
-    pub anti_abuse_config: anti_abuse::AntiAbuseConfig,
-    pub anti_abuse_admin: Option<Address>,
-    pub is_paused: bool,
-}
+    // Health check
+    pub fn health_check(env: &Env) -> HealthStatus {
+        let ops: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, OPERATION_COUNT))
+            .unwrap_or(0);
+
+        HealthStatus {
+            is_healthy: true,
+            last_operation: env.ledger().timestamp(),
+            total_operations: ops,
+            contract_version: String::from_str(env, "1.0.0"),
+        }
+    }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum ConfigSnapshotKey {
-    Snapshot(u64),
-    SnapshotIndex,
-    SnapshotCounter,
-}
-// ==================== MONITORING MODULE ====================
-mod monitoring {
-    use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+    // Get analytics
+    pub fn get_analytics(env: &Env) -> Analytics {
+        let ops: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, OPERATION_COUNT))
+            .unwrap_or(0);
+        let users: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, USER_COUNT))
+            .unwrap_or(0);
+        let errors: u64 = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(env, ERROR_COUNT))
+            .unwrap_or(0);
 
-    // Storage keys
-    const OPERATION_COUNT: &str = "op_count";
-    const USER_COUNT: &str = "usr_count";
-    const ERROR_COUNT: &str = "err_count";
+        let error_rate = if ops > 0 {
+            ((errors as u128 * 10_000) / ops as u128) as u32
+        } else {
+            0
+        };
 
-    // Event: Operation metric
-    #[contracttype]
-    #[derive(Clone, Debug)]
-    pub struct OperationMetric {
-        pub operation: Symbol,
-        pub caller: Address,
-        pub timestamp: u64,
-        pub success: bool,
+        Analytics {
+            operation_count: ops,
+            unique_users: users,
+            error_count: errors,
+            error_rate,
+        }
     }
 
-    // Event: Performance metric
-    #[contracttype]
-    #[derive(Clone, Debug)]
-    pub struct PerformanceMetric {
-        pub function: Symbol,
-        pub duration: u64,
-        pub timestamp: u64,
+    // Get state snapshot
+    pub fn get_state_snapshot(env: &Env) -> StateSnapshot {
+        let storage = env.storage().persistent();
+        StateSnapshot {
+            timestamp: env.ledger().timestamp(),
+            total_operations: storage.get(&Symbol::new(env, OPERATION_COUNT)).unwrap_or(0),
+            total_users: storage.get(&Symbol::new(env, USER_COUNT)).unwrap_or(0),
+            total_errors: storage.get(&Symbol::new(env, ERROR_COUNT)).unwrap_or(0),
+        }
     }
 
+    // Get performance stats
+    pub fn get_performance_stats(env: &Env, function_name: Symbol) -> PerformanceStats {
+        let count_key = (Symbol::new(env, "perf_cnt"), function_name.clone());
+        let time_key = (Symbol::new(env, "perf_time"), function_name.clone());
+        let last_key = (Symbol::new(env, "perf_last"), function_name.clone());
 
+        let count: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let total: u64 = env.storage().persistent().get(&time_key).unwrap_or(0);
+        let last: u64 = env.storage().persistent().get(&last_key).unwrap_or(0);
 
-const EVENT_VERSION_V2: u32 = 2;
-const PAUSE_STATE_CHANGED: Symbol = symbol_short!("PauseSt");
-const PROGRAM_REGISTRY: Symbol = symbol_short!("ProgReg");
-const PROGRAM_REGISTERED: Symbol = symbol_short!("ProgRgd");
-
-const SCHEDULES: Symbol = symbol_short!("Scheds");
-const RELEASE_HISTORY: Symbol = symbol_short!("RelHist");
-const NEXT_SCHEDULE_ID: Symbol = symbol_short!("NxtSched");
-const PROGRAM_INDEX: Symbol = symbol_short!("ProgIdx");
-const AUTH_KEY_INDEX: Symbol = symbol_short!("AuthIdx");
-
-        let avg = if count > 0 { total / count } else { 0 };
+        let avg = total.checked_div(count).unwrap_or(0);
 
         PerformanceStats {
             function_name,
@@ -386,16 +471,18 @@ const AUTH_KEY_INDEX: Symbol = symbol_short!("AuthIdx");
 }
 // ==================== END MONITORING MODULE ====================
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProgramInitializedEvent {
-    pub version: u32,
-    pub program_id: String,
-    pub authorized_payout_key: Address,
-    pub token_address: Address,
-    pub total_funds: i128,
-    pub reference_hash: Option<soroban_sdk::Bytes>,
-}
+// ==================== ANTI-ABUSE MODULE ====================
+mod anti_abuse {
+    use crate::DataKey;
+    use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AntiAbuseConfig {
+        pub window_size: u64,     // Window size in seconds
+        pub max_operations: u32,  // Max operations allowed in window
+        pub cooldown_period: u64, // Minimum seconds between operations
+    }
 
     #[contracttype]
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -411,7 +498,6 @@ pub struct ProgramInitializedEvent {
         Config,
         State(Address),
         Whitelist(Address),
-        Admin,
     }
 
     pub fn get_config(env: &Env) -> AntiAbuseConfig {
@@ -434,56 +520,175 @@ pub struct ProgramInitializedEvent {
             .instance()
             .has(&AntiAbuseKey::Whitelist(address))
     }
+
+    pub fn set_whitelist(env: &Env, address: Address, whitelisted: bool) {
+        if whitelisted {
+            env.storage()
+                .instance()
+                .set(&AntiAbuseKey::Whitelist(address), &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&AntiAbuseKey::Whitelist(address));
+        }
+    }
+
+    /// The contract admin; it also manages the anti-abuse configuration.
+    pub fn get_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Admin)
+    }
+
+    pub fn set_admin(env: &Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    pub fn clear_admin(env: &Env) {
+        env.storage().instance().remove(&DataKey::Admin);
+    }
+
+    pub fn check_rate_limit(env: &Env, address: Address) {
+        if is_whitelisted(env, address.clone()) {
+            return;
+        }
+
+        let config = get_config(env);
+        let now = env.ledger().timestamp();
+        let key = AntiAbuseKey::State(address.clone());
+
+        let mut state: AddressState =
+            env.storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(AddressState {
+                    last_operation_timestamp: 0,
+                    window_start_timestamp: now,
+                    operation_count: 0,
+                });
+
+        // 1. Cooldown check
+        if state.last_operation_timestamp > 0
+            && now
+                < state
+                    .last_operation_timestamp
+                    .saturating_add(config.cooldown_period)
+        {
+            env.events().publish(
+                (symbol_short!("abuse"), symbol_short!("cooldown")),
+                (address.clone(), now),
+            );
+            panic!("Operation in cooldown period");
+        }
+
+        // 2. Window check
+        if now
+            >= state
+                .window_start_timestamp
+                .saturating_add(config.window_size)
+        {
+            state.window_start_timestamp = now;
+            state.operation_count = 1;
+        } else {
+            if state.operation_count >= config.max_operations {
+                env.events().publish(
+                    (symbol_short!("abuse"), symbol_short!("limit")),
+                    (address.clone(), now),
+                );
+                panic!("Rate limit exceeded");
+            }
+            state.operation_count += 1;
+        }
+
+        state.last_operation_timestamp = now;
+        env.storage().persistent().set(&key, &state);
+
+        // Extend TTL for state (approx 1 day)
+        env.storage().persistent().extend_ttl(&key, 17280, 17280);
+    }
+}
+// ==================== END ANTI-ABUSE MODULE ====================
+
+mod claim_period;
+pub mod token_math;
+pub use claim_period::{ClaimRecord, ClaimStatus};
+#[allow(dead_code)]
+mod error_recovery;
+mod multisig;
+pub use multisig::{MultisigConfig, PayoutApproval};
+mod payout_log;
+pub use payout_log::PayoutPage;
+mod program_storage;
+mod reentrancy_guard;
+mod vesting;
+pub use vesting::VestingSchedule;
+#[cfg(any())]
+mod reentrancy_tests;
+#[cfg(test)]
+mod test_claim_period_expiry_cancellation;
+#[cfg(test)]
+mod test_dispute_resolution;
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod test_token_math;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProgramData {
-    pub program_id: String,
-    pub total_funds: i128,
-    pub remaining_balance: i128,
-    pub authorized_payout_key: Address,
-    pub payout_history: Vec<PayoutRecord>,
-    pub token_address: Address,  // Token contract address for transfers
-    pub initial_liquidity: i128, // Initial liquidity provided by creator
-    pub reference_hash: Option<soroban_sdk::Bytes>,
+pub struct ConfigSnapshot {
+    pub id: u64,
+    pub timestamp: u64,
+    pub fee_config: FeeConfig,
+    pub anti_abuse_config: anti_abuse::AntiAbuseConfig,
+    pub anti_abuse_admin: Option<Address>,
+    pub is_paused: bool,
 }
 
-/// Storage key type for individual programs
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum DataKey {
-    Program(String),                 // program_id -> ProgramData
-    Admin,                           // Contract Admin
-    ReleaseSchedule(String, u64),    // program_id, schedule_id -> ProgramReleaseSchedule
-    ReleaseHistory(String),          // program_id -> Vec<ProgramReleaseHistory>
-    NextScheduleId(String),          // program_id -> next schedule_id
-    MultisigConfig(String),          // program_id -> MultisigConfig
-    PayoutApproval(String, Address), // program_id, recipient -> PayoutApproval
-    PendingClaim(String, u64),       // (program_id, schedule_id) -> ClaimRecord
-    ClaimWindow,                     // u64 seconds (global config)
-    PauseFlags,                      // PauseFlags struct
-    RateLimitConfig,                 // RateLimitConfig struct
-    ProgramDependencies(String),     // program_id -> Vec<String>
-    DependencyStatus(String),        // dependency_id -> DependencyStatus
+pub enum ConfigSnapshotKey {
+    Snapshot(u64),
+    SnapshotIndex,
+    SnapshotCounter,
 }
 
 // ============================================================================
 // Event Types
 // ============================================================================
 
-/// Event emitted when a program is initialized/registerd
-
-const PROGRAM_REGISTERED: Symbol = symbol_short!("ProgReg");
+/// Event emitted when a program is initialized.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramInitializedEvent {
+    pub version: u32,
+    pub program_id: String,
+    pub authorized_payout_key: Address,
+    pub token_address: Address,
+    pub total_funds: i128,
+    pub reference_hash: Option<soroban_sdk::Bytes>,
+}
 
-// ============================================================================
-// Storage Keys
-// ============================================================================
+/// Event emitted when a program release schedule is created.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramScheduleCreated {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub amount: i128,
+    pub release_timestamp: u64,
+    pub recipient: Address,
+    pub created_by: Address,
+}
 
-/// Storage key for the program registry (list of all program IDs)
-const PROGRAM_REGISTRY: Symbol = symbol_short!("ProgReg");
-
-// ============================================================================
-// Data Structures
-// ============================================================================
+/// Event emitted when a program release schedule is executed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramScheduleReleased {
+    pub program_id: String,
+    pub schedule_id: u64,
+    pub amount: i128,
+    pub recipient: Address,
+    pub released_at: u64,
+    pub released_by: Address,
+    pub release_type: ReleaseType,
+}
 
 // ============================================================================
 // Data Structures
@@ -492,17 +697,19 @@ const PROGRAM_REGISTRY: Symbol = symbol_short!("ProgReg");
 /// Record of an individual payout transaction.
 ///
 /// # Fields
+/// * `sequence` - Position of the payout in the program's payout log
 /// * `recipient` - Address that received the payout
 /// * `amount` - Amount transferred (in token's smallest denomination)
 /// * `timestamp` - Unix timestamp when payout was executed
 ///
 /// # Usage
-/// These records are stored in the payout history to provide a complete
+/// These records are stored in the payout log to provide a complete
 /// audit trail of all prize distributions.
 ///
 /// # Example
 /// ```rust
 /// let record = PayoutRecord {
+///     sequence: 0,
 ///     recipient: winner_address,
 ///     amount: 1000_0000000, // 1000 USDC
 ///     timestamp: env.ledger().timestamp(),
@@ -511,6 +718,7 @@ const PROGRAM_REGISTRY: Symbol = symbol_short!("ProgReg");
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PayoutRecord {
+    pub sequence: u64,
     pub recipient: Address,
     pub amount: i128,
     pub timestamp: u64,
@@ -559,6 +767,7 @@ pub struct ProgramReleaseSchedule {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProgramReleaseHistory {
+    /// Release schedule id, or the vesting id for `ReleaseType::Vesting`.
     pub schedule_id: u64,
     pub program_id: String,
     pub amount: i128,
@@ -568,7 +777,6 @@ pub struct ProgramReleaseHistory {
     pub release_type: ReleaseType,
 }
 
-/// Type of release execution for programs.
 /// Dependency resolution status for a program or external escrow identifier.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -578,29 +786,105 @@ pub enum DependencyStatus {
     Failed,
 }
 
-
+/// Type of release execution for programs.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ReleaseType {
     Automatic, // Released automatically after timestamp
     Manual,    // Released manually by authorized party
+    Vesting,   // Claimed by the recipient from a vesting schedule
 }
-=======
 
-/// Event emitted when a program release schedule is created.
+/// Complete program state and configuration.
+///
+/// # Fields
+/// * `program_id` - Unique identifier for the program/hackathon
+/// * `total_funds` - Total amount of funds locked (cumulative)
+/// * `remaining_balance` - Current available balance for payouts
+/// * `authorized_payout_key` - Address authorized to trigger payouts
+/// * `payout_count` - Number of payouts in the program's payout log
+/// * `total_paid_out` - Net amount paid to recipients across all payouts
+/// * `token_address` - Token contract used for transfers
+/// * `initial_liquidity` - Amount the creator deposited at initialization
+/// * `reference_hash` - Optional off-chain reference (e.g. a terms document)
+///
+/// # Storage
+/// Stored in persistent storage with key `DataKey::Program(program_id)`.
+///
+/// # Invariants
+/// - `remaining_balance <= total_funds` (always)
+/// - `payout_count` and `total_paid_out` only grow; the records themselves
+///   live in the payout log (`get_payout_history`)
+/// - `program_id` and `authorized_payout_key` are immutable after init
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramData {
+    pub program_id: String,
+    pub total_funds: i128,
+    pub remaining_balance: i128,
+    pub authorized_payout_key: Address,
+    pub payout_count: u64,
+    pub total_paid_out: i128,
+    pub token_address: Address,
+    pub initial_liquidity: i128,
+    pub reference_hash: Option<soroban_sdk::Bytes>,
+}
+
+/// Storage key type for individual programs
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProgramScheduleCreated {
 pub enum DataKey {
-    Program(String), // program_id -> ProgramData
-    ReleaseSchedule(String, u64), // program_id, schedule_id -> ProgramReleaseSchedule
-    ReleaseHistory(String), // program_id -> Vec<ProgramReleaseHistory>
-    NextScheduleId(String), // program_id -> next schedule_id
-    ProgramDependencies(String), // program_id -> Vec<dependency_id>
-    DependencyStatus(String), // dependency_id -> DependencyStatus
+    Program(String),                 // program_id -> ProgramData
+    Admin,                           // Contract Admin
+    ReleaseSchedule(String, u64),    // program_id, schedule_id -> ProgramReleaseSchedule
+    ReleaseHistory(String),          // program_id -> Vec<ProgramReleaseHistory>
+    NextScheduleId(String),          // program_id -> next schedule_id
+    MultisigConfig(String),          // program_id -> MultisigConfig
+    PayoutApproval(String, Address), // program_id, recipient -> PayoutApproval
+    PendingClaim(String, u64),       // (program_id, claim_id) -> ClaimRecord
+    ClaimWindow,                     // u64 seconds (global config)
+    PauseFlags,                      // PauseFlags struct
+    IsPaused,                        // Global contract pause state
+    ProgramDependencies(String),     // program_id -> Vec<String>
+    DependencyStatus(String),        // dependency_id -> DependencyStatus
+}
+
+/// One program to register in `batch_initialize_programs`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramInitItem {
+    pub program_id: String,
+    pub authorized_payout_key: Address,
+    pub token_address: Address,
+    pub reference_hash: Option<soroban_sdk::Bytes>,
+}
+
+/// Per-operation pause switches set by the admin with `set_paused`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseFlags {
+    pub lock_paused: bool,
+    pub release_paused: bool,
+    pub refund_paused: bool,
+    pub pause_reason: Option<String>,
+    /// When the first flag was raised; 0 while nothing is paused.
+    pub paused_at: u64,
+}
+
+/// Aggregate view of one program returned by `get_program_aggregate_stats`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramAggregateStats {
+    pub total_funds: i128,
+    pub remaining_balance: i128,
+    pub total_paid_out: i128,
+    pub authorized_payout_key: Address,
+    pub token_address: Address,
+    pub payout_count: u64,
+    pub scheduled_count: u32,
+    pub released_count: u32,
 }
 
->>>>>>> ca030aa2ad2fbae50ef5790bfbd6aa2736ec83cd
 fn vec_contains(values: &Vec<String>, target: &String) -> bool {
     for value in values.iter() {
         if value == *target {
@@ -640,7 +924,10 @@ fn path_exists_to_target(
     visited.push_back(from_program.clone());
     let deps = get_program_dependencies_internal(env, from_program);
     for dep in deps.iter() {
-        if env.storage().instance().has(&DataKey::Program(dep.clone()))
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Program(dep.clone()))
             && path_exists_to_target(env, &dep, target_program, visited)
         {
             return true;
@@ -650,92 +937,43 @@ fn path_exists_to_target(
     false
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProgramInitItem {
-    pub program_id: String,
-    pub schedule_id: u64,
-    pub amount: i128,
-    pub release_timestamp: u64,
-    pub recipient: Address,
-    pub created_by: Address,
-}
-
-/// Event emitted when a program release schedule is executed.
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProgramScheduleReleased {
-    pub program_id: String,
-    pub schedule_id: u64,
-    pub amount: i128,
-    pub recipient: Address,
-    pub released_at: u64,
-    pub released_by: Address,
-    pub release_type: ReleaseType,
+/// Load a program's data.
+fn load_program(env: &Env, program_id: &String) -> Result<ProgramData, ProgramEscrowError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Program(program_id.clone()))
+        .ok_or(ProgramEscrowError::ProgramNotFound)
 }
 
-/// Complete program state and configuration.
-///
-/// # Fields
-/// * `program_id` - Unique identifier for the program/hackathon
-/// * `total_funds` - Total amount of funds locked (cumulative)
-/// * `remaining_balance` - Current available balance for payouts
-/// * `authorized_payout_key` - Address authorized to trigger payouts
-/// * `payout_history` - Complete record of all payouts
-/// * `token_address` - Token contract used for transfers
-///
-/// # Storage
-/// Stored in instance storage with key `PROGRAM_DATA`.
-///
-/// # Invariants
-/// - `remaining_balance <= total_funds` (always)
-/// - `remaining_balance = total_funds - sum(payout_history.amounts)`
-/// - `payout_history` is append-only
-/// - `program_id` and `authorized_payout_key` are immutable after init
-///
-/// # Example
-/// ```rust
-/// let program_data = ProgramData {
-///     program_id: String::from_str(&env, "Hackathon2024"),
-///     total_funds: 10_000_0000000,
-///     remaining_balance: 7_000_0000000,
-///     authorized_payout_key: backend_address,
-///     payout_history: vec![&env],
-///     token_address: usdc_token_address,
-/// };
-/// ```
-
-/// Complete program state and configuration.
-///
-/// # Storage Key
-/// Stored with key: `("Program", program_id)`
-///
-/// # Invariants
-/// - `remaining_balance <= total_funds` (always)
-/// - `remaining_balance = total_funds - sum(payout_history.amounts)`
-/// - `payout_history` is append-only
-/// - `program_id` and `authorized_payout_key` are immutable after registration
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct ProgramData {
-    pub program_id: String,
-    pub total_funds: i128,
-    pub remaining_balance: i128,
-    pub authorized_payout_key: Address,
-    pub payout_history: Vec<PayoutRecord>,
-    pub token_address: Address,
-    pub reference_hash: Option<soroban_sdk::Bytes>,
-}
+/// Helper function to calculate total scheduled amount for a program.
+fn get_program_total_scheduled_amount(
+    env: &Env,
+    program_id: &String,
+) -> Result<i128, ProgramEscrowError> {
+    let next_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::NextScheduleId(program_id.clone()))
+        .unwrap_or(1);
 
-/// Storage key type for individual programs
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum DataKey {
-    Program(String),              // program_id -> ProgramData
-    ReleaseSchedule(String, u64), // program_id, schedule_id -> ProgramReleaseSchedule
-    ReleaseHistory(String),       // program_id -> Vec<ProgramReleaseHistory>
-    NextScheduleId(String),       // program_id -> next schedule_id
-    IsPaused,                     // Global contract pause state
+    let mut total = 0i128;
+    for schedule_id in 1..next_id {
+        if let Some(schedule) =
+            env.storage()
+                .persistent()
+                .get::<_, ProgramReleaseSchedule>(&DataKey::ReleaseSchedule(
+                    program_id.clone(),
+                    schedule_id,
+                ))
+        {
+            if !schedule.released {
+                total = total
+                    .checked_add(schedule.amount)
+                    .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
+            }
+        }
+    }
+    Ok(total)
 }
 
 // ============================================================================
@@ -745,10 +983,6 @@ pub enum DataKey {
 #[contract]
 pub struct ProgramEscrowContract;
 
-// Event symbols for program release schedules
-const PROG_SCHEDULE_CREATED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("prg_sch_c");
-const PROG_SCHEDULE_RELEASED: soroban_sdk::Symbol = soroban_sdk::symbol_short!("prg_sch_r");
-
 #[contractimpl]
 impl ProgramEscrowContract {
     // ========================================================================
@@ -762,146 +996,42 @@ impl ProgramEscrowContract {
     /// * `program_id` - Unique identifier for this program/hackathon
     /// * `authorized_payout_key` - Address authorized to trigger payouts (backend)
     /// * `token_address` - Address of the token contract for transfers (e.g., USDC)
+    /// * `creator` - Address that deposits `initial_liquidity`, if any
+    /// * `initial_liquidity` - Optional amount transferred from `creator` at init
+    /// * `reference_hash` - Optional off-chain reference for the program
     ///
     /// # Returns
     /// * `ProgramData` - The initialized program configuration
     ///
-    /// # Panics
-    /// * If program is already initialized
+    /// # Errors
+    /// * `AlreadyInitialized` - If a program with this id already exists
+    /// * `InvalidProgramId` - If `program_id` is empty
+    /// * `InvalidAmount` - If `initial_liquidity` is negative
     ///
     /// # State Changes
-    /// - Creates ProgramData with zero balances
-    /// - Sets authorized payout key (immutable after this)
-    /// - Initializes empty payout history
+    /// - Creates the program's `ProgramData` entry and adds it to the registry
+    /// - Marks the program's dependency status as pending
     /// - Emits ProgramInitialized event
     ///
     /// # Security Considerations
-    /// - Can only be called once (prevents re-configuration)
-    /// - No authorization required (first-caller initialization)
+    /// - Program ID should be unique and descriptive
     /// - Authorized payout key should be a secure backend service
     /// - Token address must be a valid Stellar Asset Contract
-    /// - Program ID should be unique and descriptive
-    ///
-    /// # Events
-    /// Emits: `ProgramInit(program_id, authorized_payout_key, token_address, 0)`
     ///
     /// # Example
     /// ```rust
-    /// use soroban_sdk::{Address, String, Env};
-    ///
-    /// let program_id = String::from_str(&env, "ETHGlobal2024");
-    /// let backend = Address::from_string("GBACKEND...");
-    /// let usdc = Address::from_string("CUSDC...");
-    ///
     /// let program = escrow_client.init_program(
-    ///     &program_id,
-    ///     &backend,
-    ///     &usdc
+    ///     &String::from_str(&env, "Hackathon2024"),
+    ///     &backend_address,
+    ///     &usdc_token_address,
+    ///     &organizer,
+    ///     &None,
+    ///     &None,
     /// );
-    ///
-    /// println!("Program created: {}", program.program_id);
-    /// ```
-    ///
-    /// # Production Setup
-    /// ```bash
-    /// # Deploy contract
-    /// stellar contract deploy \
-    ///   --wasm target/wasm32-unknown-unknown/release/escrow.wasm \
-    ///   --source ORGANIZER_KEY
-    ///
-    /// # Initialize program
-    /// stellar contract invoke \
-    ///   --id CONTRACT_ID \
-    ///   --source ORGANIZER_KEY \
-    ///   -- init_program \
-    ///   --program_id "Hackathon2024" \
-    ///   --authorized_payout_key GBACKEND... \
-    ///   --token_address CUSDC...
     /// ```
     ///
     /// # Gas Cost
     /// Low - Initial storage writes
-
-    // ========================================================================
-    // Pause and Emergency Functions
-    // ========================================================================
-
-    /// Check if contract is paused (internal helper)
-    fn is_paused_internal(env: &Env) -> bool {
-        env.storage()
-            .instance()
-            .get::<_, bool>(&DataKey::IsPaused)
-            .unwrap_or(false)
-    }
-
-    /// Get pause status (view function)
-    pub fn is_paused(env: Env) -> bool {
-        Self::is_paused_internal(&env)
-    }
-
-    /// Pause the contract (authorized payout key only)
-    /// Prevents new fund locking, payouts, and schedule releases
-    pub fn pause(env: Env) -> () {
-        // For program-escrow, pause is triggered by the first authorized key that calls it
-        // In a multi-program setup, this would need to be per-program
-
-        if Self::is_paused_internal(&env) {
-            return; // Already paused, idempotent
-        }
-
-        env.storage().instance().set(&DataKey::IsPaused, &true);
-
-        env.events()
-            .publish((symbol_short!("pause"),), (env.ledger().timestamp(),));
-    }
-
-    /// Unpause the contract (authorized payout key only)
-    /// Resumes normal operations
-    pub fn unpause(env: Env) -> () {
-        if !Self::is_paused_internal(&env) {
-            return; // Already unpaused, idempotent
-        }
-
-        env.storage().instance().set(&DataKey::IsPaused, &false);
-
-        env.events()
-            .publish((symbol_short!("unpause"),), (env.ledger().timestamp(),));
-    }
-
-    /// Emergency withdrawal for all contract funds (authorized payout key only, only when paused)
-    pub fn emergency_withdraw(env: Env, program_id: String, recipient: Address) -> i128 {
-        // Only allow emergency withdrawal when contract is paused
-        if !Self::is_paused_internal(&env) {
-            panic!("Contract must be paused for emergency withdrawal");
-        }
-
-        // Get program data to access token address
-        let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData =
-            env.storage()
-                .instance()
-                .get(&program_key)
-                .unwrap_or_else(|| {
-                    panic!("Program not found");
-                });
-
-        let client = token::Client::new(&env, &program_data.token_address);
-        let balance = client.balance(&env.current_contract_address());
-
-        if balance <= 0 {
-            return 0; // No funds to withdraw
-        }
-
-        // Transfer all funds to recipient
-        client.transfer(&env.current_contract_address(), &recipient, &balance);
-
-        env.events().publish(
-            (symbol_short!("ewith"),),
-            (balance, env.ledger().timestamp()),
-        );
-
-        balance
-    /// The initialized ProgramData
     pub fn init_program(
         env: Env,
         program_id: String,
@@ -910,7 +1040,7 @@ impl ProgramEscrowContract {
         creator: Address,
         initial_liquidity: Option<i128>,
         reference_hash: Option<soroban_sdk::Bytes>,
-    ) -> ProgramData {
+    ) -> Result<ProgramData, ProgramEscrowError> {
         Self::initialize_program(
             env,
             program_id,
@@ -922,6 +1052,7 @@ impl ProgramEscrowContract {
         )
     }
 
+    /// Same as `init_program`.
     pub fn initialize_program(
         env: Env,
         program_id: String,
@@ -930,37 +1061,45 @@ impl ProgramEscrowContract {
         creator: Address,
         initial_liquidity: Option<i128>,
         reference_hash: Option<soroban_sdk::Bytes>,
-        };
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        let start = env.ledger().timestamp();
 
-        // Initialize fee config with zero fees (disabled by default)
-        let fee_config = FeeConfig {
-            lock_fee_rate: 0,
-            payout_fee_rate: 0,
-            fee_recipient: authorized_payout_key.clone(),
-            fee_enabled: false,
-        };
-        env.storage().instance().set(&FEE_CONFIG, &fee_config);
+        // Apply rate limiting to the authorized payout key
+        anti_abuse::check_rate_limit(&env, authorized_payout_key.clone());
 
-        // Store program data
-        env.storage().instance().set(&program_key, &program_data);
-        // Store program data
-        env.storage().instance().set(&DataKey::Program(program_id.clone()), &program_data);
-        let empty_dependencies: Vec<String> = vec![&env];
-        env.storage()
-            .instance()
-            .set(&DataKey::ProgramDependencies(program_id.clone()), &empty_dependencies);
-        env.storage().instance().set(
-            &DataKey::DependencyStatus(program_id.clone()),
-            &DependencyStatus::Pending,
-        );
-        env.storage().instance().set(&PROGRAM_DATA, &program_data);
-        env.storage()
-            .instance()
-            .set(&SCHEDULES, &Vec::<ProgramReleaseSchedule>::new(&env));
-        env.storage()
-            .instance()
-            .set(&RELEASE_HISTORY, &Vec::<ProgramReleaseHistory>::new(&env));
-        env.storage().instance().set(&NEXT_SCHEDULE_ID, &1_u64);
+        if program_id.is_empty() {
+            return Err(ProgramEscrowError::InvalidProgramId);
+        }
+        let program_key = DataKey::Program(program_id.clone());
+        if env.storage().persistent().has(&program_key) {
+            return Err(ProgramEscrowError::AlreadyInitialized);
+        }
+
+        let initial_liquidity = initial_liquidity.unwrap_or(0);
+        if initial_liquidity < 0 {
+            return Err(ProgramEscrowError::InvalidAmount);
+        }
+        if initial_liquidity > 0 {
+            creator.require_auth();
+            token::Client::new(&env, &token_address).transfer(
+                &creator,
+                &env.current_contract_address(),
+                &initial_liquidity,
+            );
+        }
+
+        let program_data = ProgramData {
+            program_id: program_id.clone(),
+            total_funds: initial_liquidity,
+            remaining_balance: initial_liquidity,
+            authorized_payout_key: authorized_payout_key.clone(),
+            payout_count: 0,
+            total_paid_out: 0,
+            token_address: token_address.clone(),
+            initial_liquidity,
+            reference_hash: reference_hash.clone(),
+        };
+        Self::register_program(&env, &program_data);
 
         // Emit ProgramInitialized event
         env.events().publish(
@@ -968,133 +1107,114 @@ impl ProgramEscrowContract {
             ProgramInitializedEvent {
                 version: EVENT_VERSION_V2,
                 program_id,
-                authorized_payout_key,
+                authorized_payout_key: authorized_payout_key.clone(),
                 token_address,
-                total_funds,
+                total_funds: initial_liquidity,
                 reference_hash,
             },
         );
 
-        program_data
+        // Track successful operation
+        monitoring::track_operation(&env, symbol_short!("init_prg"), authorized_payout_key, true);
+
+        // Track performance
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("init_prg"), duration);
+
+        Ok(program_data)
     }
 
     /// Batch-initialize multiple programs in one transaction (all-or-nothing).
+    ///
+    /// Programs start with no funds; lock them with `lock_program_funds`.
+    ///
+    /// # Returns
+    /// * `u32` - Number of programs registered
+    ///
+    /// # Errors
+    /// * `EmptyBatch` - If `items` is empty
+    /// * `BatchTooLarge` - If `items` holds more than `MAX_BATCH_SIZE` programs
+    /// * `InvalidProgramId` - If a program id is empty
+    /// * `AlreadyInitialized` - If a program id repeats or is already registered
     pub fn batch_initialize_programs(
         env: Env,
         items: Vec<ProgramInitItem>,
-    ) -> Result<u32, BatchError> {
-        let batch_size = items.len() as u32;
-        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
-            return Err(BatchError::InvalidBatchSize);
+    ) -> Result<u32, ProgramEscrowError> {
+        let batch_size = items.len();
+        if batch_size == 0 {
+            return Err(ProgramEscrowError::EmptyBatch);
+        }
+        if batch_size > MAX_BATCH_SIZE {
+            return Err(ProgramEscrowError::BatchTooLarge);
         }
         for i in 0..batch_size {
+            let program_id = items.get(i).unwrap().program_id;
+            if program_id.is_empty() {
+                return Err(ProgramEscrowError::InvalidProgramId);
+            }
             for j in (i + 1)..batch_size {
-                if items.get(i).unwrap().program_id == items.get(j).unwrap().program_id {
-                    return Err(BatchError::DuplicateProgramId);
+                if program_id == items.get(j).unwrap().program_id {
+                    return Err(ProgramEscrowError::AlreadyInitialized);
                 }
             }
-        }
-        for i in 0..batch_size {
-            let program_key = DataKey::Program(items.get(i).unwrap().program_id.clone());
-            if env.storage().instance().has(&program_key) {
-                return Err(BatchError::ProgramAlreadyExists);
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Program(program_id))
+            {
+                return Err(ProgramEscrowError::AlreadyInitialized);
             }
         }
 
-        // Update registry
-        let mut registry: Vec<String> = env
-            .storage()
-            .instance()
-            .get(&PROGRAM_REGISTRY)
-            .unwrap_or(vec![&env]);
-
-        for i in 0..batch_size {
-            let item = items.get(i).unwrap();
-            let program_id = item.program_id.clone();
-            let authorized_payout_key = item.authorized_payout_key.clone();
-            let token_address = item.token_address.clone();
-
-            if program_id.is_empty() {
-                return Err(BatchError::InvalidBatchSize);
-            }
-
+        for item in items.iter() {
             let program_data = ProgramData {
-                program_id: program_id.clone(),
+                program_id: item.program_id.clone(),
                 total_funds: 0,
                 remaining_balance: 0,
-                authorized_payout_key: authorized_payout_key.clone(),
-                payout_history: vec![&env],
-                token_address: token_address.clone(),
+                authorized_payout_key: item.authorized_payout_key.clone(),
+                payout_count: 0,
+                total_paid_out: 0,
+                token_address: item.token_address.clone(),
                 initial_liquidity: 0,
                 reference_hash: item.reference_hash.clone(),
             };
-            let program_key = DataKey::Program(program_id.clone());
-            env.storage().instance().set(&program_key, &program_data);
-
-            if i == 0 {
-                let fee_config = FeeConfig {
-                    lock_fee_rate: 0,
-                    payout_fee_rate: 0,
-                    fee_recipient: authorized_payout_key.clone(),
-                    fee_enabled: false,
-                };
-                env.storage().instance().set(&FEE_CONFIG, &fee_config);
-            }
-
-            let multisig_config = MultisigConfig {
-                threshold_amount: i128::MAX,
-                signers: vec![&env],
-                required_signatures: 0,
-            };
-            env.storage().persistent().set(
-                &DataKey::MultisigConfig(program_id.clone()),
-                &multisig_config,
-            );
+            Self::register_program(&env, &program_data);
 
-            registry.push_back(program_id.clone());
             env.events().publish(
                 (PROGRAM_REGISTERED,),
-                (program_id, authorized_payout_key, token_address, 0i128),
+                (
+                    item.program_id,
+                    item.authorized_payout_key,
+                    item.token_address,
+                    0i128,
+                ),
             );
         }
-        env.storage().instance().set(&PROGRAM_REGISTRY, &registry);
 
-        // Emit registration event
-        env.events().publish(
-            (PROGRAM_REGISTERED,),
-            (program_id, authorized_payout_key, token_address, 0i128),
-        );
-
-        // Track successful operation
-        monitoring::track_operation(&env, symbol_short!("init_prg"), caller, true);
-
-        // Track performance
-        let duration = env.ledger().timestamp().saturating_sub(start);
-        monitoring::emit_performance(&env, symbol_short!("init_prg"), duration);
-
-        program_data
+        Ok(batch_size)
     }
 
-    /// Calculate fee using floor rounding. Delegates to `token_math::calculate_fee`.
-    fn calculate_fee(amount: i128, fee_rate: i128) -> i128 {
-        token_math::calculate_fee(amount, fee_rate)
-    }
+    /// Store a new program, add it to the registry and mark its dependency
+    /// status pending.
+    fn register_program(env: &Env, program_data: &ProgramData) {
+        let program_id = program_data.program_id.clone();
+        program_storage::save(env, &DataKey::Program(program_id.clone()), program_data);
 
-    /// Get fee configuration (internal helper)
-    fn get_fee_config_internal(env: &Env) -> FeeConfig {
-        env.storage()
-            .instance()
-            .get(&FEE_CONFIG)
-            .unwrap_or_else(|| FeeConfig {
-                lock_fee_rate: 0,
-                payout_fee_rate: 0,
-                fee_recipient: env.current_contract_address(),
-                fee_enabled: false,
-            })
+        let mut registry = program_storage::registry(env);
+        registry.push_back(program_id.clone());
+        program_storage::save(env, &PROGRAM_REGISTRY, &registry);
+
+        let empty_dependencies: Vec<String> = vec![env];
+        env.storage().instance().set(
+            &DataKey::ProgramDependencies(program_id.clone()),
+            &empty_dependencies,
+        );
+        env.storage().instance().set(
+            &DataKey::DependencyStatus(program_id),
+            &DependencyStatus::Pending,
+        );
     }
 
-    /// Lock initial funds into the program escrow
-    ///
     /// Lists all registered program IDs in the contract.
     ///
     /// # Returns
@@ -1108,10 +1228,7 @@ impl ProgramEscrowContract {
     /// }
     /// ```
     pub fn list_programs(env: Env) -> Vec<String> {
-        env.storage()
-            .instance()
-            .get(&PROGRAM_REGISTRY)
-            .unwrap_or(vec![&env])
+        program_storage::registry(&env)
     }
 
     /// Checks if a program exists.
@@ -1123,18 +1240,36 @@ impl ProgramEscrowContract {
     /// * `bool` - True if program exists, false otherwise
     pub fn program_exists(env: Env, program_id: String) -> bool {
         let program_key = DataKey::Program(program_id);
-        env.storage().instance().has(&program_key)
+        env.storage().persistent().has(&program_key)
+    }
+
+    /// Gets the total number of programs registered.
+    ///
+    /// # Returns
+    /// * `u32` - Count of registered programs
+    pub fn get_program_count(env: Env) -> u32 {
+        program_storage::registry(&env).len()
     }
 
-    fn assert_dependencies_satisfied(env: &Env, program_id: &String) {
+    // ========================================================================
+    // Program Dependencies
+    // ========================================================================
+
+    fn assert_dependencies_satisfied(
+        env: &Env,
+        program_id: &String,
+    ) -> Result<(), ProgramEscrowError> {
         let dependencies = get_program_dependencies_internal(env, program_id);
         for dependency_id in dependencies.iter() {
             match dependency_status_internal(env, &dependency_id) {
                 DependencyStatus::Completed => {}
-                DependencyStatus::Pending => panic!("Dependency not satisfied"),
-                DependencyStatus::Failed => panic!("Dependency failed"),
+                DependencyStatus::Pending => {
+                    return Err(ProgramEscrowError::DependencyNotSatisfied)
+                }
+                DependencyStatus::Failed => return Err(ProgramEscrowError::DependencyFailed),
             }
         }
+        Ok(())
     }
 
     /// Defines explicit dependencies for a program.
@@ -1148,46 +1283,40 @@ impl ProgramEscrowContract {
         env: Env,
         program_id: String,
         dependency_ids: Vec<String>,
-    ) -> Vec<String> {
-        let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
-
+    ) -> Result<Vec<String>, ProgramEscrowError> {
+        let program_data = load_program(&env, &program_id)?;
         program_data.authorized_payout_key.require_auth();
 
         let old_dependencies = get_program_dependencies_internal(&env, &program_id);
         let mut validated_dependencies = vec![&env];
 
         for dependency_id in dependency_ids.iter() {
-            if dependency_id.len() == 0 {
-                panic!("Dependency id cannot be empty");
+            if dependency_id.is_empty() {
+                return Err(ProgramEscrowError::InvalidDependency);
             }
             if dependency_id == program_id {
-                panic!("Program cannot depend on itself");
+                return Err(ProgramEscrowError::InvalidDependency);
             }
             if vec_contains(&validated_dependencies, &dependency_id) {
-                panic!("Duplicate dependency");
+                return Err(ProgramEscrowError::InvalidDependency);
             }
 
             let is_program_dependency = env
                 .storage()
-                .instance()
+                .persistent()
                 .has(&DataKey::Program(dependency_id.clone()));
             let is_registered_external = env
                 .storage()
                 .instance()
                 .has(&DataKey::DependencyStatus(dependency_id.clone()));
             if !is_program_dependency && !is_registered_external {
-                panic!("Dependency not registered");
+                return Err(ProgramEscrowError::InvalidDependency);
             }
 
             if is_program_dependency {
                 let mut visited = Vec::new(&env);
                 if path_exists_to_target(&env, &dependency_id, &program_id, &mut visited) {
-                    panic!("Dependency cycle detected");
+                    return Err(ProgramEscrowError::DependencyCycle);
                 }
             }
 
@@ -1216,25 +1345,23 @@ impl ProgramEscrowContract {
             }
         }
 
-        validated_dependencies
+        Ok(validated_dependencies)
     }
 
     /// Clears all dependencies for a program.
-    pub fn clear_program_dependencies(env: Env, program_id: String) {
-        let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
-
+    pub fn clear_program_dependencies(
+        env: Env,
+        program_id: String,
+    ) -> Result<(), ProgramEscrowError> {
+        let program_data = load_program(&env, &program_id)?;
         program_data.authorized_payout_key.require_auth();
 
         let old_dependencies = get_program_dependencies_internal(&env, &program_id);
         let empty_dependencies: Vec<String> = vec![&env];
-        env.storage()
-            .instance()
-            .set(&DataKey::ProgramDependencies(program_id.clone()), &empty_dependencies);
+        env.storage().instance().set(
+            &DataKey::ProgramDependencies(program_id.clone()),
+            &empty_dependencies,
+        );
 
         for dependency_id in old_dependencies.iter() {
             env.events().publish(
@@ -1242,51 +1369,46 @@ impl ProgramEscrowContract {
                 (program_id.clone(), dependency_id.clone()),
             );
         }
+        Ok(())
     }
 
     /// Reads all dependencies configured for a program.
-    pub fn get_program_dependencies(env: Env, program_id: String) -> Vec<String> {
-        if !env
-            .storage()
-            .instance()
-            .has(&DataKey::Program(program_id.clone()))
-        {
-            panic!("Program not found");
-        }
-        get_program_dependencies_internal(&env, &program_id)
+    pub fn get_program_dependencies(
+        env: Env,
+        program_id: String,
+    ) -> Result<Vec<String>, ProgramEscrowError> {
+        load_program(&env, &program_id)?;
+        Ok(get_program_dependencies_internal(&env, &program_id))
     }
 
     /// Updates dependency status.
     ///
     /// For registered programs, only that program's authorized payout key can update status.
-    /// For external dependency ids, anti-abuse admin authorization is required.
-    pub fn set_dependency_status(env: Env, dependency_id: String, status: DependencyStatus) {
-        if dependency_id.len() == 0 {
-            panic!("Dependency id cannot be empty");
+    /// For external dependency ids, admin authorization is required.
+    pub fn set_dependency_status(
+        env: Env,
+        dependency_id: String,
+        status: DependencyStatus,
+    ) -> Result<(), ProgramEscrowError> {
+        if dependency_id.is_empty() {
+            return Err(ProgramEscrowError::InvalidDependency);
         }
 
-        if env
-            .storage()
-            .instance()
-            .has(&DataKey::Program(dependency_id.clone()))
-        {
-            let program_data: ProgramData = env
-                .storage()
-                .instance()
-                .get(&DataKey::Program(dependency_id.clone()))
-                .unwrap();
-            program_data.authorized_payout_key.require_auth();
-        } else {
-            let admin: Address = env.storage().instance().get(&DataKey::Admin)
-                .unwrap_or_else(|| panic!("Admin not set for external dependency status update"));
-            admin.require_auth();
+        match load_program(&env, &dependency_id) {
+            Ok(program_data) => program_data.authorized_payout_key.require_auth(),
+            Err(_) => {
+                let admin =
+                    anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+                admin.require_auth();
+            }
         }
 
         env.storage()
             .instance()
-            .set(&DataKey::DependencyStatus(dependency_id.clone()), &status.clone());
+            .set(&DataKey::DependencyStatus(dependency_id.clone()), &status);
         env.events()
             .publish((DEPENDENCY_STATUS_UPDATED,), (dependency_id, status));
+        Ok(())
     }
 
     /// Reads dependency status; defaults to pending if no explicit status exists.
@@ -1298,140 +1420,129 @@ impl ProgramEscrowContract {
     // Fund Management
     // ========================================================================
 
+    /// Calculate fee using floor rounding. Delegates to `token_math::calculate_fee`.
+    fn calculate_fee(amount: i128, fee_rate: i128) -> i128 {
+        token_math::calculate_fee(amount, fee_rate)
+    }
+
+    /// Get fee configuration (internal helper)
+    fn get_fee_config_internal(env: &Env) -> FeeConfig {
+        env.storage()
+            .instance()
+            .get(&FEE_CONFIG)
+            .unwrap_or_else(|| FeeConfig {
+                lock_fee_rate: 0,
+                payout_fee_rate: 0,
+                fee_recipient: env.current_contract_address(),
+                fee_enabled: false,
+            })
+    }
+
     /// Locks funds into the program escrow for prize distribution.
     ///
     /// # Arguments
     /// * `env` - The contract environment
+    /// * `program_id` - The program to credit
     /// * `amount` - Amount of tokens to lock (in token's smallest denomination)
     ///
     /// # Returns
     /// * `ProgramData` - Updated program data with new balance
     ///
-    /// # Panics
-    /// * If amount is zero or negative
-    /// * If program is not initialized
+    /// # Errors
+    /// * `FundsPaused` - If locking is paused
+    /// * `InvalidAmount` - If amount is zero or negative
+    /// * `ProgramNotFound` - If program is not registered
+    /// * `ArithmeticOverflow` - If balances would overflow
     ///
     /// # State Changes
-    /// - Increases `total_funds` by amount
-    /// - Increases `remaining_balance` by amount
+    /// - Increases `total_funds` and `remaining_balance` by the amount net of
+    ///   the lock fee; the fee is sent to the fee recipient
     /// - Emits FundsLocked event
     ///
-    /// # Prerequisites
-    /// Before calling this function:
-    /// 1. Caller must have sufficient token balance
-    /// 2. Caller must approve contract for token transfer
-    /// 3. Tokens must actually be transferred to contract
-    ///
     /// # Security Considerations
-    /// - Amount must be positive
-    /// - This function doesn't perform the actual token transfer
-    /// - Caller is responsible for transferring tokens to contract
-    /// - Consider verifying contract balance matches recorded amount
+    /// - This function doesn't perform the deposit itself: the caller transfers
+    ///   tokens to the contract first
     /// - Multiple lock operations are additive (cumulative)
     ///
     /// # Events
-    /// Emits: `FundsLocked(program_id, amount, new_remaining_balance)`
+    /// Emits: `FundsLocked(program_id, net_amount, new_remaining_balance)`
     ///
     /// # Example
     /// ```rust
-    /// use soroban_sdk::token;
-    ///
     /// // 1. Transfer tokens to contract
     /// let amount = 10_000_0000000; // 10,000 USDC
-    /// token_client.transfer(
-    ///     &organizer,
-    ///     &contract_address,
-    ///     &amount
-    /// );
+    /// token_client.transfer(&organizer, &contract_address, &amount);
     ///
     /// // 2. Record the locked funds
-    /// let updated = escrow_client.lock_program_funds(&amount);
-    /// println!("Locked: {} USDC", amount / 10_000_000);
+    /// let updated = escrow_client.lock_program_funds(&program_id, &amount);
     /// println!("Remaining: {}", updated.remaining_balance);
     /// ```
     ///
-    /// # Production Usage
-    /// ```bash
-    /// # 1. Transfer USDC to contract
-    /// stellar contract invoke \
-    ///   --id USDC_TOKEN_ID \
-    ///   --source ORGANIZER_KEY \
-    ///   -- transfer \
-    ///   --from ORGANIZER_ADDRESS \
-    ///   --to CONTRACT_ADDRESS \
-    ///   --amount 10000000000
-    ///
-    /// # 2. Record locked funds
-    /// stellar contract invoke \
-    ///   --id CONTRACT_ID \
-    ///   --source ORGANIZER_KEY \
-    ///   -- lock_program_funds \
-    ///   --amount 10000000000
-    /// ```
-    ///
     /// # Gas Cost
     /// Low - Storage update + event emission
-    ///
-    /// # Common Pitfalls
-    /// - Forgetting to transfer tokens before calling
-    /// -  Locking amount that exceeds actual contract balance
-    /// -  Not verifying contract received the tokens
-
-    pub fn lock_program_funds(env: Env, _program_id: String, amount: i128) -> ProgramData {
+    pub fn lock_program_funds(
+        env: Env,
+        program_id: String,
+        amount: i128,
+    ) -> Result<ProgramData, ProgramEscrowError> {
         if Self::check_paused(&env, symbol_short!("lock")) {
-            panic!("Funds Paused");
+            return Err(ProgramEscrowError::FundsPaused);
         }
 
         // Validate amount
         if amount <= 0 {
-            monitoring::track_operation(&env, symbol_short!("lock"), caller.clone(), false);
-            panic!("Amount must be greater than zero");
+            return Err(ProgramEscrowError::InvalidAmount);
         }
 
-        // Get program data
         let program_key = DataKey::Program(program_id.clone());
-        let mut program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| {
-                monitoring::track_operation(&env, symbol_short!("lock"), caller.clone(), false);
-                panic!("Program not found")
-            });
+        let mut program_data = load_program(&env, &program_id)?;
 
         // Calculate and collect fee if enabled
         let fee_config = Self::get_fee_config_internal(&env);
         let fee_amount = if fee_config.fee_enabled && fee_config.lock_fee_rate > 0 {
             Self::calculate_fee(amount, fee_config.lock_fee_rate)
-            .get(&PROGRAM_DATA)
-            .unwrap_or_else(|| panic!("Program not initialized"));
+        } else {
+            0
+        };
+        let net_amount = amount - fee_amount;
 
-        // Update balances
+        // Update balances with net amount
         program_data.total_funds = program_data
             .total_funds
-            .checked_add(amount)
-            .unwrap_or_else(|| panic!("Amount overflow on total_funds"));
-
+            .checked_add(net_amount)
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
         program_data.remaining_balance = program_data
             .remaining_balance
-            .checked_add(amount)
-            .unwrap_or_else(|| panic!("Amount overflow on remaining_balance"));
+            .checked_add(net_amount)
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
+
+        if fee_amount > 0 {
+            token::Client::new(&env, &program_data.token_address).transfer(
+                &env.current_contract_address(),
+                &fee_config.fee_recipient,
+                &fee_amount,
+            );
+            env.events().publish(
+                (symbol_short!("fee"),),
+                (
+                    symbol_short!("lock"),
+                    fee_amount,
+                    fee_config.lock_fee_rate,
+                    fee_config.fee_recipient.clone(),
+                ),
+            );
+        }
 
         // Store updated data
-        env.storage().instance().set(&PROGRAM_DATA, &program_data);
+        program_storage::save(&env, &program_key, &program_data);
 
-        // Emit FundsLocked event
+        // Emit FundsLocked event (with net amount after fee)
         env.events().publish(
             (FUNDS_LOCKED,),
-            FundsLockedEvent {
-                version: EVENT_VERSION_V2,
-                program_id: program_data.program_id.clone(),
-                amount,
-                remaining_balance: program_data.remaining_balance,
-            },
+            (program_id, net_amount, program_data.remaining_balance),
         );
 
-        program_data
+        Ok(program_data)
     }
 
     // ========================================================================
@@ -1440,32 +1551,95 @@ impl ProgramEscrowContract {
 
     /// Initialize the contract with an admin.
     /// This must be called before any admin protected functions (like pause) can be used.
-    pub fn initialize_contract(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            panic!("Already initialized");
+    pub fn initialize_contract(env: Env, admin: Address) -> Result<(), ProgramEscrowError> {
+        if anti_abuse::get_admin(&env).is_some() {
+            return Err(ProgramEscrowError::AlreadyInitialized);
         }
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        anti_abuse::set_admin(&env, admin);
+        Ok(())
     }
 
-    /// Set or rotate admin. If no admin is set, sets initial admin. If admin exists, current admin must authorize and the new address becomes admin.
-    pub fn set_admin(env: Env, admin: Address) {
-        if env.storage().instance().has(&DataKey::Admin) {
-            let current: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-            current.require_auth();
+    /// Set or rotate admin. If no admin is set, sets initial admin. If admin exists,
+    /// current admin must authorize and the new address becomes admin.
+    pub fn set_admin(env: Env, new_admin: Address) {
+        if let Some(current_admin) = anti_abuse::get_admin(&env) {
+            current_admin.require_auth();
         }
-        env.storage().instance().set(&DataKey::Admin, &admin);
+        anti_abuse::set_admin(&env, new_admin);
     }
 
     /// Returns the current admin address, if set.
     pub fn get_admin(env: Env) -> Option<Address> {
-        env.storage().instance().get(&DataKey::Admin)
+        anti_abuse::get_admin(&env)
     }
 
-    pub fn get_program_release_schedules(env: Env) -> Vec<ProgramReleaseSchedule> {
+    // ========================================================================
+    // Pause and Emergency Functions
+    // ========================================================================
+
+    /// Check if contract is paused (internal helper)
+    fn is_paused_internal(env: &Env) -> bool {
         env.storage()
             .instance()
-            .get(&SCHEDULES)
-            .unwrap_or_else(|| Vec::new(&env))
+            .get::<_, bool>(&DataKey::IsPaused)
+            .unwrap_or(false)
+    }
+
+    /// Whether `operation` ("lock", "release" or "refund") is paused, either
+    /// by its own flag or by the global pause.
+    fn check_paused(env: &Env, operation: Symbol) -> bool {
+        if Self::is_paused_internal(env) {
+            return true;
+        }
+        let flags = Self::get_pause_flags(env.clone());
+        if operation == symbol_short!("lock") {
+            flags.lock_paused
+        } else if operation == symbol_short!("release") {
+            flags.release_paused
+        } else if operation == symbol_short!("refund") {
+            flags.refund_paused
+        } else {
+            false
+        }
+    }
+
+    /// Get pause status (view function)
+    pub fn is_paused(env: Env) -> bool {
+        Self::is_paused_internal(&env)
+    }
+
+    /// Pause the contract (admin only).
+    /// Prevents new fund locking, payouts, and schedule releases.
+    pub fn pause(env: Env) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+        admin.require_auth();
+
+        if Self::is_paused_internal(&env) {
+            return Ok(()); // Already paused, idempotent
+        }
+
+        env.storage().instance().set(&DataKey::IsPaused, &true);
+
+        env.events()
+            .publish((symbol_short!("pause"),), (env.ledger().timestamp(),));
+        Ok(())
+    }
+
+    /// Unpause the contract (admin only).
+    /// Resumes normal operations.
+    pub fn unpause(env: Env) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+        admin.require_auth();
+
+        if !Self::is_paused_internal(&env) {
+            return Ok(()); // Already unpaused, idempotent
+        }
+
+        env.storage().instance().set(&DataKey::IsPaused, &false);
+
+        env.events()
+            .publish((symbol_short!("unpause"),), (env.ledger().timestamp(),));
+        Ok(())
     }
 
     /// Update pause flags (admin only)
@@ -1475,137 +1649,120 @@ impl ProgramEscrowContract {
         release: Option<bool>,
         refund: Option<bool>,
         reason: Option<String>,
-    ) {
-        if !env.storage().instance().has(&DataKey::Admin) {
-            panic!("Not initialized");
-        }
-
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    ) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
         admin.require_auth();
 
-        let mut flags = Self::get_pause_flags(&env);
+        let mut flags = Self::get_pause_flags(env.clone());
         let timestamp = env.ledger().timestamp();
 
         if reason.is_some() {
             flags.pause_reason = reason.clone();
         }
 
-        if let Some(paused) = lock {
-            flags.lock_paused = paused;
-            env.events().publish(
-                (PAUSE_STATE_CHANGED,),
-                (
-                    symbol_short!("lock"),
-                    paused,
-                    admin.clone(),
-                    reason.clone(),
-                    timestamp,
-                ),
-            );
-        }
-
-        if let Some(paused) = release {
-            flags.release_paused = paused;
-            env.events().publish(
-                (PAUSE_STATE_CHANGED,),
-                (
-                    symbol_short!("release"),
-                    paused,
-                    admin.clone(),
-                    reason.clone(),
-                    timestamp,
-                ),
-            );
-        }
-
-        if let Some(paused) = refund {
-            flags.refund_paused = paused;
+        for (operation, paused) in [
+            (symbol_short!("lock"), lock),
+            (symbol_short!("release"), release),
+            (symbol_short!("refund"), refund),
+        ] {
+            let Some(paused) = paused else {
+                continue;
+            };
+            if operation == symbol_short!("lock") {
+                flags.lock_paused = paused;
+            } else if operation == symbol_short!("release") {
+                flags.release_paused = paused;
+            } else {
+                flags.refund_paused = paused;
+            }
             env.events().publish(
                 (PAUSE_STATE_CHANGED,),
-                (
-                    symbol_short!("refund"),
-                    paused,
-                    admin.clone(),
-                    reason.clone(),
-                    timestamp,
-                ),
+                (operation, paused, admin.clone(), reason.clone(), timestamp),
             );
         }
 
         let any_paused = flags.lock_paused || flags.release_paused || flags.refund_paused;
-
         if any_paused {
             if flags.paused_at == 0 {
                 flags.paused_at = timestamp;
             }
         } else {
-            0
-        };
-        let net_amount = amount - fee_amount;
+            flags.pause_reason = None;
+            flags.paused_at = 0;
+        }
 
-        // Update balances with net amount
-        program_data.total_funds += net_amount;
-        program_data.remaining_balance += net_amount;
+        env.storage().instance().set(&DataKey::PauseFlags, &flags);
+        Ok(())
+    }
 
-        // Emit fee collected event if applicable
-        if fee_amount > 0 {
-        let program_data: ProgramData = env
-            .storage()
+    /// Get current pause flags
+    pub fn get_pause_flags(env: Env) -> PauseFlags {
+        env.storage()
             .instance()
-            .get(&PROGRAM_DATA)
-            .unwrap_or_else(|| panic!("Program not initialized"));
-        let token_client = token::TokenClient::new(&env, &program_data.token_address);
+            .get(&DataKey::PauseFlags)
+            .unwrap_or(PauseFlags {
+                lock_paused: false,
+                release_paused: false,
+                refund_paused: false,
+                pause_reason: None,
+                paused_at: 0,
+            })
+    }
 
-        let contract_address = env.current_contract_address();
-        let balance = token_client.balance(&contract_address);
+    /// Move the contract's whole balance of every program token to `target`
+    /// (admin only, while locking is paused).
+    ///
+    /// Program records are left as they were; this is a last resort when the
+    /// contract is compromised.
+    ///
+    /// # Returns
+    /// * `i128` - Total amount withdrawn across tokens
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If no admin is set
+    /// * `NotPaused` - If locking is not paused
+    pub fn emergency_withdraw(env: Env, target: Address) -> Result<i128, ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+        admin.require_auth();
 
-        if balance > 0 {
-            token_client.transfer(&contract_address, &target, &balance);
-            env.events().publish(
-                (symbol_short!("fee"),),
+        if !Self::get_pause_flags(env.clone()).lock_paused {
+            return Err(ProgramEscrowError::NotPaused);
+        }
+
+        let contract_address = env.current_contract_address();
+        let mut tokens: Vec<Address> = vec![&env];
+        for program_id in program_storage::registry(&env).iter() {
+            if let Ok(program_data) = load_program(&env, &program_id) {
+                if !tokens.contains(&program_data.token_address) {
+                    tokens.push_back(program_data.token_address);
+                }
+            }
+        }
+
+        let mut withdrawn = 0i128;
+        for token_address in tokens.iter() {
+            let token_client = token::Client::new(&env, &token_address);
+            let balance = token_client.balance(&contract_address);
+            if balance <= 0 {
+                continue;
+            }
+            token_client.transfer(&contract_address, &target, &balance);
+            withdrawn = withdrawn.saturating_add(balance);
+
+            env.events().publish(
+                (EMERGENCY_WITHDRAWN,),
                 (
-                    symbol_short!("lock"),
-                    fee_amount,
-                    fee_config.lock_fee_rate,
-                    fee_config.fee_recipient.clone(),
+                    admin.clone(),
+                    target.clone(),
+                    balance,
+                    env.ledger().timestamp(),
                 ),
             );
         }
 
-        // Store updated data
-        env.storage().instance().set(&program_key, &program_data);
-        let config = RateLimitConfig {
-            window_size,
-            max_operations,
-            cooldown_period,
-        };
-        env.storage()
-            .instance()
-            .set(&DataKey::RateLimitConfig, &config);
-    }
-
-        // Emit FundsLocked event (with net amount after fee)
-        env.events().publish(
-            (FUNDS_LOCKED,),
-            (
-                program_data.program_id.clone(),
-                net_amount,
-                program_data.remaining_balance,
-            ),
-        );
-
-        program_data
+        Ok(withdrawn)
     }
 
-    pub fn set_whitelist(env: Env, _address: Address, _whitelisted: bool) {
-        // Only admin can set whitelist
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .unwrap_or_else(|| panic!("Not initialized"));
-        admin.require_auth();
-    }
     // ========================================================================
     // Payout Functions
     // ========================================================================
@@ -1619,65 +1776,81 @@ impl ProgramEscrowContract {
     ///
     /// # Returns
     /// Updated ProgramData after payouts
+    ///
+    /// # Errors
+    /// * `FundsPaused` - If releases are paused
+    /// * `ProgramNotFound` - If program doesn't exist
+    /// * `DependencyNotSatisfied` / `DependencyFailed` - If a dependency blocks release
+    /// * `BatchLengthMismatch` / `EmptyBatch` - If the vectors are malformed
+    /// * `InvalidAmount` - If any amount is not positive
+    /// * `InsufficientBalance` - If the total exceeds the remaining balance
+    /// * `InsufficientApprovals` / `ApprovalExpired` - If multisig approval is missing
     pub fn batch_payout(
         env: Env,
         program_id: String,
         recipients: Vec<Address>,
         amounts: Vec<i128>,
-    ) -> ProgramData {
-        // Reentrancy guard: Check and set
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        // Reentrancy guard: Check and set. An error rolls back the flag with
+        // the rest of the invocation's state.
         reentrancy_guard::check_not_entered(&env);
         reentrancy_guard::set_entered(&env);
 
         if Self::check_paused(&env, symbol_short!("release")) {
-            reentrancy_guard::clear_entered(&env);
-            panic!("Funds Paused");
+            return Err(ProgramEscrowError::FundsPaused);
         }
 
-        // Verify authorization
         let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData =
-            env.storage()
-                .instance()
-                .get(&program_key)
-                .unwrap_or_else(|| {
-                    reentrancy_guard::clear_entered(&env);
-                    panic!("Program not found")
-                });
-
-        Self::assert_dependencies_satisfied(&env, &program_data.program_id);
-
+        let mut program_data = load_program(&env, &program_id)?;
 
+        Self::assert_dependencies_satisfied(&env, &program_id)?;
 
+        // Verify authorization
         program_data.authorized_payout_key.require_auth();
 
+        // Return lapsed claim reservations before checking the balance
+        claim_period::release_expired(&env, &mut program_data, 0, claim_period::AUTO_EXPIRE_SCAN)?;
+
         // Validate inputs
         if recipients.len() != amounts.len() {
-            panic!("Recipients and amounts vectors must have the same length");
+            return Err(ProgramEscrowError::BatchLengthMismatch);
         }
 
         if recipients.is_empty() {
-            panic!("Cannot process empty batch");
+            return Err(ProgramEscrowError::EmptyBatch);
         }
 
         // Calculate total with overflow protection
         let mut total_payout: i128 = 0;
+        let mut per_recipient: soroban_sdk::Map<Address, i128> = soroban_sdk::Map::new(&env);
         for i in 0..amounts.len() {
             let amount = amounts.get(i).unwrap();
             if amount <= 0 {
-                panic!("All amounts must be greater than zero");
+                return Err(ProgramEscrowError::InvalidAmount);
             }
             total_payout = total_payout
                 .checked_add(amount)
-                .unwrap_or_else(|| panic!("Payout amount overflow"));
+                .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
+
+            let recipient = recipients.get(i).unwrap();
+            let so_far = per_recipient.get(recipient.clone()).unwrap_or(0);
+            per_recipient.set(
+                recipient,
+                so_far
+                    .checked_add(amount)
+                    .ok_or(ProgramEscrowError::ArithmeticOverflow)?,
+            );
+        }
+
+        // The multisig threshold applies to everything one recipient gets
+        // from the batch, so splitting a payout into items does not avoid it
+        for (recipient, amount) in per_recipient.iter() {
+            multisig::require_approval(&env, &program_id, &recipient, amount)?;
         }
 
         // Validate balance
         if total_payout > program_data.remaining_balance {
-            panic!(
-                "Insufficient balance: requested {}, available {}",
-                total_payout, program_data.remaining_balance
-            );
+            return Err(ProgramEscrowError::InsufficientBalance);
         }
 
         // Calculate fees if enabled
@@ -1685,8 +1858,6 @@ impl ProgramEscrowContract {
         let mut total_fees: i128 = 0;
 
         // Execute transfers
-        let mut updated_history = program_data.payout_history.clone();
-        let timestamp = env.ledger().timestamp();
         let contract_address = env.current_contract_address();
         let token_client = token::Client::new(&env, &program_data.token_address);
 
@@ -1704,7 +1875,7 @@ impl ProgramEscrowContract {
             total_fees += fee_amount;
 
             // Transfer net amount to recipient
-            token_client.transfer(&contract_address, &recipient.clone(), &net_amount);
+            token_client.transfer(&contract_address, &recipient, &net_amount);
 
             // Transfer fee to fee recipient if applicable
             if fee_amount > 0 {
@@ -1712,12 +1883,7 @@ impl ProgramEscrowContract {
             }
 
             // Record payout (with net amount)
-            let payout_record = PayoutRecord {
-                recipient: recipient.clone(),
-                amount: net_amount,
-                timestamp,
-            };
-            updated_history.push_back(payout_record);
+            payout_log::append(&env, &mut program_data, &recipient, net_amount);
         }
 
         // Emit fee collected event if applicable
@@ -1734,28 +1900,27 @@ impl ProgramEscrowContract {
         }
 
         // Update program data
-        let mut updated_data = program_data.clone();
-        updated_data.remaining_balance = updated_data
+        program_data.remaining_balance = program_data
             .remaining_balance
             .checked_sub(total_payout)
-            .unwrap_or_else(|| panic!("Insufficient remaining balance"));
-        updated_data.payout_history = updated_history;
+            .ok_or(ProgramEscrowError::InsufficientBalance)?;
 
         // Store updated data
-        env.storage().instance().set(&program_key, &updated_data);
+        program_storage::save(&env, &program_key, &program_data);
 
         // Emit event
         env.events().publish(
             (BATCH_PAYOUT,),
             (
                 program_id,
-                recipients.len() as u32,
+                recipients.len(),
                 total_payout,
-                updated_data.remaining_balance,
+                program_data.remaining_balance,
             ),
         );
 
-        updated_data
+        reentrancy_guard::clear_entered(&env);
+        Ok(program_data)
     }
 
     /// Executes a single payout to one recipient.
@@ -1767,59 +1932,49 @@ impl ProgramEscrowContract {
     ///
     /// # Returns
     /// Updated ProgramData after payout
+    ///
+    /// # Errors
+    /// Same as `batch_payout`.
     pub fn single_payout(
         env: Env,
         program_id: String,
         recipient: Address,
         amount: i128,
-    ) -> ProgramData {
-        // Reentrancy guard: Check and set
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        // Reentrancy guard: Check and set. An error rolls back the flag with
+        // the rest of the invocation's state.
         reentrancy_guard::check_not_entered(&env);
         reentrancy_guard::set_entered(&env);
 
         if Self::check_paused(&env, symbol_short!("release")) {
-            reentrancy_guard::clear_entered(&env);
-            panic!("Funds Paused");
+            return Err(ProgramEscrowError::FundsPaused);
         }
 
-        // Verify authorization
         let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| {
-                reentrancy_guard::clear_entered(&env);
-                panic!("Program not found")
-            });
+        let mut program_data = load_program(&env, &program_id)?;
 
-        Self::assert_dependencies_satisfied(&env, &program_id);
+        Self::assert_dependencies_satisfied(&env, &program_id)?;
 
+        // Verify authorization
         program_data.authorized_payout_key.require_auth();
         // Apply rate limiting to the authorized payout key
         anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
 
-        // Verify authorization
-        // let caller = env.invoker();
-        // if caller != program_data.authorized_payout_key {
-        //     panic!("Unauthorized: only authorized payout key can trigger payouts");
-        // }
-
-
+        // Return lapsed claim reservations before checking the balance
+        claim_period::release_expired(&env, &mut program_data, 0, claim_period::AUTO_EXPIRE_SCAN)?;
 
         // Validate amount
         if amount <= 0 {
-            panic!("Amount must be greater than zero");
+            return Err(ProgramEscrowError::InvalidAmount);
         }
 
         // Validate balance
         if amount > program_data.remaining_balance {
-            panic!(
-                "Insufficient balance: requested {}, available {}",
-                amount, program_data.remaining_balance
-            );
+            return Err(ProgramEscrowError::InsufficientBalance);
         }
 
+        multisig::require_approval(&env, &program_id, &recipient, amount)?;
+
         // Calculate and collect fee if enabled
         let fee_config = Self::get_fee_config_internal(&env);
         let fee_amount = if fee_config.fee_enabled && fee_config.payout_fee_rate > 0 {
@@ -1830,7 +1985,6 @@ impl ProgramEscrowContract {
         let net_amount = amount - fee_amount;
 
         // Transfer net amount to recipient
-        // Transfer tokens
         let contract_address = env.current_contract_address();
         let token_client = token::Client::new(&env, &program_data.token_address);
         token_client.transfer(&contract_address, &recipient, &net_amount);
@@ -1849,41 +2003,29 @@ impl ProgramEscrowContract {
             );
         }
 
-        // Record payout (with net amount after fee)
-        let timestamp = env.ledger().timestamp();
-        let payout_record = PayoutRecord {
-            recipient: recipient.clone(),
-            amount: net_amount,
-            timestamp,
-        };
-
-        let mut updated_history = program_data.payout_history.clone();
-        updated_history.push_back(payout_record);
-
-        // Update program data
-        let mut updated_data = program_data.clone();
-        updated_data.remaining_balance = updated_data
+        // Update program data and record the payout (with net amount after fee)
+        program_data.remaining_balance = program_data
             .remaining_balance
             .checked_sub(amount)
-            .unwrap_or_else(|| panic!("Insufficient remaining balance"));
-        updated_data.payout_history = updated_history;
+            .ok_or(ProgramEscrowError::InsufficientBalance)?;
+        payout_log::append(&env, &mut program_data, &recipient, net_amount);
 
         // Store updated data
-        env.storage().instance().set(&program_key, &updated_data);
+        program_storage::save(&env, &program_key, &program_data);
 
         // Emit Payout event (with net amount after fee)
-        // Emit event
         env.events().publish(
             (PAYOUT,),
             (
                 program_id,
                 recipient,
                 net_amount,
-                updated_data.remaining_balance,
+                program_data.remaining_balance,
             ),
         );
 
-        updated_data
+        reentrancy_guard::clear_entered(&env);
+        Ok(program_data)
     }
 
     // ========================================================================
@@ -1902,12 +2044,12 @@ impl ProgramEscrowContract {
     /// # Returns
     /// * `ProgramData` - Updated program data
     ///
-    /// # Panics
-    /// * If program is not initialized
-    /// * If caller is not authorized payout key
-    /// * If amount is invalid
-    /// * If timestamp is in the past
-    /// * If amount exceeds remaining balance
+    /// # Errors
+    /// * `FundsPaused` - If the contract is paused
+    /// * `ProgramNotFound` - If program is not registered
+    /// * `InvalidAmount` - If amount is zero or negative
+    /// * `ArithmeticOverflow` - If the schedule id counter overflows
+    /// * `InsufficientBalance` - If scheduled total exceeds remaining balance
     ///
     /// # State Changes
     /// - Creates ProgramReleaseSchedule record
@@ -1934,21 +2076,15 @@ impl ProgramEscrowContract {
         amount: i128,
         release_timestamp: u64,
         recipient: Address,
-    ) -> ProgramData {
+    ) -> Result<ProgramData, ProgramEscrowError> {
         let start = env.ledger().timestamp();
 
         // Check if contract is paused
         if Self::is_paused_internal(&env) {
-            panic!("Contract is paused");
+            return Err(ProgramEscrowError::FundsPaused);
         }
 
-        // Get program data
-        let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
+        let program_data = load_program(&env, &program_id)?;
 
         // Apply rate limiting to the authorized payout key
         anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
@@ -1958,28 +2094,30 @@ impl ProgramEscrowContract {
 
         // Validate amount
         if amount <= 0 {
-            panic!("Amount must be greater than zero");
+            return Err(ProgramEscrowError::InvalidAmount);
         }
 
-    env.storage().instance().set(&SCHEDULES, &schedules);
-    env.storage()
-        .instance()
-        let next_id = schedule_id
-            .checked_add(1)
-            .unwrap_or_else(|| panic!("Schedule ID overflow"));
-
         // Check sufficient remaining balance
-        let scheduled_total = get_program_total_scheduled_amount(&env, &program_id);
-        if scheduled_total + amount > program_data.remaining_balance {
-            panic!("Insufficient balance for scheduled amount");
+        let scheduled_total = get_program_total_scheduled_amount(&env, &program_id)?;
+        if scheduled_total
+            .checked_add(amount)
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?
+            > program_data.remaining_balance
+        {
+            return Err(ProgramEscrowError::InsufficientBalance);
         }
 
+        multisig::require_approval(&env, &program_id, &recipient, amount)?;
+
         // Get next schedule ID
         let schedule_id: u64 = env
             .storage()
             .persistent()
             .get(&DataKey::NextScheduleId(program_id.clone()))
             .unwrap_or(1);
+        let next_id = schedule_id
+            .checked_add(1)
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
 
         // Create release schedule
         let schedule = ProgramReleaseSchedule {
@@ -1991,76 +2129,26 @@ impl ProgramEscrowContract {
             released_at: None,
             released_by: None,
         };
-    /// Create a release schedule entry that can be triggered at/after `release_timestamp`.
-    pub fn create_program_release_schedule(
-        env: Env,
-        recipient: Address,
-        amount: i128,
-        release_timestamp: u64,
-    ) -> ProgramReleaseSchedule {
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&PROGRAM_DATA)
-            .unwrap_or_else(|| panic!("Program not initialized"));
-
-        program_data.authorized_payout_key.require_auth();
-
-        if amount <= 0 {
-            panic!("Amount must be greater than zero");
-        }
-
-        let mut schedules: Vec<ProgramReleaseSchedule> = env
-            .storage()
-            .instance()
-            .get(&SCHEDULES)
-            .unwrap_or_else(|| Vec::new(&env));
-        let schedule_id: u64 = env
-            .storage()
-            .instance()
-            .get(&NEXT_SCHEDULE_ID)
-            .unwrap_or(1_u64);
-
-        let schedule = ProgramReleaseSchedule {
-            schedule_id,
-            recipient,
-            amount,
-            release_timestamp,
-            released: false,
-            released_at: None,
-            released_by: None,
-        };
-        schedules.push_back(schedule.clone());
-
-        env.storage().instance().set(&SCHEDULES, &schedules);
-        env.storage()
-            .instance()
-            .set(&NEXT_SCHEDULE_ID, &(schedule_id + 1));
-
-        schedule
-    }
 
         // Store schedule
-        env.storage().persistent().set(
+        program_storage::save(
+            &env,
             &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
             &schedule,
         );
 
         // Update next schedule ID
-        env.storage().persistent().set(
-            &DataKey::NextScheduleId(program_id.clone()),
-            &(schedule_id + 1),
-        );
+        program_storage::save(&env, &DataKey::NextScheduleId(program_id.clone()), &next_id);
 
         // Emit program schedule created event
         env.events().publish(
             (PROG_SCHEDULE_CREATED,),
             ProgramScheduleCreated {
-                program_id: program_id.clone(),
+                program_id,
                 schedule_id,
                 amount,
                 release_timestamp,
-                recipient: recipient.clone(),
+                recipient,
                 created_by: program_data.authorized_payout_key.clone(),
             },
         );
@@ -2069,7 +2157,7 @@ impl ProgramEscrowContract {
         monitoring::track_operation(
             &env,
             symbol_short!("create_p"),
-            program_data.authorized_payout_key,
+            program_data.authorized_payout_key.clone(),
             true,
         );
 
@@ -2077,9 +2165,7 @@ impl ProgramEscrowContract {
         let duration = env.ledger().timestamp().saturating_sub(start);
         monitoring::emit_performance(&env, symbol_short!("create_p"), duration);
 
-        // Return updated program data
-        let updated_data: ProgramData = env.storage().instance().get(&program_key).unwrap();
-        updated_data
+        Ok(program_data)
     }
 
     /// Automatically releases funds for program schedules that are due.
@@ -2090,11 +2176,13 @@ impl ProgramEscrowContract {
     /// * `program_id` - The program to check for due schedules
     /// * `schedule_id` - The specific schedule to release
     ///
-    /// # Panics
-    /// * If program doesn't exist
-    /// * If schedule doesn't exist
-    /// * If schedule is already released
-    /// * If schedule is not yet due
+    /// # Errors
+    /// * `FundsPaused` - If the contract is paused
+    /// * `ProgramNotFound` - If program doesn't exist
+    /// * `ScheduleNotFound` - If schedule doesn't exist
+    /// * `ScheduleAlreadyReleased` - If schedule is already released
+    /// * `DependencyNotSatisfied` / `DependencyFailed` - If a dependency blocks release
+    /// * `ScheduleNotDue` - If schedule is not yet due
     ///
     /// # State Changes
     /// - Transfers tokens to recipient
@@ -2106,115 +2194,49 @@ impl ProgramEscrowContract {
     /// # Example
     /// ```rust
     /// // Anyone can call this after the timestamp
-    /// escrow_client.release_program_schedule_automatic(&"Hackathon2024", &1);
+    /// escrow_client.release_prog_schedule_automatic(&"Hackathon2024", &1);
     /// ```
-    pub fn release_prog_schedule_automatic(env: Env, program_id: String, schedule_id: u64) {
+    pub fn release_prog_schedule_automatic(
+        env: Env,
+        program_id: String,
+        schedule_id: u64,
+    ) -> Result<(), ProgramEscrowError> {
         let start = env.ledger().timestamp();
-        let caller = env.current_contract_address();
 
         // Check if contract is paused
-        if Self::is_paused_internal(&env) {
-            panic!("Contract is paused");
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(ProgramEscrowError::FundsPaused);
         }
 
-        // Get program data
-        let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
-
-        // Get schedule
-        if !env
-            .storage()
-            .instance()
-            .get(&SCHEDULES)
-            .unwrap_or_else(|| Vec::new(&env));
-        let mut release_history: Vec<ProgramReleaseHistory> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-            .unwrap();
-
-        Self::assert_dependencies_satisfied(&env, &program_data.program_id);
-
-        let now = env.ledger().timestamp();
-        if now < schedule.release_timestamp {
-            panic!("Schedule not yet due for release");
+        let program_data = load_program(&env, &program_id)?;
+        let schedule =
+            Self::get_program_release_schedule(env.clone(), program_id.clone(), schedule_id)?;
+        if schedule.released {
+            return Err(ProgramEscrowError::ScheduleAlreadyReleased);
         }
 
-        // Get token client
-        let contract_address = env.current_contract_address();
-        let token_client = token::Client::new(&env, &program_data.token_address);
-
-        // Transfer funds
-        token_client.transfer(&contract_address, &schedule.recipient, &schedule.amount);
-
-        // Update schedule
-        schedule.released = true;
-        schedule.released_at = Some(now);
-        schedule.released_by = Some(env.current_contract_address());
-
-        // Update program data
-        let mut updated_data = program_data.clone();
-        updated_data.remaining_balance -= schedule.amount;
-
-        // Add to release history
-        let history_entry = ProgramReleaseHistory {
-            schedule_id,
-            program_id: program_id.clone(),
-            amount: schedule.amount,
-            recipient: schedule.recipient.clone(),
-            released_at: now,
-            released_by: env.current_contract_address(),
-            release_type: ReleaseType::Automatic,
-        };
+        Self::assert_dependencies_satisfied(&env, &program_id)?;
 
-        let mut history: Vec<ProgramReleaseHistory> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ReleaseHistory(program_id.clone()))
-            .unwrap_or(vec![&env]);
-        history.push_back(history_entry);
+        if env.ledger().timestamp() < schedule.release_timestamp {
+            return Err(ProgramEscrowError::ScheduleNotDue);
+        }
 
-        // Store updates
-        env.storage().persistent().set(
-            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
-            &schedule,
+        let released_by = env.current_contract_address();
+        Self::release_schedule(
+            &env,
+            program_data,
+            schedule,
+            released_by.clone(),
+            ReleaseType::Automatic,
         );
-        env.storage().instance().set(&program_key, &updated_data);
-        env.storage()
-            .persistent()
-            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
-
-        // Emit program schedule released event
-        env.events().publish(
-            (PROG_SCHEDULE_RELEASED,),
-            ProgramScheduleReleased {
-                program_id: program_id.clone(),
-                schedule_id,
-                amount: schedule.amount,
-                recipient: schedule.recipient.clone(),
-                amount: schedule.amount,
-                timestamp: now,
-            });
 
-            env.events().publish(
-                (PAYOUT,),
-                PayoutEvent {
-                    version: EVENT_VERSION_V2,
-                    program_id: program_data.program_id.clone(),
-                    recipient: schedule.recipient.clone(),
-                    amount: schedule.amount,
-                    remaining_balance: program_data.remaining_balance,
-                },
-            );
+        // Track successful operation
+        monitoring::track_operation(&env, symbol_short!("rel_auto"), released_by, true);
 
-            release_history.push_back(ProgramReleaseHistory {
-                schedule_id: schedule.schedule_id,
-                recipient: schedule.recipient,
-                amount: schedule.amount,
+        // Track performance
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("rel_auto"), duration);
+        Ok(())
     }
 
     /// Manually releases funds for a program schedule (authorized payout key only).
@@ -2225,11 +2247,11 @@ impl ProgramEscrowContract {
     /// * `program_id` - The program containing the schedule
     /// * `schedule_id` - The schedule to release
     ///
-    /// # Panics
-    /// * If program doesn't exist
-    /// * If caller is not authorized payout key
-    /// * If schedule doesn't exist
-    /// * If schedule is already released
+    /// # Errors
+    /// * `FundsPaused` - If the contract is paused
+    /// * `ProgramNotFound` - If program doesn't exist
+    /// * `ScheduleNotFound` - If schedule doesn't exist
+    /// * `ScheduleAlreadyReleased` - If schedule is already released
     ///
     /// # State Changes
     /// - Transfers tokens to recipient
@@ -2246,16 +2268,18 @@ impl ProgramEscrowContract {
     /// // Authorized key can release early
     /// escrow_client.release_program_schedule_manual(&"Hackathon2024", &1);
     /// ```
-    pub fn release_program_schedule_manual(env: Env, program_id: String, schedule_id: u64) {
+    pub fn release_program_schedule_manual(
+        env: Env,
+        program_id: String,
+        schedule_id: u64,
+    ) -> Result<(), ProgramEscrowError> {
         let start = env.ledger().timestamp();
 
-        // Get program data
-        let program_key = DataKey::Program(program_id.clone());
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(ProgramEscrowError::FundsPaused);
+        }
+
+        let program_data = load_program(&env, &program_id)?;
 
         // Apply rate limiting to the authorized payout key
         anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
@@ -2263,126 +2287,145 @@ impl ProgramEscrowContract {
         // Verify authorization
         program_data.authorized_payout_key.require_auth();
 
-        // Get schedule
-        if !env
-            .storage()
-            .persistent()
-            .has(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-        {
-            panic!("Schedule not found");
+        let schedule =
+            Self::get_program_release_schedule(env.clone(), program_id.clone(), schedule_id)?;
+        if schedule.released {
+            return Err(ProgramEscrowError::ScheduleAlreadyReleased);
         }
 
-        let mut schedule: ProgramReleaseSchedule = env
-            .storage()
-            .persistent()
-            .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-            .unwrap();
+        let released_by = program_data.authorized_payout_key.clone();
+        Self::release_schedule(
+            &env,
+            program_data,
+            schedule,
+            released_by.clone(),
+            ReleaseType::Manual,
+        );
 
-        // Check if already released
-        if schedule.released {
-            panic!("Schedule already released");
-        }
+        // Track successful operation
+        monitoring::track_operation(&env, symbol_short!("rel_man"), released_by, true);
 
-        // Get token client
-        let contract_address = env.current_contract_address();
-        let token_client = token::Client::new(&env, &program_data.token_address);
+        // Track performance
+        let duration = env.ledger().timestamp().saturating_sub(start);
+        monitoring::emit_performance(&env, symbol_short!("rel_man"), duration);
+        Ok(())
+    }
+
+    /// Pay out a due schedule: transfer, mark it released, record it in the
+    /// release history and payout log, and emit `prg_sch_r`.
+    fn release_schedule(
+        env: &Env,
+        mut program_data: ProgramData,
+        mut schedule: ProgramReleaseSchedule,
+        released_by: Address,
+        release_type: ReleaseType,
+    ) {
+        let program_id = program_data.program_id.clone();
+        let now = env.ledger().timestamp();
 
         // Transfer funds
-        token_client.transfer(&contract_address, &schedule.recipient, &schedule.amount);
+        token::Client::new(env, &program_data.token_address).transfer(
+            &env.current_contract_address(),
+            &schedule.recipient,
+            &schedule.amount,
+        );
 
         // Update schedule
-        let now = env.ledger().timestamp();
         schedule.released = true;
         schedule.released_at = Some(now);
-        schedule.released_by = Some(program_data.authorized_payout_key.clone());
+        schedule.released_by = Some(released_by.clone());
 
-        // Update program data
-        let mut updated_data = program_data.clone();
-        updated_data.remaining_balance -= schedule.amount;
+        // Update program data; the schedule was checked against the balance
+        // when it was created
+        program_data.remaining_balance -= schedule.amount;
+        payout_log::append(env, &mut program_data, &schedule.recipient, schedule.amount);
 
         // Add to release history
-        let history_entry = ProgramReleaseHistory {
-            schedule_id,
-            program_id: program_id.clone(),
-            amount: schedule.amount,
-            recipient: schedule.recipient.clone(),
-            released_at: now,
-            released_by: program_data.authorized_payout_key.clone(),
-            release_type: ReleaseType::Manual,
-        };
-
         let mut history: Vec<ProgramReleaseHistory> = env
             .storage()
             .persistent()
             .get(&DataKey::ReleaseHistory(program_id.clone()))
-            .unwrap_or(vec![&env]);
-        history.push_back(history_entry);
+            .unwrap_or(vec![env]);
+        history.push_back(ProgramReleaseHistory {
+            schedule_id: schedule.schedule_id,
+            program_id: program_id.clone(),
+            amount: schedule.amount,
+            recipient: schedule.recipient.clone(),
+            released_at: now,
+            released_by: released_by.clone(),
+            release_type: release_type.clone(),
+        });
 
         // Store updates
-        env.storage().persistent().set(
-            &DataKey::ReleaseSchedule(program_id.clone(), schedule_id),
+        program_storage::save(
+            env,
+            &DataKey::ReleaseSchedule(program_id.clone(), schedule.schedule_id),
             &schedule,
         );
-        env.storage().instance().set(&program_key, &updated_data);
-        env.storage()
-            .persistent()
-            .set(&DataKey::ReleaseHistory(program_id.clone()), &history);
+        program_storage::save(env, &DataKey::Program(program_id.clone()), &program_data);
+        program_storage::save(env, &DataKey::ReleaseHistory(program_id.clone()), &history);
 
         // Emit program schedule released event
         env.events().publish(
             (PROG_SCHEDULE_RELEASED,),
             ProgramScheduleReleased {
-                program_id: program_id.clone(),
-                schedule_id,
+                program_id,
+                schedule_id: schedule.schedule_id,
                 amount: schedule.amount,
-                recipient: schedule.recipient.clone(),
+                recipient: schedule.recipient,
                 released_at: now,
-                released_by: program_data.authorized_payout_key.clone(),
-                release_type: ReleaseType::Manual,
+                released_by,
+                release_type,
             },
         );
-
-        // Track successful operation
-        monitoring::track_operation(
-            &env,
-            symbol_short!("rel_man"),
-            program_data.authorized_payout_key,
-            true,
-        );
-
-        // Track performance
-        let duration = env.ledger().timestamp().saturating_sub(start);
-        monitoring::emit_performance(&env, symbol_short!("rel_man"), duration);
-    }
+    }
 
     // ========================================================================
     // View Functions (Read-only)
     // ========================================================================
 
-    pub fn get_program_info_v2(env: Env, _program_id: String) -> ProgramData {
-        Self::get_program_info(env)
+    /// Retrieves a program's configuration and balances.
+    ///
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    pub fn get_program_info(
+        env: Env,
+        program_id: String,
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        load_program(&env, &program_id)
+    }
+
+    pub fn get_program_info_v2(
+        env: Env,
+        program_id: String,
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        Self::get_program_info(env, program_id)
     }
 
-    pub fn lock_program_funds_v2(env: Env, _program_id: String, amount: i128) -> ProgramData {
-        Self::lock_program_funds(env, _program_id, amount)
+    pub fn lock_program_funds_v2(
+        env: Env,
+        program_id: String,
+        amount: i128,
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        Self::lock_program_funds(env, program_id, amount)
     }
 
     pub fn single_payout_v2(
         env: Env,
-        _program_id: String,
+        program_id: String,
         recipient: Address,
         amount: i128,
-    ) -> ProgramData {
-        Self::single_payout(env, _program_id, recipient, amount)
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        Self::single_payout(env, program_id, recipient, amount)
     }
 
     pub fn batch_payout_v2(
         env: Env,
-        _program_id: String,
+        program_id: String,
         recipients: Vec<Address>,
         amounts: Vec<i128>,
-    ) -> ProgramData {
-        Self::batch_payout(env, _program_id, recipients, amounts)
+    ) -> Result<ProgramData, ProgramEscrowError> {
+        Self::batch_payout(env, program_id, recipients, amounts)
     }
 
     /// Retrieves the remaining balance for a specific program.
@@ -2393,17 +2436,579 @@ impl ProgramEscrowContract {
     /// # Returns
     /// * `i128` - Remaining balance
     ///
-    /// # Panics
-    /// * If program doesn't exist
-    pub fn get_remaining_balance(env: Env, program_id: String) -> i128 {
-        let program_key = DataKey::Program(program_id);
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    pub fn get_remaining_balance(env: Env, program_id: String) -> Result<i128, ProgramEscrowError> {
+        Ok(load_program(&env, &program_id)?.remaining_balance)
+    }
+
+    /// Get aggregate statistics for a program.
+    ///
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    pub fn get_program_aggregate_stats(
+        env: Env,
+        program_id: String,
+    ) -> Result<ProgramAggregateStats, ProgramEscrowError> {
+        let program_data = load_program(&env, &program_id)?;
+
+        let mut scheduled_count = 0u32;
+        let mut released_count = 0u32;
+        for schedule in Self::get_all_prog_release_schedules(env.clone(), program_id).iter() {
+            if schedule.released {
+                released_count += 1;
+            } else {
+                scheduled_count += 1;
+            }
+        }
+
+        Ok(ProgramAggregateStats {
+            total_funds: program_data.total_funds,
+            remaining_balance: program_data.remaining_balance,
+            total_paid_out: program_data.total_paid_out,
+            authorized_payout_key: program_data.authorized_payout_key,
+            token_address: program_data.token_address,
+            payout_count: program_data.payout_count,
+            scheduled_count,
+            released_count,
+        })
+    }
+    /// Get a page of a program's payout history, oldest first.
+    ///
+    /// # Arguments
+    /// * `program_id` - The program to query
+    /// * `cursor` - Sequence number of the first record (0 for the start)
+    /// * `limit` - Maximum records to return, capped at 100
+    ///
+    /// # Returns
+    /// * `PayoutPage` - The records and the cursor of the next page, if any
+    ///
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    pub fn get_payout_history(
+        env: Env,
+        program_id: String,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<PayoutPage, ProgramEscrowError> {
         let program_data: ProgramData = env
             .storage()
-            .instance()
+            .persistent()
+            .get(&DataKey::Program(program_id))
+            .ok_or(ProgramEscrowError::ProgramNotFound)?;
+
+        Ok(payout_log::page(&env, &program_data, cursor, limit))
+    }
+
+    /// Get a page of the payouts a program made to one recipient, oldest first.
+    ///
+    /// # Arguments
+    /// * `program_id` - The program to query
+    /// * `recipient` - The recipient to filter by
+    /// * `cursor` - Index into this recipient's payouts (0 for the start)
+    /// * `limit` - Maximum records to return, capped at 100
+    ///
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    pub fn get_payouts_by_recipient(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<PayoutPage, ProgramEscrowError> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Program(program_id.clone()))
+        {
+            return Err(ProgramEscrowError::ProgramNotFound);
+        }
+
+        Ok(payout_log::page_by_recipient(
+            &env,
+            &program_id,
+            &recipient,
+            cursor,
+            limit,
+        ))
+    }
+
+    // ========================================================================
+    // Multisig Payout Approval
+    // ========================================================================
+
+    /// Configure multisig approval for a program's payouts (admin only).
+    ///
+    /// Payouts of `threshold_amount` or more to one recipient then need
+    /// approvals from `required_signatures` of `signers`, given within
+    /// `approval_ttl` seconds of the first approval (0 for no expiry).
+    /// Setting `required_signatures` to 0 turns the check off.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If no admin is set
+    /// * `ProgramNotFound` - If program doesn't exist
+    /// * `InvalidMultisigConfig` - If the threshold is not positive, signers
+    ///   repeat, or more signatures are required than there are signers
+    pub fn set_multisig_config(
+        env: Env,
+        program_id: String,
+        threshold_amount: i128,
+        signers: Vec<Address>,
+        required_signatures: u32,
+        approval_ttl: u64,
+    ) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+        admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Program(program_id.clone()))
+        {
+            return Err(ProgramEscrowError::ProgramNotFound);
+        }
+
+        let config = MultisigConfig {
+            threshold_amount,
+            signers,
+            required_signatures,
+            approval_ttl,
+        };
+        multisig::set_config(&env, &program_id, &config)?;
+
+        env.events().publish(
+            (symbol_short!("ms_cfg"),),
+            (
+                program_id,
+                threshold_amount,
+                required_signatures,
+                approval_ttl,
+            ),
+        );
+        Ok(())
+    }
+
+    /// Get a program's multisig configuration. Programs that were never
+    /// configured report `required_signatures == 0`.
+    pub fn get_multisig_config(env: Env, program_id: String) -> MultisigConfig {
+        multisig::config(&env, &program_id)
+    }
+
+    /// Approve paying `amount` to `recipient` from a program (signers only).
+    ///
+    /// Approving a different amount than the pending approval, or approving
+    /// after it expired, discards the earlier signatures.
+    ///
+    /// # Returns
+    /// * `PayoutApproval` - The approval with `approver` added
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `Unauthorized` - If `approver` is not a configured signer
+    pub fn approve_payout(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        amount: i128,
+        approver: Address,
+    ) -> Result<PayoutApproval, ProgramEscrowError> {
+        multisig::approve(&env, &program_id, &recipient, amount, &approver)
+    }
+
+    /// Withdraw `approver`'s approval of the pending payout to `recipient`.
+    pub fn revoke_payout_approval(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        approver: Address,
+    ) -> Result<(), ProgramEscrowError> {
+        multisig::revoke(&env, &program_id, &recipient, &approver)
+    }
+
+    /// Get the pending approval for a payout to `recipient`, if any.
+    pub fn get_payout_approval(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+    ) -> Option<PayoutApproval> {
+        multisig::approval(&env, &program_id, &recipient)
+    }
+
+    // ========================================================================
+    // Claim Period
+    // ========================================================================
+
+    /// Reserve `amount` of a program's balance for `recipient`, who must
+    /// execute the claim by `claim_deadline` (authorized payout key only).
+    ///
+    /// Expired claims of the program are returned to its balance first.
+    ///
+    /// # Returns
+    /// * `u64` - The new claim id
+    ///
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `InsufficientBalance` - If amount exceeds the remaining balance
+    /// * `InvalidClaimDeadline` - If the deadline is not in the future
+    pub fn create_pending_claim(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        amount: i128,
+        claim_deadline: u64,
+    ) -> Result<u64, ProgramEscrowError> {
+        claim_period::create_pending_claim(&env, &program_id, &recipient, amount, claim_deadline)
+    }
+
+    /// Like `create_pending_claim`, with the deadline set to now plus the
+    /// configured claim window.
+    pub fn create_claim_with_window(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<u64, ProgramEscrowError> {
+        let claim_deadline = env
+            .ledger()
+            .timestamp()
+            .checked_add(claim_period::get_claim_window(&env))
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
+        claim_period::create_pending_claim(&env, &program_id, &recipient, amount, claim_deadline)
+    }
+
+    /// Transfer a pending claim's reserved amount to its recipient (recipient only).
+    ///
+    /// # Errors
+    /// * `ClaimNotFound` - If the claim doesn't exist
+    /// * `Unauthorized` - If `caller` is not the claim's recipient
+    /// * `ClaimAlreadyProcessed` - If the claim is no longer pending
+    /// * `ClaimExpired` - If the deadline has passed
+    pub fn execute_claim(
+        env: Env,
+        program_id: String,
+        claim_id: u64,
+        caller: Address,
+    ) -> Result<(), ProgramEscrowError> {
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(ProgramEscrowError::FundsPaused);
+        }
+        reentrancy_guard::check_not_entered(&env);
+        reentrancy_guard::set_entered(&env);
+        let result = claim_period::execute_claim(&env, &program_id, claim_id, &caller);
+        reentrancy_guard::clear_entered(&env);
+        result
+    }
+
+    /// Cancel a pending or expired claim and return its amount to the
+    /// program balance (admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If `admin` is not the contract admin
+    /// * `ClaimNotFound` - If the claim doesn't exist
+    /// * `ClaimAlreadyProcessed` - If the claim is no longer pending
+    pub fn cancel_claim(
+        env: Env,
+        program_id: String,
+        claim_id: u64,
+        admin: Address,
+    ) -> Result<(), ProgramEscrowError> {
+        claim_period::cancel_claim(&env, &program_id, claim_id, &admin)
+    }
+
+    /// Return the amounts of a program's expired claims to its balance.
+    ///
+    /// Anyone may call this; it only settles claims whose deadline passed.
+    /// Checks at most `limit` pending claims (capped at
+    /// `claim_period::MAX_EXPIRE_SCAN`) from position `offset` of the
+    /// program's pending list; expired claims leave that list, so continue
+    /// from `offset + limit - expired`.
+    ///
+    /// # Returns
+    /// * `u32` - Number of claims expired
+    pub fn expire_claims(
+        env: Env,
+        program_id: String,
+        offset: u32,
+        limit: u32,
+    ) -> Result<u32, ProgramEscrowError> {
+        claim_period::expire_claims(&env, &program_id, offset, limit)
+    }
+
+    /// Get a claim by id.
+    pub fn get_claim(
+        env: Env,
+        program_id: String,
+        claim_id: u64,
+    ) -> Result<ClaimRecord, ProgramEscrowError> {
+        claim_period::get_claim(&env, &program_id, claim_id)
+    }
+
+    /// Claims of a program that can still be executed, oldest first,
+    /// starting at position `offset` of its pending list. `limit` is capped
+    /// at `payout_log::MAX_PAGE_SIZE`.
+    pub fn get_pending_claims(
+        env: Env,
+        program_id: String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ClaimRecord> {
+        claim_period::get_pending_claims(&env, &program_id, offset, limit)
+    }
+
+    /// Claims payable to `recipient` across all programs that can still be
+    /// executed, oldest first. Paged like `get_pending_claims`.
+    pub fn get_pending_claims_by_recipient(
+        env: Env,
+        recipient: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ClaimRecord> {
+        claim_period::get_pending_claims_by_recipient(&env, &recipient, offset, limit)
+    }
+
+    /// Set the claim window used by `create_claim_with_window` (admin only).
+    pub fn set_claim_window(
+        env: Env,
+        admin: Address,
+        window_seconds: u64,
+    ) -> Result<(), ProgramEscrowError> {
+        claim_period::set_claim_window(&env, &admin, window_seconds)
+    }
+
+    /// Get the claim window in seconds (default 24 hours).
+    pub fn get_claim_window(env: Env) -> u64 {
+        claim_period::get_claim_window(&env)
+    }
+
+    // ========================================================================
+    // Vesting
+    // ========================================================================
+
+    /// Create a vesting schedule paying `total_amount` to `recipient` over
+    /// time (authorized payout key only).
+    ///
+    /// Nothing unlocks until `start + cliff`; after that the unlocked share
+    /// grows linearly until everything has unlocked at `start + duration`.
+    /// With a non-zero `period` it grows in steps of `period` seconds, e.g.
+    /// monthly for a one-year fellowship. The amount is reserved from the
+    /// program balance immediately.
+    ///
+    /// # Returns
+    /// * `u64` - The new vesting id
+    ///
+    /// # Errors
+    /// * `ProgramNotFound` - If program doesn't exist
+    /// * `InvalidAmount` - If amount is not positive
+    /// * `InvalidVestingSchedule` - If duration is zero or cliff or period exceed it
+    /// * `InsufficientBalance` - If amount exceeds the remaining balance
+    /// * `InsufficientApprovals` / `ApprovalExpired` - If multisig approval is missing
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_vesting_schedule(
+        env: Env,
+        program_id: String,
+        recipient: Address,
+        total_amount: i128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+        period: u64,
+    ) -> Result<u64, ProgramEscrowError> {
+        if Self::is_paused_internal(&env) {
+            return Err(ProgramEscrowError::FundsPaused);
+        }
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .persistent()
+            .get(&program_key)
+            .ok_or(ProgramEscrowError::ProgramNotFound)?;
+
+        anti_abuse::check_rate_limit(&env, program_data.authorized_payout_key.clone());
+        program_data.authorized_payout_key.require_auth();
+
+        multisig::require_approval(&env, &program_id, &recipient, total_amount)?;
+        let schedule = vesting::create(
+            &env,
+            &mut program_data,
+            &recipient,
+            total_amount,
+            start,
+            cliff,
+            duration,
+            period,
+        )?;
+        program_storage::save(&env, &program_key, &program_data);
+
+        env.events().publish(
+            (symbol_short!("vest_new"),),
+            (
+                program_id,
+                schedule.vesting_id,
+                recipient,
+                total_amount,
+                start,
+                cliff,
+                duration,
+                period,
+            ),
+        );
+        Ok(schedule.vesting_id)
+    }
+
+    /// Transfer everything that has vested and not yet been claimed to the
+    /// schedule's recipient (recipient only).
+    ///
+    /// # Returns
+    /// * `i128` - The amount transferred
+    ///
+    /// # Errors
+    /// * `VestingNotFound` - If the schedule doesn't exist
+    /// * `NothingVested` - If nothing new has unlocked since the last claim
+    pub fn claim_vested(
+        env: Env,
+        program_id: String,
+        vesting_id: u64,
+    ) -> Result<i128, ProgramEscrowError> {
+        if Self::check_paused(&env, symbol_short!("release")) {
+            return Err(ProgramEscrowError::FundsPaused);
+        }
+
+        let mut schedule = vesting::get(&env, &program_id, vesting_id)?;
+        schedule.recipient.require_auth();
+
+        let now = env.ledger().timestamp();
+        let claimable = vesting::vested_amount(&schedule, now)? - schedule.claimed;
+        if claimable <= 0 {
+            return Err(ProgramEscrowError::NothingVested);
+        }
+
+        reentrancy_guard::check_not_entered(&env);
+        reentrancy_guard::set_entered(&env);
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .persistent()
             .get(&program_key)
-            .unwrap_or_else(|| panic!("Program not found"));
+            .ok_or(ProgramEscrowError::ProgramNotFound)?;
 
-        program_data.remaining_balance
+        // Update state before the transfer; the amount was reserved at creation.
+        schedule.claimed += claimable;
+        vesting::save(&env, &schedule);
+        vesting::record_claim(&env, &schedule, claimable);
+        payout_log::append(&env, &mut program_data, &schedule.recipient, claimable);
+        program_storage::save(&env, &program_key, &program_data);
+
+        let token_client = token::Client::new(&env, &program_data.token_address);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &schedule.recipient,
+            &claimable,
+        );
+
+        reentrancy_guard::clear_entered(&env);
+
+        env.events().publish(
+            (symbol_short!("vest_clm"),),
+            (
+                program_id,
+                vesting_id,
+                schedule.recipient,
+                claimable,
+                schedule.claimed,
+            ),
+        );
+        Ok(claimable)
+    }
+
+    /// Stop a vesting schedule (admin only). What has vested stays
+    /// claimable by the recipient; the unvested remainder returns to the
+    /// program balance.
+    ///
+    /// # Returns
+    /// * `i128` - The amount returned to the program
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If no admin is set
+    /// * `VestingNotFound` - If the schedule doesn't exist
+    /// * `VestingAlreadyRevoked` - If the schedule was already revoked
+    pub fn revoke_vesting(
+        env: Env,
+        program_id: String,
+        vesting_id: u64,
+    ) -> Result<i128, ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+        admin.require_auth();
+
+        let mut schedule = vesting::get(&env, &program_id, vesting_id)?;
+        if schedule.revoked_at.is_some() {
+            return Err(ProgramEscrowError::VestingAlreadyRevoked);
+        }
+
+        let now = env.ledger().timestamp();
+        let vested = vesting::vested_amount(&schedule, now)?;
+        let unvested = schedule.total_amount - vested;
+
+        let program_key = DataKey::Program(program_id.clone());
+        let mut program_data: ProgramData = env
+            .storage()
+            .persistent()
+            .get(&program_key)
+            .ok_or(ProgramEscrowError::ProgramNotFound)?;
+        program_data.remaining_balance = program_data
+            .remaining_balance
+            .checked_add(unvested)
+            .ok_or(ProgramEscrowError::ArithmeticOverflow)?;
+        program_storage::save(&env, &program_key, &program_data);
+
+        schedule.total_amount = vested;
+        schedule.revoked_at = Some(now);
+        vesting::save(&env, &schedule);
+
+        env.events().publish(
+            (symbol_short!("vest_rvk"),),
+            (program_id, vesting_id, vested, unvested),
+        );
+        Ok(unvested)
+    }
+
+    /// Get a vesting schedule.
+    pub fn get_vesting_schedule(
+        env: Env,
+        program_id: String,
+        vesting_id: u64,
+    ) -> Result<VestingSchedule, ProgramEscrowError> {
+        vesting::get(&env, &program_id, vesting_id)
+    }
+
+    /// Get all vesting schedules of a program, oldest first.
+    pub fn get_vesting_schedules(env: Env, program_id: String) -> Vec<VestingSchedule> {
+        vesting::list(&env, &program_id)
+    }
+
+    /// Claims made on a vesting schedule, oldest first, starting at claim
+    /// `offset`. `limit` is capped at `payout_log::MAX_PAGE_SIZE`.
+    pub fn get_vesting_claims(
+        env: Env,
+        program_id: String,
+        vesting_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<ProgramReleaseHistory> {
+        vesting::claims(&env, &program_id, vesting_id, offset, limit)
+    }
+
+    /// Amount of a vesting schedule the recipient could claim now.
+    pub fn get_claimable_vested(
+        env: Env,
+        program_id: String,
+        vesting_id: u64,
+    ) -> Result<i128, ProgramEscrowError> {
+        let schedule = vesting::get(&env, &program_id, vesting_id)?;
+        Ok(vesting::vested_amount(&schedule, env.ledger().timestamp())? - schedule.claimed)
     }
 
     /// Update fee configuration (admin only - uses authorized_payout_key)
@@ -2419,28 +3024,22 @@ impl ProgramEscrowContract {
         payout_fee_rate: Option<i128>,
         fee_recipient: Option<Address>,
         fee_enabled: Option<bool>,
-    ) {
-        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+    ) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
         admin.require_auth();
 
         let mut fee_config = Self::get_fee_config_internal(&env);
 
         if let Some(rate) = lock_fee_rate {
-            if rate < 0 || rate > MAX_FEE_RATE {
-                panic!(
-                    "Invalid lock fee rate: must be between 0 and {}",
-                    MAX_FEE_RATE
-                );
+            if !(0..=MAX_FEE_RATE).contains(&rate) {
+                return Err(ProgramEscrowError::InvalidFeeRate);
             }
             fee_config.lock_fee_rate = rate;
         }
 
         if let Some(rate) = payout_fee_rate {
-            if rate < 0 || rate > MAX_FEE_RATE {
-                panic!(
-                    "Invalid payout fee rate: must be between 0 and {}",
-                    MAX_FEE_RATE
-                );
+            if !(0..=MAX_FEE_RATE).contains(&rate) {
+                return Err(ProgramEscrowError::InvalidFeeRate);
             }
             fee_config.payout_fee_rate = rate;
         }
@@ -2465,6 +3064,7 @@ impl ProgramEscrowContract {
                 fee_config.fee_enabled,
             ),
         );
+        Ok(())
     }
 
     /// Get current fee configuration (view function)
@@ -2472,18 +3072,46 @@ impl ProgramEscrowContract {
         Self::get_fee_config_internal(&env)
     }
 
-    /// Gets the total number of programs registered.
+    /// Move programs stored by earlier versions from instance storage into
+    /// per-program persistent entries (admin only).
+    ///
+    /// Also folds the old instance-stored registry into the persistent one.
+    /// Ids that were already migrated, or never existed, are skipped.
+    ///
+    /// One call moves at most `MIGRATION_PAGE` legacy payouts and release
+    /// schedules across all the ids given. A program with more is carried
+    /// over several calls and reads as missing until its last page is moved;
+    /// call again with the same id while `program_migration_pending` is true.
     ///
     /// # Returns
-    /// * `u32` - Count of registered programs
-    pub fn get_program_count(env: Env) -> u32 {
-        let registry: Vec<String> = env
-            .storage()
-            .instance()
-            .get(&PROGRAM_REGISTRY)
-            .unwrap_or(vec![&env]);
+    /// * `Ok(u32)` - Number of programs whose migration finished in this call
+    pub fn migrate_program_storage(
+        env: Env,
+        program_ids: Vec<String>,
+    ) -> Result<u32, ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
+        admin.require_auth();
+
+        program_storage::migrate_registry(&env);
+        let mut budget = program_storage::MIGRATION_PAGE;
+        let mut migrated = 0u32;
+        for program_id in program_ids.iter() {
+            if program_storage::migrate_program(&env, &program_id, &mut budget) {
+                migrated += 1;
+            }
+        }
+
+        env.events().publish(
+            (symbol_short!("st_migr"),),
+            (migrated, env.ledger().timestamp()),
+        );
+        Ok(migrated)
+    }
 
-        registry.len()
+    /// Whether `program_id` still has entries left in instance storage for
+    /// `migrate_program_storage` to move.
+    pub fn program_migration_pending(env: Env, program_id: String) -> bool {
+        program_storage::migration_pending(&env, &program_id)
     }
 
     // ========================================================================
@@ -2514,15 +3142,6 @@ impl ProgramEscrowContract {
     // Anti-Abuse Administrative Functions
     // ========================================================================
 
-    /// Sets the administrative address for anti-abuse configuration.
-    /// Can only be called once or by the existing admin.
-    pub fn set_admin(env: Env, new_admin: Address) {
-        if let Some(current_admin) = anti_abuse::get_admin(&env) {
-            current_admin.require_auth();
-        }
-        anti_abuse::set_admin(&env, new_admin);
-    }
-
     /// Updates the rate limit configuration.
     /// Only the admin can call this.
     pub fn update_rate_limit_config(
@@ -2530,8 +3149,8 @@ impl ProgramEscrowContract {
         window_size: u64,
         max_operations: u32,
         cooldown_period: u64,
-    ) {
-        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+    ) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
         admin.require_auth();
 
         anti_abuse::set_config(
@@ -2542,15 +3161,21 @@ impl ProgramEscrowContract {
                 cooldown_period,
             },
         );
+        Ok(())
     }
 
     /// Adds or removes an address from the whitelist.
     /// Only the admin can call this.
-    pub fn set_whitelist(env: Env, address: Address, whitelisted: bool) {
-        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+    pub fn set_whitelist(
+        env: Env,
+        address: Address,
+        whitelisted: bool,
+    ) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
         admin.require_auth();
 
         anti_abuse::set_whitelist(&env, address, whitelisted);
+        Ok(())
     }
 
     /// Checks if an address is whitelisted.
@@ -2565,8 +3190,8 @@ impl ProgramEscrowContract {
 
     /// Creates an on-chain snapshot of critical configuration (admin-only).
     /// Returns the snapshot id.
-    pub fn create_config_snapshot(env: Env) -> u64 {
-        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+    pub fn create_config_snapshot(env: Env) -> Result<u64, ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
         admin.require_auth();
 
         let next_id: u64 = env
@@ -2621,7 +3246,7 @@ impl ProgramEscrowContract {
             (next_id, snapshot.timestamp),
         );
 
-        next_id
+        Ok(next_id)
     }
 
     /// Lists retained configuration snapshots in chronological order.
@@ -2647,17 +3272,19 @@ impl ProgramEscrowContract {
     }
 
     /// Restores contract configuration from a prior snapshot (admin-only).
-    pub fn restore_config_snapshot(env: Env, snapshot_id: u64) {
-        let admin = anti_abuse::get_admin(&env).expect("Admin not set");
+    pub fn restore_config_snapshot(env: Env, snapshot_id: u64) -> Result<(), ProgramEscrowError> {
+        let admin = anti_abuse::get_admin(&env).ok_or(ProgramEscrowError::NotInitialized)?;
         admin.require_auth();
 
         let snapshot: ConfigSnapshot = env
             .storage()
             .instance()
             .get(&ConfigSnapshotKey::Snapshot(snapshot_id))
-            .unwrap_or_else(|| panic!("Snapshot not found"));
+            .ok_or(ProgramEscrowError::SnapshotNotFound)?;
 
-        env.storage().instance().set(&FEE_CONFIG, &snapshot.fee_config);
+        env.storage()
+            .instance()
+            .set(&FEE_CONFIG, &snapshot.fee_config);
         anti_abuse::set_config(&env, snapshot.anti_abuse_config);
 
         match snapshot.anti_abuse_admin {
@@ -2665,19 +3292,16 @@ impl ProgramEscrowContract {
             None => anti_abuse::clear_admin(&env),
         }
 
-    ProgramAggregateStats {
-        total_funds: program_data.total_funds,
-        remaining_balance: program_data.remaining_balance,
-        total_paid_out: program_data
-                            .total_funds
-                            .checked_sub(program_data.remaining_balance)
-                            .unwrap_or_else(|| panic!("Arithmetic error in total_paid_out"))
-        authorized_payout_key: program_data.authorized_payout_key.clone(),
-        payout_history: program_data.payout_history.clone(),
-        token_address: program_data.token_address.clone(),
-        payout_count: program_data.payout_history.len(),
-        scheduled_count,
-        released_count,
+        env.storage()
+            .instance()
+            .set(&DataKey::IsPaused, &snapshot.is_paused);
+
+        env.events().publish(
+            (symbol_short!("cfg_snap"), symbol_short!("restore")),
+            (snapshot_id, env.ledger().timestamp()),
+        );
+
+        Ok(())
     }
 
     // ========================================================================
@@ -2694,53 +3318,17 @@ impl ProgramEscrowContract {
     /// # Returns
     /// * `ProgramReleaseSchedule` - The schedule details
     ///
-    /// # Panics
-    /// * If schedule doesn't exist
+    /// # Errors
+    /// * `ScheduleNotFound` - If schedule doesn't exist
     pub fn get_program_release_schedule(
         env: Env,
         program_id: String,
         schedule_id: u64,
-    ) -> ProgramReleaseSchedule {
+    ) -> Result<ProgramReleaseSchedule, ProgramEscrowError> {
         env.storage()
             .persistent()
             .get(&DataKey::ReleaseSchedule(program_id, schedule_id))
-            .unwrap_or_else(|| panic!("Schedule not found"))
-    /// Get aggregate statistics for the program
-    pub fn get_program_aggregate_stats(env: Env) -> ProgramAggregateStats {
-        let program_data: ProgramData = env
-            .storage()
-            .instance()
-            .get(&PROGRAM_DATA)
-            .unwrap_or_else(|| panic!("Program not initialized"));
-        let schedules: Vec<ProgramReleaseSchedule> = env
-            .storage()
-            .instance()
-            .get(&SCHEDULES)
-            .unwrap_or_else(|| Vec::new(&env));
-
-        let mut scheduled_count = 0u32;
-        let mut released_count = 0u32;
-
-        for i in 0..schedules.len() {
-            let schedule = schedules.get(i).unwrap();
-            if schedule.released {
-                released_count += 1;
-            } else {
-                scheduled_count += 1;
-            }
-        }
-
-        ProgramAggregateStats {
-            total_funds: program_data.total_funds,
-            remaining_balance: program_data.remaining_balance,
-            total_paid_out: program_data.total_funds - program_data.remaining_balance,
-            authorized_payout_key: program_data.authorized_payout_key.clone(),
-            payout_history: program_data.payout_history.clone(),
-            token_address: program_data.token_address.clone(),
-            payout_count: program_data.payout_history.len(),
-            scheduled_count,
-            released_count,
-        }
+            .ok_or(ProgramEscrowError::ScheduleNotFound)
     }
 
     /// Retrieves all release schedules for a program.
@@ -2842,296 +3430,216 @@ impl ProgramEscrowContract {
     }
 }
 
-/// Helper function to calculate total scheduled amount for a program.
-fn get_program_total_scheduled_amount(env: &Env, program_id: &String) -> i128 {
-    let next_id: u64 = env
-        .storage()
-        .persistent()
-        .get(&DataKey::NextScheduleId(program_id.clone()))
-        .unwrap_or(1);
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        token, Address, Env, String,
+    };
 
-    let mut total = 0i128;
-    for schedule_id in 1..next_id {
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-        {
-            let schedule: ProgramReleaseSchedule = env
-                .storage()
-                .persistent()
-                .get(&DataKey::ReleaseSchedule(program_id.clone(), schedule_id))
-                .unwrap();
-            if !schedule.released {
-                total = total.checked_add(schedule.amount).unwrap_or_else(|| panic!("Scheduled amount overflow"));
-            }
-        }
+    // Test helper to create a mock token contract
+    fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+        let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+        let token_address = token_contract.address();
+        token::Client::new(env, &token_address)
     }
 
-    pub fn get_program_count(env: Env) -> u32 {
-        if env.storage().instance().has(&PROGRAM_DATA) {
-            1
-        } else {
-            0
-        }
+    fn register(
+        client: &ProgramEscrowContractClient,
+        program_id: &String,
+        key: &Address,
+        token: &Address,
+    ) {
+        let creator = Address::generate(&client.env);
+        client.initialize_program(program_id, key, token, &creator, &None, &None);
     }
 
     // ========================================================================
-    // Program Registration Tests
+    // Release Schedule Tests
     // ========================================================================
 
-    fn setup_program_with_schedule(
+    /// Register `program_id`, fund the contract with `total_amount` and
+    /// schedule all of it for `winner`. Returns the token.
+    fn setup_program_with_schedule<'a>(
         env: &Env,
-        client: &ProgramEscrowContractClient<'static>,
+        client: &ProgramEscrowContractClient<'a>,
         authorized_key: &Address,
-        token: &Address,
         program_id: &String,
         total_amount: i128,
         winner: &Address,
         release_timestamp: u64,
-    ) {
-        // Register program
-        client.initialize_program(program_id, authorized_key, token);
-
-        // Create and fund token
+    ) -> token::Client<'a> {
         let token_client = create_token_contract(env, authorized_key);
-        let token_admin = token::StellarAssetClient::new(env, &token_client.address);
-        token_admin.mint(authorized_key, &total_amount);
+        token::StellarAssetClient::new(env, &token_client.address)
+            .mint(&client.address, &total_amount);
 
-        // Lock funds for program
-        token_client.approve(
-            authorized_key,
-            &env.current_contract_address(),
-            &total_amount,
-            &1000,
-        );
+        register(client, program_id, authorized_key, &token_client.address);
         client.lock_program_funds(program_id, &total_amount);
-
-        // Create release schedule
         client.create_program_release_schedule(
             program_id,
             &total_amount,
             &release_timestamp,
-            &winner,
+            winner,
         );
+        token_client
     }
 
     #[test]
     fn test_single_program_release_schedule() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
         let authorized_key = Address::generate(&env);
         let winner = Address::generate(&env);
-        let token = Address::generate(&env);
         let program_id = String::from_str(&env, "Hackathon2024");
         let amount = 1000_0000000;
-        let release_timestamp = 1000;
-
-        env.mock_all_auths();
 
-        // Setup program with schedule
         setup_program_with_schedule(
             &env,
             &client,
             &authorized_key,
-            &token,
             &program_id,
             amount,
             &winner,
-            release_timestamp,
+            1000,
         );
 
-        // Verify schedule was created
         let schedule = client.get_program_release_schedule(&program_id, &1);
         assert_eq!(schedule.schedule_id, 1);
         assert_eq!(schedule.amount, amount);
-        assert_eq!(schedule.release_timestamp, release_timestamp);
+        assert_eq!(schedule.release_timestamp, 1000);
         assert_eq!(schedule.recipient, winner);
         assert!(!schedule.released);
 
-        // Check pending schedules
         let pending = client.get_pending_program_schedules(&program_id);
         assert_eq!(pending.len(), 1);
-
-        // Event verification can be added later - focusing on core functionality
-    pub fn get_program_release_schedule(env: Env, schedule_id: u64) -> ProgramReleaseSchedule {
-        let schedules = Self::get_release_schedules(env);
-        for s in schedules.iter() {
-            if s.schedule_id == schedule_id {
-                return s;
-            }
-        }
-        panic!("Schedule not found");
     }
 
     #[test]
     fn test_multiple_program_release_schedules() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
         let authorized_key = Address::generate(&env);
         let winner1 = Address::generate(&env);
         let winner2 = Address::generate(&env);
-        let token = Address::generate(&env);
         let program_id = String::from_str(&env, "Hackathon2024");
-        let amount1 = 600_0000000;
-        let amount2 = 400_0000000;
-        let total_amount = amount1 + amount2;
-
-        env.mock_all_auths();
-
-        // Register program
-        client.initialize_program(&program_id, &authorized_key, &token);
-
-        // Create and fund token
         let token_client = create_token_contract(&env, &authorized_key);
-        let token_admin = token::StellarAssetClient::new(&env, &token_client.address);
-        token_admin.mint(&authorized_key, &total_amount);
-
-        // Lock funds for program
-        token_client.approve(
-            &authorized_key,
-            &env.current_contract_address(),
-            &total_amount,
-            &1000,
-        );
-        client.lock_program_funds(&program_id, &total_amount);
 
-        // Create first release schedule
-        client.create_program_release_schedule(&program_id, &amount1, &1000, &winner1.clone());
+        register(&client, &program_id, &authorized_key, &token_client.address);
+        client.lock_program_funds(&program_id, &1000_0000000);
+        client.create_program_release_schedule(&program_id, &600_0000000, &1000, &winner1);
+        client.create_program_release_schedule(&program_id, &400_0000000, &2000, &winner2);
 
-        // Create second release schedule
-        client.create_program_release_schedule(&program_id, &amount2, &2000, &winner2.clone());
-
-        // Verify both schedules exist
         let all_schedules = client.get_all_prog_release_schedules(&program_id);
         assert_eq!(all_schedules.len(), 2);
 
-        // Verify schedule IDs
         let schedule1 = client.get_program_release_schedule(&program_id, &1);
         let schedule2 = client.get_program_release_schedule(&program_id, &2);
-        assert_eq!(schedule1.schedule_id, 1);
-        assert_eq!(schedule2.schedule_id, 2);
-
-        // Verify amounts
-        assert_eq!(schedule1.amount, amount1);
-        assert_eq!(schedule2.amount, amount2);
-
-        // Verify recipients
+        assert_eq!(schedule1.amount, 600_0000000);
         assert_eq!(schedule1.recipient, winner1);
+        assert_eq!(schedule2.amount, 400_0000000);
         assert_eq!(schedule2.recipient, winner2);
 
-        // Check pending schedules
-        let pending = client.get_pending_program_schedules(&program_id);
-        assert_eq!(pending.len(), 2);
-
-        // Event verification can be added later - focusing on core functionality
+        // Nothing is left to schedule
+        let res = client.try_create_program_release_schedule(&program_id, &1, &3000, &winner1);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::InsufficientBalance)));
     }
 
     #[test]
     fn test_program_automatic_release_at_timestamp() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
         let authorized_key = Address::generate(&env);
         let winner = Address::generate(&env);
-        let token = Address::generate(&env);
         let program_id = String::from_str(&env, "Hackathon2024");
         let amount = 1000_0000000;
-        let release_timestamp = 1000;
-
-        env.mock_all_auths();
 
-        // Setup program with schedule
-        setup_program_with_schedule(
+        let token_client = setup_program_with_schedule(
             &env,
             &client,
             &authorized_key,
-            &token,
             &program_id,
             amount,
             &winner,
-            release_timestamp,
+            1000,
         );
 
         // Try to release before timestamp (should fail)
         env.ledger().set_timestamp(999);
         let result = client.try_release_prog_schedule_automatic(&program_id, &1);
-        assert!(result.is_err());
+        assert_eq!(result, Err(Ok(ProgramEscrowError::ScheduleNotDue)));
 
-        // Advance time to after release timestamp
         env.ledger().set_timestamp(1001);
-
-        // Release automatically
         client.release_prog_schedule_automatic(&program_id, &1);
 
-        // Verify schedule was released
         let schedule = client.get_program_release_schedule(&program_id, &1);
         assert!(schedule.released);
         assert_eq!(schedule.released_at, Some(1001));
-        assert_eq!(schedule.released_by, Some(env.current_contract_address()));
-
-        // Check no pending schedules
-        let pending = client.get_pending_program_schedules(&program_id);
-        assert_eq!(pending.len(), 0);
+        assert_eq!(schedule.released_by, Some(contract_id));
+        assert_eq!(token_client.balance(&winner), amount);
+        assert_eq!(client.get_remaining_balance(&program_id), 0);
 
-        // Verify release history
         let history = client.get_program_release_history(&program_id);
         assert_eq!(history.len(), 1);
         assert_eq!(history.get(0).unwrap().release_type, ReleaseType::Automatic);
 
-        // Event verification can be added later - focusing on core functionality
+        let res = client.try_release_prog_schedule_automatic(&program_id, &1);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::ScheduleAlreadyReleased)));
     }
 
     #[test]
     fn test_program_manual_trigger_before_after_timestamp() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
         let authorized_key = Address::generate(&env);
         let winner = Address::generate(&env);
-        let token = Address::generate(&env);
         let program_id = String::from_str(&env, "Hackathon2024");
-        let amount = 1000_0000000;
-        let release_timestamp = 1000;
-
-        env.mock_all_auths();
 
-        // Setup program with schedule
         setup_program_with_schedule(
             &env,
             &client,
             &authorized_key,
-            &token,
             &program_id,
-            amount,
+            1000_0000000,
             &winner,
-            release_timestamp,
+            1000,
         );
 
-        // Manually release before timestamp (authorized key can do this)
+        // Authorized key can release early
         env.ledger().set_timestamp(999);
         client.release_program_schedule_manual(&program_id, &1);
 
-        // Verify schedule was released
         let schedule = client.get_program_release_schedule(&program_id, &1);
         assert!(schedule.released);
         assert_eq!(schedule.released_at, Some(999));
-        assert_eq!(schedule.released_by, Some(authorized_key.clone()));
+        assert_eq!(schedule.released_by, Some(authorized_key));
 
-        // Verify release history
         let history = client.get_program_release_history(&program_id);
         assert_eq!(history.len(), 1);
         assert_eq!(history.get(0).unwrap().release_type, ReleaseType::Manual);
 
-        // Event verification can be added later - focusing on core functionality
+        let stats = client.get_program_aggregate_stats(&program_id);
+        assert_eq!(stats.released_count, 1);
+        assert_eq!(stats.scheduled_count, 0);
+        assert_eq!(stats.total_paid_out, 1000_0000000);
     }
 
+    // ========================================================================
+    // Program Registration Tests
+    // ========================================================================
 
     #[test]
     fn test_register_single_program() {
@@ -3143,107 +3651,87 @@ fn get_program_total_scheduled_amount(env: &Env, program_id: &String) -> i128 {
         let token = Address::generate(&env);
         let prog_id = String::from_str(&env, "Hackathon2024");
 
-        // Register program
-        let program = client.initialize_program(&prog_id, &backend, &token);
+        let program = client.initialize_program(&prog_id, &backend, &token, &backend, &None, &None);
 
-        // Verify program data
         assert_eq!(program.program_id, prog_id);
         assert_eq!(program.authorized_payout_key, backend);
         assert_eq!(program.token_address, token);
         assert_eq!(program.total_funds, 0);
         assert_eq!(program.remaining_balance, 0);
-        assert_eq!(program.payout_history.len(), 0);
+        assert_eq!(program.payout_count, 0);
 
-        // Verify it exists
         assert!(client.program_exists(&prog_id));
         assert_eq!(client.get_program_count(), 1);
+
+        let res = client.try_initialize_program(&prog_id, &backend, &token, &backend, &None, &None);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::AlreadyInitialized)));
+        let empty = String::from_str(&env, "");
+        let res = client.try_initialize_program(&empty, &backend, &token, &backend, &None, &None);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::InvalidProgramId)));
     }
 
     #[test]
-    fn test_multiple_programs_isolation() {
-    pub fn release_prog_schedule_automatic(env: Env, schedule_id: u64) {
-        let mut schedules = Self::get_release_schedules(env.clone());
-        let program_data = Self::get_program_info(env.clone());
-        let now = env.ledger().timestamp();
-        let mut released_schedule: Option<ProgramReleaseSchedule> = None;
-
-        let mut found = false;
-        for i in 0..schedules.len() {
-            let mut s = schedules.get(i).unwrap();
-            if s.schedule_id == schedule_id {
-                if s.released {
-                    panic!("Already released");
-                }
-                if now < s.release_timestamp {
-                    panic!("Not yet due");
-                }
-
-                // Transfer funds
-                let token_client = token::Client::new(&env, &program_data.token_address);
-                token_client.transfer(&env.current_contract_address(), &s.recipient, &s.amount);
-
-                s.released = true;
-                s.released_at = Some(now);
-                s.released_by = Some(env.current_contract_address());
-                released_schedule = Some(s.clone());
-                schedules.set(i, s);
-                found = true;
-                break;
-            }
-        }
+    fn test_initial_liquidity_is_deposited() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
-        if !found {
-            panic!("Schedule not found");
-        }
+        let backend = Address::generate(&env);
+        let creator = Address::generate(&env);
+        let token_client = create_token_contract(&env, &creator);
+        token::StellarAssetClient::new(&env, &token_client.address).mint(&creator, &5_000);
+
+        let prog_id = String::from_str(&env, "Seeded");
+        let program = client.initialize_program(
+            &prog_id,
+            &backend,
+            &token_client.address,
+            &creator,
+            &Some(2_000),
+            &None,
+        );
 
-        env.storage().instance().set(&SCHEDULES, &schedules);
+        assert_eq!(program.initial_liquidity, 2_000);
+        assert_eq!(program.remaining_balance, 2_000);
+        assert_eq!(token_client.balance(&contract_id), 2_000);
+        assert_eq!(token_client.balance(&creator), 3_000);
+    }
 
-        if let Some(s) = released_schedule {
-            let mut updated_program_data = program_data.clone();
-            updated_program_data.remaining_balance -= s.amount;
-            env.storage()
-                .instance()
-                .set(&PROGRAM_DATA, &updated_program_data);
+    #[test]
+    fn test_batch_initialize_programs_is_all_or_nothing() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
-            let mut history: Vec<ProgramReleaseHistory> = env
-                .storage()
-                .instance()
-                .get(&RELEASE_HISTORY)
-                .unwrap_or_else(|| Vec::new(&env));
-            history.push_back(ProgramReleaseHistory {
-                schedule_id: s.schedule_id,
-                recipient: s.recipient.clone(),
-                amount: s.amount,
-                released_at: now,
-                release_type: ReleaseType::Automatic,
-            });
-            env.storage().instance().set(&RELEASE_HISTORY, &history);
+        let backend = Address::generate(&env);
+        let token = Address::generate(&env);
+        let item = |id: &str| ProgramInitItem {
+            program_id: String::from_str(&env, id),
+            authorized_payout_key: backend.clone(),
+            token_address: token.clone(),
+            reference_hash: None,
+        };
 
-            env.events().publish(
-                (PAYOUT,),
-                PayoutEvent {
-                    version: EVENT_VERSION_V2,
-                    program_id: updated_program_data.program_id.clone(),
-                    recipient: s.recipient,
-                    amount: s.amount,
-                    remaining_balance: updated_program_data.remaining_balance,
-                },
-            );
-        }
-    }
+        assert_eq!(
+            client.batch_initialize_programs(&soroban_sdk::vec![&env, item("A"), item("B")]),
+            2
+        );
+        let res =
+            client.try_batch_initialize_programs(&soroban_sdk::vec![&env, item("C"), item("A")]);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::AlreadyInitialized)));
+        let res =
+            client.try_batch_initialize_programs(&soroban_sdk::vec![&env, item("D"), item("D")]);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::AlreadyInitialized)));
+        let res = client.try_batch_initialize_programs(&soroban_sdk::vec![&env]);
+        assert_eq!(res, Err(Ok(ProgramEscrowError::EmptyBatch)));
 
-pub fn create_pending_claim(
-        env: Env,
-        program_id: String,
-        recipient: Address,
-        amount: i128,
-        claim_deadline: u64,
-    ) -> u64 {
-        claim_period::create_pending_claim(&env, &program_id, &recipient, amount, claim_deadline)
+        assert_eq!(client.get_program_count(), 2);
+        assert!(!client.program_exists(&String::from_str(&env, "C")));
     }
 
     #[test]
-    #[should_panic(expected = "Program not found")]
+    #[should_panic(expected = "Error(Contract, #3)")]
     fn test_get_nonexistent_program() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
@@ -3253,65 +3741,154 @@ pub fn create_pending_claim(
         client.get_program_info(&prog_id);
     }
 
-    // ========================================================================
-    // Fund Locking Tests
-    // ========================================================================
+    // ========================================================================
+    // Dependency Tests
+    // ========================================================================
+
+    #[test]
+    fn test_dependency_gated_release_flow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
+
+        let token_admin = Address::generate(&env);
+        let token_client = create_token_contract(&env, &token_admin);
+        let amount = 10_000_000i128;
+        token::StellarAssetClient::new(&env, &token_client.address).mint(&contract_id, &amount);
+
+        let dependency_program = String::from_str(&env, "dependency-program");
+        let target_program = String::from_str(&env, "target-program");
+        let dep_backend = Address::generate(&env);
+        let target_backend = Address::generate(&env);
+        let winner = Address::generate(&env);
+
+        register(
+            &client,
+            &dependency_program,
+            &dep_backend,
+            &token_client.address,
+        );
+        register(
+            &client,
+            &target_program,
+            &target_backend,
+            &token_client.address,
+        );
+        client.lock_program_funds(&target_program, &amount);
+        client.create_program_release_schedule(&target_program, &amount, &1000, &winner);
+        client.set_program_dependencies(
+            &target_program,
+            &soroban_sdk::vec![&env, dependency_program.clone()],
+        );
+
+        env.ledger().set_timestamp(1001);
+        let blocked = client.try_release_prog_schedule_automatic(&target_program, &1);
+        assert_eq!(blocked, Err(Ok(ProgramEscrowError::DependencyNotSatisfied)));
+
+        client.set_dependency_status(&dependency_program, &DependencyStatus::Completed);
+        client.release_prog_schedule_automatic(&target_program, &1);
+
+        let schedule = client.get_program_release_schedule(&target_program, &1);
+        assert!(schedule.released);
+        assert_eq!(token_client.balance(&winner), amount);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #18)")]
+    fn test_dependency_failed_blocks_release() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
+
+        let token = Address::generate(&env);
+        let dependency_program = String::from_str(&env, "dependency-failed");
+        let target_program = String::from_str(&env, "target-failed");
+        let winner = Address::generate(&env);
+
+        register(
+            &client,
+            &dependency_program,
+            &Address::generate(&env),
+            &token,
+        );
+        register(&client, &target_program, &Address::generate(&env), &token);
+        client.lock_program_funds(&target_program, &5_000_000);
+        client.create_program_release_schedule(&target_program, &5_000_000, &1000, &winner);
+        client.set_program_dependencies(
+            &target_program,
+            &soroban_sdk::vec![&env, dependency_program.clone()],
+        );
+
+        client.set_dependency_status(&dependency_program, &DependencyStatus::Failed);
+        env.ledger().set_timestamp(1001);
+        client.release_prog_schedule_automatic(&target_program, &1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #20)")]
+    fn test_dependency_cycle_rejection() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
+
+        let token = Address::generate(&env);
+        let program_a = String::from_str(&env, "cycle-a");
+        let program_b = String::from_str(&env, "cycle-b");
+
+        register(&client, &program_a, &Address::generate(&env), &token);
+        register(&client, &program_b, &Address::generate(&env), &token);
+        client.set_program_dependencies(&program_a, &soroban_sdk::vec![&env, program_b.clone()]);
+        client.set_program_dependencies(&program_b, &soroban_sdk::vec![&env, program_a.clone()]);
+    }
 
     #[test]
-    fn test_lock_funds_single_program() {
+    fn test_dependency_events_created_and_cleared() {
         let env = Env::default();
         env.mock_all_auths();
-
-        let admin = Address::generate(&env);
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
 
-        let backend = Address::generate(&env);
-        let prog_id = String::from_str(&env, "Hackathon2024");
+        let token = Address::generate(&env);
+        let program_a = String::from_str(&env, "event-a");
+        let program_b = String::from_str(&env, "event-b");
 
-        // Register program
-        client.initialize_program(&prog_id, &backend, &token_client.address);
+        register(&client, &program_a, &Address::generate(&env), &token);
+        register(&client, &program_b, &Address::generate(&env), &token);
 
-        // Lock funds
-        let amount = 10_000_0000000i128; // 10,000 USDC
-        let updated = client.lock_program_funds(&prog_id, &amount);
+        client.set_program_dependencies(&program_a, &soroban_sdk::vec![&env, program_b.clone()]);
+        assert_eq!(client.get_program_dependencies(&program_a).len(), 1);
 
-        assert_eq!(updated.total_funds, amount);
-        assert_eq!(updated.remaining_balance, amount);
+        client.clear_program_dependencies(&program_a);
+        assert_eq!(client.get_program_dependencies(&program_a).len(), 0);
     }
 
+    // ========================================================================
+    // Fund Locking Tests
+    // ========================================================================
+
     #[test]
     fn test_lock_funds_multiple_programs_isolation() {
         let env = Env::default();
-        env.mock_all_auths();
-
-        let admin = Address::generate(&env);
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
-
-        let backend1 = Address::generate(&env);
-        let backend2 = Address::generate(&env);
+        let token = Address::generate(&env);
 
         let prog1 = String::from_str(&env, "Program1");
         let prog2 = String::from_str(&env, "Program2");
+        register(&client, &prog1, &Address::generate(&env), &token);
+        register(&client, &prog2, &Address::generate(&env), &token);
 
-        // Register programs
-        client.initialize_program(&prog1, &backend1, &token_client.address);
-        client.initialize_program(&prog2, &backend2, &token_client.address);
-
-        // Lock different amounts in each program
         let amount1 = 5_000_0000000i128;
         let amount2 = 10_000_0000000i128;
-
         client.lock_program_funds(&prog1, &amount1);
         client.lock_program_funds(&prog2, &amount2);
 
         // Verify isolation - funds don't mix
         let info1 = client.get_program_info(&prog1);
         let info2 = client.get_program_info(&prog2);
-
         assert_eq!(info1.total_funds, amount1);
         assert_eq!(info1.remaining_balance, amount1);
         assert_eq!(info2.total_funds, amount2);
@@ -3321,19 +3898,17 @@ pub fn create_pending_claim(
     #[test]
     fn test_lock_funds_cumulative() {
         let env = Env::default();
-        env.mock_all_auths();
-
-        let admin = Address::generate(&env);
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
 
-        let backend = Address::generate(&env);
         let prog_id = String::from_str(&env, "Hackathon2024");
+        register(
+            &client,
+            &prog_id,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
 
-        client.initialize_program(&prog_id, &backend, &token_client.address);
-
-        // Lock funds multiple times
         client.lock_program_funds(&prog_id, &1_000_0000000);
         client.lock_program_funds(&prog_id, &2_000_0000000);
         client.lock_program_funds(&prog_id, &3_000_0000000);
@@ -3344,17 +3919,19 @@ pub fn create_pending_claim(
     }
 
     #[test]
-    #[should_panic(expected = "Amount must be greater than zero")]
+    #[should_panic(expected = "Error(Contract, #5)")]
     fn test_lock_zero_funds() {
         let env = Env::default();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
-        let backend = Address::generate(&env);
-        let token = Address::generate(&env);
         let prog_id = String::from_str(&env, "Hackathon2024");
-
-        client.initialize_program(&prog_id, &backend, &token);
+        register(
+            &client,
+            &prog_id,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
         client.lock_program_funds(&prog_id, &0);
     }
 
@@ -3363,20 +3940,20 @@ pub fn create_pending_claim(
     // ========================================================================
 
     #[test]
-    #[should_panic(expected = "Recipients and amounts vectors must have the same length")]
+    #[should_panic(expected = "Error(Contract, #9)")]
     fn test_batch_payout_mismatched_lengths() {
         let env = Env::default();
         env.mock_all_auths();
-
-        let admin = Address::generate(&env);
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
 
-        let backend = Address::generate(&env);
         let prog_id = String::from_str(&env, "Test");
-
-        client.initialize_program(&prog_id, &backend, &token_client.address);
+        register(
+            &client,
+            &prog_id,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
         client.lock_program_funds(&prog_id, &10_000_0000000);
 
         let recipients = soroban_sdk::vec![&env, Address::generate(&env), Address::generate(&env)];
@@ -3386,20 +3963,20 @@ pub fn create_pending_claim(
     }
 
     #[test]
-    #[should_panic(expected = "Insufficient balance")]
+    #[should_panic(expected = "Error(Contract, #6)")]
     fn test_batch_payout_insufficient_balance() {
         let env = Env::default();
         env.mock_all_auths();
-
-        let admin = Address::generate(&env);
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
 
-        let backend = Address::generate(&env);
         let prog_id = String::from_str(&env, "Test");
-
-        client.initialize_program(&prog_id, &backend, &token_client.address);
+        register(
+            &client,
+            &prog_id,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
         client.lock_program_funds(&prog_id, &5_000_0000000);
 
         let recipients = soroban_sdk::vec![&env, Address::generate(&env)];
@@ -3409,24 +3986,53 @@ pub fn create_pending_claim(
     }
 
     #[test]
-    fn test_program_count() {
+    fn test_batch_payout_pays_each_recipient() {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register_contract(None, ProgramEscrowContract);
         let client = ProgramEscrowContractClient::new(&env, &contract_id);
 
-        assert_eq!(client.get_program_count(), 0);
-
         let backend = Address::generate(&env);
-        let token = Address::generate(&env);
+        let token_client = create_token_contract(&env, &backend);
+        token::StellarAssetClient::new(&env, &token_client.address).mint(&contract_id, &1_000);
 
-        client.initialize_program(&String::from_str(&env, "P1"), &backend, &token);
-        assert_eq!(client.get_program_count(), 1);
+        let prog_id = String::from_str(&env, "Test");
+        register(&client, &prog_id, &backend, &token_client.address);
+        client.lock_program_funds(&prog_id, &1_000);
 
-        client.initialize_program(&String::from_str(&env, "P2"), &backend, &token);
-        assert_eq!(client.get_program_count(), 2);
+        let winner1 = Address::generate(&env);
+        let winner2 = Address::generate(&env);
+        let data = client.batch_payout(
+            &prog_id,
+            &soroban_sdk::vec![&env, winner1.clone(), winner2.clone()],
+            &soroban_sdk::vec![&env, 300i128, 200i128],
+        );
 
-        client.initialize_program(&String::from_str(&env, "P3"), &backend, &token);
-        assert_eq!(client.get_program_count(), 3);
+        assert_eq!(data.remaining_balance, 500);
+        assert_eq!(data.payout_count, 2);
+        assert_eq!(token_client.balance(&winner1), 300);
+        assert_eq!(token_client.balance(&winner2), 200);
+    }
+
+    #[test]
+    fn test_program_count() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
+
+        assert_eq!(client.get_program_count(), 0);
+
+        let token = Address::generate(&env);
+        for (i, id) in ["P1", "P2", "P3"].iter().enumerate() {
+            register(
+                &client,
+                &String::from_str(&env, id),
+                &Address::generate(&env),
+                &token,
+            );
+            assert_eq!(client.get_program_count(), i as u32 + 1);
+        }
+        assert_eq!(client.list_programs().len(), 3);
     }
 
     // ========================================================================
@@ -3449,12 +4055,12 @@ pub fn create_pending_claim(
         let backend = Address::generate(&env);
         let token = Address::generate(&env);
 
-        client.initialize_program(&String::from_str(&env, "P1"), &backend, &token);
+        register(&client, &String::from_str(&env, "P1"), &backend, &token);
 
         // Advance time by 30s (less than 60s cooldown)
         env.ledger().with_mut(|li| li.timestamp += 30);
 
-        client.initialize_program(&String::from_str(&env, "P2"), &backend, &token);
+        register(&client, &String::from_str(&env, "P2"), &backend, &token);
     }
 
     #[test]
@@ -3473,10 +4079,9 @@ pub fn create_pending_claim(
         let backend = Address::generate(&env);
         let token = Address::generate(&env);
 
-        client.initialize_program(&String::from_str(&env, "P1"), &backend, &token);
-        client.initialize_program(&String::from_str(&env, "P2"), &backend, &token);
-        client.initialize_program(&String::from_str(&env, "P3"), &backend, &token);
-        // Should panic
+        register(&client, &String::from_str(&env, "P1"), &backend, &token);
+        register(&client, &String::from_str(&env, "P2"), &backend, &token);
+        register(&client, &String::from_str(&env, "P3"), &backend, &token);
     }
 
     #[test]
@@ -3495,10 +4100,11 @@ pub fn create_pending_claim(
         let token = Address::generate(&env);
 
         client.set_whitelist(&backend, &true);
+        assert!(client.is_whitelisted(&backend));
 
-        client.initialize_program(&String::from_str(&env, "P1"), &backend, &token);
-        client.initialize_program(&String::from_str(&env, "P2"), &backend, &token);
-        // Should work because whitelisted
+        // Works because whitelisted
+        register(&client, &String::from_str(&env, "P1"), &backend, &token);
+        register(&client, &String::from_str(&env, "P2"), &backend, &token);
     }
 
     #[test]
@@ -3519,6 +4125,10 @@ pub fn create_pending_claim(
         assert_eq!(config.cooldown_period, 120);
     }
 
+    // ========================================================================
+    // Configuration Snapshot Tests
+    // ========================================================================
+
     #[test]
     fn test_config_snapshot_create_and_restore() {
         let env = Env::default();
@@ -3579,250 +4189,17 @@ pub fn create_pending_claim(
     }
 }
 
-/// ============================================================================
-// Tests
-// ============================================================================
-
 #[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        token, Address, Env, String,
-    };
-
-    // Test helper to create a mock token contract
-    fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
-        let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
-        let token_address = token_contract.address();
-        token::Client::new(env, &token_address)
-    }
-
-    #[test]
-    #[should_panic(expected = "Program not found")]
-    fn test_get_nonexistent_program() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-
-        let prog_id = String::from_str(&env, "DoesNotExist");
-        client.get_program_info();
-    }
-
-    #[test]
-    fn test_dependency_gated_release_flow() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-
-        let token_admin = Address::generate(&env);
-        let token_client = create_token_contract(&env, &token_admin);
-        let token_asset = token::StellarAssetClient::new(&env, &token_client.address);
-
-        let dep_backend = Address::generate(&env);
-        let target_backend = Address::generate(&env);
-        let dependency_program = String::from_str(&env, "dependency-program");
-        let target_program = String::from_str(&env, "target-program");
-        let winner = Address::generate(&env);
-        let amount = 10_000_000i128;
-
-        token_asset.mint(&token_admin, &amount);
-        token_client.transfer(&token_admin, &contract_id, &amount);
-
-        client.initialize_program(&dependency_program, &dep_backend, &token_client.address, &Address::generate(&env), &None, &None);
-        client.initialize_program(&target_program, &target_backend, &token_client.address, &Address::generate(&env), &None, &None);
-        client.lock_program_funds(&amount);
-        client.create_program_release_schedule(&1000, &winner, &target_program, &amount);
-
-        let dependencies = soroban_sdk::vec![&env, dependency_program.clone()];
-        client.set_program_dependencies(&target_program, &dependencies);
-
-        env.ledger().set_timestamp(1001);
-        let blocked = client.try_release_prog_schedule_automatic(&1);
-        assert!(blocked.is_err());
-
-        client.set_dependency_status(&dependency_program, &DependencyStatus::Completed);
-        client.release_prog_schedule_automatic(&1);
-
-        let schedule = client.get_program_release_schedule(&1);
-        assert!(schedule.released);
-    }
-
-    #[test]
-    #[should_panic(expected = "Dependency failed")]
-    fn test_dependency_failed_blocks_release() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-
-        let token_admin = Address::generate(&env);
-        let token_client = create_token_contract(&env, &token_admin);
-        let token_asset = token::StellarAssetClient::new(&env, &token_client.address);
-
-        let dep_backend = Address::generate(&env);
-        let target_backend = Address::generate(&env);
-        let dependency_program = String::from_str(&env, "dependency-failed");
-        let target_program = String::from_str(&env, "target-failed");
-        let winner = Address::generate(&env);
-        let amount = 5_000_000i128;
-
-        token_asset.mint(&token_admin, &amount);
-        token_client.transfer(&token_admin, &contract_id, &amount);
-
-        client.initialize_program(&dependency_program, &dep_backend, &token_client.address, &Address::generate(&env), &None, &None);
-        client.initialize_program(&target_program, &target_backend, &token_client.address, &Address::generate(&env), &None, &None);
-        client.lock_program_funds(&amount);
-        client.create_program_release_schedule(&1000, &winner, &target_program, &amount);
-        client.set_program_dependencies(
-            &target_program,
-            &soroban_sdk::vec![&env, dependency_program.clone()],
-        );
-
-        client.set_dependency_status(&dependency_program, &DependencyStatus::Failed);
-        env.ledger().set_timestamp(1001);
-        client.release_prog_schedule_automatic(&1);
-    }
-
-    #[test]
-    #[should_panic(expected = "Dependency cycle detected")]
-    fn test_dependency_cycle_rejection() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-
-        let backend_a = Address::generate(&env);
-        let backend_b = Address::generate(&env);
-        let token = Address::generate(&env);
-        let program_a = String::from_str(&env, "cycle-a");
-        let program_b = String::from_str(&env, "cycle-b");
-
-        client.initialize_program(&program_a, &backend_a, &token, &Address::generate(&env), &None, &None);
-        client.initialize_program(&program_b, &backend_b, &token, &Address::generate(&env), &None, &None);
-        client.set_program_dependencies(&program_a, &soroban_sdk::vec![&env, program_b.clone()]);
-        client.set_program_dependencies(&program_b, &soroban_sdk::vec![&env, program_a.clone()]);
-    }
-
-    #[test]
-    fn test_dependency_events_created_and_cleared() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-
-        let backend_a = Address::generate(&env);
-        let backend_b = Address::generate(&env);
-        let token = Address::generate(&env);
-        let program_a = String::from_str(&env, "event-a");
-        let program_b = String::from_str(&env, "event-b");
-
-        client.initialize_program(&program_a, &backend_a, &token, &Address::generate(&env), &None, &None);
-        client.initialize_program(&program_b, &backend_b, &token, &Address::generate(&env), &None, &None);
-
-        client.set_program_dependencies(&program_a, &soroban_sdk::vec![&env, program_b.clone()]);
-        let dependencies = client.get_program_dependencies(&program_a);
-        assert_eq!(dependencies.len(), 1);
-
-        client.clear_program_dependencies(&program_a);
-        let cleared_dependencies = client.get_program_dependencies(&program_a);
-        assert_eq!(cleared_dependencies.len(), 0);
-    }
-
-    // ========================================================================
-    // Fund Locking Tests
-    // ========================================================================
-
-    #[test]
-    fn test_lock_funds_single_program() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let admin = Address::generate(&env);
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
-
-        let backend = Address::generate(&env);
-        let prog_id = String::from_str(&env, "Hackathon2024");
-
-        // Register program
-        client.initialize_program(&prog_id, &backend, &token_client.address, &Address::generate(&env), &None, &None);
-
-        // Lock funds
-        let amount = 10_000_0000000i128; // 10,000 USDC
-        let updated = client.lock_program_funds(&amount);
-
-        assert_eq!(updated.total_funds, amount);
-        assert_eq!(updated.remaining_balance, amount);
-    }
-
-    #[test]
-    fn test_lock_funds_multiple_programs_isolation() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let admin = Address::generate(&env);
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
-
-        let backend1 = Address::generate(&env);
-        let backend2 = Address::generate(&env);
-
-        let prog1 = String::from_str(&env, "Program1");
-        let prog2 = String::from_str(&env, "Program2");
-
-        // Register programs
-        client.initialize_program(&prog1, &backend1, &token_client.address, &Address::generate(&env), &None, &None);
-        client.initialize_program(&prog2, &backend2, &token_client.address, &Address::generate(&env), &None, &None);
-
-        // Lock different amounts in each program
-        let amount1 = 5_000_0000000i128;
-        let amount2 = 10_000_0000000i128;
-
-        client.lock_program_funds(&amount1);
-        client.lock_program_funds(&amount2);
-
-        // Verify isolation - funds don't mix
-        let info1 = client.get_program_info();
-        let info2 = client.get_program_info();
-
-        assert_eq!(info1.total_funds, amount1);
-        assert_eq!(info1.remaining_balance, amount1);
-        assert_eq!(info2.total_funds, amount2);
-        assert_eq!(info2.remaining_balance, amount2);
-    }
-
-    #[test]
-    fn test_lock_funds_cumulative() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let admin = Address::generate(&env);
-        let contract_id = env.register_contract(None, ProgramEscrowContract);
-        let client = ProgramEscrowContractClient::new(&env, &contract_id);
-        let token_client = create_token_contract(&env, &admin);
-
-        let backend = Address::generate(&env);
-        let prog_id = String::from_str(&env, "Hackathon2024");
-
-        client.initialize_program(&prog_id, &backend, &token_client.address, &Address::generate(&env), &None, &None);
-
-        // Lock funds multiple times
-        client.lock_program_funds(&1_000_0000000);
-        client.lock_program_funds(&2_000_0000000);
-        client.lock_program_funds(&3_000_0000000);
-            }
-
-    
-}
+mod test_pause;
 
 #[cfg(test)]
-
+mod test_multisig;
 #[cfg(test)]
-mod test_pause;
+mod test_payout_log;
+#[cfg(test)]
+mod test_program_storage;
+#[cfg(test)]
+mod test_vesting;
 
 #[cfg(test)]
 #[cfg(any())]
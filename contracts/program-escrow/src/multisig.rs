@@ -0,0 +1,262 @@
+// ============================================================================
+// Multisig Payout Approval
+//
+// Each program can require M-of-N approvals from sponsor/judge signers before
+// paying out `threshold_amount` or more to one recipient. Storage (persistent):
+//
+//   DataKey::MultisigConfig(id)                   MultisigConfig
+//   DataKey::PayoutApproval(id, recipient)        PayoutApproval
+//   MultisigKey::Unapproved(id, recipient)        UnapprovedPayouts
+//
+// The threshold applies to what a recipient has been paid without an
+// approval, not to each payout alone, so an amount split into smaller
+// payouts still needs signatures once the parts add up to the threshold.
+// The running total covers one `approval_ttl` window from its first payout
+// (it never lapses when `approval_ttl` is 0) and starts over once a payout
+// is approved.
+//
+// An approval names the recipient and the exact gross amount. Signers add to
+// it with `approve_payout` and withdraw with `revoke_payout_approval`; an
+// approval for a different amount, or one past `expires_at`, starts over.
+// Only approvers who are signers under the current config are counted.
+//
+// Every path that commits program funds to a recipient calls
+// `require_approval`: `single_payout`, `batch_payout` (once per recipient,
+// for the sum of that recipient's items), `create_pending_claim`,
+// `create_program_release_schedule` and `create_vesting_schedule`. A
+// satisfied approval is consumed, so it pays out once.
+// ============================================================================
+
+use crate::{program_storage, DataKey, ProgramEscrowError};
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MultisigKey {
+    Unapproved(String, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnapprovedPayouts {
+    /// Paid to the recipient without an approval since `since`.
+    pub total: i128,
+    pub since: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MultisigConfig {
+    /// Payouts of at least this amount need approval.
+    pub threshold_amount: i128,
+    pub signers: Vec<Address>,
+    /// Approvals needed; 0 disables the check.
+    pub required_signatures: u32,
+    /// Seconds an approval stays valid after its first signature; 0 for no expiry.
+    pub approval_ttl: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutApproval {
+    pub program_id: String,
+    pub recipient: Address,
+    pub amount: i128,
+    pub approvals: Vec<Address>,
+    pub expires_at: u64,
+}
+
+pub(crate) fn disabled(env: &Env) -> MultisigConfig {
+    MultisigConfig {
+        threshold_amount: i128::MAX,
+        signers: vec![env],
+        required_signatures: 0,
+        approval_ttl: 0,
+    }
+}
+
+pub(crate) fn config(env: &Env, program_id: &String) -> MultisigConfig {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MultisigConfig(program_id.clone()))
+        .unwrap_or_else(|| disabled(env))
+}
+
+pub(crate) fn set_config(
+    env: &Env,
+    program_id: &String,
+    config: &MultisigConfig,
+) -> Result<(), ProgramEscrowError> {
+    if config.threshold_amount <= 0 || config.required_signatures > config.signers.len() {
+        return Err(ProgramEscrowError::InvalidMultisigConfig);
+    }
+    for (i, signer) in config.signers.iter().enumerate() {
+        if config.signers.last_index_of(&signer) != Some(i as u32) {
+            return Err(ProgramEscrowError::InvalidMultisigConfig);
+        }
+    }
+    program_storage::save(env, &DataKey::MultisigConfig(program_id.clone()), config);
+    Ok(())
+}
+
+pub(crate) fn approval(
+    env: &Env,
+    program_id: &String,
+    recipient: &Address,
+) -> Option<PayoutApproval> {
+    env.storage().persistent().get(&DataKey::PayoutApproval(
+        program_id.clone(),
+        recipient.clone(),
+    ))
+}
+
+/// Add `approver`'s signature to the approval for paying `amount` to
+/// `recipient`.
+pub(crate) fn approve(
+    env: &Env,
+    program_id: &String,
+    recipient: &Address,
+    amount: i128,
+    approver: &Address,
+) -> Result<PayoutApproval, ProgramEscrowError> {
+    if amount <= 0 {
+        return Err(ProgramEscrowError::InvalidAmount);
+    }
+    let config = config(env, program_id);
+    if !config.signers.contains(approver) {
+        return Err(ProgramEscrowError::Unauthorized);
+    }
+    approver.require_auth();
+
+    let now = env.ledger().timestamp();
+    let mut record = match approval(env, program_id, recipient) {
+        Some(record) if record.amount == amount && now <= record.expires_at => record,
+        _ => PayoutApproval {
+            program_id: program_id.clone(),
+            recipient: recipient.clone(),
+            amount,
+            approvals: vec![env],
+            expires_at: if config.approval_ttl == 0 {
+                u64::MAX
+            } else {
+                now.saturating_add(config.approval_ttl)
+            },
+        },
+    };
+    if !record.approvals.contains(approver) {
+        record.approvals.push_back(approver.clone());
+    }
+    program_storage::save(
+        env,
+        &DataKey::PayoutApproval(program_id.clone(), recipient.clone()),
+        &record,
+    );
+
+    env.events().publish(
+        (symbol_short!("pay_appr"),),
+        (
+            program_id.clone(),
+            recipient.clone(),
+            amount,
+            approver.clone(),
+            record.approvals.len(),
+        ),
+    );
+    Ok(record)
+}
+
+/// Withdraw `approver`'s signature. A no-op if they have not approved.
+pub(crate) fn revoke(
+    env: &Env,
+    program_id: &String,
+    recipient: &Address,
+    approver: &Address,
+) -> Result<(), ProgramEscrowError> {
+    approver.require_auth();
+
+    let mut record = match approval(env, program_id, recipient) {
+        Some(record) => record,
+        None => return Ok(()),
+    };
+    let index = match record.approvals.first_index_of(approver) {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+    record.approvals.remove(index);
+
+    let key = DataKey::PayoutApproval(program_id.clone(), recipient.clone());
+    if record.approvals.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        program_storage::save(env, &key, &record);
+    }
+
+    env.events().publish(
+        (symbol_short!("pay_rvk"),),
+        (program_id.clone(), recipient.clone(), approver.clone()),
+    );
+    Ok(())
+}
+
+/// Check that paying `amount` to `recipient` is approved, consuming the
+/// approval when one was needed. Payouts below the threshold are added to
+/// the recipient's running total instead.
+pub(crate) fn require_approval(
+    env: &Env,
+    program_id: &String,
+    recipient: &Address,
+    amount: i128,
+) -> Result<(), ProgramEscrowError> {
+    let config = config(env, program_id);
+    if config.required_signatures == 0 {
+        return Ok(());
+    }
+
+    let now = env.ledger().timestamp();
+    let key = MultisigKey::Unapproved(program_id.clone(), recipient.clone());
+    let unapproved = env
+        .storage()
+        .persistent()
+        .get::<_, UnapprovedPayouts>(&key)
+        .filter(|unapproved| {
+            config.approval_ttl == 0 || now <= unapproved.since.saturating_add(config.approval_ttl)
+        })
+        .unwrap_or(UnapprovedPayouts {
+            total: 0,
+            since: now,
+        });
+    let total = unapproved.total.saturating_add(amount);
+    if total < config.threshold_amount {
+        program_storage::save(
+            env,
+            &key,
+            &UnapprovedPayouts {
+                total,
+                since: unapproved.since,
+            },
+        );
+        return Ok(());
+    }
+
+    let record = approval(env, program_id, recipient)
+        .filter(|record| record.amount == amount)
+        .ok_or(ProgramEscrowError::InsufficientApprovals)?;
+    if now > record.expires_at {
+        return Err(ProgramEscrowError::ApprovalExpired);
+    }
+    let valid = record
+        .approvals
+        .iter()
+        .filter(|approver| config.signers.contains(approver))
+        .count() as u32;
+    if valid < config.required_signatures {
+        return Err(ProgramEscrowError::InsufficientApprovals);
+    }
+
+    env.storage().persistent().remove(&DataKey::PayoutApproval(
+        program_id.clone(),
+        recipient.clone(),
+    ));
+    env.storage().persistent().remove(&key);
+    Ok(())
+}
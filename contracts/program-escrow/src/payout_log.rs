@@ -0,0 +1,138 @@
+// ============================================================================
+// Payout History Log
+//
+// Payouts are appended to a per-program log of individually keyed entries
+// instead of a `Vec` inside `ProgramData`, so recording a payout writes a
+// constant amount of data however long the history grows:
+//
+//   PayoutLogKey::Record(id, seq)                     PayoutRecord
+//   PayoutLogKey::RecipientCount(id, recipient)       u64
+//   PayoutLogKey::RecipientEntry(id, recipient, n)    u64, seq of the n-th payout
+//
+// Sequence numbers start at 0 and equal `ProgramData.payout_count` at the
+// time of the payout. Entries are never rewritten or removed, so a cursor
+// keeps its place while new payouts are appended.
+// ============================================================================
+
+use crate::{program_storage, PayoutRecord, ProgramData};
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+/// Upper bound on records returned by one history page.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutLogKey {
+    Record(String, u64),
+    RecipientCount(String, Address),
+    RecipientEntry(String, Address, u64),
+}
+
+/// One page of payout history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutPage {
+    pub records: Vec<PayoutRecord>,
+    /// Cursor for the following page; `None` once the log is exhausted.
+    pub next_cursor: Option<u64>,
+}
+
+/// Record a payout of `amount` to `recipient` and update the program's
+/// running totals. The caller saves `program`.
+pub(crate) fn append(env: &Env, program: &mut ProgramData, recipient: &Address, amount: i128) {
+    append_at(env, program, recipient, amount, env.ledger().timestamp());
+}
+
+/// As `append`, keeping the timestamp of a payout made earlier.
+pub(crate) fn append_at(
+    env: &Env,
+    program: &mut ProgramData,
+    recipient: &Address,
+    amount: i128,
+    timestamp: u64,
+) {
+    let program_id = program.program_id.clone();
+    let sequence = program.payout_count;
+    let record = PayoutRecord {
+        sequence,
+        recipient: recipient.clone(),
+        amount,
+        timestamp,
+    };
+    program_storage::save(
+        env,
+        &PayoutLogKey::Record(program_id.clone(), sequence),
+        &record,
+    );
+
+    let count_key = PayoutLogKey::RecipientCount(program_id.clone(), recipient.clone());
+    let position: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    program_storage::save(
+        env,
+        &PayoutLogKey::RecipientEntry(program_id, recipient.clone(), position),
+        &sequence,
+    );
+    program_storage::save(env, &count_key, &(position + 1));
+
+    program.payout_count = sequence + 1;
+    program.total_paid_out += amount;
+}
+
+fn page_limit(limit: u32) -> u64 {
+    limit.min(MAX_PAGE_SIZE) as u64
+}
+
+/// Payouts of `program_id` in sequence order, starting at sequence `cursor`.
+pub(crate) fn page(env: &Env, program: &ProgramData, cursor: u64, limit: u32) -> PayoutPage {
+    let end = cursor
+        .saturating_add(page_limit(limit))
+        .min(program.payout_count);
+    let mut records = Vec::new(env);
+    for sequence in cursor..end {
+        let key = PayoutLogKey::Record(program.program_id.clone(), sequence);
+        if let Some(record) = env.storage().persistent().get(&key) {
+            records.push_back(record);
+        }
+    }
+    PayoutPage {
+        records,
+        next_cursor: if end < program.payout_count {
+            Some(end)
+        } else {
+            None
+        },
+    }
+}
+
+/// Payouts of `program_id` to `recipient`, oldest first. `cursor` counts
+/// that recipient's payouts, not program-wide sequence numbers.
+pub(crate) fn page_by_recipient(
+    env: &Env,
+    program_id: &String,
+    recipient: &Address,
+    cursor: u64,
+    limit: u32,
+) -> PayoutPage {
+    let storage = env.storage().persistent();
+    let count: u64 = storage
+        .get(&PayoutLogKey::RecipientCount(
+            program_id.clone(),
+            recipient.clone(),
+        ))
+        .unwrap_or(0);
+    let end = cursor.saturating_add(page_limit(limit)).min(count);
+    let mut records = Vec::new(env);
+    for position in cursor..end {
+        let entry = PayoutLogKey::RecipientEntry(program_id.clone(), recipient.clone(), position);
+        let record = storage
+            .get::<_, u64>(&entry)
+            .and_then(|sequence| storage.get(&PayoutLogKey::Record(program_id.clone(), sequence)));
+        if let Some(record) = record {
+            records.push_back(record);
+        }
+    }
+    PayoutPage {
+        records,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
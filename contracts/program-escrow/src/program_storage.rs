@@ -0,0 +1,217 @@
+// ============================================================================
+// Program Storage Layout
+//
+// Per-program state lives in persistent storage, one entry per key, so an
+// invocation only loads the programs it touches rather than every program's
+// data and payout history with the contract instance:
+//
+//   DataKey::Program(id)                ProgramData
+//   DataKey::ReleaseSchedule(id, sid)   ProgramReleaseSchedule
+//   DataKey::ReleaseHistory(id)         Vec<ProgramReleaseHistory>
+//   DataKey::NextScheduleId(id)         u64, next schedule id (starts at 1)
+//   PROGRAM_REGISTRY                    Vec<String> of registered program ids
+//
+// Entries are bumped whenever they are written ("bump on touch"): once an
+// entry's remaining TTL drops below `TTL_THRESHOLD` it is extended to
+// `TTL_EXTEND_TO`. Contract-wide configuration (admin, fees, pause flags)
+// stays in instance storage.
+//
+// Deployments from before this layout kept programs and the registry in
+// instance storage, with each program's payouts in a `Vec` inside its data.
+// `migrate_program_storage` moves them across, replaying the payouts into the
+// payout log. One call moves at most `MIGRATION_PAGE` payouts and schedules,
+// so a large program is carried over several calls; its progress is kept
+// under `MigrationKey::Progress` and the legacy entry stays in place until
+// the last page. Until then the program reads as missing.
+// ============================================================================
+
+use crate::{
+    payout_log, DataKey, ProgramData, ProgramReleaseHistory, ProgramReleaseSchedule,
+    PROGRAM_REGISTRY,
+};
+use soroban_sdk::{contracttype, Address, Bytes, Env, IntoVal, String, Val, Vec};
+
+/// Ledgers per day at a ~5 second close time.
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Remaining TTL below which a touched entry is extended.
+pub(crate) const TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+
+/// TTL a touched entry is extended to.
+pub(crate) const TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+/// Extend a persistent entry that was just written.
+pub(crate) fn extend<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Write a persistent entry and extend its TTL.
+pub(crate) fn save<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    extend(env, key);
+}
+
+/// Most legacy payouts and schedules one `migrate_program_storage` call
+/// moves.
+pub(crate) const MIGRATION_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MigrationKey {
+    /// program id -> MigrationProgress of a migration that has not finished
+    Progress(String),
+}
+
+/// A program part-way through `migrate_program`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationProgress {
+    /// The program as it will be stored, with the payouts replayed so far.
+    pub program: ProgramData,
+    /// Legacy payouts already replayed into the payout log.
+    pub payouts: u32,
+    /// Next schedule id to move.
+    pub next_schedule: u64,
+}
+
+/// A payout as stored inline by earlier versions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyPayoutRecord {
+    pub recipient: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// `ProgramData` as stored in instance storage by earlier versions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyProgramData {
+    pub program_id: String,
+    pub total_funds: i128,
+    pub remaining_balance: i128,
+    pub authorized_payout_key: Address,
+    pub payout_history: Vec<LegacyPayoutRecord>,
+    pub token_address: Address,
+    pub initial_liquidity: i128,
+    pub reference_hash: Option<Bytes>,
+}
+
+pub(crate) fn registry(env: &Env) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&PROGRAM_REGISTRY)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Whether `program_id` still has entries in instance storage.
+pub(crate) fn migration_pending(env: &Env, program_id: &String) -> bool {
+    env.storage()
+        .instance()
+        .has(&DataKey::Program(program_id.clone()))
+}
+
+/// Move one program's entries from instance to persistent storage, spending
+/// at most `budget` payouts and schedules and deducting what it used.
+///
+/// Returns `true` once the program is fully moved. Returns `false` when the
+/// budget ran out first, in which case calling again picks up where this
+/// call stopped, and when the program has no instance entry, so migrating an
+/// already-migrated program is a no-op.
+pub(crate) fn migrate_program(env: &Env, program_id: &String, budget: &mut u32) -> bool {
+    let instance = env.storage().instance();
+    let program_key = DataKey::Program(program_id.clone());
+    let legacy: LegacyProgramData = match instance.get(&program_key) {
+        Some(legacy) => legacy,
+        None => return false,
+    };
+    let progress_key = MigrationKey::Progress(program_id.clone());
+    let mut progress: MigrationProgress =
+        env.storage()
+            .persistent()
+            .get(&progress_key)
+            .unwrap_or(MigrationProgress {
+                program: ProgramData {
+                    program_id: legacy.program_id,
+                    total_funds: legacy.total_funds,
+                    remaining_balance: legacy.remaining_balance,
+                    authorized_payout_key: legacy.authorized_payout_key,
+                    payout_count: 0,
+                    total_paid_out: 0,
+                    token_address: legacy.token_address,
+                    initial_liquidity: legacy.initial_liquidity,
+                    reference_hash: legacy.reference_hash,
+                },
+                payouts: 0,
+                next_schedule: 1,
+            });
+
+    let history = legacy.payout_history;
+    while progress.payouts < history.len() && *budget > 0 {
+        let payout = history.get_unchecked(progress.payouts);
+        payout_log::append_at(
+            env,
+            &mut progress.program,
+            &payout.recipient,
+            payout.amount,
+            payout.timestamp,
+        );
+        progress.payouts += 1;
+        *budget -= 1;
+    }
+
+    let next_key = DataKey::NextScheduleId(program_id.clone());
+    let next_id: u64 = instance.get(&next_key).unwrap_or(1);
+    while progress.next_schedule < next_id && *budget > 0 {
+        let key = DataKey::ReleaseSchedule(program_id.clone(), progress.next_schedule);
+        if let Some(schedule) = instance.get::<_, ProgramReleaseSchedule>(&key) {
+            save(env, &key, &schedule);
+            instance.remove(&key);
+        }
+        progress.next_schedule += 1;
+        *budget -= 1;
+    }
+
+    if progress.payouts < history.len() || progress.next_schedule < next_id {
+        save(env, &progress_key, &progress);
+        return false;
+    }
+
+    save(env, &program_key, &progress.program);
+    instance.remove(&program_key);
+    env.storage().persistent().remove(&progress_key);
+    if instance.has(&next_key) {
+        save(env, &next_key, &next_id);
+        instance.remove(&next_key);
+    }
+    let history_key = DataKey::ReleaseHistory(program_id.clone());
+    if let Some(history) = instance.get::<_, Vec<ProgramReleaseHistory>>(&history_key) {
+        save(env, &history_key, &history);
+        instance.remove(&history_key);
+    }
+    true
+}
+
+/// Fold an instance-stored registry into the persistent one, keeping order
+/// and skipping ids already present.
+pub(crate) fn migrate_registry(env: &Env) {
+    let instance = env.storage().instance();
+    let legacy: Vec<String> = match instance.get(&PROGRAM_REGISTRY) {
+        Some(legacy) => legacy,
+        None => return,
+    };
+    let mut merged = registry(env);
+    for program_id in legacy.iter() {
+        if !merged.contains(&program_id) {
+            merged.push_back(program_id);
+        }
+    }
+    save(env, &PROGRAM_REGISTRY, &merged);
+    instance.remove(&PROGRAM_REGISTRY);
+}
@@ -89,7 +89,7 @@ fn test_edge_zero_initial_state() {
     let (client, _admin, token_client, _token_admin) = setup_program(&env, 0);
 
     assert_eq!(client.get_remaining_balance(), 0);
-    assert_eq!(client.get_program_info().payout_history.len(), 0);
+    assert_eq!(client.get_program_info().payout_count, 0);
     assert_eq!(token_client.balance(&client.address), 0);
 }
 
@@ -134,7 +134,7 @@ fn test_batch_payout_token_transfer_integration() {
 
     let data = client.batch_payout(&recipients, &amounts);
     assert_eq!(data.remaining_balance, 90_000);
-    assert_eq!(data.payout_history.len(), 3);
+    assert_eq!(data.payout_count, 3);
 
     assert_eq!(token_client.balance(&r1), 10_000);
     assert_eq!(token_client.balance(&r2), 20_000);
@@ -161,7 +161,7 @@ fn test_complete_lifecycle_integration() {
     let info = client.get_program_info();
     assert_eq!(info.total_funds, 300_000);
     assert_eq!(info.remaining_balance, 150_000);
-    assert_eq!(info.payout_history.len(), 3);
+    assert_eq!(info.payout_count, 3);
     assert_eq!(token_client.balance(&client.address), 150_000);
 }
 
@@ -216,7 +216,7 @@ fn test_stress_high_load_many_payouts() {
     }
 
     let info = client.get_program_info();
-    assert_eq!(info.payout_history.len(), 100);
+    assert_eq!(info.payout_count, 100);
     assert_eq!(info.remaining_balance, 700_000);
     assert_eq!(token_client.balance(&client.address), 700_000);
 }
@@ -446,7 +446,7 @@ fn test_full_lifecycle_multi_program_batch_payouts() {
         &vec![&env, 100_000, 75_000, 50_000],
     );
     assert_eq!(data_a1.remaining_balance, 275_000);
-    assert_eq!(data_a1.payout_history.len(), 3);
+    assert_eq!(data_a1.payout_count, 3);
     assert_eq!(token_client.balance(&winner_a1), 100_000);
     assert_eq!(token_client.balance(&winner_a2), 75_000);
     assert_eq!(token_client.balance(&winner_a3), 50_000);
@@ -460,7 +460,7 @@ fn test_full_lifecycle_multi_program_batch_payouts() {
         &vec![&env, 120_000, 80_000],
     );
     assert_eq!(data_b1.remaining_balance, 200_000);
-    assert_eq!(data_b1.payout_history.len(), 2);
+    assert_eq!(data_b1.payout_count, 2);
     assert_eq!(token_client.balance(&winner_b1), 120_000);
     assert_eq!(token_client.balance(&winner_b2), 80_000);
 
@@ -474,7 +474,7 @@ fn test_full_lifecycle_multi_program_batch_payouts() {
         &vec![&env, 125_000, 50_000],
     );
     assert_eq!(data_a2.remaining_balance, 100_000);
-    assert_eq!(data_a2.payout_history.len(), 5);
+    assert_eq!(data_a2.payout_count, 5);
     assert_eq!(token_client.balance(&winner_a4), 125_000);
     assert_eq!(token_client.balance(&winner_a5), 50_000);
 
@@ -493,7 +493,7 @@ fn test_full_lifecycle_multi_program_batch_payouts() {
         &vec![&env, 60_000, 40_000, 30_000],
     );
     assert_eq!(data_b2.remaining_balance, 70_000);
-    assert_eq!(data_b2.payout_history.len(), 5);
+    assert_eq!(data_b2.payout_count, 5);
     assert_eq!(token_client.balance(&winner_b3), 60_000);
     assert_eq!(token_client.balance(&winner_b4), 40_000);
     assert_eq!(token_client.balance(&winner_b5), 30_000);
@@ -506,7 +506,7 @@ fn test_full_lifecycle_multi_program_batch_payouts() {
     let info_a = client_a.get_program_info();
     assert_eq!(info_a.total_funds, 500_000);
     assert_eq!(info_a.remaining_balance, 100_000);
-    assert_eq!(info_a.payout_history.len(), 5);
+    assert_eq!(info_a.payout_count, 5);
 
     // Program B: 400_000 locked − (120k + 80k + 60k + 40k + 30k) = 70_000
     assert_eq!(client_b.get_remaining_balance(), 70_000);
@@ -515,7 +515,7 @@ fn test_full_lifecycle_multi_program_batch_payouts() {
     let info_b = client_b.get_program_info();
     assert_eq!(info_b.total_funds, 400_000);
     assert_eq!(info_b.remaining_balance, 70_000);
-    assert_eq!(info_b.payout_history.len(), 5);
+    assert_eq!(info_b.payout_count, 5);
 
     // ── Phase 5: Aggregate stats verification ───────────────────────────
     let stats_a = client_a.get_program_aggregate_stats();
@@ -652,7 +652,7 @@ fn test_anti_abuse_whitelist_bypass() {
     }
 
     let info = client.get_program_info();
-    assert_eq!(info.payout_history.len() as u32, max_ops + 5);
+    assert_eq!(info.payout_count, (max_ops + 5) as u64);
 }
 
 // =============================================================================
@@ -1319,8 +1319,8 @@ fn test_multi_tenant_no_cross_program_balance_or_analytics() {
     assert_eq!(client_b.get_remaining_balance(), 300_000);
     let info_a = client_a.get_program_info();
     let info_b = client_b.get_program_info();
-    assert_eq!(info_a.payout_history.len(), 1);
-    assert_eq!(info_b.payout_history.len(), 0);
+    assert_eq!(info_a.payout_count, 1);
+    assert_eq!(info_b.payout_count, 0);
     assert_eq!(client_a.get_program_aggregate_stats().payout_count, 1);
     assert_eq!(client_b.get_program_aggregate_stats().payout_count, 0);
 }
@@ -1693,18 +1693,21 @@ fn test_batch_payout_happy_path_multiple_recipients() {
     assert_eq!(data.remaining_balance, 0);
 
     // Verify payout history has all three records
-    assert_eq!(data.payout_history.len(), 3);
+    assert_eq!(data.payout_count, 3);
 
     // Verify each payout record
-    let payout1 = data.payout_history.get(0).unwrap();
+    let history = client
+        .get_payout_history(&data.program_id, &0, &10)
+        .records;
+    let payout1 = history.get(0).unwrap();
     assert_eq!(payout1.recipient, r1);
     assert_eq!(payout1.amount, 1_000_000);
 
-    let payout2 = data.payout_history.get(1).unwrap();
+    let payout2 = history.get(1).unwrap();
     assert_eq!(payout2.recipient, r2);
     assert_eq!(payout2.amount, 2_000_000);
 
-    let payout3 = data.payout_history.get(2).unwrap();
+    let payout3 = history.get(2).unwrap();
     assert_eq!(payout3.recipient, r3);
     assert_eq!(payout3.amount, 3_000_000);
 
@@ -1734,17 +1737,16 @@ fn test_batch_payout_with_duplicate_recipient_addresses() {
     assert_eq!(data.remaining_balance, 0);
 
     // Payout history should have all three records (duplicates are allowed)
-    assert_eq!(data.payout_history.len(), 3);
+    assert_eq!(data.payout_count, 3);
 
     // Count occurrences of r1 in history
-    let mut r1_count = 0;
+    let r1_history = client
+        .get_payouts_by_recipient(&data.program_id, &r1, &0, &10)
+        .records;
+    let r1_count = r1_history.len();
     let mut r1_total = 0i128;
-    for i in 0..data.payout_history.len() {
-        let record = data.payout_history.get(i).unwrap();
-        if record.recipient == r1 {
-            r1_count += 1;
-            r1_total += record.amount;
-        }
+    for record in r1_history.iter() {
+        r1_total += record.amount;
     }
 
     // r1 should appear twice with correct total
@@ -1782,19 +1784,22 @@ fn test_batch_payout_maximum_batch_size() {
     assert_eq!(data.remaining_balance, 0);
 
     // Payout history should have all records
-    assert_eq!(data.payout_history.len(), batch_size as u32);
+    assert_eq!(data.payout_count, batch_size as u64);
 
     // Verify total payout amount
     let mut total_paid = 0i128;
-    for i in 0..data.payout_history.len() {
-        let record = data.payout_history.get(i).unwrap();
+    let history = client
+        .get_payout_history(&data.program_id, &0, &(batch_size as u32))
+        .records;
+    for record in history.iter() {
         total_paid += record.amount;
     }
     assert_eq!(total_paid, total_amount);
+    assert_eq!(data.total_paid_out, total_amount);
 }
 
 #[test]
-#[should_panic(expected = "Cannot process empty batch")]
+#[should_panic(expected = "Error(Contract, #10)")]
 fn test_batch_payout_empty_batch_panic() {
     // Test that empty batch is rejected
     let env = Env::default();
@@ -1808,7 +1813,7 @@ fn test_batch_payout_empty_batch_panic() {
 }
 
 #[test]
-#[should_panic(expected = "Recipients and amounts vectors must have the same length")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_batch_payout_mismatched_arrays_panic() {
     // Test that mismatched recipient/amount arrays are rejected
     let env = Env::default();
@@ -1822,7 +1827,7 @@ fn test_batch_payout_mismatched_arrays_panic() {
 }
 
 #[test]
-#[should_panic(expected = "All amounts must be greater than zero")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_batch_payout_invalid_amount_zero_panic() {
     // Test that zero amounts are rejected
     let env = Env::default();
@@ -1836,7 +1841,7 @@ fn test_batch_payout_invalid_amount_zero_panic() {
 }
 
 #[test]
-#[should_panic(expected = "All amounts must be greater than zero")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_batch_payout_invalid_amount_negative_panic() {
     // Test that negative amounts are rejected
     let env = Env::default();
@@ -1850,7 +1855,7 @@ fn test_batch_payout_invalid_amount_negative_panic() {
 }
 
 #[test]
-#[should_panic(expected = "Insufficient balance")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_batch_payout_insufficient_balance_panic() {
     // Test that insufficient balance is rejected
     let env = Env::default();
@@ -1882,7 +1887,7 @@ fn test_batch_payout_partial_spend() {
     assert_eq!(data.remaining_balance, 4_000_000);
 
     // Payout history should have both records
-    assert_eq!(data.payout_history.len(), 2);
+    assert_eq!(data.payout_count, 2);
 }
 
 #[test]
@@ -1897,7 +1902,7 @@ fn test_batch_payout_atomicity_all_or_nothing() {
 
     // Get program state before payout
     let program_data_before = client.get_program_info();
-    let history_len_before = program_data_before.payout_history.len();
+    let history_len_before = program_data_before.payout_count;
     let balance_before = program_data_before.remaining_balance;
 
     // Execute successful batch payout
@@ -1907,13 +1912,13 @@ fn test_batch_payout_atomicity_all_or_nothing() {
     let data = client.batch_payout(&recipients, &amounts);
 
     // All records must be written
-    assert_eq!(data.payout_history.len(), history_len_before + 2);
+    assert_eq!(data.payout_count, history_len_before + 2);
 
     // Balance must be fully updated
     assert_eq!(data.remaining_balance, balance_before - 3_000_000);
 
     // All conditions should be satisfied together (atomicity)
-    assert_eq!(data.payout_history.len(), 2);
+    assert_eq!(data.payout_count, 2);
     assert_eq!(data.remaining_balance, 0);
 }
 
@@ -1931,7 +1936,7 @@ fn test_batch_payout_sequential_batches() {
     let data1 = client.batch_payout(&recipients1, &amounts1);
 
     // Verify after first batch
-    assert_eq!(data1.payout_history.len(), 1);
+    assert_eq!(data1.payout_count, 1);
     assert_eq!(data1.remaining_balance, 6_000_000);
 
     // Second batch
@@ -1942,17 +1947,20 @@ fn test_batch_payout_sequential_batches() {
     let data2 = client.batch_payout(&recipients2, &amounts2);
 
     // Verify after second batch
-    assert_eq!(data2.payout_history.len(), 3);
+    assert_eq!(data2.payout_count, 3);
     assert_eq!(data2.remaining_balance, 0);
 
     // Verify history order
-    let record1 = data2.payout_history.get(0).unwrap();
+    let history = client
+        .get_payout_history(&data2.program_id, &0, &10)
+        .records;
+    let record1 = history.get(0).unwrap();
     assert_eq!(record1.amount, 3_000_000);
 
-    let record2 = data2.payout_history.get(1).unwrap();
+    let record2 = history.get(1).unwrap();
     assert_eq!(record2.amount, 2_000_000);
 
-    let record3 = data2.payout_history.get(2).unwrap();
+    let record3 = history.get(2).unwrap();
     assert_eq!(record3.amount, 4_000_000);
 }
 
@@ -21,9 +21,7 @@ use soroban_sdk::{
     token, Address, Env, String,
 };
 
-use crate::{
-    ClaimRecord, ClaimStatus, DataKey, ProgramEscrowContract, ProgramEscrowContractClient,
-};
+use crate::{ClaimStatus, ProgramEscrowContract, ProgramEscrowContractClient};
 
 fn create_token_contract<'a>(
     env: &Env,
@@ -40,9 +38,7 @@ struct TestSetup<'a> {
     env: Env,
     client: ProgramEscrowContractClient<'a>,
     token: token::Client<'a>,
-    token_admin: token::StellarAssetClient<'a>,
     admin: Address,
-    payout_key: Address,
     contributor: Address,
     program_id: String,
 }
@@ -65,10 +61,17 @@ fn setup<'a>() -> TestSetup<'a> {
     let program_id = String::from_str(&env, "TestProgram2024");
 
     // initialize program
-    client.init_program(&program_id, &payout_key, &token.address, &payout_key, &None, &None);
+    client.init_program(
+        &program_id,
+        &payout_key,
+        &token.address,
+        &payout_key,
+        &None,
+        &None,
+    );
 
     // lock funds
-    client.lock_program_funds(&500_000_i128);
+    client.lock_program_funds(&program_id, &500_000_i128);
 
     client.set_admin(&admin);
 
@@ -88,9 +91,7 @@ fn setup<'a>() -> TestSetup<'a> {
         env,
         client,
         token,
-        token_admin,
         admin,
-        payout_key,
         contributor,
         program_id,
     }
@@ -149,7 +150,7 @@ fn test_claim_within_window_succeeds() {
     );
 
     // assert escrow balance decreased
-    let program = t.client.get_program_info();
+    let program = t.client.get_program_info(&t.program_id);
     assert_eq!(program.remaining_balance, 500_000 - claim_amount);
 }
 
@@ -158,7 +159,7 @@ fn test_claim_within_window_succeeds() {
 // ═══════════════════════════════════════════════════════════════════════════
 
 #[test]
-#[should_panic(expected = "ClaimExpired")]
+#[should_panic(expected = "Error(Contract, #23)")]
 fn test_claim_after_expiry_fails() {
     let t = setup();
     let env = &t.env;
@@ -180,7 +181,7 @@ fn test_claim_after_expiry_fails() {
         ..env.ledger().get()
     });
 
-    // verifies claim is still Pending — nothing auto-cancels it
+    // verifies claim is still Pending until the program's claims are swept
     let claim = t.client.get_claim(&t.program_id, &claim_id);
     assert_eq!(claim.status, ClaimStatus::Pending);
 
@@ -210,7 +211,7 @@ fn test_admin_cancel_pending_claim_restores_escrow() {
     );
 
     // Escrow balance should have decreased when claim was created (reserved)
-    let balance_after_create = t.client.get_remaining_balance();
+    let balance_after_create = t.client.get_remaining_balance(&t.program_id);
 
     // Admin cancels the still-active pending claim (well within deadline)
     env.ledger().set(LedgerInfo {
@@ -221,7 +222,7 @@ fn test_admin_cancel_pending_claim_restores_escrow() {
     t.client.cancel_claim(&t.program_id, &claim_id, &t.admin);
 
     // Assert funds returned to escrow
-    let balance_after_cancel = t.client.get_remaining_balance();
+    let balance_after_cancel = t.client.get_remaining_balance(&t.program_id);
     assert_eq!(
         balance_after_cancel,
         balance_after_create + claim_amount,
@@ -270,13 +271,13 @@ fn test_admin_cancel_expired_claim_succeeds() {
         ..env.ledger().get()
     });
 
-    let balance_before_cancel = t.client.get_remaining_balance();
+    let balance_before_cancel = t.client.get_remaining_balance(&t.program_id);
 
     // Admin cleans up the expired claim
     t.client.cancel_claim(&t.program_id, &claim_id, &t.admin);
 
     // Funds should return to escrow
-    let balance_after_cancel = t.client.get_remaining_balance();
+    let balance_after_cancel = t.client.get_remaining_balance(&t.program_id);
     assert_eq!(
         balance_after_cancel,
         balance_before_cancel + claim_amount,
@@ -296,7 +297,7 @@ fn test_admin_cancel_expired_claim_succeeds() {
 // ═══════════════════════════════════════════════════════════════════════════
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn test_non_admin_cannot_cancel_claim() {
     let t = setup();
     let env = &t.env;
@@ -318,7 +319,7 @@ fn test_non_admin_cannot_cancel_claim() {
 // ═══════════════════════════════════════════════════════════════════════════
 
 #[test]
-#[should_panic(expected = "ClaimAlreadyProcessed")]
+#[should_panic(expected = "Error(Contract, #22)")]
 fn test_cannot_double_claim() {
     let t = setup();
     let env = &t.env;
@@ -342,7 +343,7 @@ fn test_cannot_double_claim() {
 // ═══════════════════════════════════════════════════════════════════════════
 
 #[test]
-#[should_panic(expected = "ClaimAlreadyProcessed")]
+#[should_panic(expected = "Error(Contract, #22)")]
 fn test_cannot_execute_cancelled_claim() {
     let t = setup();
     let env = &t.env;
@@ -365,7 +366,7 @@ fn test_cannot_execute_cancelled_claim() {
 // ═══════════════════════════════════════════════════════════════════════════
 
 #[test]
-#[should_panic(expected = "Unauthorized")]
+#[should_panic(expected = "Error(Contract, #4)")]
 fn test_wrong_recipient_cannot_execute_claim() {
     let t = setup();
     let env = &t.env;
@@ -380,3 +381,259 @@ fn test_wrong_recipient_cannot_execute_claim() {
     // An unrelated address tries to execute the claim — should panic
     t.client.execute_claim(&t.program_id, &claim_id, &impostor);
 }
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TEST 9: Pending claims are listed per program and per recipient
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_pending_claims_listed_per_program_and_recipient() {
+    let t = setup();
+    let env = &t.env;
+
+    let now: u64 = env.ledger().timestamp();
+    let other = Address::generate(env);
+    let first =
+        t.client
+            .create_pending_claim(&t.program_id, &t.contributor, &1_000_i128, &(now + 86_400));
+    let second = t
+        .client
+        .create_pending_claim(&t.program_id, &other, &2_000_i128, &(now + 86_400));
+    let third =
+        t.client
+            .create_pending_claim(&t.program_id, &t.contributor, &3_000_i128, &(now + 3_600));
+
+    let pending = t.client.get_pending_claims(&t.program_id, &0, &10);
+    assert_eq!(pending.len(), 3);
+    assert_eq!(pending.get(0).unwrap().claim_id, first);
+    assert_eq!(pending.get(1).unwrap().claim_id, second);
+
+    let mine = t
+        .client
+        .get_pending_claims_by_recipient(&t.contributor, &0, &10);
+    assert_eq!(mine.len(), 2);
+    assert_eq!(mine.get(1).unwrap().claim_id, third);
+
+    let page = t.client.get_pending_claims(&t.program_id, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().claim_id, second);
+    let page = t
+        .client
+        .get_pending_claims_by_recipient(&t.contributor, &1, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().claim_id, third);
+    assert_eq!(t.client.get_pending_claims(&t.program_id, &3, &5).len(), 0);
+
+    // Executed and cancelled claims drop out of both listings.
+    t.client
+        .execute_claim(&t.program_id, &first, &t.contributor);
+    t.client.cancel_claim(&t.program_id, &second, &t.admin);
+    assert_eq!(t.client.get_pending_claims(&t.program_id, &0, &10).len(), 1);
+    assert_eq!(
+        t.client
+            .get_pending_claims_by_recipient(&other, &0, &10)
+            .len(),
+        0
+    );
+
+    // Past its deadline a claim is no longer listed, even before a sweep.
+    env.ledger().set(LedgerInfo {
+        timestamp: now + 7_200,
+        ..env.ledger().get()
+    });
+    assert_eq!(t.client.get_pending_claims(&t.program_id, &0, &10).len(), 0);
+    assert_eq!(
+        t.client
+            .get_pending_claims_by_recipient(&t.contributor, &0, &10)
+            .len(),
+        0
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TEST 10: Expired claims return their funds to the program balance
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_expire_claims_restores_escrow() {
+    let t = setup();
+    let env = &t.env;
+
+    let now: u64 = env.ledger().timestamp();
+    let short =
+        t.client
+            .create_pending_claim(&t.program_id, &t.contributor, &4_000_i128, &(now + 3_600));
+    let long =
+        t.client
+            .create_pending_claim(&t.program_id, &t.contributor, &6_000_i128, &(now + 86_400));
+    let balance_after_create = t.client.get_remaining_balance(&t.program_id);
+
+    // Nothing has expired yet.
+    assert_eq!(t.client.expire_claims(&t.program_id, &0, &10), 0);
+
+    env.ledger().set(LedgerInfo {
+        timestamp: now + 7_200,
+        ..env.ledger().get()
+    });
+    assert_eq!(t.client.expire_claims(&t.program_id, &0, &10), 1);
+    assert_eq!(
+        t.client.get_remaining_balance(&t.program_id),
+        balance_after_create + 4_000
+    );
+    assert_eq!(
+        t.client.get_claim(&t.program_id, &short).status,
+        ClaimStatus::Expired
+    );
+    assert_eq!(
+        t.client.get_claim(&t.program_id, &long).status,
+        ClaimStatus::Pending
+    );
+
+    // Expiry is settled once; an expired claim can no longer be cancelled.
+    assert_eq!(t.client.expire_claims(&t.program_id, &0, &10), 0);
+    let res = t.client.try_cancel_claim(&t.program_id, &short, &t.admin);
+    assert!(res.is_err());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TEST 11: Lapsed reservations are released before new claims and payouts
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_lapsed_reservation_is_reusable() {
+    let t = setup();
+    let env = &t.env;
+
+    let now: u64 = env.ledger().timestamp();
+    let balance = t.client.get_remaining_balance(&t.program_id);
+    let lapsed =
+        t.client
+            .create_pending_claim(&t.program_id, &t.contributor, &balance, &(now + 3_600));
+    assert_eq!(t.client.get_remaining_balance(&t.program_id), 0);
+
+    env.ledger().set(LedgerInfo {
+        timestamp: now + 7_200,
+        ..env.ledger().get()
+    });
+
+    // The whole balance is reserved by the lapsed claim, yet a new claim for
+    // it succeeds because the creation sweeps expired claims first.
+    let other = Address::generate(env);
+    let fresh = t
+        .client
+        .create_pending_claim(&t.program_id, &other, &balance, &(now + 86_400));
+    assert_eq!(
+        t.client.get_claim(&t.program_id, &lapsed).status,
+        ClaimStatus::Expired
+    );
+    assert_eq!(t.client.get_remaining_balance(&t.program_id), 0);
+
+    t.client.execute_claim(&t.program_id, &fresh, &other);
+    assert_eq!(t.token.balance(&other), balance);
+
+    // Executed claims are recorded in the payout history.
+    let history = t.client.get_payout_history(&t.program_id, &0, &10);
+    assert_eq!(history.records.len(), 1);
+    assert_eq!(history.records.get(0).unwrap().recipient, other);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TEST 12: Automatic sweeps check a bounded number of claims
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_automatic_sweep_is_bounded() {
+    let t = setup();
+    let env = &t.env;
+
+    let now: u64 = env.ledger().timestamp();
+    let backlog = crate::claim_period::AUTO_EXPIRE_SCAN as u64 + 5;
+    let mut claims = soroban_sdk::Vec::new(env);
+    for _ in 0..backlog {
+        claims.push_back(t.client.create_pending_claim(
+            &t.program_id,
+            &t.contributor,
+            &100_i128,
+            &(now + 3_600),
+        ));
+    }
+
+    env.ledger().set(LedgerInfo {
+        timestamp: now + 7_200,
+        ..env.ledger().get()
+    });
+    let other = Address::generate(env);
+    t.client
+        .create_pending_claim(&t.program_id, &other, &100_i128, &(now + 86_400));
+
+    // Only the oldest claims were checked by the creation sweep.
+    let scan = crate::claim_period::AUTO_EXPIRE_SCAN;
+    for (i, claim_id) in claims.iter().enumerate() {
+        let expected = if (i as u32) < scan {
+            ClaimStatus::Expired
+        } else {
+            ClaimStatus::Pending
+        };
+        assert_eq!(
+            t.client.get_claim(&t.program_id, &claim_id).status,
+            expected
+        );
+    }
+
+    // `expire_claims` settles the rest.
+    assert_eq!(t.client.expire_claims(&t.program_id, &0, &10), 5);
+    assert_eq!(
+        t.client
+            .get_claim(&t.program_id, &claims.last().unwrap())
+            .status,
+        ClaimStatus::Expired
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// TEST 13: Manual sweeps are bounded and resume past live claims
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_expire_claims_is_bounded_and_resumable() {
+    let t = setup();
+    let env = &t.env;
+
+    let now: u64 = env.ledger().timestamp();
+    let live = crate::claim_period::MAX_EXPIRE_SCAN;
+    // Long-lived claims at the front of the pending list, lapsing ones after.
+    for _ in 0..live {
+        t.client
+            .create_pending_claim(&t.program_id, &t.contributor, &10_i128, &(now + 86_400));
+    }
+    let mut lapsing = soroban_sdk::Vec::new(env);
+    for _ in 0..3 {
+        lapsing.push_back(t.client.create_pending_claim(
+            &t.program_id,
+            &t.contributor,
+            &10_i128,
+            &(now + 3_600),
+        ));
+    }
+
+    env.ledger().set(LedgerInfo {
+        timestamp: now + 7_200,
+        ..env.ledger().get()
+    });
+    // A limit above the cap still checks only the live claims.
+    assert_eq!(t.client.expire_claims(&t.program_id, &0, &(live + 10)), 0);
+    assert_eq!(
+        t.client
+            .get_claim(&t.program_id, &lapsing.get(0).unwrap())
+            .status,
+        ClaimStatus::Pending
+    );
+
+    assert_eq!(t.client.expire_claims(&t.program_id, &live, &10), 3);
+    for claim_id in lapsing.iter() {
+        assert_eq!(
+            t.client.get_claim(&t.program_id, &claim_id).status,
+            ClaimStatus::Expired
+        );
+    }
+}
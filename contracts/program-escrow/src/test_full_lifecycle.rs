@@ -110,12 +110,12 @@ fn test_complex_multi_program_lifecycle_integration() {
     let info_a = client.get_program_info();
     assert_eq!(info_a.total_funds, 500_000);
     assert_eq!(info_a.remaining_balance, 0);
-    assert_eq!(info_a.payout_history.len(), 4);
+    assert_eq!(info_a.payout_count, 4);
 
     let info_b = client_b.get_program_info();
     assert_eq!(info_b.total_funds, 1_000_000);
     assert_eq!(info_b.remaining_balance, 600_000);
-    assert_eq!(info_b.payout_history.len(), 1);
+    assert_eq!(info_b.payout_count, 1);
 
     // Verify token isolation
     assert_eq!(token_client.balance(&client.address), 0);
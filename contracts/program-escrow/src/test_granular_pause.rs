@@ -182,7 +182,7 @@ fn test_partial_update_preserves_other_flags() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_lock_blocked_when_lock_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 0);
@@ -224,7 +224,7 @@ fn test_batch_allowed_when_only_lock_paused() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_single_payout_blocked_when_release_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 1_000);
@@ -235,7 +235,7 @@ fn test_single_payout_blocked_when_release_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_batch_payout_blocked_when_release_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 1_000);
@@ -303,7 +303,7 @@ fn test_batch_allowed_when_only_refund_paused() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_lock_blocked_when_lock_and_release_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 0);
@@ -318,7 +318,7 @@ fn test_lock_blocked_when_lock_and_release_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_single_payout_blocked_when_lock_and_release_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 500);
@@ -334,7 +334,7 @@ fn test_single_payout_blocked_when_lock_and_release_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_batch_payout_blocked_when_lock_and_release_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 500);
@@ -354,7 +354,7 @@ fn test_batch_payout_blocked_when_lock_and_release_paused() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_lock_blocked_when_lock_and_refund_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 0);
@@ -420,7 +420,7 @@ fn test_lock_allowed_when_release_and_refund_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_single_payout_blocked_when_release_and_refund_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 600);
@@ -436,7 +436,7 @@ fn test_single_payout_blocked_when_release_and_refund_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_batch_blocked_when_release_and_refund_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 600);
@@ -456,7 +456,7 @@ fn test_batch_blocked_when_release_and_refund_paused() {
 // ---------------------------------------------------------------------------
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_lock_blocked_when_all_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 0);
@@ -471,7 +471,7 @@ fn test_lock_blocked_when_all_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_single_payout_blocked_when_all_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 500);
@@ -487,7 +487,7 @@ fn test_single_payout_blocked_when_all_paused() {
 }
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_batch_payout_blocked_when_all_paused() {
     let env = Env::default();
     let (client, _token) = setup(&env, 500);
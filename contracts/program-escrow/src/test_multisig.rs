@@ -0,0 +1,385 @@
+// Tests for per-program multisig payout approval: configuration, approval,
+// revocation and expiry, enforced on single and batch payouts and claims,
+// and on split payouts that add up to the threshold.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, String,
+};
+
+struct MultisigSetup<'a> {
+    env: Env,
+    client: ProgramEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    program_id: String,
+    signers: [Address; 3],
+}
+
+impl<'a> MultisigSetup<'a> {
+    /// A funded program where payouts of 1_000 or more need 2 of 3 approvals,
+    /// valid for an hour.
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(10_000);
+
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_admin(&admin);
+        client.set_whitelist(&admin, &true);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&contract_id, &10_000);
+
+        let program_id = String::from_str(&env, "grand-prize");
+        client.batch_initialize_programs(&vec![
+            &env,
+            ProgramInitItem {
+                program_id: program_id.clone(),
+                authorized_payout_key: admin,
+                token_address: token_id.clone(),
+                reference_hash: None,
+            },
+        ]);
+        env.as_contract(&contract_id, || {
+            let key = DataKey::Program(program_id.clone());
+            let mut program: ProgramData = env.storage().persistent().get(&key).unwrap();
+            program.total_funds = 10_000;
+            program.remaining_balance = 10_000;
+            program_storage::save(&env, &key, &program);
+        });
+
+        let signers = [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        client.set_multisig_config(
+            &program_id,
+            &1_000,
+            &vec![
+                &env,
+                signers[0].clone(),
+                signers[1].clone(),
+                signers[2].clone(),
+            ],
+            &2,
+            &3_600,
+        );
+
+        Self {
+            token: token::Client::new(&env, &token_id),
+            env,
+            client,
+            program_id,
+            signers,
+        }
+    }
+
+    fn approve(&self, recipient: &Address, amount: i128, signer: usize) {
+        self.client
+            .approve_payout(&self.program_id, recipient, &amount, &self.signers[signer]);
+    }
+
+    fn payout(&self, recipient: &Address, amount: i128) -> Result<(), ProgramEscrowError> {
+        match self
+            .client
+            .try_single_payout(&self.program_id, recipient, &amount)
+        {
+            Ok(_) => Ok(()),
+            Err(Ok(err)) => Err(err),
+            Err(Err(_)) => panic!("unexpected host error"),
+        }
+    }
+}
+
+#[test]
+fn test_payout_below_threshold_needs_no_approval() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.payout(&recipient, 999).unwrap();
+    assert_eq!(s.token.balance(&recipient), 999);
+}
+
+#[test]
+fn test_payout_above_threshold_needs_quorum() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    assert_eq!(
+        s.payout(&recipient, 1_000),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+
+    s.approve(&recipient, 1_000, 0);
+    assert_eq!(
+        s.payout(&recipient, 1_000),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+
+    s.approve(&recipient, 1_000, 2);
+    let approval = s
+        .client
+        .get_payout_approval(&s.program_id, &recipient)
+        .unwrap();
+    assert_eq!(approval.approvals.len(), 2);
+    assert_eq!(approval.expires_at, 10_000 + 3_600);
+
+    s.payout(&recipient, 1_000).unwrap();
+    assert_eq!(s.token.balance(&recipient), 1_000);
+
+    // The approval is consumed by the payout.
+    assert_eq!(
+        s.client.get_payout_approval(&s.program_id, &recipient),
+        None
+    );
+    assert_eq!(
+        s.payout(&recipient, 1_000),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+}
+
+#[test]
+fn test_split_payouts_add_up_to_threshold() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.payout(&recipient, 600).unwrap();
+    assert_eq!(
+        s.payout(&recipient, 400),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+    // Other paths that commit funds count towards the same total.
+    assert_eq!(
+        s.client
+            .try_create_pending_claim(&s.program_id, &recipient, &400, &20_000),
+        Err(Ok(ProgramEscrowError::InsufficientApprovals))
+    );
+
+    s.approve(&recipient, 400, 0);
+    s.approve(&recipient, 400, 1);
+    s.payout(&recipient, 400).unwrap();
+    assert_eq!(s.token.balance(&recipient), 1_000);
+
+    // The approval settles the running total.
+    s.payout(&recipient, 999).unwrap();
+    assert_eq!(
+        s.payout(&recipient, 1),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+
+    // It covers one approval window from its first payout.
+    s.env.ledger().set_timestamp(10_000 + 3_601);
+    s.payout(&recipient, 1).unwrap();
+    assert_eq!(s.token.balance(&recipient), 2_000);
+}
+
+#[test]
+fn test_approval_is_bound_to_amount() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.approve(&recipient, 1_000, 0);
+    s.approve(&recipient, 1_000, 1);
+    assert_eq!(
+        s.payout(&recipient, 2_000),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+
+    // Approving another amount starts a fresh approval.
+    s.approve(&recipient, 2_000, 0);
+    let approval = s
+        .client
+        .get_payout_approval(&s.program_id, &recipient)
+        .unwrap();
+    assert_eq!(approval.amount, 2_000);
+    assert_eq!(approval.approvals.len(), 1);
+}
+
+#[test]
+fn test_non_signer_cannot_approve() {
+    let s = MultisigSetup::new();
+    let outsider = Address::generate(&s.env);
+    let res =
+        s.client
+            .try_approve_payout(&s.program_id, &Address::generate(&s.env), &1_000, &outsider);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::Unauthorized)));
+}
+
+#[test]
+fn test_revoked_approval_does_not_count() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.approve(&recipient, 1_000, 0);
+    s.approve(&recipient, 1_000, 1);
+    s.client
+        .revoke_payout_approval(&s.program_id, &recipient, &s.signers[1]);
+    assert_eq!(
+        s.payout(&recipient, 1_000),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+
+    // Revoking twice is a no-op; revoking the last signature drops the record.
+    s.client
+        .revoke_payout_approval(&s.program_id, &recipient, &s.signers[1]);
+    s.client
+        .revoke_payout_approval(&s.program_id, &recipient, &s.signers[0]);
+    assert_eq!(
+        s.client.get_payout_approval(&s.program_id, &recipient),
+        None
+    );
+}
+
+#[test]
+fn test_expired_approval_is_rejected() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.approve(&recipient, 1_000, 0);
+    s.approve(&recipient, 1_000, 1);
+
+    s.env.ledger().set_timestamp(10_000 + 3_601);
+    assert_eq!(
+        s.payout(&recipient, 1_000),
+        Err(ProgramEscrowError::ApprovalExpired)
+    );
+
+    // Approving after expiry starts over with a new window.
+    s.approve(&recipient, 1_000, 2);
+    let approval = s
+        .client
+        .get_payout_approval(&s.program_id, &recipient)
+        .unwrap();
+    assert_eq!(approval.approvals, vec![&s.env, s.signers[2].clone()]);
+    assert_eq!(approval.expires_at, 10_000 + 3_601 + 3_600);
+}
+
+#[test]
+fn test_removed_signer_approval_does_not_count() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    s.approve(&recipient, 1_000, 0);
+    s.approve(&recipient, 1_000, 1);
+
+    s.client.set_multisig_config(
+        &s.program_id,
+        &1_000,
+        &vec![&s.env, s.signers[0].clone(), s.signers[2].clone()],
+        &2,
+        &3_600,
+    );
+    assert_eq!(
+        s.payout(&recipient, 1_000),
+        Err(ProgramEscrowError::InsufficientApprovals)
+    );
+}
+
+#[test]
+fn test_batch_payout_checks_each_item() {
+    let s = MultisigSetup::new();
+    let small = Address::generate(&s.env);
+    let large = Address::generate(&s.env);
+    let recipients = vec![&s.env, small.clone(), large.clone()];
+    let amounts = vec![&s.env, 500, 1_500];
+
+    let res = s
+        .client
+        .try_batch_payout(&s.program_id, &recipients, &amounts);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InsufficientApprovals)));
+    assert_eq!(s.token.balance(&small), 0);
+
+    s.approve(&large, 1_500, 0);
+    s.approve(&large, 1_500, 1);
+    s.client.batch_payout(&s.program_id, &recipients, &amounts);
+    assert_eq!(s.token.balance(&small), 500);
+    assert_eq!(s.token.balance(&large), 1_500);
+}
+
+#[test]
+fn test_batch_payout_sums_items_per_recipient() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    let other = Address::generate(&s.env);
+    // Three items under the threshold add up to 1_500 for one recipient.
+    let recipients = vec![
+        &s.env,
+        recipient.clone(),
+        other.clone(),
+        recipient.clone(),
+        recipient.clone(),
+    ];
+    let amounts = vec![&s.env, 500, 900, 500, 500];
+
+    let res = s
+        .client
+        .try_batch_payout(&s.program_id, &recipients, &amounts);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InsufficientApprovals)));
+
+    // An approval for one item's amount does not cover the total.
+    s.approve(&recipient, 500, 0);
+    s.approve(&recipient, 500, 1);
+    let res = s
+        .client
+        .try_batch_payout(&s.program_id, &recipients, &amounts);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InsufficientApprovals)));
+
+    s.approve(&recipient, 1_500, 0);
+    s.approve(&recipient, 1_500, 1);
+    s.client.batch_payout(&s.program_id, &recipients, &amounts);
+    assert_eq!(s.token.balance(&recipient), 1_500);
+    assert_eq!(s.token.balance(&other), 900);
+}
+
+#[test]
+fn test_pending_claim_needs_approval() {
+    let s = MultisigSetup::new();
+    let recipient = Address::generate(&s.env);
+    let res = s
+        .client
+        .try_create_pending_claim(&s.program_id, &recipient, &1_000, &20_000);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InsufficientApprovals)));
+
+    s.approve(&recipient, 1_000, 1);
+    s.approve(&recipient, 1_000, 2);
+    s.client
+        .create_pending_claim(&s.program_id, &recipient, &1_000, &20_000);
+}
+
+#[test]
+fn test_invalid_multisig_config() {
+    let s = MultisigSetup::new();
+    let one = vec![&s.env, s.signers[0].clone()];
+    let res = s
+        .client
+        .try_set_multisig_config(&s.program_id, &1_000, &one, &2, &0);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InvalidMultisigConfig)));
+
+    let repeated = vec![&s.env, s.signers[0].clone(), s.signers[0].clone()];
+    let res = s
+        .client
+        .try_set_multisig_config(&s.program_id, &1_000, &repeated, &2, &0);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InvalidMultisigConfig)));
+
+    let res = s
+        .client
+        .try_set_multisig_config(&s.program_id, &0, &one, &1, &0);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::InvalidMultisigConfig)));
+
+    let missing = String::from_str(&s.env, "missing");
+    let res = s
+        .client
+        .try_set_multisig_config(&missing, &1_000, &one, &1, &0);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::ProgramNotFound)));
+
+    // Turning the check off lets large payouts through.
+    s.client
+        .set_multisig_config(&s.program_id, &1_000, &one, &0, &0);
+    assert_eq!(
+        s.client
+            .get_multisig_config(&s.program_id)
+            .required_signatures,
+        0
+    );
+    let recipient = Address::generate(&s.env);
+    s.payout(&recipient, 5_000).unwrap();
+}
@@ -38,7 +38,14 @@ fn setup_program_with_admin<'a>(
 
     env.mock_all_auths();
     let program_id = String::from_str(env, "test-prog");
-    client.init_program(&program_id, &payout_key, &token_client.address, &admin, &None, &None);
+    client.init_program(
+        &program_id,
+        &payout_key,
+        &token_client.address,
+        &admin,
+        &None,
+        &None,
+    );
     (client, admin, payout_key, token_client)
 }
 
@@ -50,9 +57,9 @@ fn test_default_pause_flags_are_all_false() {
     let (contract, _admin) = setup_with_admin(&env);
 
     let flags = contract.get_pause_flags();
-    assert_eq!(flags.lock_paused, false);
-    assert_eq!(flags.release_paused, false);
-    assert_eq!(flags.refund_paused, false);
+    assert!(!flags.lock_paused);
+    assert!(!flags.release_paused);
+    assert!(!flags.refund_paused);
 }
 
 // --- set_paused: lock ---
@@ -66,9 +73,9 @@ fn test_set_paused_lock() {
     contract.set_paused(&Some(true), &None, &None, &None);
 
     let flags = contract.get_pause_flags();
-    assert_eq!(flags.lock_paused, true);
-    assert_eq!(flags.release_paused, false);
-    assert_eq!(flags.refund_paused, false);
+    assert!(flags.lock_paused);
+    assert!(!flags.release_paused);
+    assert!(!flags.refund_paused);
 }
 
 #[test]
@@ -81,7 +88,7 @@ fn test_unset_paused_lock() {
     contract.set_paused(&Some(false), &None, &None, &None);
 
     let flags = contract.get_pause_flags();
-    assert_eq!(flags.lock_paused, false);
+    assert!(!flags.lock_paused);
 }
 
 // --- set_paused: release ---
@@ -95,9 +102,9 @@ fn test_set_paused_release() {
     contract.set_paused(&None, &Some(true), &None, &None);
 
     let flags = contract.get_pause_flags();
-    assert_eq!(flags.lock_paused, false);
-    assert_eq!(flags.release_paused, true);
-    assert_eq!(flags.refund_paused, false);
+    assert!(!flags.lock_paused);
+    assert!(flags.release_paused);
+    assert!(!flags.refund_paused);
 }
 
 // --- mixed pause states ---
@@ -112,36 +119,36 @@ fn test_mixed_pause_states() {
     contract.set_paused(&Some(true), &Some(true), &Some(false), &None);
 
     let flags = contract.get_pause_flags();
-    assert_eq!(flags.lock_paused, true);
-    assert_eq!(flags.release_paused, true);
-    assert_eq!(flags.refund_paused, false);
+    assert!(flags.lock_paused);
+    assert!(flags.release_paused);
+    assert!(!flags.refund_paused);
 
     // Only update release back to unpaused; lock should stay paused
     contract.set_paused(&None, &Some(false), &None, &None);
 
     let flags = contract.get_pause_flags();
-    assert_eq!(flags.lock_paused, true);
-    assert_eq!(flags.release_paused, false);
-    assert_eq!(flags.refund_paused, false);
+    assert!(flags.lock_paused);
+    assert!(!flags.release_paused);
+    assert!(!flags.refund_paused);
 }
 
 // --- lock_program_funds enforcement ---
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_lock_program_funds_paused() {
     let env = Env::default();
     env.mock_all_auths();
     let (contract, _admin, _payout_key, _token) = setup_program_with_admin(&env);
 
     contract.set_paused(&Some(true), &None, &None, &None);
-    contract.lock_program_funds(&1000);
+    contract.lock_program_funds(&String::from_str(&env, "test-prog"), &1000);
 }
 
 // --- single_payout enforcement ---
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_single_payout_paused() {
     let env = Env::default();
     env.mock_all_auths();
@@ -149,13 +156,13 @@ fn test_single_payout_paused() {
     let recipient = Address::generate(&env);
 
     contract.set_paused(&None, &Some(true), &None, &None);
-    contract.single_payout(&recipient, &100);
+    contract.single_payout(&String::from_str(&env, "test-prog"), &recipient, &100);
 }
 
 // --- batch_payout enforcement ---
 
 #[test]
-#[should_panic(expected = "Funds Paused")]
+#[should_panic(expected = "Error(Contract, #7)")]
 fn test_batch_payout_paused() {
     let env = Env::default();
     env.mock_all_auths();
@@ -166,13 +173,13 @@ fn test_batch_payout_paused() {
     let amounts = soroban_sdk::vec![&env, 100i128];
 
     contract.set_paused(&None, &Some(true), &None, &None);
-    contract.batch_payout(&recipients, &amounts);
+    contract.batch_payout(&String::from_str(&env, "test-prog"), &recipients, &amounts);
 }
 
 // --- initialize_contract guard ---
 
 #[test]
-#[should_panic(expected = "Already initialized")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_double_initialize_contract() {
     let env = Env::default();
 
@@ -189,7 +196,7 @@ fn test_double_initialize_contract() {
 // --- set_paused requires initialization ---
 
 #[test]
-#[should_panic(expected = "Not initialized")]
+#[should_panic(expected = "Error(Contract, #2)")]
 fn test_set_paused_before_initialize() {
     let env = Env::default();
     let contract_id = env.register_contract(None, ProgramEscrowContract);
@@ -233,7 +240,7 @@ fn test_set_paused_emits_events() {
 
     let data: (Symbol, bool, Address, Option<String>, u64) = emitted.2.try_into_val(&env).unwrap();
     assert_eq!(data.0, Symbol::new(&env, "lock"));
-    assert_eq!(data.1, true);
+    assert!(data.1);
     assert_eq!(data.2, admin);
     assert_eq!(data.3, None);
     assert!(data.4 > 0);
@@ -252,7 +259,7 @@ fn test_operations_resume_after_unpause() {
     contract.set_paused(&Some(false), &None, &None, &None);
 
     // Should succeed now
-    contract.lock_program_funds(&1000);
+    contract.lock_program_funds(&String::from_str(&env, "test-prog"), &1000);
 }
 
 #[test]
@@ -266,7 +273,7 @@ fn test_emergency_withdraw_non_admin_fails() {
 }
 
 #[test]
-#[should_panic(expected = "Not paused")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_emergency_withdraw_unpaused_fails() {
     let env = Env::default();
     env.mock_all_auths();
@@ -293,7 +300,7 @@ fn test_emergency_withdraw_succeeds() {
     token_client.transfer(&admin, &contract.address, &500);
 
     // Lock some funds to get balance in contract state
-    contract.lock_program_funds(&500);
+    contract.lock_program_funds(&String::from_str(&env, "test-prog"), &500);
     assert_eq!(token_client.balance(&contract.address), 500);
 
     let reason = soroban_sdk::String::from_str(&env, "Hacked");
@@ -343,12 +350,12 @@ fn setup_rbac_program_env_strict<'a>(
     // Initialize program with operator as payout_key
     let program_id = String::from_str(env, "rbac-program");
     contract_client.init_program(&program_id, &operator, &token_address, &admin, &None, &None);
-    
+
     // Mint and lock funds
     let depositor = Address::generate(env);
     token_admin_client.mint(&depositor, &1000);
     token_client.transfer(&depositor, &contract_client.address, &500);
-    contract_client.lock_program_funds(&500);
+    contract_client.lock_program_funds(&program_id, &500);
 
     // Now reset auths - subsequent operations need proper auth
     env.mock_auths(&[]);
@@ -385,12 +392,12 @@ fn setup_rbac_program_env<'a>(
     // Initialize program with operator as payout_key
     let program_id = String::from_str(env, "rbac-program");
     contract_client.init_program(&program_id, &operator, &token_address, &admin, &None, &None);
-    
+
     // Mint and lock funds
     let depositor = Address::generate(env);
     token_admin_client.mint(&depositor, &1000);
     token_client.transfer(&depositor, &contract_client.address, &500);
-    contract_client.lock_program_funds(&500);
+    contract_client.lock_program_funds(&program_id, &500);
 
     (admin, operator, token_client, contract_client)
 }
@@ -401,7 +408,7 @@ fn test_rbac_admin_can_emergency_withdraw_when_paused() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (admin, _operator, token_client, contract_client) = setup_rbac_program_env(&env);
+    let (_admin, _operator, token_client, contract_client) = setup_rbac_program_env(&env);
     let target = Address::generate(&env);
 
     contract_client.set_paused(&Some(true), &None, &None, &None);
@@ -432,7 +439,7 @@ fn test_rbac_operator_cannot_emergency_withdraw() {
 
 /// emergency_withdraw FAILS even for admin when contract is NOT paused
 #[test]
-#[should_panic(expected = "Not paused")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_rbac_admin_emergency_withdraw_requires_paused_state() {
     let env = Env::default();
     env.mock_all_auths();
@@ -516,7 +523,7 @@ fn test_rbac_pause_state_preserved_after_emergency_withdraw() {
 
 /// Partial pause: only release paused (not lock) — emergency_withdraw still requires lock_paused
 #[test]
-#[should_panic(expected = "Not paused")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_rbac_emergency_withdraw_requires_lock_paused_not_release_paused() {
     let env = Env::default();
     env.mock_all_auths();
@@ -532,7 +539,7 @@ fn test_rbac_emergency_withdraw_requires_lock_paused_not_release_paused() {
 
 /// Partial pause: only refund paused (not lock) — emergency_withdraw still requires lock_paused
 #[test]
-#[should_panic(expected = "Not paused")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_rbac_emergency_withdraw_requires_lock_paused_not_refund_paused() {
     let env = Env::default();
     env.mock_all_auths();
@@ -568,20 +575,34 @@ fn test_rbac_emergency_withdraw_drains_all_funds() {
     contract_client.initialize_contract(&admin);
 
     // Initialize multiple programs
-    // NOTE: Contract currently only supports one program per instance
     let program_id_1 = String::from_str(&env, "prog-1");
-    contract_client.init_program(&program_id_1, &operator, &token_address, &admin, &None, &None);
-    
-    // let program_id_2 = String::from_str(&env, "prog-2");
-    // contract_client.init_program(&program_id_2, &operator, &token_address, &admin, &None, &None);
-    
+    contract_client.init_program(
+        &program_id_1,
+        &operator,
+        &token_address,
+        &admin,
+        &None,
+        &None,
+    );
+
+    let program_id_2 = String::from_str(&env, "prog-2");
+    contract_client.init_program(
+        &program_id_2,
+        &operator,
+        &token_address,
+        &admin,
+        &None,
+        &None,
+    );
+
     // Mint and distribute funds to programs
     let depositor = Address::generate(&env);
     token_admin_client.mint(&depositor, &3000);
 
     // Transfer to contract and lock in each program
     token_client.transfer(&depositor, &contract_client.address, &1500);
-    contract_client.lock_program_funds(&500); // This locks 500 for the current program context
+    contract_client.lock_program_funds(&program_id_1, &500);
+    contract_client.lock_program_funds(&program_id_2, &1000);
 
     assert!(
         token_client.balance(&contract_client.address) > 0,
@@ -625,12 +646,12 @@ fn test_rbac_after_emergency_withdraw_can_unpause_and_reuse() {
 
     // Verify contract can be reused (balance is 0 now but lock should work)
     // We need to mint tokens to the contract first since lock_program_funds doesn't transfer them from caller
-    let token_admin = Address::generate(&env);
+    let _token_admin = Address::generate(&env);
     let token_sac = token::StellarAssetClient::new(&env, &token_client.address);
     env.mock_all_auths();
     token_sac.mint(&contract_client.address, &200);
 
-    contract_client.lock_program_funds(&200);
+    contract_client.lock_program_funds(&String::from_str(&env, "rbac-program"), &200);
     // Note: this will fail since we drained the contract, but the point is
     // that the pause check passes
     assert_eq!(token_client.balance(&contract_client.address), 200);
@@ -638,7 +659,7 @@ fn test_rbac_after_emergency_withdraw_can_unpause_and_reuse() {
 
 /// Only lock_paused gate affects emergency_withdraw, not release or refund pause
 #[test]
-#[should_panic(expected = "Not paused")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_rbac_emergency_withdraw_ignores_release_and_refund_pause() {
     let env = Env::default();
     env.mock_all_auths();
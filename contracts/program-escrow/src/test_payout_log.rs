@@ -0,0 +1,216 @@
+// Tests for the append-only payout log: paging through a program's history,
+// the per-recipient view and replay of inline histories during migration.
+
+use super::*;
+use program_storage::{LegacyPayoutRecord, LegacyProgramData};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger, vec, Address, Env, String, Vec};
+
+fn setup(env: &Env) -> (ProgramEscrowContractClient<'static>, String) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProgramEscrowContract);
+    let client = ProgramEscrowContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.set_admin(&admin);
+
+    let program_id = String::from_str(env, "hack-2025");
+    client.batch_initialize_programs(&vec![
+        env,
+        ProgramInitItem {
+            program_id: program_id.clone(),
+            authorized_payout_key: admin,
+            token_address: Address::generate(env),
+            reference_hash: None,
+        },
+    ]);
+    (client, program_id)
+}
+
+/// Record payouts the way `single_payout` and `batch_payout` do.
+fn pay(
+    env: &Env,
+    client: &ProgramEscrowContractClient,
+    program_id: &String,
+    payouts: &[(&Address, i128)],
+) {
+    env.as_contract(&client.address, || {
+        let key = DataKey::Program(program_id.clone());
+        let mut program: ProgramData = env.storage().persistent().get(&key).unwrap();
+        for (recipient, amount) in payouts {
+            payout_log::append(env, &mut program, recipient, *amount);
+        }
+        program_storage::save(env, &key, &program);
+    });
+}
+
+fn load(env: &Env, client: &ProgramEscrowContractClient, program_id: &String) -> ProgramData {
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Program(program_id.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_payout_history_pages_in_sequence_order() {
+    let env = Env::default();
+    let (client, program_id) = setup(&env);
+    let recipients: Vec<Address> = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for i in 0..5u32 {
+        let recipient = recipients.get(i % 3).unwrap();
+        pay(
+            &env,
+            &client,
+            &program_id,
+            &[(&recipient, 100 * (i as i128 + 1))],
+        );
+    }
+
+    let program = load(&env, &client, &program_id);
+    assert_eq!(program.payout_count, 5);
+    assert_eq!(program.total_paid_out, 1_500);
+
+    let first = client.get_payout_history(&program_id, &0, &2);
+    assert_eq!(first.records.len(), 2);
+    assert_eq!(first.records.get(0).unwrap().sequence, 0);
+    assert_eq!(first.records.get(1).unwrap().amount, 200);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.get_payout_history(&program_id, &2, &2);
+    assert_eq!(second.records.get(0).unwrap().sequence, 2);
+    assert_eq!(second.next_cursor, Some(4));
+
+    let last = client.get_payout_history(&program_id, &4, &2);
+    assert_eq!(last.records.len(), 1);
+    assert_eq!(last.records.get(0).unwrap().amount, 500);
+    assert_eq!(last.next_cursor, None);
+
+    // A cursor past the end returns an empty page.
+    let past = client.get_payout_history(&program_id, &9, &2);
+    assert_eq!(past.records.len(), 0);
+    assert_eq!(past.next_cursor, None);
+}
+
+#[test]
+fn test_payout_history_page_size_is_capped() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let (client, program_id) = setup(&env);
+    let recipient = Address::generate(&env);
+    let payouts = [(&recipient, 1i128); 120];
+    pay(&env, &client, &program_id, &payouts);
+
+    let page = client.get_payout_history(&program_id, &0, &u32::MAX);
+    assert_eq!(page.records.len(), payout_log::MAX_PAGE_SIZE);
+    assert_eq!(page.next_cursor, Some(payout_log::MAX_PAGE_SIZE as u64));
+}
+
+#[test]
+fn test_payouts_by_recipient() {
+    let env = Env::default();
+    let (client, program_id) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+    pay(&env, &client, &program_id, &[(&alice, 10), (&bob, 20)]);
+    env.ledger().set_timestamp(2_000);
+    pay(&env, &client, &program_id, &[(&alice, 30), (&alice, 40)]);
+
+    let page = client.get_payouts_by_recipient(&program_id, &alice, &0, &2);
+    assert_eq!(page.records.len(), 2);
+    assert_eq!(page.records.get(0).unwrap().sequence, 0);
+    assert_eq!(page.records.get(0).unwrap().timestamp, 1_000);
+    assert_eq!(page.records.get(1).unwrap().sequence, 2);
+    assert_eq!(page.next_cursor, Some(2));
+
+    let rest = client.get_payouts_by_recipient(&program_id, &alice, &2, &2);
+    assert_eq!(rest.records.len(), 1);
+    assert_eq!(rest.records.get(0).unwrap().amount, 40);
+    assert_eq!(rest.next_cursor, None);
+
+    let bob_page = client.get_payouts_by_recipient(&program_id, &bob, &0, &10);
+    assert_eq!(bob_page.records.len(), 1);
+    assert_eq!(bob_page.records.get(0).unwrap().sequence, 1);
+
+    let nobody = client.get_payouts_by_recipient(&program_id, &Address::generate(&env), &0, &10);
+    assert_eq!(nobody.records.len(), 0);
+    assert_eq!(nobody.next_cursor, None);
+}
+
+#[test]
+fn test_payout_history_unknown_program() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let missing = String::from_str(&env, "missing");
+    assert_eq!(
+        client.try_get_payout_history(&missing, &0, &10),
+        Err(Ok(ProgramEscrowError::ProgramNotFound))
+    );
+    assert_eq!(
+        client.try_get_payouts_by_recipient(&missing, &Address::generate(&env), &0, &10),
+        Err(Ok(ProgramEscrowError::ProgramNotFound))
+    );
+}
+
+#[test]
+fn test_migration_replays_inline_payout_history() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let program_id = String::from_str(&env, "legacy");
+    let winner = Address::generate(&env);
+    let runner_up = Address::generate(&env);
+
+    env.as_contract(&client.address, || {
+        let legacy = LegacyProgramData {
+            program_id: program_id.clone(),
+            total_funds: 1_000,
+            remaining_balance: 400,
+            authorized_payout_key: winner.clone(),
+            payout_history: vec![
+                &env,
+                LegacyPayoutRecord {
+                    recipient: winner.clone(),
+                    amount: 500,
+                    timestamp: 10,
+                },
+                LegacyPayoutRecord {
+                    recipient: runner_up.clone(),
+                    amount: 100,
+                    timestamp: 20,
+                },
+            ],
+            token_address: Address::generate(&env),
+            initial_liquidity: 0,
+            reference_hash: None,
+        };
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Program(program_id.clone()), &legacy);
+        storage.set(&PROGRAM_REGISTRY, &vec![&env, program_id.clone()]);
+    });
+
+    assert_eq!(
+        client.migrate_program_storage(&vec![&env, program_id.clone()]),
+        1
+    );
+
+    let program = load(&env, &client, &program_id);
+    assert_eq!(program.payout_count, 2);
+    assert_eq!(program.total_paid_out, 600);
+    assert_eq!(program.remaining_balance, 400);
+
+    let history = client.get_payout_history(&program_id, &0, &10).records;
+    assert_eq!(history.get(0).unwrap().timestamp, 10);
+    assert_eq!(history.get(1).unwrap().recipient, runner_up);
+    assert_eq!(
+        client
+            .get_payouts_by_recipient(&program_id, &winner, &0, &10)
+            .records
+            .len(),
+        1
+    );
+}
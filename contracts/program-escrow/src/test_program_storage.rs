@@ -0,0 +1,210 @@
+// Tests for the per-program persistent storage layout and the migration of
+// programs stored in instance storage by earlier versions.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _},
+    Address, Env, String, Vec,
+};
+
+fn setup(env: &Env) -> (ProgramEscrowContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ProgramEscrowContract);
+    let client = ProgramEscrowContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.set_admin(&admin);
+    (client, admin)
+}
+
+fn register(env: &Env, client: &ProgramEscrowContractClient, admin: &Address, ids: &[&str]) {
+    let token = Address::generate(env);
+    let mut items = Vec::new(env);
+    for id in ids {
+        items.push_back(ProgramInitItem {
+            program_id: String::from_str(env, id),
+            authorized_payout_key: admin.clone(),
+            token_address: token.clone(),
+            reference_hash: None,
+        });
+    }
+    client.batch_initialize_programs(&items);
+}
+
+/// Rewrite a program the way earlier versions stored it.
+fn move_to_instance(env: &Env, client: &ProgramEscrowContractClient, program_id: &String) {
+    env.as_contract(&client.address, || {
+        let key = DataKey::Program(program_id.clone());
+        let program: ProgramData = env.storage().persistent().get(&key).unwrap();
+        env.storage().persistent().remove(&key);
+        let legacy = program_storage::LegacyProgramData {
+            program_id: program.program_id,
+            total_funds: program.total_funds,
+            remaining_balance: program.remaining_balance,
+            authorized_payout_key: program.authorized_payout_key,
+            payout_history: Vec::new(env),
+            token_address: program.token_address,
+            initial_liquidity: program.initial_liquidity,
+            reference_hash: program.reference_hash,
+        };
+        env.storage().instance().set(&key, &legacy);
+
+        let registry = program_storage::registry(env);
+        env.storage().persistent().remove(&PROGRAM_REGISTRY);
+        env.storage().instance().set(&PROGRAM_REGISTRY, &registry);
+    });
+}
+
+#[test]
+fn test_programs_are_stored_per_key_in_persistent_storage() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    register(&env, &client, &admin, &["prog-1", "prog-2"]);
+
+    env.as_contract(&client.address, || {
+        let key = DataKey::Program(String::from_str(&env, "prog-1"));
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+        assert_eq!(
+            env.storage().persistent().get_ttl(&key),
+            program_storage::TTL_EXTEND_TO
+        );
+        assert!(!env.storage().instance().has(&PROGRAM_REGISTRY));
+    });
+    assert_eq!(client.list_programs().len(), 2);
+    assert_eq!(client.get_program_count(), 2);
+}
+
+#[test]
+fn test_migrate_program_storage_moves_instance_entries() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    register(&env, &client, &admin, &["legacy"]);
+    let legacy = String::from_str(&env, "legacy");
+    move_to_instance(&env, &client, &legacy);
+    env.as_contract(&client.address, || {
+        let schedule = ProgramReleaseSchedule {
+            schedule_id: 1,
+            recipient: admin.clone(),
+            amount: 100,
+            release_timestamp: 0,
+            released: false,
+            released_at: None,
+            released_by: None,
+        };
+        let storage = env.storage().instance();
+        storage.set(&DataKey::ReleaseSchedule(legacy.clone(), 1), &schedule);
+        storage.set(&DataKey::NextScheduleId(legacy.clone()), &2u64);
+    });
+    assert!(!client.program_exists(&legacy));
+
+    let ids = soroban_sdk::vec![&env, legacy.clone(), String::from_str(&env, "unknown")];
+    assert_eq!(client.migrate_program_storage(&ids), 1);
+
+    assert!(client.program_exists(&legacy));
+    assert_eq!(
+        client.list_programs(),
+        soroban_sdk::vec![&env, legacy.clone()]
+    );
+    env.as_contract(&client.address, || {
+        let schedule_key = DataKey::ReleaseSchedule(legacy.clone(), 1);
+        assert!(env.storage().persistent().has(&schedule_key));
+        assert!(!env.storage().instance().has(&schedule_key));
+        assert!(!env
+            .storage()
+            .instance()
+            .has(&DataKey::Program(legacy.clone())));
+    });
+
+    // Running it again is a no-op.
+    assert_eq!(client.migrate_program_storage(&ids), 0);
+    assert_eq!(client.list_programs().len(), 1);
+}
+
+#[test]
+fn test_migrated_registry_merges_with_new_programs() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    register(&env, &client, &admin, &["old"]);
+    move_to_instance(&env, &client, &String::from_str(&env, "old"));
+    register(&env, &client, &admin, &["new"]);
+
+    client.migrate_program_storage(&soroban_sdk::vec![&env, String::from_str(&env, "old")]);
+    assert_eq!(
+        client.list_programs(),
+        soroban_sdk::vec![
+            &env,
+            String::from_str(&env, "new"),
+            String::from_str(&env, "old")
+        ]
+    );
+}
+
+#[test]
+fn test_large_program_migrates_over_several_calls() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    register(&env, &client, &admin, &["big"]);
+    let big = String::from_str(&env, "big");
+    move_to_instance(&env, &client, &big);
+
+    let recipient = Address::generate(&env);
+    let payouts = program_storage::MIGRATION_PAGE + 30;
+    env.as_contract(&client.address, || {
+        let storage = env.storage().instance();
+        let key = DataKey::Program(big.clone());
+        let mut legacy: program_storage::LegacyProgramData = storage.get(&key).unwrap();
+        for i in 0..payouts {
+            legacy
+                .payout_history
+                .push_back(program_storage::LegacyPayoutRecord {
+                    recipient: recipient.clone(),
+                    amount: 10,
+                    timestamp: i as u64,
+                });
+        }
+        storage.set(&key, &legacy);
+        for schedule_id in 1..=30u64 {
+            let schedule = ProgramReleaseSchedule {
+                schedule_id,
+                recipient: recipient.clone(),
+                amount: 5,
+                release_timestamp: 0,
+                released: false,
+                released_at: None,
+                released_by: None,
+            };
+            storage.set(
+                &DataKey::ReleaseSchedule(big.clone(), schedule_id),
+                &schedule,
+            );
+        }
+        storage.set(&DataKey::NextScheduleId(big.clone()), &31u64);
+    });
+
+    let ids = soroban_sdk::vec![&env, big.clone()];
+    // 80 payouts and 30 schedules take three pages of 50.
+    assert_eq!(client.migrate_program_storage(&ids), 0);
+    assert!(client.program_migration_pending(&big));
+    assert!(!client.program_exists(&big));
+    assert_eq!(client.migrate_program_storage(&ids), 0);
+    assert!(client.program_migration_pending(&big));
+    assert_eq!(client.migrate_program_storage(&ids), 1);
+    assert!(!client.program_migration_pending(&big));
+
+    let program = client.get_program_info(&big);
+    assert_eq!(program.payout_count, payouts as u64);
+    assert_eq!(program.total_paid_out, payouts as i128 * 10);
+    let page = client.get_payout_history(&big, &0, &100);
+    assert_eq!(page.records.get(0).unwrap().timestamp, 0);
+    env.as_contract(&client.address, || {
+        let key = DataKey::ReleaseSchedule(big.clone(), 30);
+        assert!(env.storage().persistent().has(&key));
+        assert!(!env.storage().instance().has(&key));
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&program_storage::MigrationKey::Progress(big.clone())));
+    });
+
+    assert_eq!(client.migrate_program_storage(&ids), 0);
+}
@@ -0,0 +1,250 @@
+// Tests for program vesting schedules: cliff, linear and periodic unlock,
+// partial claims recorded in release history, and admin revocation.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env, String,
+};
+
+const MONTH: u64 = 30 * 24 * 60 * 60;
+
+struct VestingSetup<'a> {
+    env: Env,
+    client: ProgramEscrowContractClient<'a>,
+    token: token::Client<'a>,
+    program_id: String,
+    fellow: Address,
+}
+
+impl<'a> VestingSetup<'a> {
+    /// A program holding 100_000 at timestamp 1_000.
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+
+        let contract_id = env.register_contract(None, ProgramEscrowContract);
+        let client = ProgramEscrowContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_admin(&admin);
+        client.set_whitelist(&admin, &true);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        token::StellarAssetClient::new(&env, &token_id).mint(&contract_id, &100_000);
+
+        let program_id = String::from_str(&env, "fellowship");
+        client.batch_initialize_programs(&vec![
+            &env,
+            ProgramInitItem {
+                program_id: program_id.clone(),
+                authorized_payout_key: admin,
+                token_address: token_id.clone(),
+                reference_hash: None,
+            },
+        ]);
+        env.as_contract(&contract_id, || {
+            let key = DataKey::Program(program_id.clone());
+            let mut program: ProgramData = env.storage().persistent().get(&key).unwrap();
+            program.total_funds = 100_000;
+            program.remaining_balance = 100_000;
+            program_storage::save(&env, &key, &program);
+        });
+
+        Self {
+            fellow: Address::generate(&env),
+            token: token::Client::new(&env, &token_id),
+            env,
+            client,
+            program_id,
+        }
+    }
+
+    fn vest(&self, total: i128, cliff: u64, duration: u64, period: u64) -> u64 {
+        self.client.create_vesting_schedule(
+            &self.program_id,
+            &self.fellow,
+            &total,
+            &1_000,
+            &cliff,
+            &duration,
+            &period,
+        )
+    }
+
+    fn at(&self, timestamp: u64) {
+        self.env.ledger().set_timestamp(timestamp);
+    }
+
+    fn claim(&self, vesting_id: u64) -> Result<i128, ProgramEscrowError> {
+        match self.client.try_claim_vested(&self.program_id, &vesting_id) {
+            Ok(Ok(amount)) => Ok(amount),
+            Err(Ok(err)) => Err(err),
+            _ => panic!("unexpected host error"),
+        }
+    }
+}
+
+#[test]
+fn test_linear_vesting_with_cliff() {
+    let s = VestingSetup::new();
+    let id = s.vest(1_200, 100, 1_200, 0);
+    assert_eq!(s.client.get_remaining_balance(&s.program_id), 98_800);
+
+    s.at(1_099);
+    assert_eq!(s.client.get_claimable_vested(&s.program_id, &id), 0);
+    assert_eq!(s.claim(id), Err(ProgramEscrowError::NothingVested));
+
+    // At the cliff the time since start unlocks at once.
+    s.at(1_100);
+    assert_eq!(s.claim(id), Ok(100));
+
+    s.at(1_700);
+    assert_eq!(s.client.get_claimable_vested(&s.program_id, &id), 600);
+    assert_eq!(s.claim(id), Ok(600));
+    assert_eq!(s.claim(id), Err(ProgramEscrowError::NothingVested));
+
+    s.at(5_000);
+    assert_eq!(s.claim(id), Ok(500));
+    assert_eq!(s.token.balance(&s.fellow), 1_200);
+
+    let schedule = s.client.get_vesting_schedule(&s.program_id, &id);
+    assert_eq!(schedule.claimed, 1_200);
+    assert_eq!(s.client.get_remaining_balance(&s.program_id), 98_800);
+}
+
+#[test]
+fn test_monthly_vesting_over_a_year() {
+    let s = VestingSetup::new();
+    let id = s.vest(12_000, 0, 12 * MONTH, MONTH);
+
+    // Unlocks in whole months.
+    s.at(1_000 + MONTH - 1);
+    assert_eq!(s.client.get_claimable_vested(&s.program_id, &id), 0);
+    s.at(1_000 + MONTH + MONTH / 2);
+    assert_eq!(s.claim(id), Ok(1_000));
+    s.at(1_000 + 6 * MONTH);
+    assert_eq!(s.claim(id), Ok(5_000));
+    s.at(1_000 + 12 * MONTH);
+    assert_eq!(s.claim(id), Ok(6_000));
+    assert_eq!(s.token.balance(&s.fellow), 12_000);
+}
+
+#[test]
+fn test_vesting_claims_are_recorded_in_release_history() {
+    let s = VestingSetup::new();
+    let id = s.vest(1_200, 0, 1_200, 0);
+    // A release schedule that shares the vesting schedule's id.
+    let other = Address::generate(&s.env);
+    s.client
+        .create_program_release_schedule(&s.program_id, &500, &1_100, &other);
+    s.at(1_300);
+    s.claim(id).unwrap();
+    s.client.release_prog_schedule_automatic(&s.program_id, &id);
+    s.at(1_600);
+    s.claim(id).unwrap();
+
+    let claims = s.client.get_vesting_claims(&s.program_id, &id, &0, &10);
+    assert_eq!(claims.len(), 2);
+    let first = claims.get(0).unwrap();
+    assert_eq!(first.schedule_id, id);
+    assert_eq!(first.amount, 300);
+    assert_eq!(first.recipient, s.fellow);
+    assert_eq!(first.release_type, ReleaseType::Vesting);
+    assert_eq!(claims.get(1).unwrap().released_at, 1_600);
+
+    let rest = s.client.get_vesting_claims(&s.program_id, &id, &1, &10);
+    assert_eq!(rest.len(), 1);
+    assert_eq!(rest.get(0).unwrap().released_at, 1_600);
+
+    let history = s.client.get_program_release_history(&s.program_id);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap(), first);
+    let scheduled = history.get(1).unwrap();
+    assert_eq!(scheduled.schedule_id, id);
+    assert_eq!(scheduled.recipient, other);
+    assert_eq!(scheduled.release_type, ReleaseType::Automatic);
+
+    let payouts = s
+        .client
+        .get_payouts_by_recipient(&s.program_id, &s.fellow, &0, &10);
+    assert_eq!(payouts.records.len(), 2);
+}
+
+#[test]
+fn test_revoke_returns_unvested_remainder() {
+    let s = VestingSetup::new();
+    let id = s.vest(1_200, 0, 1_200, 0);
+    s.at(1_300);
+    s.claim(id).unwrap();
+
+    s.at(1_400);
+    assert_eq!(s.client.revoke_vesting(&s.program_id, &id), 800);
+    assert_eq!(s.client.get_remaining_balance(&s.program_id), 98_800 + 800);
+
+    // The 100 vested between the last claim and revocation stays claimable.
+    s.at(5_000);
+    assert_eq!(s.claim(id), Ok(100));
+    assert_eq!(s.claim(id), Err(ProgramEscrowError::NothingVested));
+    assert_eq!(s.token.balance(&s.fellow), 400);
+
+    let res = s.client.try_revoke_vesting(&s.program_id, &id);
+    assert_eq!(res, Err(Ok(ProgramEscrowError::VestingAlreadyRevoked)));
+}
+
+#[test]
+fn test_invalid_vesting_schedules() {
+    let s = VestingSetup::new();
+    let create = |total: i128, cliff: u64, duration: u64, period: u64| {
+        s.client.try_create_vesting_schedule(
+            &s.program_id,
+            &s.fellow,
+            &total,
+            &1_000,
+            &cliff,
+            &duration,
+            &period,
+        )
+    };
+    assert_eq!(
+        create(1_000, 0, 0, 0),
+        Err(Ok(ProgramEscrowError::InvalidVestingSchedule))
+    );
+    assert_eq!(
+        create(1_000, 200, 100, 0),
+        Err(Ok(ProgramEscrowError::InvalidVestingSchedule))
+    );
+    assert_eq!(
+        create(1_000, 0, 100, 200),
+        Err(Ok(ProgramEscrowError::InvalidVestingSchedule))
+    );
+    assert_eq!(
+        create(0, 0, 100, 0),
+        Err(Ok(ProgramEscrowError::InvalidAmount))
+    );
+    assert_eq!(
+        create(100_001, 0, 100, 0),
+        Err(Ok(ProgramEscrowError::InsufficientBalance))
+    );
+
+    assert_eq!(
+        s.client.try_get_vesting_schedule(&s.program_id, &1),
+        Err(Ok(ProgramEscrowError::VestingNotFound))
+    );
+    assert_eq!(s.client.get_vesting_schedules(&s.program_id).len(), 0);
+}
+
+#[test]
+fn test_vesting_schedules_are_listed_per_program() {
+    let s = VestingSetup::new();
+    let first = s.vest(1_000, 0, 100, 0);
+    let second = s.vest(2_000, 0, 100, 0);
+
+    let schedules = s.client.get_vesting_schedules(&s.program_id);
+    assert_eq!(schedules.len(), 2);
+    assert_eq!(schedules.get(0).unwrap().vesting_id, first);
+    assert_eq!(schedules.get(1).unwrap().total_amount, 2_000);
+    assert_eq!(second, first + 1);
+}
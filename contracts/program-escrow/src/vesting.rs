@@ -0,0 +1,215 @@
+// ============================================================================
+// Vesting Schedules
+//
+// A vesting schedule unlocks `total_amount` for one recipient over time,
+// instead of all at once like a `ProgramReleaseSchedule`:
+//
+//   nothing before start + cliff
+//   total_amount * elapsed / duration afterwards, where elapsed is rounded
+//   down to a whole number of `period`s (period 0 unlocks continuously)
+//   everything from start + duration on
+//
+// Storage (persistent):
+//
+//   VestingKey::Schedule(id, vesting_id)   VestingSchedule
+//   VestingKey::NextId(id)                 u64, next vesting id (starts at 1)
+//
+// Creating a schedule reserves `total_amount` out of the program's
+// `remaining_balance`. The recipient pulls what has unlocked with
+// `claim_vested`; each claim is recorded in the program's release history
+// with `ReleaseType::Vesting`. Vesting ids are numbered apart from release
+// schedule ids, so a history entry's `schedule_id` is read together with its
+// `release_type`. Revoking a schedule freezes it at what has vested so far
+// (still claimable) and returns the rest to the program.
+// ============================================================================
+
+use crate::{
+    payout_log, program_storage, DataKey, ProgramData, ProgramEscrowError, ProgramReleaseHistory,
+    ReleaseType,
+};
+use soroban_sdk::{contracttype, Address, Env, String, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VestingKey {
+    Schedule(String, u64),
+    NextId(String),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub vesting_id: u64,
+    pub program_id: String,
+    pub recipient: Address,
+    /// Amount the schedule vests in total; lowered to the vested amount on revocation.
+    pub total_amount: i128,
+    pub start: u64,
+    /// Seconds after `start` before anything unlocks.
+    pub cliff: u64,
+    /// Seconds after `start` at which everything has unlocked.
+    pub duration: u64,
+    /// Unlock granularity in seconds; 0 unlocks continuously.
+    pub period: u64,
+    pub claimed: i128,
+    pub revoked_at: Option<u64>,
+}
+
+/// Amount of `schedule` unlocked at `now`, claimed or not.
+pub fn vested_amount(schedule: &VestingSchedule, now: u64) -> Result<i128, ProgramEscrowError> {
+    if schedule.revoked_at.is_some() {
+        return Ok(schedule.total_amount);
+    }
+    let elapsed = now.saturating_sub(schedule.start);
+    if now < schedule.start || elapsed < schedule.cliff {
+        return Ok(0);
+    }
+    if elapsed >= schedule.duration {
+        return Ok(schedule.total_amount);
+    }
+    let elapsed = match schedule.period {
+        0 => elapsed,
+        period => elapsed - elapsed % period,
+    };
+    schedule
+        .total_amount
+        .checked_mul(elapsed as i128)
+        .map(|scaled| scaled / schedule.duration as i128)
+        .ok_or(ProgramEscrowError::ArithmeticOverflow)
+}
+
+pub(crate) fn get(
+    env: &Env,
+    program_id: &String,
+    vesting_id: u64,
+) -> Result<VestingSchedule, ProgramEscrowError> {
+    env.storage()
+        .persistent()
+        .get(&VestingKey::Schedule(program_id.clone(), vesting_id))
+        .ok_or(ProgramEscrowError::VestingNotFound)
+}
+
+pub(crate) fn save(env: &Env, schedule: &VestingSchedule) {
+    program_storage::save(
+        env,
+        &VestingKey::Schedule(schedule.program_id.clone(), schedule.vesting_id),
+        schedule,
+    );
+}
+
+/// Validate and store a new schedule, reserving its amount from `program`.
+/// The caller saves `program`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create(
+    env: &Env,
+    program: &mut ProgramData,
+    recipient: &Address,
+    total_amount: i128,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+    period: u64,
+) -> Result<VestingSchedule, ProgramEscrowError> {
+    if total_amount <= 0 {
+        return Err(ProgramEscrowError::InvalidAmount);
+    }
+    if duration == 0 || cliff > duration || period > duration {
+        return Err(ProgramEscrowError::InvalidVestingSchedule);
+    }
+    if start.checked_add(duration).is_none() {
+        return Err(ProgramEscrowError::InvalidVestingSchedule);
+    }
+    program.remaining_balance = program
+        .remaining_balance
+        .checked_sub(total_amount)
+        .filter(|balance| *balance >= 0)
+        .ok_or(ProgramEscrowError::InsufficientBalance)?;
+
+    let next_key = VestingKey::NextId(program.program_id.clone());
+    let vesting_id: u64 = env.storage().persistent().get(&next_key).unwrap_or(1);
+    program_storage::save(env, &next_key, &(vesting_id + 1));
+
+    let schedule = VestingSchedule {
+        vesting_id,
+        program_id: program.program_id.clone(),
+        recipient: recipient.clone(),
+        total_amount,
+        start,
+        cliff,
+        duration,
+        period,
+        claimed: 0,
+        revoked_at: None,
+    };
+    save(env, &schedule);
+    Ok(schedule)
+}
+
+/// All vesting schedules of `program_id`, oldest first.
+pub(crate) fn list(env: &Env, program_id: &String) -> Vec<VestingSchedule> {
+    let next_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&VestingKey::NextId(program_id.clone()))
+        .unwrap_or(1);
+    let mut schedules = Vec::new(env);
+    for vesting_id in 1..next_id {
+        if let Ok(schedule) = get(env, program_id, vesting_id) {
+            schedules.push_back(schedule);
+        }
+    }
+    schedules
+}
+
+/// Record a vesting claim in the program's release history.
+pub(crate) fn record_claim(env: &Env, schedule: &VestingSchedule, amount: i128) {
+    let key = DataKey::ReleaseHistory(schedule.program_id.clone());
+    let mut history: Vec<ProgramReleaseHistory> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    history.push_back(ProgramReleaseHistory {
+        schedule_id: schedule.vesting_id,
+        program_id: schedule.program_id.clone(),
+        amount,
+        recipient: schedule.recipient.clone(),
+        released_at: env.ledger().timestamp(),
+        released_by: schedule.recipient.clone(),
+        release_type: ReleaseType::Vesting,
+    });
+    program_storage::save(env, &key, &history);
+}
+
+/// Claims of one schedule from the release history, oldest first, skipping
+/// the first `offset`. At most `payout_log::MAX_PAGE_SIZE` are returned.
+pub(crate) fn claims(
+    env: &Env,
+    program_id: &String,
+    vesting_id: u64,
+    offset: u32,
+    limit: u32,
+) -> Vec<ProgramReleaseHistory> {
+    let history: Vec<ProgramReleaseHistory> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ReleaseHistory(program_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let limit = limit.min(payout_log::MAX_PAGE_SIZE);
+    let mut page = Vec::new(env);
+    let mut skipped = 0u32;
+    for record in history.iter() {
+        if page.len() >= limit {
+            break;
+        }
+        if record.release_type != ReleaseType::Vesting || record.schedule_id != vesting_id {
+            continue;
+        }
+        if skipped < offset {
+            skipped += 1;
+            continue;
+        }
+        page.push_back(record);
+    }
+    page
+}
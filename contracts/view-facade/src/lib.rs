@@ -1 +1,286 @@
+#![no_std]
+//! Read-only aggregator over bounty escrow and program escrow instances.
+//!
+//! The facade holds no state of its own. Every entrypoint cross-calls the
+//! target contract's existing view functions and folds the results, so a
+//! frontend can fetch a page of escrows or a depositor summary with a single
+//! simulation instead of one `get_escrow_info` call per bounty.
+//!
+//! Calls use `try_invoke_contract`: an id that does not exist (or a target
+//! that is not an escrow at all) is skipped rather than aborting the batch.
 
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, Env, IntoVal, String, Symbol, Val, Vec,
+};
+
+/// Page size used when walking the bounty escrow's depositor index.
+const DEPOSITOR_PAGE_SIZE: u32 = 50;
+
+// ---------------------------------------------------------------------------
+// Mirrors of the bounty escrow storage types.
+//
+// These must match `bounty_escrow::Escrow` field-for-field so the values
+// returned by `get_escrow_info` decode. The integration tests register the
+// real bounty escrow contract and fail if the layouts drift apart.
+// ---------------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    Locked,
+    Released,
+    Refunded,
+    PartiallyRefunded,
+    Template,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundMode {
+    Full,
+    Partial,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundRecord {
+    pub amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+    pub mode: RefundMode,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Escrow {
+    pub depositor: Address,
+    pub amount: i128,
+    pub remaining_amount: i128,
+    pub status: EscrowStatus,
+    pub deadline: u64,
+    pub refund_history: Vec<RefundRecord>,
+    pub lock_fee: i128,
+    pub release_fee: i128,
+    pub token: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowWithId {
+    pub bounty_id: u64,
+    pub escrow: Escrow,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AggregateStats {
+    pub total_locked: i128,
+    pub total_released: i128,
+    pub total_refunded: i128,
+    pub count_locked: u32,
+    pub count_released: u32,
+    pub count_refunded: u32,
+}
+
+// ---------------------------------------------------------------------------
+// Facade views
+// ---------------------------------------------------------------------------
+
+/// Flattened escrow view returned by `get_bounty_batch`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BountyView {
+    pub bounty_id: u64,
+    pub depositor: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub remaining_amount: i128,
+    pub status: EscrowStatus,
+    pub deadline: u64,
+}
+
+/// Per-depositor totals across every escrow they funded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositorSummary {
+    pub depositor: Address,
+    /// Sum of net amounts locked across all of the depositor's escrows.
+    pub total_deposited: i128,
+    /// Funds still held in the depositor's Locked/PartiallyRefunded escrows.
+    pub total_active: i128,
+    pub active_bounties: i64,
+    pub completed_bounties: i64,
+}
+
+/// Remaining balance of one program in a program escrow instance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramView {
+    pub program_id: String,
+    pub remaining_balance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AggregatedBountyStats {
+    pub total_locked: i128,
+    pub total_released: i128,
+    pub total_refunded: i128,
+    pub total_bounties: u32,
+    pub active_bounties: u32,
+}
+
+#[contract]
+pub struct ViewFacade;
+
+#[contractimpl]
+impl ViewFacade {
+    /// Fetch several escrows from `bounty_contract` in one call.
+    /// Ids that do not exist are omitted, so the result may be shorter
+    /// than `bounty_ids`.
+    pub fn get_bounty_batch(
+        env: Env,
+        bounty_contract: Address,
+        bounty_ids: Vec<u64>,
+    ) -> Vec<BountyView> {
+        let mut views = Vec::new(&env);
+        for bounty_id in bounty_ids.iter() {
+            let args: Vec<Val> = (bounty_id,).into_val(&env);
+            if let Some(escrow) = try_call::<Escrow>(
+                &env,
+                &bounty_contract,
+                Symbol::new(&env, "get_escrow_info"),
+                args,
+            ) {
+                views.push_back(BountyView {
+                    bounty_id,
+                    depositor: escrow.depositor,
+                    token: escrow.token,
+                    amount: escrow.amount,
+                    remaining_amount: escrow.remaining_amount,
+                    status: escrow.status,
+                    deadline: escrow.deadline,
+                });
+            }
+        }
+        views
+    }
+
+    /// Totals for every escrow funded by `depositor`.
+    pub fn get_depositor_summary(
+        env: Env,
+        bounty_contract: Address,
+        depositor: Address,
+    ) -> DepositorSummary {
+        let mut summary = DepositorSummary {
+            depositor: depositor.clone(),
+            total_deposited: 0,
+            total_active: 0,
+            active_bounties: 0,
+            completed_bounties: 0,
+        };
+
+        let mut offset = 0u32;
+        loop {
+            let args: Vec<Val> = (depositor.clone(), offset, DEPOSITOR_PAGE_SIZE).into_val(&env);
+            let page = try_call::<Vec<EscrowWithId>>(
+                &env,
+                &bounty_contract,
+                Symbol::new(&env, "query_escrows_by_depositor"),
+                args,
+            )
+            .unwrap_or(Vec::new(&env));
+
+            for item in page.iter() {
+                let escrow = item.escrow;
+                match escrow.status {
+                    EscrowStatus::Locked | EscrowStatus::PartiallyRefunded => {
+                        summary.total_deposited += escrow.amount;
+                        summary.total_active += escrow.remaining_amount;
+                        summary.active_bounties += 1;
+                    }
+                    EscrowStatus::Released | EscrowStatus::Refunded => {
+                        summary.total_deposited += escrow.amount;
+                        summary.completed_bounties += 1;
+                    }
+                    EscrowStatus::Template => {}
+                }
+            }
+
+            if page.len() < DEPOSITOR_PAGE_SIZE {
+                break;
+            }
+            offset += DEPOSITOR_PAGE_SIZE;
+        }
+        summary
+    }
+
+    /// Remaining balance for several programs in `program_contract`.
+    /// Programs the contract does not know about are omitted.
+    pub fn get_program_batch(
+        env: Env,
+        program_contract: Address,
+        program_ids: Vec<String>,
+    ) -> Vec<ProgramView> {
+        let mut views = Vec::new(&env);
+        for program_id in program_ids.iter() {
+            let args: Vec<Val> = (program_id.clone(),).into_val(&env);
+            if let Some(remaining_balance) = try_call::<i128>(
+                &env,
+                &program_contract,
+                Symbol::new(&env, "get_remaining_balance"),
+                args,
+            ) {
+                views.push_back(ProgramView {
+                    program_id,
+                    remaining_balance,
+                });
+            }
+        }
+        views
+    }
+
+    /// Contract-wide totals from `bounty_contract`, or all zeroes if the
+    /// target cannot be queried.
+    pub fn get_aggregated_bounty_stats(
+        env: Env,
+        bounty_contract: Address,
+    ) -> AggregatedBountyStats {
+        let stats = try_call::<AggregateStats>(
+            &env,
+            &bounty_contract,
+            Symbol::new(&env, "get_aggregate_stats"),
+            Vec::new(&env),
+        )
+        .unwrap_or(AggregateStats {
+            total_locked: 0,
+            total_released: 0,
+            total_refunded: 0,
+            count_locked: 0,
+            count_released: 0,
+            count_refunded: 0,
+        });
+
+        AggregatedBountyStats {
+            total_locked: stats.total_locked,
+            total_released: stats.total_released,
+            total_refunded: stats.total_refunded,
+            total_bounties: stats.count_locked + stats.count_released + stats.count_refunded,
+            active_bounties: stats.count_locked,
+        }
+    }
+}
+
+/// Invoke `func` on `contract`, returning `None` on any failure: a missing
+/// contract, a contract error, or a return value that does not decode as `T`.
+fn try_call<T>(env: &Env, contract: &Address, func: Symbol, args: Vec<Val>) -> Option<T>
+where
+    T: soroban_sdk::TryFromVal<Env, Val>,
+{
+    match env.try_invoke_contract::<T, soroban_sdk::Error>(contract, &func, args) {
+        Ok(Ok(value)) => Some(value),
+        _ => None,
+    }
+}
+
+mod test;